@@ -0,0 +1,83 @@
+//! Criterion benchmarks of the polynomial multiplication algorithms
+//!
+//! Run with `cargo bench`; reports are written to `target/criterion`.
+//! A single group can be selected with e.g. `cargo bench -- thresholds`.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use mult_polynomial::{HybridConfig, Polynomial, PolynomialMultAlg};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::hint::black_box;
+
+/// Operand pair with `size` coefficients each, fixed across runs
+fn operands(size: usize) -> (Polynomial, Polynomial) {
+    let mut rng = StdRng::seed_from_u64(size as u64);
+    (
+        Polynomial::random_with_rng(-10.0, 10.0, size, &mut rng),
+        Polynomial::random_with_rng(-10.0, 10.0, size, &mut rng),
+    )
+}
+
+/// Compare the algorithms across input sizes
+fn algorithms(c: &mut Criterion) {
+    let mut group = c.benchmark_group("algorithms");
+    for size in [16, 64, 256, 1024, 4096] {
+        let (p1, p2) = operands(size);
+        group.throughput(Throughput::Elements(size as u64));
+        for (name, algorithm) in [
+            ("naive", PolynomialMultAlg::Naive),
+            ("naive-compensated", PolynomialMultAlg::NaiveCompensated),
+            ("toom3", PolynomialMultAlg::CookTooms),
+            (
+                "thresholded",
+                PolynomialMultAlg::Thresholded(PolynomialMultAlg::AUTO_THRESHOLD),
+            ),
+            ("hybrid", PolynomialMultAlg::Hybrid(HybridConfig::default())),
+        ] {
+            // the quadratic algorithms get too slow to sample at large sizes
+            if size > 1024 && name.starts_with("naive") {
+                continue;
+            }
+            group.bench_with_input(BenchmarkId::new(name, size), &size, |b, _| {
+                b.iter(|| black_box(&p1).multiply(black_box(&p2), algorithm))
+            });
+        }
+    }
+    group.finish();
+}
+
+/// Compare Toom-3 thresholds at fixed input sizes
+fn thresholds(c: &mut Criterion) {
+    for size in [1024, 8192] {
+        let (p1, p2) = operands(size);
+        let mut group = c.benchmark_group(format!("thresholds/{}", size));
+        for threshold in [8, 16, 32, 64, 128, 256] {
+            group.bench_with_input(
+                BenchmarkId::from_parameter(threshold),
+                &threshold,
+                |b, &threshold| {
+                    b.iter(|| black_box(&p1).multiply_thresholded(black_box(&p2), threshold))
+                },
+            );
+        }
+        group.finish();
+    }
+}
+
+/// Build polynomials from their roots, a product tree over the fast multiplier
+fn from_roots(c: &mut Criterion) {
+    let mut group = c.benchmark_group("from_roots");
+    for size in [256, 1024, 4096] {
+        let roots =
+            Polynomial::random_with_rng(-1.0, 1.0, size, &mut StdRng::seed_from_u64(size as u64))
+                .coeffs;
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &roots, |b, roots| {
+            b.iter(|| Polynomial::from_roots(black_box(roots)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, algorithms, thresholds, from_roots);
+criterion_main!(benches);
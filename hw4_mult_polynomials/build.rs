@@ -0,0 +1,44 @@
+//! Record the toolchain, commit and optimization settings of the build for the benchmark metadata
+
+use std::process::Command;
+
+/// Trimmed standard output of a successful command
+fn output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    let text = String::from_utf8(output.stdout).ok()?;
+    (output.status.success() && !text.trim().is_empty()).then(|| text.trim().to_string())
+}
+
+fn main() {
+    let env = |key: &str| std::env::var(key).unwrap_or_default();
+    let rustc = output(&env("RUSTC"), &["--version"]).unwrap_or_default();
+    let commit = output("git", &["describe", "--always", "--dirty", "--abbrev=12"]);
+    if let Some(git_dir) = output("git", &["rev-parse", "--git-dir"]) {
+        for file in ["HEAD", "index"] {
+            let path = std::path::Path::new(&git_dir).join(file);
+            if path.exists() {
+                println!("cargo:rerun-if-changed={}", path.display());
+            }
+        }
+    }
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rustc-env=MULT_POLYNOMIAL_RUSTC={}", rustc);
+    println!(
+        "cargo:rustc-env=MULT_POLYNOMIAL_COMMIT={}",
+        commit.unwrap_or_default()
+    );
+    println!("cargo:rustc-env=MULT_POLYNOMIAL_PROFILE={}", env("PROFILE"));
+    println!(
+        "cargo:rustc-env=MULT_POLYNOMIAL_OPT_LEVEL={}",
+        env("OPT_LEVEL")
+    );
+    println!(
+        "cargo:rustc-env=MULT_POLYNOMIAL_TARGET_FEATURES={}",
+        env("CARGO_CFG_TARGET_FEATURE")
+    );
+    // flags are separated by 0x1f in the encoded form
+    println!(
+        "cargo:rustc-env=MULT_POLYNOMIAL_RUSTFLAGS={}",
+        env("CARGO_ENCODED_RUSTFLAGS").replace('\u{1f}', " ")
+    );
+}
@@ -0,0 +1,87 @@
+#ifndef MULT_POLYNOMIAL_H
+#define MULT_POLYNOMIAL_H
+
+/* Generated by cbindgen from src/ffi.rs, do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * The product was written to `out`
+ */
+#define POLY_OK 0
+
+/**
+ * An input or output pointer was null while its length was nonzero
+ */
+#define POLY_ERR_NULL -1
+
+/**
+ * `out_len` is smaller than `poly_product_len(a_len, b_len)`
+ */
+#define POLY_ERR_OUT_TOO_SMALL -2
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Number of coefficients in the product of polynomials with `a_len` and `b_len` coefficients
+ */
+size_t poly_product_len(size_t a_len, size_t b_len);
+
+/**
+ * Multiply using the naive O(n²) algorithm
+ *
+ * Returns `POLY_OK` on success, or a negative error code.
+ *
+ * # Safety
+ * `a` and `b` must point to `a_len` and `b_len` readable values and `out` to `out_len`
+ * writable values; `out` must not overlap the inputs.
+ */
+int32_t poly_multiply_naive(const double *a,
+                            size_t a_len,
+                            const double *b,
+                            size_t b_len,
+                            double *out,
+                            size_t out_len);
+
+/**
+ * Multiply using the Cook-Tooms algorithm with k=3
+ *
+ * Returns `POLY_OK` on success, or a negative error code.
+ *
+ * # Safety
+ * Same requirements as [`poly_multiply_naive`].
+ */
+int32_t poly_multiply_toom3(const double *a,
+                            size_t a_len,
+                            const double *b,
+                            size_t b_len,
+                            double *out,
+                            size_t out_len);
+
+/**
+ * Multiply using Toom-3, switching to the naive algorithm below `threshold` coefficients
+ *
+ * Returns `POLY_OK` on success, or a negative error code.
+ *
+ * # Safety
+ * Same requirements as [`poly_multiply_naive`].
+ */
+int32_t poly_multiply_thresholded(const double *a,
+                                  size_t a_len,
+                                  const double *b,
+                                  size_t b_len,
+                                  double *out,
+                                  size_t out_len,
+                                  size_t threshold);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* MULT_POLYNOMIAL_H */
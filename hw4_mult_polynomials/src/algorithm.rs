@@ -0,0 +1,108 @@
+//! Pluggable multiplication algorithms
+//!
+//! Implement [`MultiplyAlgorithm`] to run your own multiplication (e.g. an FFT convolution)
+//! through [`Polynomial::multiply_with`] and the benchmark harness, next to the built-in ones.
+
+use crate::polynomial::multiply_impl;
+use crate::{Polynomial, PolynomialMultAlg};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Multiplication of coefficient slices, from lowest to highest degree
+/// ## Example
+/// ```
+/// use mult_polynomial::{MultiplyAlgorithm, Polynomial};
+/// /// Schoolbook multiplication, one product coefficient at a time
+/// struct Schoolbook;
+/// impl MultiplyAlgorithm for Schoolbook {
+///     fn multiply(&self, a: &[f64], b: &[f64]) -> Vec<f64> {
+///         if a.is_empty() || b.is_empty() {
+///             return vec![];
+///         }
+///         (0..a.len() + b.len() - 1)
+///             .map(|k| {
+///                 let i_min = k.saturating_sub(b.len() - 1);
+///                 (i_min..a.len().min(k + 1)).map(|i| a[i] * b[k - i]).sum()
+///             })
+///             .collect()
+///     }
+///     fn label(&self) -> String {
+///         "schoolbook".to_string()
+///     }
+/// }
+/// let p = Polynomial::new(vec![1.0, 2.0]);
+/// assert_eq!(p.multiply_with(&p, &Schoolbook), Polynomial::new(vec![1.0, 4.0, 4.0]));
+/// ```
+pub trait MultiplyAlgorithm {
+    /// Product of `a` and `b`, with `a.len() + b.len() - 1` coefficients (none if either is empty)
+    fn multiply(&self, a: &[f64], b: &[f64]) -> Vec<f64>;
+
+    /// Name of the algorithm, used as its column in the benchmark results (the type name by default)
+    fn label(&self) -> String {
+        core::any::type_name::<Self>().to_string()
+    }
+}
+
+impl MultiplyAlgorithm for PolynomialMultAlg {
+    fn multiply(&self, a: &[f64], b: &[f64]) -> Vec<f64> {
+        multiply_impl(a, b, *self)
+    }
+
+    fn label(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl fmt::Debug for dyn MultiplyAlgorithm + Send + Sync {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.label())
+    }
+}
+
+impl Polynomial {
+    /// Multiply with another polynomial using the given algorithm, built-in or user-defined
+    pub fn multiply_with(
+        &self,
+        other: &Polynomial,
+        algorithm: &dyn MultiplyAlgorithm,
+    ) -> Polynomial {
+        self.with_coeffs(algorithm.multiply(&self.coeffs, &other.coeffs))
+    }
+}
+
+#[cfg(all(test, feature = "thread_rng"))]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    /// Naive product computed on the reversed operands, then reversed back
+    struct Reversed;
+
+    impl MultiplyAlgorithm for Reversed {
+        fn multiply(&self, a: &[f64], b: &[f64]) -> Vec<f64> {
+            let rev = |x: &[f64]| x.iter().rev().copied().collect::<Vec<_>>();
+            rev(&multiply_impl(&rev(a), &rev(b), PolynomialMultAlg::Naive))
+        }
+    }
+
+    /// Test built-in and user-defined algorithms through the trait
+    #[test]
+    fn test_multiply_with() {
+        let p1 = Polynomial::random(-10.0, 10.0, 100);
+        let p2 = Polynomial::random(-10.0, 10.0, 70);
+        let expected = p1.multiply_naive(&p2);
+        let algorithms: Vec<&dyn MultiplyAlgorithm> = vec![
+            &PolynomialMultAlg::CookTooms,
+            &PolynomialMultAlg::Thresholded(16),
+            &Reversed,
+        ];
+        for algorithm in algorithms {
+            assert!(p1
+                .multiply_with(&p2, algorithm)
+                .approx_eq(&expected, 1e-9, 1e-9));
+        }
+        assert_eq!(PolynomialMultAlg::Thresholded(16).label(), "thresholded:16");
+        assert!(Reversed.label().ends_with("Reversed"));
+    }
+}
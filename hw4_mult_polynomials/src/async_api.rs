@@ -0,0 +1,81 @@
+//! Async wrappers off-loading multiplications to the Tokio blocking pool
+//!
+//! Enabled with the `async` feature. The futures must be polled within a Tokio runtime.
+
+use crate::{
+    multiply_batch_with_deadlines, MultiplyTask, PolyError, Polynomial, PolynomialMultAlg,
+};
+
+/// Run a blocking closure on the Tokio blocking pool, resuming any panic in the caller
+async fn spawn_blocking<T, F>(f: F) -> T
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    match tokio::task::spawn_blocking(f).await {
+        Ok(value) => value,
+        Err(e) => std::panic::resume_unwind(e.into_panic()),
+    }
+}
+
+/// Multiply two polynomials with the given algorithm without blocking the async executor
+/// ## Example
+/// ```
+/// use mult_polynomial::{multiply_async, Polynomial, PolynomialMultAlg};
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let p = Polynomial::new(vec![1.0, 1.0]);
+/// let product = multiply_async(p.clone(), p, PolynomialMultAlg::CookTooms).await;
+/// assert_eq!(product, Polynomial::new(vec![1.0, 2.0, 1.0]));
+/// # });
+/// ```
+pub async fn multiply_async(
+    lhs: Polynomial,
+    rhs: Polynomial,
+    algorithm: PolynomialMultAlg,
+) -> Polynomial {
+    spawn_blocking(move || lhs.multiply(&rhs, algorithm)).await
+}
+
+/// Multiply using a thresholded approach without blocking the async executor,
+/// reporting progress as the fraction of the recursion tree completed
+///
+/// The callback is invoked from the blocking thread.
+pub async fn multiply_thresholded_with_progress_async(
+    lhs: Polynomial,
+    rhs: Polynomial,
+    threshold: usize,
+    progress: impl FnMut(f64) + Send + 'static,
+) -> Polynomial {
+    spawn_blocking(move || lhs.multiply_thresholded_with_progress(&rhs, threshold, progress)).await
+}
+
+/// Async version of [`multiply_batch_with_deadlines`](crate::multiply_batch_with_deadlines)
+pub async fn multiply_batch_with_deadlines_async(
+    tasks: Vec<MultiplyTask>,
+) -> Vec<Result<Polynomial, PolyError>> {
+    spawn_blocking(move || multiply_batch_with_deadlines(&tasks)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that the async wrappers produce the same products as the blocking calls
+    #[tokio::test]
+    async fn test_async_wrappers() {
+        let p1 = Polynomial::random(-10.0, 10.0, 200);
+        let p2 = Polynomial::random(-10.0, 10.0, 150);
+        let expected = p1.multiply_thresholded(&p2, 16);
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let (product, with_progress) = tokio::join!(
+            multiply_async(p1.clone(), p2.clone(), PolynomialMultAlg::Thresholded(16)),
+            multiply_thresholded_with_progress_async(p1.clone(), p2.clone(), 16, move |f| {
+                tx.send(f).unwrap()
+            }),
+        );
+        assert_eq!(product, expected);
+        assert_eq!(with_progress, expected);
+        assert_eq!(rx.iter().last(), Some(1.0));
+    }
+}
@@ -0,0 +1,118 @@
+//! Third-party multiplications as benchmark baselines
+//!
+//! Enabled with the `baselines` feature. Both implement [`MultiplyAlgorithm`], so they can be
+//! added to [`BenchmarkConfig::custom_algorithms`](crate::benchmark::BenchmarkConfig::custom_algorithms)
+//! to show where the course implementations stand relative to production code.
+
+use crate::MultiplyAlgorithm;
+use num_complex::Complex64;
+use rustfft::FftPlanner;
+use std::sync::{Arc, Mutex};
+
+/// FFT convolution with `rustfft`, O(n log n) up to rounding errors of the transforms
+///
+/// The operands are zero-padded to the next power of two at least as long as the product.
+/// Plans are cached across calls, as production code would do.
+/// ## Example
+/// ```
+/// use mult_polynomial::{Polynomial, RustFft};
+/// let p = Polynomial::new(vec![1.0, 2.0]);
+/// let product = p.multiply_with(&p, &RustFft::default());
+/// assert!(product.approx_eq(&Polynomial::new(vec![1.0, 4.0, 4.0]), 1e-12, 0.0));
+/// ```
+pub struct RustFft {
+    planner: Mutex<FftPlanner<f64>>,
+}
+
+impl Default for RustFft {
+    fn default() -> Self {
+        RustFft {
+            planner: Mutex::new(FftPlanner::new()),
+        }
+    }
+}
+
+impl MultiplyAlgorithm for RustFft {
+    fn multiply(&self, a: &[f64], b: &[f64]) -> Vec<f64> {
+        if a.is_empty() || b.is_empty() {
+            return Vec::new();
+        }
+        let len = a.len() + b.len() - 1;
+        let size = len.next_power_of_two();
+        let (forward, inverse) = {
+            let mut planner = self.planner.lock().unwrap();
+            (
+                planner.plan_fft_forward(size),
+                planner.plan_fft_inverse(size),
+            )
+        };
+        let transform = |x: &[f64]| {
+            let mut values: Vec<Complex64> = x.iter().map(|&c| Complex64::new(c, 0.0)).collect();
+            values.resize(size, Complex64::new(0.0, 0.0));
+            forward.process(&mut values);
+            values
+        };
+        let mut values = transform(a);
+        for (v, w) in values.iter_mut().zip(transform(b)) {
+            *v *= w;
+        }
+        inverse.process(&mut values);
+        // rustfft does not normalize the inverse transform
+        (values.iter().take(len))
+            .map(|v| v.re / size as f64)
+            .collect()
+    }
+
+    fn label(&self) -> String {
+        "rustfft_time".to_string()
+    }
+}
+
+/// Multiplication of the `polynomial` crate (schoolbook, one product coefficient at a time)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PolynomialCrate;
+
+impl MultiplyAlgorithm for PolynomialCrate {
+    fn multiply(&self, a: &[f64], b: &[f64]) -> Vec<f64> {
+        if a.is_empty() || b.is_empty() {
+            return Vec::new();
+        }
+        let a = polynomial::Polynomial::new(a.to_vec());
+        let b = polynomial::Polynomial::new(b.to_vec());
+        let mut product = (&a * &b).data().to_vec();
+        // zero leading coefficients are trimmed by the crate
+        product.resize(a.data().len() + b.data().len() - 1, 0.0);
+        product
+    }
+
+    fn label(&self) -> String {
+        "polynomial_crate_time".to_string()
+    }
+}
+
+/// All baselines, ready for [`BenchmarkConfig::custom_algorithms`](crate::benchmark::BenchmarkConfig::custom_algorithms)
+pub fn baselines() -> Vec<Arc<dyn MultiplyAlgorithm + Send + Sync>> {
+    vec![Arc::new(RustFft::default()), Arc::new(PolynomialCrate)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Polynomial;
+
+    /// Both baselines agree with the naive product, also on unbalanced and odd sizes
+    #[test]
+    fn test_baselines() {
+        for (n, m) in [(1, 1), (3, 100), (1000, 999), (257, 64)] {
+            let p1 = Polynomial::random(-10.0, 10.0, n);
+            let p2 = Polynomial::random(-10.0, 10.0, m);
+            let expected = p1.multiply_naive(&p2);
+            for baseline in baselines() {
+                let product = p1.multiply_with(&p2, &*baseline);
+                assert!(product.approx_eq(&expected, 1e-9, 1e-9), "{:?}", baseline);
+            }
+        }
+        assert!(PolynomialCrate.multiply(&[], &[1.0]).is_empty());
+        assert!(RustFft::default().multiply(&[1.0], &[]).is_empty());
+    }
+}
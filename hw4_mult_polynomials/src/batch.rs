@@ -0,0 +1,255 @@
+//! Batch multiplication of many independent polynomial pairs
+
+use crate::polynomial::{multiply_abortable, thresholded_multiply_with_scratch, Scratch};
+use crate::{PolyError, Polynomial, PolynomialMultAlg};
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// Multiply many polynomial pairs with the same algorithm
+///
+/// The Toom-3 evaluation buffers are allocated once and reused across all levels and pairs,
+/// which matters when many medium-sized products are computed.
+/// ## Example
+/// ```
+/// use mult_polynomial::{multiply_batch, Polynomial, PolynomialMultAlg};
+/// let p = Polynomial::new(vec![1.0, 1.0]);
+/// let products = multiply_batch(&[(p.clone(), p.clone())], PolynomialMultAlg::CookTooms);
+/// assert_eq!(products, vec![Polynomial::new(vec![1.0, 2.0, 1.0])]);
+/// ```
+pub fn multiply_batch(
+    pairs: &[(Polynomial, Polynomial)],
+    algorithm: PolynomialMultAlg,
+) -> Vec<Polynomial> {
+    let mut scratch = Scratch::default();
+    pairs
+        .iter()
+        .map(|(lhs, rhs)| multiply_reusing(lhs, rhs, algorithm, &mut scratch))
+        .collect()
+}
+
+/// Same as [`multiply_batch`], distributing the pairs over one thread per available core
+pub fn multiply_batch_parallel(
+    pairs: &[(Polynomial, Polynomial)],
+    algorithm: PolynomialMultAlg,
+) -> Vec<Polynomial> {
+    let workers = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+    let chunk = pairs.len().div_ceil(workers).max(1);
+
+    std::thread::scope(|scope| {
+        let handles = pairs
+            .chunks(chunk)
+            .map(|chunk| scope.spawn(move || multiply_batch(chunk, algorithm)))
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect()
+    })
+}
+
+/// Multiply a single pair, reusing the Toom-3 workspace in `scratch`
+fn multiply_reusing(
+    lhs: &Polynomial,
+    rhs: &Polynomial,
+    algorithm: PolynomialMultAlg,
+    scratch: &mut Scratch,
+) -> Polynomial {
+    let (a, b) = (&lhs.coeffs, &rhs.coeffs);
+    match algorithm {
+        PolynomialMultAlg::CookTooms => {
+            lhs.with_coeffs(thresholded_multiply_with_scratch(a, b, 5, None, scratch))
+        }
+        PolynomialMultAlg::Thresholded(threshold) => lhs.with_coeffs(
+            thresholded_multiply_with_scratch(a, b, threshold, None, scratch),
+        ),
+        _ => lhs.multiply(rhs, algorithm),
+    }
+}
+
+/// A single multiplication to be completed before a deadline
+#[derive(Debug, Clone)]
+pub struct MultiplyTask {
+    pub lhs: Polynomial,
+    pub rhs: Polynomial,
+    pub algorithm: PolynomialMultAlg,
+    /// Point in time after which the product is no longer useful
+    pub deadline: Instant,
+}
+
+/// Multiply a batch of polynomial pairs, each with its own deadline
+///
+/// Tasks are scheduled earliest-deadline-first on a pool of worker threads
+/// (one per available core). A task whose deadline has passed before it starts is skipped,
+/// and a running product is abandoned soon after its deadline passes, whatever the algorithm:
+/// Toom-3 based products (`CookTooms`, `Thresholded`, `Hybrid`) check it after every base case,
+/// naive ones every 1024 rows of a tile of 16 KiB of the result, and compensated ones every
+/// 1024 coefficients. Naive products of at most 2048 coefficients run to completion.
+///
+/// Results are returned in the order of `tasks`.
+/// ## Example
+/// ```
+/// use mult_polynomial::{multiply_batch_with_deadlines, MultiplyTask, Polynomial, PolynomialMultAlg};
+/// use std::time::{Duration, Instant};
+/// let p = Polynomial::random(-10.0, 10.0, 100);
+/// let task = MultiplyTask {
+///     lhs: p.clone(),
+///     rhs: p.clone(),
+///     algorithm: PolynomialMultAlg::CookTooms,
+///     deadline: Instant::now() + Duration::from_secs(60),
+/// };
+/// let results = multiply_batch_with_deadlines(&[task]);
+/// assert_eq!(results[0], Ok(p.multiply_cook_tooms_k3(&p)));
+/// ```
+pub fn multiply_batch_with_deadlines(tasks: &[MultiplyTask]) -> Vec<Result<Polynomial, PolyError>> {
+    // Earliest deadline at the end, so that workers can pop it
+    let mut queue = (0..tasks.len()).collect::<Vec<_>>();
+    queue.sort_by(|&i, &j| tasks[j].deadline.cmp(&tasks[i].deadline));
+    let queue = Mutex::new(queue);
+
+    let workers = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .min(tasks.len());
+
+    let mut results = vec![None; tasks.len()];
+    std::thread::scope(|scope| {
+        let handles = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    while let Some(i) = queue.lock().unwrap().pop() {
+                        done.push((i, run_task(&tasks[i])));
+                    }
+                    done
+                })
+            })
+            .collect::<Vec<_>>();
+        for handle in handles {
+            for (i, result) in handle.join().unwrap() {
+                results[i] = Some(result);
+            }
+        }
+    });
+
+    results.into_iter().map(Option::unwrap).collect()
+}
+
+/// Run a single task, giving up once its deadline has passed
+fn run_task(task: &MultiplyTask) -> Result<Polynomial, PolyError> {
+    let overdue = || PolyError::DeadlineExceeded {
+        overdue: Instant::now().saturating_duration_since(task.deadline),
+    };
+    if Instant::now() >= task.deadline {
+        return Err(overdue());
+    }
+
+    let (a, b) = (&task.lhs.coeffs, &task.rhs.coeffs);
+    let should_abort = || Instant::now() >= task.deadline;
+    let product = multiply_abortable(a, b, task.algorithm, &should_abort);
+
+    match product {
+        Some(coeffs) if !should_abort() => Ok(task.lhs.with_coeffs(coeffs)),
+        _ => Err(overdue()),
+    }
+}
+
+#[cfg(all(test, feature = "thread_rng"))]
+mod tests {
+    use super::*;
+    use crate::HybridConfig;
+    use std::time::Duration;
+
+    /// Test that batch products match individual products
+    #[test]
+    fn test_multiply_batch() {
+        let pairs = (1..40)
+            .map(|i| {
+                (
+                    Polynomial::random(-10.0, 10.0, 7 * i),
+                    Polynomial::random(-10.0, 10.0, 5 * i),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        for algorithm in [PolynomialMultAlg::Naive, PolynomialMultAlg::Thresholded(8)] {
+            let expected = pairs
+                .iter()
+                .map(|(a, b)| a.multiply(b, algorithm))
+                .collect::<Vec<_>>();
+            assert_eq!(multiply_batch(&pairs, algorithm), expected);
+            assert_eq!(multiply_batch_parallel(&pairs, algorithm), expected);
+        }
+        assert!(multiply_batch_parallel(&[], PolynomialMultAlg::Naive).is_empty());
+    }
+
+    /// Test that tasks meet or miss their deadlines as expected
+    #[test]
+    fn test_batch_with_deadlines() {
+        let small = Polynomial::random(-10.0, 10.0, 64);
+        let large = Polynomial::random(-10.0, 10.0, 200_000);
+        let now = Instant::now();
+
+        let tasks = vec![
+            MultiplyTask {
+                lhs: small.clone(),
+                rhs: small.clone(),
+                algorithm: PolynomialMultAlg::Thresholded(16),
+                deadline: now + Duration::from_secs(600),
+            },
+            // already expired
+            MultiplyTask {
+                lhs: small.clone(),
+                rhs: small.clone(),
+                algorithm: PolynomialMultAlg::Naive,
+                deadline: now,
+            },
+            // cannot finish within a millisecond
+            MultiplyTask {
+                lhs: large.clone(),
+                rhs: large.clone(),
+                algorithm: PolynomialMultAlg::CookTooms,
+                deadline: now + Duration::from_millis(1),
+            },
+        ];
+
+        let results = multiply_batch_with_deadlines(&tasks);
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0], Ok(small.multiply_thresholded(&small, 16)));
+        assert!(matches!(
+            results[1],
+            Err(PolyError::DeadlineExceeded { .. })
+        ));
+        assert!(matches!(
+            results[2],
+            Err(PolyError::DeadlineExceeded { .. })
+        ));
+
+        // every algorithm gives up on a long product, and completes a short one unchanged
+        let medium = Polynomial::random(-10.0, 10.0, 3000);
+        for algorithm in [
+            PolynomialMultAlg::Naive,
+            PolynomialMultAlg::NaiveCompensated,
+            PolynomialMultAlg::Hybrid(HybridConfig::default()),
+        ] {
+            let task = |lhs: &Polynomial, deadline| MultiplyTask {
+                lhs: lhs.clone(),
+                rhs: lhs.clone(),
+                algorithm,
+                deadline,
+            };
+            let start = Instant::now();
+            let results = multiply_batch_with_deadlines(&[
+                task(&large, start + Duration::from_millis(1)),
+                task(&medium, start + Duration::from_secs(600)),
+            ]);
+            assert!(start.elapsed() < Duration::from_secs(5), "{}", algorithm);
+            assert!(matches!(
+                results[0],
+                Err(PolyError::DeadlineExceeded { .. })
+            ));
+            assert_eq!(results[1], Ok(medium.multiply(&medium, algorithm)));
+        }
+    }
+}
@@ -0,0 +1,2781 @@
+//! Benchmarking code for polynomial multiplication algorithms
+//!
+//! Requires the `std` feature. Run the benchmark binary in release mode to get accurate results.
+
+use crate::checkpoint::{self, Checkpoint, CHECKPOINT_FILE};
+pub use crate::metadata::RunMetadata;
+use crate::modular::{product_bound, schoolbook_exact};
+use crate::serialization::{text_header, COEFF_TYPE, FORMAT_VERSION};
+use crate::trace::span;
+use crate::{
+    multiply_exact, multiply_kronecker, CoefficientDistribution, MultiplyAlgorithm, OpCounts,
+    PolyError, Polynomial, PolynomialMultAlg, RandomPolynomial,
+};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// File format of the benchmark results
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// `all_results.csv` with the aggregated time of each algorithm, one row per size
+    #[default]
+    Csv,
+    /// `all_results.json` with the time of every run, grouped by size and algorithm
+    Json,
+}
+
+/// How the runs of one algorithm at one size are combined into the reported time
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Aggregation {
+    /// Mean of all runs
+    #[default]
+    Mean,
+    /// Mean after dropping the given fraction (in `[0, 0.5)`) of the fastest and of the slowest runs
+    TrimmedMean(f64),
+    /// Fastest run, i.e. the minimum of `k` runs
+    Min,
+}
+
+impl Aggregation {
+    /// Combine the times of a nonempty set of runs
+    pub fn apply(self, times: &[f64]) -> f64 {
+        assert!(!times.is_empty(), "Cannot aggregate zero runs");
+        match self {
+            Aggregation::Mean => Summary::new(times).mean,
+            Aggregation::TrimmedMean(fraction) => {
+                assert!(
+                    (0.0..0.5).contains(&fraction),
+                    "Trimmed fraction must be in [0, 0.5)"
+                );
+                let mut sorted = times.to_vec();
+                sorted.sort_by(f64::total_cmp);
+                let trim = (fraction * times.len() as f64) as usize;
+                Summary::new(&sorted[trim..times.len() - trim]).mean
+            }
+            Aggregation::Min => Summary::new(times).min,
+        }
+    }
+}
+
+/// Coefficients of the random operands of the benchmark
+///
+/// The speed of the floating-point algorithms hardly depends on the coefficients, but their
+/// errors do: cancellation in the evaluation-interpolation steps grows with the dynamic range,
+/// and integer inputs are multiplied exactly by the naive algorithm while the sums stay below 2⁵³.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum InputDistribution {
+    /// Uniform on `[-10, 10)`, as [`Polynomial::random`]
+    #[default]
+    Uniform,
+    /// Standard normal
+    Gaussian,
+    /// Uniform on `[-10, 10)`, each coefficient nonzero with the given probability in `(0, 1]`
+    Sparse(f64),
+    /// Log-uniform magnitudes on `[1e-100, 1e100)` with random signs
+    HugeRange,
+    /// Integers uniform on `[-1000, 1000]`
+    Integer,
+}
+
+impl InputDistribution {
+    /// Draw a polynomial with exactly `size` coefficients: the leading one is redrawn
+    /// until it is nonzero, so sparse and integer operands are not trimmed
+    pub fn generate(self, size: usize, rng: &mut StdRng) -> Polynomial {
+        let uniform = CoefficientDistribution::Uniform {
+            min: -10.0,
+            max: 10.0,
+        };
+        let (distribution, integer, density) = match self {
+            InputDistribution::Uniform => {
+                return Polynomial::random_with_rng(-10.0, 10.0, size, rng)
+            }
+            InputDistribution::Gaussian => (
+                CoefficientDistribution::Normal {
+                    mean: 0.0,
+                    std_dev: 1.0,
+                },
+                false,
+                1.0,
+            ),
+            InputDistribution::Sparse(density) => (uniform, false, density),
+            InputDistribution::HugeRange => (
+                CoefficientDistribution::LogUniform {
+                    min: 1e-100,
+                    max: 1e100,
+                },
+                false,
+                1.0,
+            ),
+            InputDistribution::Integer => (
+                CoefficientDistribution::Uniform {
+                    min: -1000.5,
+                    max: 1000.5,
+                },
+                true,
+                1.0,
+            ),
+        };
+        let draw = |size: usize, density: f64, rng: &mut StdRng| {
+            (RandomPolynomial::new(size))
+                .distribution(distribution)
+                .integer(integer)
+                .density(density)
+                .generate_with_rng(rng)
+                .coeffs
+        };
+        let mut coeffs = draw(size, density, rng);
+        if coeffs.len() < size {
+            coeffs.resize(size, 0.0);
+            while coeffs[size - 1] == 0.0 {
+                coeffs[size - 1] = draw(1, 1.0, rng).first().copied().unwrap_or(0.0);
+            }
+        }
+        Polynomial::new(coeffs)
+    }
+}
+
+impl std::fmt::Display for InputDistribution {
+    /// Name as in the `distribution` column, e.g. `sparse:0.05`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputDistribution::Uniform => f.write_str("uniform"),
+            InputDistribution::Gaussian => f.write_str("gaussian"),
+            InputDistribution::Sparse(density) => write!(f, "sparse:{}", density),
+            InputDistribution::HugeRange => f.write_str("huge-range"),
+            InputDistribution::Integer => f.write_str("integer"),
+        }
+    }
+}
+
+impl std::str::FromStr for InputDistribution {
+    type Err = PolyError;
+
+    /// Parse `uniform`, `gaussian`, `sparse:<density>` (`sparse` for a density of 0.1),
+    /// `huge-range` or `integer`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let unknown = || PolyError::InvalidArgument(format!("unknown distribution `{}`", s));
+        match s.split_once(':') {
+            None => match s {
+                "uniform" => Ok(InputDistribution::Uniform),
+                "gaussian" => Ok(InputDistribution::Gaussian),
+                "sparse" => Ok(InputDistribution::Sparse(0.1)),
+                "huge-range" => Ok(InputDistribution::HugeRange),
+                "integer" => Ok(InputDistribution::Integer),
+                _ => Err(unknown()),
+            },
+            Some(("sparse", density)) => density
+                .parse()
+                .map(InputDistribution::Sparse)
+                .map_err(|_| unknown()),
+            Some(_) => Err(unknown()),
+        }
+    }
+}
+
+/// Settings of the comprehensive benchmark
+#[derive(Debug, Clone)]
+pub struct BenchmarkConfig {
+    /// Input sizes (number of coefficients of both operands, or of the first one
+    /// for aspect ratios other than 1)
+    pub sizes: Vec<usize>,
+    /// Ratios `n / m` of the operand lengths: each size `n` is paired with a second operand
+    /// of `m = n / ratio` coefficients (at least 1) for every ratio;
+    /// `[1]` times equal sizes only, anything else adds `other_size` and `aspect_ratio` columns
+    pub aspect_ratios: Vec<usize>,
+    /// Coefficient distributions of the operands, each timed at every size and aspect ratio;
+    /// `[Uniform]` is the plain sweep, anything else adds a `distribution` column
+    pub distributions: Vec<InputDistribution>,
+    /// Algorithms to time, one CSV column each
+    pub algorithms: Vec<PolynomialMultAlg>,
+    /// User-defined algorithms timed after [`BenchmarkConfig::algorithms`],
+    /// in columns named by [`MultiplyAlgorithm::label`]
+    pub custom_algorithms: Vec<Arc<dyn MultiplyAlgorithm + Send + Sync>>,
+    /// Directory for the results file
+    pub output_dir: PathBuf,
+    /// Format of the results file
+    pub format: OutputFormat,
+    /// Seed for the random inputs (`None` draws it from the operating system);
+    /// each size and aspect ratio gets its own seed derived from it, so the inputs
+    /// do not depend on the number of threads
+    pub seed: Option<u64>,
+    /// Threads measuring different sizes and aspect ratios at the same time (0 for one per core),
+    /// each timing its multiplications on a single thread; shared caches and memory bandwidth
+    /// make the times noisier than with 1, the default. Ignored when measuring memory
+    pub threads: usize,
+    /// Pin every worker thread to its own core (if there are more threads than cores,
+    /// several share one); only with `threads` other than 1
+    pub pin_threads: bool,
+    /// Untimed multiplications of every algorithm before the timed runs of each size,
+    /// to warm up the caches and the allocator
+    pub warmup_runs: usize,
+    /// How the runs are combined into the main time column
+    pub aggregation: Aggregation,
+    /// Also measure the peak heap usage of each algorithm on the first input pair;
+    /// ignored without the `mem_profile` feature, and requires its `CountingAllocator`
+    /// as the global allocator
+    pub measure_memory: bool,
+    /// Also count the floating-point operations of each algorithm on the first input pair;
+    /// ignored without the `op_count` feature
+    pub count_ops: bool,
+    /// Also write the time of every run to `all_runs.csv`, one row per size, algorithm and run
+    pub raw_runs: bool,
+    /// Time budget of every algorithm in each cell: its runs stop once their times add up
+    /// to the budget (after at least one), and it is skipped (`skipped` in the results)
+    /// if a single run is predicted to exceed it from the smaller cells; `None` for no limit
+    pub max_time_per_cell: Option<Duration>,
+    /// Also check every algorithm against the naive product of every input pair (untimed),
+    /// failing with [`PolyError::VerificationFailed`] beyond [`VERIFY_TOLERANCE`]
+    pub verify: bool,
+    /// Also measure the error of every algorithm against the exact product of integer inputs
+    /// at each size (untimed) and write it to `accuracy.csv`, see [`measure_accuracy`]
+    pub accuracy: bool,
+    /// Numbers of points at which [`run_evaluation_benchmark`] times the evaluation strategies
+    /// for every size (the command-line tool runs it after the multiplications unless empty)
+    pub evaluation_points: Vec<usize>,
+    /// Coefficient sizes in bits at which [`run_exact_benchmark`] times the exact integer
+    /// multiplications for every size (the command-line tool runs it after the multiplications unless empty)
+    pub exact_bits: Vec<u32>,
+    /// Also append every run to this SQLite database, see [`ResultStore`](crate::ResultStore);
+    /// requires the `sqlite` feature
+    pub database: Option<PathBuf>,
+    /// Name of the machine recorded in the database (the host name by default)
+    pub machine: Option<String>,
+    /// Continue an interrupted run from `checkpoint.csv` in the output directory: its cells
+    /// with the same number of runs are read back instead of timed again, with the seed of
+    /// the interrupted run, and the result files are rewritten with all cells. Fails if the
+    /// checkpoint has other result columns, another seed than [`BenchmarkConfig::seed`], or
+    /// other threads, warm-up runs, time budget, memory, operation count or verification settings;
+    /// without a checkpoint, every cell is measured. A completed run removes its checkpoint
+    pub resume: bool,
+}
+
+/// Largest deviation from the naive product accepted by [`BenchmarkConfig::verify`],
+/// relative to the largest coefficient of the naive product
+pub const VERIFY_TOLERANCE: f64 = 1e-8;
+
+impl Default for BenchmarkConfig {
+    /// Powers of two up to 2^17 with intermediate sizes, naive, Cook-Tooms and
+    /// thresholds 2, 4, ..., 2^15, written to `output_data`
+    fn default() -> Self {
+        // Generate a range of sizes including powers of 2 and intermediate values
+        let mut sizes = Vec::new();
+        for i in 0..=17 {
+            let power_of_two = 1 << i;
+            sizes.push(power_of_two);
+
+            // Add an intermediate size between powers of 2
+            if i > 3 {
+                sizes.push(power_of_two + (power_of_two / 2));
+            }
+        }
+
+        let mut algorithms = vec![PolynomialMultAlg::Naive, PolynomialMultAlg::CookTooms];
+        algorithms.extend((1..16).map(|i| PolynomialMultAlg::Thresholded(1 << i)));
+
+        BenchmarkConfig {
+            sizes,
+            aspect_ratios: vec![1],
+            distributions: vec![InputDistribution::Uniform],
+            algorithms,
+            custom_algorithms: Vec::new(),
+            output_dir: PathBuf::from("output_data"),
+            format: OutputFormat::Csv,
+            seed: None,
+            threads: 1,
+            pin_threads: false,
+            warmup_runs: 1,
+            aggregation: Aggregation::Mean,
+            measure_memory: false,
+            count_ops: false,
+            raw_runs: false,
+            max_time_per_cell: None,
+            verify: false,
+            accuracy: false,
+            evaluation_points: Vec::new(),
+            exact_bits: Vec::new(),
+            database: None,
+            machine: None,
+            resume: false,
+        }
+    }
+}
+
+impl BenchmarkConfig {
+    /// Check the settings, failing with [`PolyError::InvalidArgument`] on a zero size,
+    /// aspect ratio or number of evaluation points, a sparse density outside of `(0, 1]`,
+    /// coefficient bits outside of `1..=62`, a zero time per cell,
+    /// a trimmed fraction outside of `[0, 0.5)`, or a database without the `sqlite` feature
+    pub fn validate(&self) -> Result<(), PolyError> {
+        let invalid = |message: &str| Err(PolyError::InvalidArgument(message.to_string()));
+        if self.sizes.contains(&0) {
+            return invalid("sizes must be positive");
+        }
+        if self.aspect_ratios.contains(&0) {
+            return invalid("aspect ratios must be positive");
+        }
+        if self.evaluation_points.contains(&0) {
+            return invalid("numbers of evaluation points must be positive");
+        }
+        if (self.distributions.iter())
+            .any(|d| matches!(d, InputDistribution::Sparse(p) if !(*p > 0.0 && *p <= 1.0)))
+        {
+            return invalid("sparse densities must be in (0, 1]");
+        }
+        if self.exact_bits.iter().any(|bits| !(1..=62).contains(bits)) {
+            return invalid("coefficient bits must be in 1..=62");
+        }
+        if self.max_time_per_cell == Some(Duration::ZERO) {
+            return invalid("the time per cell must be positive");
+        }
+        if let Aggregation::TrimmedMean(fraction) = self.aggregation {
+            if !(0.0..0.5).contains(&fraction) {
+                return invalid("trimmed fraction must be in [0, 0.5)");
+            }
+        }
+        if cfg!(not(feature = "sqlite")) && self.database.is_some() {
+            return invalid("writing to a database requires the `sqlite` feature");
+        }
+        Ok(())
+    }
+
+    /// Parse a TOML benchmark file, taking the defaults for missing keys
+    ///
+    /// The keys are named like the fields of [`BenchmarkConfig`], except that `algorithms`
+    /// holds names such as `naive` or `hybrid:256:64`, with a bare `thresholded` expanded
+    /// to one entry per `thresholds` value, `distributions` holds names such as `gaussian`
+    /// or `sparse:0.05` (see [`InputDistribution`]), `max_time_per_cell` is in seconds,
+    /// `format` is `csv` or `json`, `aggregation` is
+    /// `mean`, `trimmed-mean` (with the fraction `trim`, 0.1 by default) or `min`,
+    /// and the `[runs]` table holds the [`RunsPerSize`] fields. Requires the `config` feature.
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::benchmark::{BenchmarkConfig, RunsPerSize};
+    /// let (config, runs) = BenchmarkConfig::from_toml(
+    ///     "sizes = [256, 1024]\n\
+    ///      algorithms = [\"naive\", \"thresholded\"]\n\
+    ///      thresholds = [32, 64]\n\
+    ///      seed = 1\n\
+    ///      [runs]\n\
+    ///      min_runs = 5\n\
+    ///      total_size = 10000\n",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(config.sizes, vec![256, 1024]);
+    /// assert_eq!(config.algorithms.len(), 3);
+    /// assert_eq!(runs.runs(256), 39);
+    /// ```
+    #[cfg(feature = "config")]
+    pub fn from_toml(text: &str) -> Result<(BenchmarkConfig, RunsPerSize), PolyError> {
+        let file: ConfigFile =
+            toml::from_str(text).map_err(|e| PolyError::InvalidFormat(e.to_string()))?;
+        let invalid = |message: String| PolyError::InvalidFormat(message);
+        let defaults = BenchmarkConfig::default();
+        let algorithms = match file.algorithms {
+            Some(names) => {
+                let thresholds = file.thresholds.unwrap_or_else(|| thresholds(&defaults));
+                expand_algorithms(&names, &thresholds)?
+            }
+            None => match file.thresholds {
+                Some(thresholds) => (defaults.algorithms.iter())
+                    .filter(|alg| !matches!(alg, PolynomialMultAlg::Thresholded(_)))
+                    .copied()
+                    .chain(thresholds.into_iter().map(PolynomialMultAlg::Thresholded))
+                    .collect(),
+                None => defaults.algorithms,
+            },
+        };
+        let config = BenchmarkConfig {
+            sizes: file.sizes.unwrap_or(defaults.sizes),
+            aspect_ratios: file.aspect_ratios.unwrap_or(defaults.aspect_ratios),
+            distributions: match file.distributions {
+                Some(names) => (names.iter())
+                    .map(|name| name.parse())
+                    .collect::<Result<_, _>>()?,
+                None => defaults.distributions,
+            },
+            algorithms,
+            custom_algorithms: Vec::new(),
+            output_dir: file.output_dir.unwrap_or(defaults.output_dir),
+            format: match file.format.as_deref() {
+                None | Some("csv") => OutputFormat::Csv,
+                Some("json") => OutputFormat::Json,
+                Some(format) => return Err(invalid(format!("unknown format `{}`", format))),
+            },
+            seed: file.seed,
+            threads: file.threads.unwrap_or(defaults.threads),
+            pin_threads: file.pin_threads.unwrap_or(defaults.pin_threads),
+            warmup_runs: file.warmup_runs.unwrap_or(defaults.warmup_runs),
+            aggregation: match file.aggregation.as_deref() {
+                None | Some("mean") => Aggregation::Mean,
+                Some("trimmed-mean") => Aggregation::TrimmedMean(file.trim.unwrap_or(0.1)),
+                Some("min") => Aggregation::Min,
+                Some(aggregation) => {
+                    return Err(invalid(format!("unknown aggregation `{}`", aggregation)))
+                }
+            },
+            measure_memory: file.measure_memory.unwrap_or(defaults.measure_memory),
+            count_ops: file.count_ops.unwrap_or(defaults.count_ops),
+            raw_runs: file.raw_runs.unwrap_or(defaults.raw_runs),
+            max_time_per_cell: match file.max_time_per_cell {
+                Some(seconds) => Some(Duration::try_from_secs_f64(seconds).map_err(|_| {
+                    invalid(format!("invalid time per cell {} (in seconds)", seconds))
+                })?),
+                None => defaults.max_time_per_cell,
+            },
+            verify: file.verify.unwrap_or(defaults.verify),
+            accuracy: file.accuracy.unwrap_or(defaults.accuracy),
+            evaluation_points: file.evaluation_points.unwrap_or(defaults.evaluation_points),
+            exact_bits: file.exact_bits.unwrap_or(defaults.exact_bits),
+            database: file.database,
+            machine: file.machine,
+            resume: file.resume.unwrap_or(defaults.resume),
+        };
+        config.validate()?;
+        Ok((config, file.runs.unwrap_or_default()))
+    }
+
+    /// Read a TOML benchmark file, see [`BenchmarkConfig::from_toml`]
+    #[cfg(feature = "config")]
+    pub fn from_toml_file(
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<(BenchmarkConfig, RunsPerSize), PolyError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| PolyError::Io(format!("{}: {}", path.display(), e)))?;
+        Self::from_toml(&text).map_err(|e| match e {
+            PolyError::InvalidFormat(message) => {
+                PolyError::InvalidFormat(format!("{}: {}", path.display(), message))
+            }
+            e => e,
+        })
+    }
+}
+
+/// Thresholds of the thresholded algorithms of `config`
+#[cfg(feature = "config")]
+fn thresholds(config: &BenchmarkConfig) -> Vec<usize> {
+    (config.algorithms.iter())
+        .filter_map(|alg| match alg {
+            PolynomialMultAlg::Thresholded(threshold) => Some(*threshold),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Parse algorithm names, with one entry per threshold for a bare `thresholded`
+#[cfg(feature = "config")]
+fn expand_algorithms(
+    names: &[String],
+    thresholds: &[usize],
+) -> Result<Vec<PolynomialMultAlg>, PolyError> {
+    let mut algorithms = Vec::new();
+    for name in names {
+        if name == "thresholded" {
+            algorithms.extend(
+                thresholds
+                    .iter()
+                    .map(|&t| PolynomialMultAlg::Thresholded(t)),
+            );
+        } else {
+            algorithms.push(name.parse()?);
+        }
+    }
+    Ok(algorithms)
+}
+
+/// Keys of a TOML benchmark file, see [`BenchmarkConfig::from_toml`]
+#[cfg(feature = "config")]
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    sizes: Option<Vec<usize>>,
+    aspect_ratios: Option<Vec<usize>>,
+    distributions: Option<Vec<String>>,
+    algorithms: Option<Vec<String>>,
+    thresholds: Option<Vec<usize>>,
+    runs: Option<RunsPerSize>,
+    output_dir: Option<PathBuf>,
+    format: Option<String>,
+    seed: Option<u64>,
+    threads: Option<usize>,
+    pin_threads: Option<bool>,
+    warmup_runs: Option<usize>,
+    aggregation: Option<String>,
+    trim: Option<f64>,
+    measure_memory: Option<bool>,
+    count_ops: Option<bool>,
+    raw_runs: Option<bool>,
+    max_time_per_cell: Option<f64>,
+    verify: Option<bool>,
+    accuracy: Option<bool>,
+    evaluation_points: Option<Vec<usize>>,
+    exact_bits: Option<Vec<u32>>,
+    database: Option<PathBuf>,
+    machine: Option<String>,
+    resume: Option<bool>,
+}
+
+/// Number of timed runs at each size, `max(min_runs, total_size / size)`,
+/// so that small and noisy sizes get more runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "config",
+    derive(serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct RunsPerSize {
+    /// Runs of every size
+    pub min_runs: usize,
+    /// Total number of coefficients shared by the runs of one size
+    /// (0 for `min_runs` runs at every size)
+    pub total_size: usize,
+}
+
+impl Default for RunsPerSize {
+    /// `max(10, 100000 / size)` runs
+    fn default() -> Self {
+        RunsPerSize {
+            min_runs: 10,
+            total_size: 100000,
+        }
+    }
+}
+
+impl RunsPerSize {
+    /// The same number of runs at every size
+    pub fn fixed(runs: usize) -> Self {
+        RunsPerSize {
+            min_runs: runs,
+            total_size: 0,
+        }
+    }
+
+    /// Number of runs of inputs with `size` coefficients
+    pub fn runs(&self, size: usize) -> usize {
+        self.min_runs.max(self.total_size / size)
+    }
+}
+
+/// Timings of all algorithms at one input size
+#[derive(Debug, Clone)]
+pub struct SizeResult {
+    /// Number of coefficients of the first operand
+    pub size: usize,
+    /// Number of coefficients of the second operand (equal to `size` for an aspect ratio of 1)
+    pub other_size: usize,
+    /// Configured ratio of the operand lengths
+    pub aspect_ratio: usize,
+    /// Distribution of the coefficients of both operands
+    pub distribution: InputDistribution,
+    /// Runs requested for every algorithm
+    pub runs: usize,
+    /// Time of every run in seconds, indexed as `times[algorithm][run]` following the order
+    /// of [`BenchmarkConfig::algorithms`], then of [`BenchmarkConfig::custom_algorithms`];
+    /// with [`BenchmarkConfig::max_time_per_cell`], an algorithm may have fewer runs,
+    /// or none if it was skipped
+    pub times: Vec<Vec<f64>>,
+    /// Peak heap usage in bytes of each algorithm, empty unless memory is measured
+    pub peak_bytes: Vec<usize>,
+    /// Floating-point operations of each algorithm, empty unless operations are counted
+    pub op_counts: Vec<OpCounts>,
+    /// Largest deviation of each algorithm from the naive product over all runs,
+    /// relative to its largest coefficient; empty unless products are verified
+    pub max_discrepancy: Vec<f64>,
+}
+
+impl SizeResult {
+    /// Mean time in seconds of the algorithm at `index`
+    pub fn mean(&self, index: usize) -> f64 {
+        let runs = self.times[index].len() as f64;
+        self.times[index].iter().map(|t| t / runs).sum()
+    }
+
+    /// Time of the algorithm at `index` combined according to `aggregation`
+    ///
+    /// Panics if the algorithm was skipped.
+    pub fn aggregate(&self, index: usize, aggregation: Aggregation) -> f64 {
+        aggregation.apply(&self.times[index])
+    }
+
+    /// Statistical summary of the times of the algorithm at `index`
+    ///
+    /// Panics if the algorithm was skipped.
+    pub fn summary(&self, index: usize) -> Summary {
+        Summary::new(&self.times[index])
+    }
+
+    /// Whether the algorithm at `index` was not run, as a single run was predicted
+    /// to exceed [`BenchmarkConfig::max_time_per_cell`]
+    pub fn skipped(&self, index: usize) -> bool {
+        self.times[index].is_empty()
+    }
+}
+
+/// Statistics of the run times of one algorithm at one size, in seconds
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Summary {
+    /// Fastest run
+    pub min: f64,
+    /// Median run
+    pub median: f64,
+    /// Mean of all runs
+    pub mean: f64,
+    /// Sample standard deviation (zero for a single run)
+    pub std_dev: f64,
+    /// Half width of the 95% confidence interval of the mean,
+    /// i.e. the interval is `mean ± ci95`
+    pub ci95: f64,
+}
+
+impl Summary {
+    /// Summarize the times of a nonempty set of runs
+    pub fn new(times: &[f64]) -> Summary {
+        assert!(!times.is_empty(), "Cannot summarize zero runs");
+        let n = times.len();
+        let mut sorted = times.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let median = if n % 2 == 1 {
+            sorted[n / 2]
+        } else {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0
+        };
+        let mean = times.iter().map(|t| t / n as f64).sum::<f64>();
+        let std_dev = if n > 1 {
+            let variance = times.iter().map(|t| (t - mean) * (t - mean)).sum::<f64>();
+            (variance / (n - 1) as f64).sqrt()
+        } else {
+            0.0
+        };
+        Summary {
+            min: sorted[0],
+            median,
+            mean,
+            std_dev,
+            ci95: t_975(n.saturating_sub(1)) * std_dev / (n as f64).sqrt(),
+        }
+    }
+}
+
+/// Power law `time ≈ coefficient · size^exponent` fitted by least squares on the log-log scale
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ComplexityFit {
+    /// Estimated exponent, about 2 for the naive algorithm and log₃5 ≈ 1.465 for Toom-3
+    pub exponent: f64,
+    /// Time in seconds extrapolated to size 1
+    pub coefficient: f64,
+    /// Coefficient of determination of the fit of `ln(time)` against `ln(size)`
+    pub r_squared: f64,
+}
+
+impl ComplexityFit {
+    /// Fit `(size, time)` points, ignoring those without positive coordinates
+    ///
+    /// `None` unless at least two distinct sizes remain.
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::benchmark::ComplexityFit;
+    /// let points: Vec<_> = [10.0, 100.0, 1000.0].iter().map(|&n| (n, 3e-9 * n * n)).collect();
+    /// let fit = ComplexityFit::new(&points).unwrap();
+    /// assert!((fit.exponent - 2.0).abs() < 1e-12);
+    /// assert!((fit.r_squared - 1.0).abs() < 1e-12);
+    /// ```
+    pub fn new(points: &[(f64, f64)]) -> Option<ComplexityFit> {
+        let logs: Vec<(f64, f64)> = (points.iter())
+            .filter(|&&(size, time)| size > 0.0 && time > 0.0)
+            .map(|&(size, time)| (size.ln(), time.ln()))
+            .collect();
+        let n = logs.len() as f64;
+        let mean_x = logs.iter().map(|p| p.0).sum::<f64>() / n;
+        let mean_y = logs.iter().map(|p| p.1).sum::<f64>() / n;
+        let sxx = logs.iter().map(|p| (p.0 - mean_x).powi(2)).sum::<f64>();
+        let sxy = logs
+            .iter()
+            .map(|p| (p.0 - mean_x) * (p.1 - mean_y))
+            .sum::<f64>();
+        let syy = logs.iter().map(|p| (p.1 - mean_y).powi(2)).sum::<f64>();
+        if logs.len() < 2 || sxx == 0.0 {
+            return None;
+        }
+        let exponent = sxy / sxx;
+        let residual = syy - exponent * sxy;
+        Some(ComplexityFit {
+            exponent,
+            coefficient: (mean_y - exponent * mean_x).exp(),
+            // constant times are fitted exactly by a zero exponent
+            r_squared: if syy > 0.0 { 1.0 - residual / syy } else { 1.0 },
+        })
+    }
+}
+
+/// Fit the complexity of every algorithm to its aggregated times on equal operand lengths
+/// of at least `min_size` coefficients, in the order of the result columns
+///
+/// Small sizes are dominated by constant overheads, so `min_size` excludes them from the fit.
+/// Algorithms with fewer than two such sizes are left out. With several input distributions,
+/// each is fitted separately, named like `naive_time_gaussian`.
+pub fn fit_complexity(
+    config: &BenchmarkConfig,
+    results: &[SizeResult],
+    min_size: usize,
+) -> Vec<(String, ComplexityFit)> {
+    let names = column_names(config);
+    (config.distributions.iter())
+        .flat_map(|&distribution| {
+            let square: Vec<&SizeResult> = (results.iter())
+                .filter(|r| r.aspect_ratio == 1 && r.size >= min_size)
+                .filter(|r| r.distribution == distribution)
+                .collect();
+            (names.iter().enumerate())
+                .filter_map(|(j, name)| {
+                    let points: Vec<(f64, f64)> = (square.iter())
+                        .filter(|r| !r.skipped(j))
+                        .map(|r| (r.size as f64, r.aggregate(j, config.aggregation)))
+                        .collect();
+                    let name = if sweeps_distributions(config) {
+                        format!("{}_{}", name, distribution)
+                    } else {
+                        name.clone()
+                    };
+                    ComplexityFit::new(&points).map(|fit| (name, fit))
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Write the fitted complexities to `complexity.csv` in the output directory
+pub fn write_complexity(
+    config: &BenchmarkConfig,
+    fits: &[(String, ComplexityFit)],
+) -> Result<(), PolyError> {
+    let file = std::fs::File::create(config.output_dir.join("complexity.csv"))?;
+    let mut file = std::io::BufWriter::new(file);
+    writeln!(file, "{}", text_header("complexity"))?;
+    writeln!(file, "algorithm,exponent,coefficient,r_squared")?;
+    for (name, fit) in fits {
+        writeln!(
+            file,
+            "{},{},{},{}",
+            name, fit.exponent, fit.coefficient, fit.r_squared
+        )?;
+    }
+    file.flush()?;
+    Ok(())
+}
+
+/// Bound on the magnitude of the integer coefficients of the accuracy inputs: the exact product
+/// fits in `i128`, but its coefficients need up to `40 + log₂ n` bits, more than an `f64` has
+const ACCURACY_MAGNITUDE: i64 = 1 << 20;
+
+/// Largest error of every algorithm against the exact product at one input size
+#[derive(Debug, Clone)]
+pub struct AccuracyResult {
+    /// Number of coefficients of both operands
+    pub size: usize,
+    /// Largest deviation of each algorithm from the exact product, relative to its largest
+    /// coefficient, in the order of the result columns
+    pub max_relative_error: Vec<f64>,
+}
+
+/// Measure the error of every algorithm of `config` on one pair of random operands per size
+///
+/// The operands of each size have integer coefficients in `[-2²⁰, 2²⁰]` drawn from `seed`,
+/// so their product is computed exactly by [`multiply_exact`](crate::multiply_exact). The error of an
+/// algorithm is its largest deviation from that product relative to the largest coefficient,
+/// which includes the rounding of sums above 2⁵³ even for the naive algorithm.
+/// ## Example
+/// ```
+/// use mult_polynomial::benchmark::{measure_accuracy, BenchmarkConfig};
+/// use mult_polynomial::PolynomialMultAlg;
+/// let config = BenchmarkConfig {
+///     sizes: vec![16, 500],
+///     algorithms: vec![PolynomialMultAlg::Naive, PolynomialMultAlg::CookTooms],
+///     ..BenchmarkConfig::default()
+/// };
+/// let accuracy = measure_accuracy(&config, 1);
+/// assert_eq!(accuracy[0].max_relative_error[0], 0.0); // small sums are exact
+/// assert!(accuracy[1].max_relative_error[1] < 1e-12);
+/// ```
+pub fn measure_accuracy(config: &BenchmarkConfig, seed: u64) -> Vec<AccuracyResult> {
+    let algorithms = benchmarked_algorithms(config);
+    (config.sizes.iter().enumerate())
+        .map(|(i, &size)| {
+            let mut rng = StdRng::seed_from_u64(cell_seed(seed, i));
+            let mut operand = || -> Vec<i64> {
+                (0..size)
+                    .map(|_| rng.gen_range(-ACCURACY_MAGNITUDE..=ACCURACY_MAGNITUDE))
+                    .collect()
+            };
+            let (a, b) = (operand(), operand());
+            let exact = multiply_exact(&a, &b).expect("accuracy inputs fit in i128");
+            let (a, b): (Vec<f64>, Vec<f64>) = (
+                a.iter().map(|&x| x as f64).collect(),
+                b.iter().map(|&x| x as f64).collect(),
+            );
+            let max_relative_error = (algorithms.iter())
+                .map(|algorithm| relative_error(&algorithm.multiply(&a, &b), &exact))
+                .collect();
+            AccuracyResult {
+                size,
+                max_relative_error,
+            }
+        })
+        .collect()
+}
+
+/// Largest deviation of `product` from `exact` relative to the largest coefficient of `exact`
+fn relative_error(product: &[f64], exact: &[i128]) -> f64 {
+    let coeff = |k: usize| product.get(k).copied().unwrap_or(0.0);
+    let deviation = (0..product.len().max(exact.len()))
+        .map(|k| {
+            let exact = exact.get(k).copied().unwrap_or(0);
+            // split the exact coefficient into its nearest float and the remainder,
+            // so the difference is not rounded before it is taken
+            let high = exact as f64;
+            let low = (exact - high as i128) as f64;
+            ((coeff(k) - high) - low).abs()
+        })
+        .fold(0.0, f64::max);
+    let scale = exact.iter().fold(0.0, |m: f64, &c| m.max((c as f64).abs()));
+    if scale > 0.0 {
+        deviation / scale
+    } else {
+        deviation
+    }
+}
+
+/// Write the measured errors to `accuracy.csv` in the output directory,
+/// one row per size and algorithm
+pub fn write_accuracy(
+    config: &BenchmarkConfig,
+    results: &[AccuracyResult],
+) -> Result<(), PolyError> {
+    let file = std::fs::File::create(config.output_dir.join("accuracy.csv"))?;
+    let mut file = std::io::BufWriter::new(file);
+    writeln!(file, "{}", text_header("accuracy"))?;
+    writeln!(file, "size,algorithm,max_relative_error")?;
+    let names = column_names(config);
+    for result in results {
+        for (name, error) in names.iter().zip(&result.max_relative_error) {
+            writeln!(file, "{},{},{:e}", result.size, name, error)?;
+        }
+    }
+    file.flush()?;
+    Ok(())
+}
+
+/// Way of evaluating a polynomial at many points, timed by [`run_evaluation_benchmark`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EvaluationStrategy {
+    /// [`Polynomial::evaluate`] at one point after another, `O(n)` each
+    Horner,
+    /// [`Polynomial::evaluate_fast`] at one point after another, `O(n)` each
+    /// with more independent operations
+    Estrin,
+    /// [`Polynomial::evaluate_multipoint`] at all points at once with a subproduct tree
+    Multipoint,
+    /// [`Polynomial::evaluate_slice`] at blocks of points side by side, `O(n)` each
+    Slice,
+}
+
+impl EvaluationStrategy {
+    /// All strategies, in the order of the columns of `evaluation.csv`
+    pub const ALL: [EvaluationStrategy; 4] = [
+        EvaluationStrategy::Horner,
+        EvaluationStrategy::Estrin,
+        EvaluationStrategy::Multipoint,
+        EvaluationStrategy::Slice,
+    ];
+
+    /// CSV column name of the timings of the strategy
+    pub fn column_name(self) -> &'static str {
+        match self {
+            EvaluationStrategy::Horner => "horner_time",
+            EvaluationStrategy::Estrin => "estrin_time",
+            EvaluationStrategy::Multipoint => "multipoint_time",
+            EvaluationStrategy::Slice => "slice_time",
+        }
+    }
+
+    /// Values of `p` at `points`
+    pub fn evaluate(self, p: &Polynomial, points: &[f64]) -> Vec<f64> {
+        match self {
+            EvaluationStrategy::Horner => points.iter().map(|&x| p.evaluate(x)).collect(),
+            EvaluationStrategy::Estrin => points.iter().map(|&x| p.evaluate_fast(x)).collect(),
+            EvaluationStrategy::Multipoint => p.evaluate_multipoint(points),
+            EvaluationStrategy::Slice => p.evaluate_slice(points),
+        }
+    }
+}
+
+/// Timings of all evaluation strategies at one size and number of points
+#[derive(Debug, Clone)]
+pub struct EvaluationResult {
+    /// Number of coefficients of the polynomial
+    pub size: usize,
+    /// Number of points it is evaluated at
+    pub points: usize,
+    /// Time of every run in seconds, indexed as `times[strategy][run]` following
+    /// [`EvaluationStrategy::ALL`]
+    pub times: Vec<Vec<f64>>,
+}
+
+impl EvaluationResult {
+    /// Time of the strategy at `index`, combined over the runs by `aggregation`
+    pub fn aggregate(&self, index: usize, aggregation: Aggregation) -> f64 {
+        aggregation.apply(&self.times[index])
+    }
+}
+
+/// Time every [`EvaluationStrategy`] on a random polynomial of each size of `config`
+/// at each number of points of [`BenchmarkConfig::evaluation_points`]
+///
+/// Each size and number of points takes `runs_per_test(size)` timed runs after
+/// [`BenchmarkConfig::warmup_runs`] untimed ones, on coefficients in `[-1, 1]` and points in
+/// `[-1/2, 1/2]`, where multipoint evaluation is accurate. The aggregated times are written to
+/// `evaluation.csv` in the output directory, one row per size and number of points,
+/// and also returned.
+/// ## Example
+/// ```
+/// use mult_polynomial::benchmark::{run_evaluation_benchmark, BenchmarkConfig};
+/// let config = BenchmarkConfig {
+///     sizes: vec![64, 256],
+///     evaluation_points: vec![1, 100],
+///     output_dir: std::env::temp_dir().join("mult_polynomial_evaluation_doc"),
+///     ..BenchmarkConfig::default()
+/// };
+/// let results = run_evaluation_benchmark(&config, &|_| 3).unwrap();
+/// assert_eq!(results.len(), 4);
+/// assert_eq!((results[3].size, results[3].points), (256, 100));
+/// ```
+pub fn run_evaluation_benchmark(
+    config: &BenchmarkConfig,
+    runs_per_test: &dyn Fn(usize) -> usize,
+) -> Result<Vec<EvaluationResult>, PolyError> {
+    config.validate()?;
+    std::fs::create_dir_all(&config.output_dir)?;
+    let cells: Vec<(usize, usize)> = (config.sizes.iter())
+        .flat_map(|&size| config.evaluation_points.iter().map(move |&k| (size, k)))
+        .collect();
+    let runs: Vec<usize> = cells.iter().map(|&(size, _)| runs_per_test(size)).collect();
+    if runs.contains(&0) {
+        return Err(PolyError::InvalidArgument(
+            "number of runs per test must be positive".to_string(),
+        ));
+    }
+    let seed = config.seed.unwrap_or_else(|| StdRng::from_entropy().gen());
+    let file = std::fs::File::create(config.output_dir.join("evaluation.csv"))?;
+    let mut file = std::io::BufWriter::new(file);
+    writeln!(file, "{}", text_header("evaluation"))?;
+    write!(file, "size,points")?;
+    for strategy in EvaluationStrategy::ALL {
+        write!(file, ",{}", strategy.column_name())?;
+    }
+    writeln!(file)?;
+
+    let cell_work = |(size, k): (usize, usize)| (size as u64 * k as u64).max(1);
+    let total_work = (cells.iter().zip(&runs))
+        .map(|(&cell, &runs)| runs as u64 * cell_work(cell))
+        .sum();
+    let progress = Progress::new(total_work);
+    let mut results = Vec::with_capacity(cells.len());
+    for (i, (&(size, k), &runs)) in cells.iter().zip(&runs).enumerate() {
+        let label = format!("evaluation of size {} at {} points", size, k);
+        let mut rng = StdRng::seed_from_u64(cell_seed(seed, i));
+        let p = Polynomial::random_with_rng(-1.0, 1.0, size, &mut rng);
+        let points: Vec<f64> = (0..k).map(|_| rng.gen_range(-0.5..=0.5)).collect();
+        for strategy in EvaluationStrategy::ALL {
+            for _ in 0..config.warmup_runs {
+                std::hint::black_box(strategy.evaluate(&p, &points));
+            }
+        }
+        let mut times = vec![Vec::with_capacity(runs); EvaluationStrategy::ALL.len()];
+        for run in 0..runs {
+            progress.run(&label, run, runs);
+            for (strategy, times) in EvaluationStrategy::ALL.iter().zip(&mut times) {
+                let timer = Instant::now();
+                std::hint::black_box(strategy.evaluate(&p, &points));
+                times.push(timer.elapsed().as_secs_f64());
+            }
+            progress.advance(cell_work((size, k)));
+        }
+        progress.finish_cell(&label);
+        let result = EvaluationResult {
+            size,
+            points: k,
+            times,
+        };
+        write!(file, "{},{}", size, k)?;
+        for j in 0..EvaluationStrategy::ALL.len() {
+            write!(file, ",{}", result.aggregate(j, config.aggregation))?;
+        }
+        writeln!(file)?;
+        results.push(result);
+    }
+    progress.finish();
+    file.flush()?;
+    Ok(results)
+}
+
+/// Exact multiplication of integer polynomials, timed by [`run_exact_benchmark`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExactStrategy {
+    /// Schoolbook multiplication in `i128`, `O(n²)`
+    Schoolbook,
+    /// [`multiply_exact`](crate::multiply_exact): NTTs modulo word-sized primes and the CRT
+    Crt,
+    /// [`multiply_kronecker`](crate::multiply_kronecker): one product of packed big integers
+    Kronecker,
+}
+
+impl ExactStrategy {
+    /// All strategies, in the order of the columns of `exact.csv`
+    pub const ALL: [ExactStrategy; 3] = [
+        ExactStrategy::Schoolbook,
+        ExactStrategy::Crt,
+        ExactStrategy::Kronecker,
+    ];
+
+    /// CSV column name of the timings of the strategy
+    pub fn column_name(self) -> &'static str {
+        match self {
+            ExactStrategy::Schoolbook => "schoolbook_time",
+            ExactStrategy::Crt => "crt_time",
+            ExactStrategy::Kronecker => "kronecker_time",
+        }
+    }
+
+    /// Product of `a` and `b`, failing if its coefficients may not fit in `i128`
+    pub fn multiply(self, a: &[i64], b: &[i64]) -> Result<Vec<i128>, PolyError> {
+        match self {
+            ExactStrategy::Schoolbook if a.is_empty() || b.is_empty() => Ok(Vec::new()),
+            ExactStrategy::Schoolbook => {
+                product_bound(a, b)?;
+                Ok(schoolbook_exact(a, b))
+            }
+            ExactStrategy::Crt => multiply_exact(a, b),
+            ExactStrategy::Kronecker => multiply_kronecker(a, b),
+        }
+    }
+}
+
+/// Timings of all exact strategies at one size and coefficient size
+#[derive(Debug, Clone)]
+pub struct ExactResult {
+    /// Number of coefficients of both operands
+    pub size: usize,
+    /// Bits of the magnitude of the coefficients
+    pub bits: u32,
+    /// Time of every run in seconds, indexed as `times[strategy][run]` following
+    /// [`ExactStrategy::ALL`]
+    pub times: Vec<Vec<f64>>,
+}
+
+impl ExactResult {
+    /// Time of the strategy at `index`, combined over the runs by `aggregation`
+    pub fn aggregate(&self, index: usize, aggregation: Aggregation) -> f64 {
+        aggregation.apply(&self.times[index])
+    }
+}
+
+/// Time every [`ExactStrategy`] on random integer polynomials of each size of `config`
+/// with coefficients of each number of bits of [`BenchmarkConfig::exact_bits`]
+///
+/// Coefficients are uniform in `[-(2ᵇ - 1), 2ᵇ - 1]` for `b` bits; each cell takes `runs_per_test(size)`
+/// timed runs after [`BenchmarkConfig::warmup_runs`] untimed ones. Fails with
+/// [`PolyError::InvalidArgument`] if a product may not fit in `i128` (e.g. 62 bits at size 16).
+/// The aggregated times are written to `exact.csv` in the output directory, one row per size
+/// and number of bits, and also returned.
+/// ## Example
+/// ```
+/// use mult_polynomial::benchmark::{run_exact_benchmark, BenchmarkConfig};
+/// let config = BenchmarkConfig {
+///     sizes: vec![64, 256],
+///     exact_bits: vec![8, 40],
+///     output_dir: std::env::temp_dir().join("mult_polynomial_exact_doc"),
+///     ..BenchmarkConfig::default()
+/// };
+/// let results = run_exact_benchmark(&config, &|_| 3).unwrap();
+/// assert_eq!(results.len(), 4);
+/// assert_eq!((results[3].size, results[3].bits), (256, 40));
+/// ```
+pub fn run_exact_benchmark(
+    config: &BenchmarkConfig,
+    runs_per_test: &dyn Fn(usize) -> usize,
+) -> Result<Vec<ExactResult>, PolyError> {
+    config.validate()?;
+    std::fs::create_dir_all(&config.output_dir)?;
+    let cells: Vec<(usize, u32)> = (config.sizes.iter())
+        .flat_map(|&size| config.exact_bits.iter().map(move |&bits| (size, bits)))
+        .collect();
+    let runs: Vec<usize> = cells.iter().map(|&(size, _)| runs_per_test(size)).collect();
+    if runs.contains(&0) {
+        return Err(PolyError::InvalidArgument(
+            "number of runs per test must be positive".to_string(),
+        ));
+    }
+    let seed = config.seed.unwrap_or_else(|| StdRng::from_entropy().gen());
+    let file = std::fs::File::create(config.output_dir.join("exact.csv"))?;
+    let mut file = std::io::BufWriter::new(file);
+    writeln!(file, "{}", text_header("exact"))?;
+    write!(file, "size,bits")?;
+    for strategy in ExactStrategy::ALL {
+        write!(file, ",{}", strategy.column_name())?;
+    }
+    writeln!(file)?;
+
+    let total_work = (cells.iter().zip(&runs))
+        .map(|(&(size, _), &runs)| runs as u64 * size as u64)
+        .sum();
+    let progress = Progress::new(total_work);
+    let mut results = Vec::with_capacity(cells.len());
+    for (i, (&(size, bits), &runs)) in cells.iter().zip(&runs).enumerate() {
+        let label = format!("exact product of size {} with {} bits", size, bits);
+        let mut rng = StdRng::seed_from_u64(cell_seed(seed, i));
+        let magnitude = (1i64 << bits) - 1;
+        let mut operand = || -> Vec<i64> {
+            (0..size)
+                .map(|_| rng.gen_range(-magnitude..=magnitude))
+                .collect()
+        };
+        let (a, b) = (operand(), operand());
+        for strategy in ExactStrategy::ALL {
+            for _ in 0..config.warmup_runs {
+                std::hint::black_box(strategy.multiply(&a, &b)?);
+            }
+        }
+        let mut times = vec![Vec::with_capacity(runs); ExactStrategy::ALL.len()];
+        for run in 0..runs {
+            progress.run(&label, run, runs);
+            for (strategy, times) in ExactStrategy::ALL.iter().zip(&mut times) {
+                let timer = Instant::now();
+                std::hint::black_box(strategy.multiply(&a, &b)?);
+                times.push(timer.elapsed().as_secs_f64());
+            }
+            progress.advance(size as u64);
+        }
+        progress.finish_cell(&label);
+        let result = ExactResult { size, bits, times };
+        write!(file, "{},{}", size, bits)?;
+        for j in 0..ExactStrategy::ALL.len() {
+            write!(file, ",{}", result.aggregate(j, config.aggregation))?;
+        }
+        writeln!(file)?;
+        results.push(result);
+    }
+    progress.finish();
+    file.flush()?;
+    Ok(results)
+}
+
+/// 97.5% quantile of Student's t-distribution with `df` degrees of freedom
+fn t_975(df: usize) -> f64 {
+    const TABLE: [f64; 30] = [
+        12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228, 2.201, 2.179, 2.160,
+        2.145, 2.131, 2.120, 2.110, 2.101, 2.093, 2.086, 2.080, 2.074, 2.069, 2.064, 2.060, 2.056,
+        2.052, 2.048, 2.045, 2.042,
+    ];
+    match df {
+        0 => 0.0, // a single run has no spread to estimate
+        1..=30 => TABLE[df - 1],
+        31..=60 => 2.000,
+        61..=120 => 1.980,
+        _ => 1.960,
+    }
+}
+
+/// CSV column name of the timings of an algorithm
+pub fn column_name(algorithm: PolynomialMultAlg) -> String {
+    match algorithm {
+        PolynomialMultAlg::Naive => "naive_time".to_string(),
+        PolynomialMultAlg::NaiveCompensated => "naive_compensated_time".to_string(),
+        PolynomialMultAlg::CookTooms => "cook_tooms_time".to_string(),
+        PolynomialMultAlg::Thresholded(threshold) => format!("threshold_{}", threshold),
+        PolynomialMultAlg::Hybrid(config) => format!(
+            "hybrid_{}_{}",
+            config.toom3_threshold, config.karatsuba_threshold
+        ),
+    }
+}
+
+/// Run a single benchmark for a specific algorithm and input size
+pub fn benchmark_single(
+    algorithm: PolynomialMultAlg,
+    p1: &Polynomial,
+    p2: &Polynomial,
+) -> Duration {
+    let timer = Instant::now();
+
+    match algorithm {
+        PolynomialMultAlg::Naive => {
+            p1.multiply_naive(p2);
+        }
+        PolynomialMultAlg::NaiveCompensated => {
+            p1.multiply_naive_compensated(p2);
+        }
+        PolynomialMultAlg::CookTooms => {
+            p1.multiply_cook_tooms_k3(p2);
+        }
+        PolynomialMultAlg::Thresholded(threshold) => {
+            p1.multiply_thresholded(p2, threshold);
+        }
+        PolynomialMultAlg::Hybrid(config) => {
+            p1.multiply_hybrid(p2, config);
+        }
+    }
+
+    timer.elapsed()
+}
+
+/// Run `warmup_runs` untimed multiplications, then time a single one
+pub fn benchmark_single_warm(
+    algorithm: &dyn MultiplyAlgorithm,
+    p1: &Polynomial,
+    p2: &Polynomial,
+    warmup_runs: usize,
+) -> Duration {
+    for _ in 0..warmup_runs {
+        std::hint::black_box(algorithm.multiply(&p1.coeffs, &p2.coeffs));
+    }
+    let timer = Instant::now();
+    std::hint::black_box(algorithm.multiply(&p1.coeffs, &p2.coeffs));
+    timer.elapsed()
+}
+
+/// Built-in and custom algorithms of `config`, in the order of the result columns
+pub fn benchmarked_algorithms(config: &BenchmarkConfig) -> Vec<&(dyn MultiplyAlgorithm + Sync)> {
+    let built_in = (config.algorithms.iter()).map(|a| a as &(dyn MultiplyAlgorithm + Sync));
+    let custom = (config.custom_algorithms.iter()).map(|a| &**a as &(dyn MultiplyAlgorithm + Sync));
+    built_in.chain(custom).collect()
+}
+
+/// Column names of the built-in and custom algorithms of `config`
+pub fn column_names(config: &BenchmarkConfig) -> Vec<String> {
+    let built_in = config.algorithms.iter().map(|&a| column_name(a));
+    let custom = config.custom_algorithms.iter().map(|a| a.label());
+    built_in.chain(custom).collect()
+}
+
+/// Estimated work of one run on operands of `n` and `m` coefficients, in arbitrary units
+///
+/// Grows like Toom-3 on square inputs, `(n·m)^0.75`, so the progress bar and its estimated
+/// completion time account for large sizes taking much longer than small ones.
+fn run_work(n: usize, m: usize) -> u64 {
+    ((n as f64 * m as f64).powf(0.75) as u64).max(1)
+}
+
+/// Progress display of a benchmark: a progress bar with the elapsed and estimated remaining time
+/// with the `progress` feature, or a line per finished size without it
+struct Progress {
+    #[cfg(feature = "progress")]
+    bar: indicatif::ProgressBar,
+}
+
+impl Progress {
+    /// Progress over `total_work` units of [`run_work`]
+    #[cfg_attr(not(feature = "progress"), allow(unused_variables))]
+    fn new(total_work: u64) -> Progress {
+        #[cfg(feature = "progress")]
+        {
+            let bar = indicatif::ProgressBar::new(total_work);
+            bar.set_style(
+                indicatif::ProgressStyle::with_template(
+                    "[{elapsed_precise}] {wide_bar} {percent:>3}% ETA {eta_precise} {msg}",
+                )
+                .expect("valid progress bar template"),
+            );
+            Progress { bar }
+        }
+        #[cfg(not(feature = "progress"))]
+        Progress {}
+    }
+
+    /// Show that run `run` of `runs` of the cell `label` is starting
+    #[cfg_attr(not(feature = "progress"), allow(unused_variables))]
+    fn run(&self, label: &str, run: usize, runs: usize) {
+        #[cfg(feature = "progress")]
+        self.bar
+            .set_message(format!("{}, run {}/{}", label, run + 1, runs));
+    }
+
+    /// Add `work` finished units
+    #[cfg_attr(not(feature = "progress"), allow(unused_variables))]
+    fn advance(&self, work: u64) {
+        #[cfg(feature = "progress")]
+        self.bar.inc(work);
+    }
+
+    /// Report a finished cell (only without a progress bar, which already shows it)
+    #[cfg_attr(feature = "progress", allow(unused_variables))]
+    fn finish_cell(&self, label: &str) {
+        #[cfg(not(feature = "progress"))]
+        println!("Simulated {}", label);
+    }
+
+    /// Remove the progress bar
+    fn finish(&self) {
+        #[cfg(feature = "progress")]
+        self.bar.finish_and_clear();
+    }
+}
+
+/// Whether operands of different lengths are benchmarked
+pub fn is_rectangular(config: &BenchmarkConfig) -> bool {
+    config.aspect_ratios != [1]
+}
+
+/// Whether operands with other than uniform coefficients are benchmarked
+pub fn sweeps_distributions(config: &BenchmarkConfig) -> bool {
+    config.distributions != [InputDistribution::Uniform]
+}
+
+/// Largest deviation of `product` from `reference` relative to the largest coefficient of `reference`
+pub(crate) fn relative_discrepancy(product: &Polynomial, reference: &Polynomial) -> f64 {
+    let len = product.coeffs.len().max(reference.coeffs.len());
+    let coeff = |p: &Polynomial, k: usize| p.coeffs.get(k).copied().unwrap_or(0.0);
+    let deviation = (0..len)
+        .map(|k| (coeff(product, k) - coeff(reference, k)).abs())
+        .fold(0.0, f64::max);
+    let scale = reference
+        .coeffs
+        .iter()
+        .fold(0.0, |m: f64, c| m.max(c.abs()));
+    if scale > 0.0 {
+        deviation / scale
+    } else {
+        deviation
+    }
+}
+
+/// Whether the peak heap usage is measured
+fn measures_memory(config: &BenchmarkConfig) -> bool {
+    cfg!(feature = "mem_profile") && config.measure_memory
+}
+
+/// Peak heap usage in bytes of a single multiplication
+#[cfg(feature = "mem_profile")]
+fn measure_peak(algorithm: &dyn MultiplyAlgorithm, p1: &Polynomial, p2: &Polynomial) -> usize {
+    crate::memory::peak_during(|| algorithm.multiply(&p1.coeffs, &p2.coeffs))
+}
+
+#[cfg(not(feature = "mem_profile"))]
+fn measure_peak(_algorithm: &dyn MultiplyAlgorithm, _p1: &Polynomial, _p2: &Polynomial) -> usize {
+    unreachable!("memory is only measured with the `mem_profile` feature")
+}
+
+/// Whether the floating-point operations are counted
+fn counts_ops(config: &BenchmarkConfig) -> bool {
+    cfg!(feature = "op_count") && config.count_ops
+}
+
+/// Floating-point operations of a single multiplication
+#[cfg(feature = "op_count")]
+fn measure_ops(algorithm: &dyn MultiplyAlgorithm, p1: &Polynomial, p2: &Polynomial) -> OpCounts {
+    crate::count_ops(|| algorithm.multiply(&p1.coeffs, &p2.coeffs)).1
+}
+
+#[cfg(not(feature = "op_count"))]
+fn measure_ops(_algorithm: &dyn MultiplyAlgorithm, _p1: &Polynomial, _p2: &Polynomial) -> OpCounts {
+    unreachable!("operations are only counted with the `op_count` feature")
+}
+
+/// One size, aspect ratio and input distribution of the benchmark
+struct Cell<'a> {
+    size: usize,
+    aspect_ratio: usize,
+    distribution: InputDistribution,
+    runs: usize,
+    /// Description shown in the progress display
+    label: &'a str,
+}
+
+/// Time every algorithm on `cell.runs` random input pairs drawn from `rng`
+fn measure_cell(
+    config: &BenchmarkConfig,
+    algorithms: &[&(dyn MultiplyAlgorithm + Sync)],
+    names: &[String],
+    cell: Cell,
+    rng: &mut StdRng,
+    progress: &Progress,
+    budget: &TimeBudget,
+) -> Result<SizeResult, PolyError> {
+    let Cell {
+        size,
+        aspect_ratio,
+        distribution,
+        runs,
+        ..
+    } = cell;
+    let other_size = (size / aspect_ratio).max(1);
+    span!(
+        DEBUG,
+        "benchmark_cell",
+        size = size,
+        other_size = other_size,
+        runs = runs
+    );
+    // times[algorithm][run] in seconds
+    let mut times = vec![Vec::with_capacity(runs); algorithms.len()];
+    let mut peak_bytes = Vec::new();
+    let mut op_counts = Vec::new();
+    let mut max_discrepancy = if config.verify {
+        vec![0.0; algorithms.len()]
+    } else {
+        Vec::new()
+    };
+    // algorithms run until their time is spent, not at all if one run would exceed it
+    let length = size + other_size;
+    let mut active: Vec<bool> = (0..algorithms.len())
+        .map(|j| !budget.predicts_over(j, length))
+        .collect();
+    let mut spent = vec![0.0; algorithms.len()];
+    for run in 0..runs {
+        if !active.contains(&true) {
+            break;
+        }
+        progress.run(cell.label, run, runs);
+        let p1 = distribution.generate(size, rng);
+        let p2 = distribution.generate(other_size, rng);
+
+        let warmup_runs = if run == 0 { config.warmup_runs } else { 0 };
+        let running = active.clone();
+        for (j, ((time, &algorithm), name)) in
+            (times.iter_mut().zip(algorithms).zip(names)).enumerate()
+        {
+            if !running[j] {
+                continue;
+            }
+            span!(DEBUG, "benchmark_run", algorithm = name.as_str(), run = run);
+            let duration = benchmark_single_warm(algorithm, &p1, &p2, warmup_runs);
+            time.push(duration.as_secs_f64());
+            spent[j] += duration.as_secs_f64();
+            active[j] &= !budget.is_spent(spent[j]);
+        }
+        if config.verify {
+            let reference = p1.multiply_naive(&p2);
+            for (j, max) in max_discrepancy.iter_mut().enumerate() {
+                if !running[j] {
+                    continue;
+                }
+                let discrepancy =
+                    relative_discrepancy(&p1.multiply_with(&p2, algorithms[j]), &reference);
+                *max = f64::max(*max, discrepancy);
+                if discrepancy > VERIFY_TOLERANCE || discrepancy.is_nan() {
+                    return Err(PolyError::VerificationFailed {
+                        algorithm: names[j].clone(),
+                        size,
+                        discrepancy,
+                    });
+                }
+            }
+        }
+        // the allocations and operations do not depend on the coefficients,
+        // so one input pair suffices
+        if run == 0 && measures_memory(config) {
+            peak_bytes = (algorithms.iter().zip(&running))
+                .map(|(&algorithm, &ran)| match ran {
+                    true => measure_peak(algorithm, &p1, &p2),
+                    false => 0,
+                })
+                .collect();
+        }
+        if run == 0 && counts_ops(config) {
+            op_counts = (algorithms.iter().zip(&running))
+                .map(|(&algorithm, &ran)| match ran {
+                    true => measure_ops(algorithm, &p1, &p2),
+                    false => OpCounts::default(),
+                })
+                .collect();
+        }
+        progress.advance(cell_work((size, aspect_ratio)));
+    }
+    progress.finish_cell(cell.label);
+    for (j, time) in times.iter().enumerate() {
+        if let Some(&fastest) = time.iter().min_by(|x, y| x.total_cmp(y)) {
+            budget.record(j, length, fastest);
+        }
+    }
+    Ok(SizeResult {
+        size,
+        other_size,
+        aspect_ratio,
+        distribution,
+        runs,
+        times,
+        peak_bytes,
+        op_counts,
+        max_discrepancy,
+    })
+}
+
+/// Time budget of every algorithm in each cell, see [`BenchmarkConfig::max_time_per_cell`]
+struct TimeBudget {
+    /// Budget in seconds, `None` for no limit
+    limit: Option<f64>,
+    /// Operand lengths `n + m` and fastest run in seconds of every measured cell,
+    /// for each algorithm
+    measured: Mutex<Vec<Vec<(usize, f64)>>>,
+}
+
+impl TimeBudget {
+    /// Budget of `limit` for each of `algorithms` algorithms
+    fn new(limit: Option<Duration>, algorithms: usize) -> TimeBudget {
+        TimeBudget {
+            limit: limit.map(|limit| limit.as_secs_f64()),
+            measured: Mutex::new(vec![Vec::new(); algorithms]),
+        }
+    }
+
+    /// Whether `spent` seconds use up the budget
+    fn is_spent(&self, spent: f64) -> bool {
+        self.limit.is_some_and(|limit| spent >= limit)
+    }
+
+    /// Whether a single run of the algorithm at `index` on operands of `length` coefficients
+    /// in total is predicted to exceed the budget
+    ///
+    /// No algorithm is faster than linear, so a run at `length` takes at least
+    /// `length / l` times the fastest run measured at any `l ≤ length`.
+    fn predicts_over(&self, index: usize, length: usize) -> bool {
+        let Some(limit) = self.limit else {
+            return false;
+        };
+        let measured = self.measured.lock().unwrap_or_else(PoisonError::into_inner);
+        (measured[index].iter())
+            .filter(|&&(l, _)| l <= length)
+            .any(|&(l, fastest)| fastest * length as f64 / l as f64 > limit)
+    }
+
+    /// Record the fastest run of the algorithm at `index` on operands of `length` coefficients
+    fn record(&self, index: usize, length: usize, fastest: f64) {
+        if self.limit.is_some() {
+            let mut measured = self.measured.lock().unwrap_or_else(PoisonError::into_inner);
+            measured[index].push((length, fastest));
+        }
+    }
+}
+
+/// Write the results of the cell at `index` to the results file and, if any, the raw runs file
+fn write_cell(
+    config: &BenchmarkConfig,
+    names: &[String],
+    index: usize,
+    result: &SizeResult,
+    raw_data_file: &mut impl Write,
+    runs_file: Option<&mut impl Write>,
+) -> Result<(), PolyError> {
+    let (size, other_size, aspect_ratio) = (result.size, result.other_size, result.aspect_ratio);
+    let runs = result.runs;
+    match config.format {
+        OutputFormat::Csv => {
+            write!(raw_data_file, "{}", size)?;
+            if is_rectangular(config) {
+                write!(raw_data_file, ",{},{}", other_size, aspect_ratio)?;
+            }
+            if sweeps_distributions(config) {
+                write!(raw_data_file, ",{}", result.distribution)?;
+            }
+            for j in 0..names.len() {
+                if result.skipped(j) {
+                    let columns = 5
+                        + result.peak_bytes.len().min(1)
+                        + 2 * result.op_counts.len().min(1)
+                        + result.max_discrepancy.len().min(1);
+                    write!(raw_data_file, "{}", ",skipped".repeat(columns))?;
+                    continue;
+                }
+                let summary = result.summary(j);
+                write!(
+                    raw_data_file,
+                    ",{},{},{},{},{}",
+                    result.aggregate(j, config.aggregation),
+                    summary.min,
+                    summary.median,
+                    summary.std_dev,
+                    summary.ci95
+                )?;
+                if let Some(bytes) = result.peak_bytes.get(j) {
+                    write!(raw_data_file, ",{}", bytes)?;
+                }
+                if let Some(ops) = result.op_counts.get(j) {
+                    write!(raw_data_file, ",{},{}", ops.mults, ops.adds)?;
+                }
+                if let Some(discrepancy) = result.max_discrepancy.get(j) {
+                    write!(raw_data_file, ",{}", discrepancy)?;
+                }
+            }
+            writeln!(raw_data_file)?;
+        }
+        OutputFormat::Json => {
+            let separator = if index == 0 { "" } else { "," };
+            writeln!(raw_data_file, "{}", separator)?;
+            let mut shape = if is_rectangular(config) {
+                format!(
+                    "\"other_size\": {}, \"aspect_ratio\": {}, ",
+                    other_size, aspect_ratio
+                )
+            } else {
+                String::new()
+            };
+            if sweeps_distributions(config) {
+                shape += &format!("\"distribution\": \"{}\", ", result.distribution);
+            }
+            writeln!(
+                raw_data_file,
+                "    {{\"size\": {}, {}\"runs\": {}, \"algorithms\": [",
+                size, shape, runs
+            )?;
+            let times = &result.times;
+            for (j, time) in times.iter().enumerate() {
+                // custom algorithms have no threshold
+                let built_in = config.algorithms.get(j);
+                let threshold = match built_in {
+                    Some(PolynomialMultAlg::Thresholded(threshold)) => threshold.to_string(),
+                    Some(PolynomialMultAlg::Hybrid(config)) => config.toom3_threshold.to_string(),
+                    _ => "null".to_string(),
+                };
+                let karatsuba_threshold = match built_in {
+                    Some(PolynomialMultAlg::Hybrid(config)) => {
+                        format!("\"karatsuba_threshold\": {}, ", config.karatsuba_threshold)
+                    }
+                    _ => String::new(),
+                };
+                let skipped = result.skipped(j);
+                let statistics = if skipped {
+                    "\"skipped\": true, ".to_string()
+                } else {
+                    let summary = result.summary(j);
+                    format!(
+                        "\"min\": {}, \"median\": {}, \"mean\": {}, \"std\": {}, \"ci95\": {}, ",
+                        summary.min, summary.median, summary.mean, summary.std_dev, summary.ci95
+                    )
+                };
+                let peak_bytes = (result.peak_bytes.get(j))
+                    .filter(|_| !skipped)
+                    .map_or(String::new(), |b| format!("\"peak_bytes\": {}, ", b));
+                let op_counts = (result.op_counts.get(j))
+                    .filter(|_| !skipped)
+                    .map_or(String::new(), |ops| {
+                        format!("\"mults\": {}, \"adds\": {}, ", ops.mults, ops.adds)
+                    });
+                let max_error = (result.max_discrepancy.get(j))
+                    .filter(|_| !skipped)
+                    .map_or(String::new(), |d| format!("\"max_error\": {}, ", d));
+                let time = time.iter().map(f64::to_string).collect::<Vec<_>>();
+                let separator = if j + 1 < times.len() { "," } else { "" };
+                writeln!(
+                    raw_data_file,
+                    "      {{\"algorithm\": \"{}\", \"threshold\": {}, \
+                     {}{}{}{}{}\"times\": [{}]}}{}",
+                    built_in.map_or_else(|| names[j].clone(), |a| a.name().to_string()),
+                    threshold,
+                    statistics,
+                    karatsuba_threshold,
+                    peak_bytes,
+                    op_counts,
+                    max_error,
+                    time.join(", "),
+                    separator
+                )?;
+            }
+            write!(raw_data_file, "    ]}}")?;
+        }
+    }
+    if let Some(file) = runs_file {
+        for (name, times) in names.iter().zip(&result.times) {
+            for (run, time) in times.iter().enumerate() {
+                write!(file, "{},", size)?;
+                if is_rectangular(config) {
+                    write!(file, "{},{},", other_size, aspect_ratio)?;
+                }
+                if sweeps_distributions(config) {
+                    write!(file, "{},", result.distribution)?;
+                }
+                writeln!(file, "{},{},{}", name, run, time)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Measure the cells `0..cells` on `threads` worker threads, returning the results in cell order
+///
+/// Workers claim the next unmeasured cell until all are taken, or one failed.
+fn measure_parallel(
+    cells: usize,
+    threads: usize,
+    pin_threads: bool,
+    measure: &(dyn Fn(usize) -> Result<SizeResult, PolyError> + Sync),
+) -> Vec<Result<SizeResult, PolyError>> {
+    let next = AtomicUsize::new(0);
+    let failed = AtomicBool::new(false);
+    let core_ids = if pin_threads {
+        core_affinity::get_core_ids().unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+    let (next, failed) = (&next, &failed);
+    let mut measured: Vec<(usize, Result<SizeResult, PolyError>)> = std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|worker| {
+                let core = core_ids.get(worker % core_ids.len().max(1)).copied();
+                scope.spawn(move || {
+                    if let Some(core) = core {
+                        core_affinity::set_for_current(core);
+                    }
+                    let mut measured = Vec::new();
+                    while !failed.load(Ordering::Relaxed) {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        if i >= cells {
+                            break;
+                        }
+                        let result = measure(i);
+                        failed.fetch_or(result.is_err(), Ordering::Relaxed);
+                        measured.push((i, result));
+                    }
+                    measured
+                })
+            })
+            .collect();
+        (workers.into_iter())
+            .flat_map(|worker| worker.join().unwrap())
+            .collect()
+    });
+    // cells are claimed in order, so the measured ones are a prefix of all cells
+    measured.sort_by_key(|&(i, _)| i);
+    measured.into_iter().map(|(_, result)| result).collect()
+}
+
+/// Number of threads measuring cells: one with memory measurement, whose counters are process wide
+fn worker_threads(config: &BenchmarkConfig) -> usize {
+    match config.threads {
+        _ if measures_memory(config) => 1,
+        0 => std::thread::available_parallelism().map_or(1, |n| n.get()),
+        threads => threads,
+    }
+}
+
+/// Parameters of `config` that change the measured values of a cell other than its shape,
+/// number of runs, seed and columns, which a resumed run must share with the interrupted one
+pub(crate) fn checkpoint_parameters(config: &BenchmarkConfig) -> String {
+    let max_time =
+        (config.max_time_per_cell).map_or("none".to_string(), |t| t.as_secs_f64().to_string());
+    format!(
+        "threads={} pin_threads={} warmup_runs={} max_time_per_cell={} memory={} ops={} verify={}",
+        worker_threads(config),
+        config.pin_threads,
+        config.warmup_runs,
+        max_time,
+        measures_memory(config),
+        counts_ops(config),
+        config.verify
+    )
+}
+
+/// Seed of the inputs of the cell at `index`, independent of the order in which cells are measured
+fn cell_seed(seed: u64, index: usize) -> u64 {
+    seed ^ (index as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15)
+}
+
+/// Estimated work of one run of the cell of `size` and `aspect_ratio`
+fn cell_work((size, aspect_ratio): (usize, usize)) -> u64 {
+    run_work(size, (size / aspect_ratio).max(1))
+}
+
+/// Run a comprehensive benchmark with different input sizes and characteristics
+///
+/// Every algorithm is timed on `runs_per_test(size)` random inputs of each size and aspect ratio
+/// (see [`BenchmarkConfig::aspect_ratios`]) after [`BenchmarkConfig::warmup_runs`] untimed ones.
+/// Each measured cell is appended to `checkpoint.csv` as soon as it finishes, so that
+/// [`BenchmarkConfig::resume`] can continue an interrupted run (the file is removed once
+/// the run completes), and the results are written to `all_results.csv` or `all_results.json` in the configured output
+/// directory, depending on the output format, and appended to [`BenchmarkConfig::database`] if set.
+/// The machine, build and seed are written to `metadata.json` (see [`RunMetadata`]),
+/// and with [`BenchmarkConfig::accuracy`] the errors of the algorithms to `accuracy.csv`.
+/// The timings are also returned.
+pub fn run_comprehensive_benchmark(
+    config: &BenchmarkConfig,
+    runs_per_test: &dyn Fn(usize) -> usize,
+) -> Result<Vec<SizeResult>, PolyError> {
+    config.validate()?;
+    // Create a directory for the output data files
+    std::fs::create_dir_all(&config.output_dir)?;
+
+    let file_name = match config.format {
+        OutputFormat::Csv => "all_results.csv",
+        OutputFormat::Json => "all_results.json",
+    };
+    let algorithms = benchmarked_algorithms(config);
+    let names = column_names(config);
+    let parameters = checkpoint_parameters(config);
+    let checkpoint_path = config.output_dir.join(CHECKPOINT_FILE);
+    let saved = if config.resume && checkpoint_path.exists() {
+        Some(checkpoint::load(&checkpoint_path)?)
+    } else {
+        None
+    };
+    let seed = match &saved {
+        Some(saved) => {
+            if config.seed.is_some_and(|seed| seed != saved.seed) {
+                return Err(PolyError::InvalidArgument(format!(
+                    "cannot resume a run with seed {} using seed {}",
+                    saved.seed,
+                    config.seed.unwrap_or_default()
+                )));
+            }
+            if saved.names != names {
+                return Err(PolyError::InvalidArgument(format!(
+                    "cannot resume a run of the columns {} with the columns {}",
+                    saved.names.join(","),
+                    names.join(",")
+                )));
+            }
+            if saved.parameters != parameters {
+                return Err(PolyError::InvalidArgument(format!(
+                    "cannot resume a run with the parameters {} using {}",
+                    saved.parameters, parameters
+                )));
+            }
+            saved.seed
+        }
+        None => config.seed.unwrap_or_else(|| StdRng::from_entropy().gen()),
+    };
+    let metadata = RunMetadata::collect(seed);
+    metadata.write(config.output_dir.join("metadata.json"))?;
+    let raw_data_file = std::fs::File::create(config.output_dir.join(file_name))?;
+    let mut raw_data_file = std::io::BufWriter::new(raw_data_file);
+    let mut runs_file = if config.raw_runs {
+        let file = std::fs::File::create(config.output_dir.join("all_runs.csv"))?;
+        let mut file = std::io::BufWriter::new(file);
+        writeln!(file, "{}", text_header("runs"))?;
+        let shape = if is_rectangular(config) {
+            "other_size,aspect_ratio,"
+        } else {
+            ""
+        };
+        let distribution = if sweeps_distributions(config) {
+            "distribution,"
+        } else {
+            ""
+        };
+        writeln!(file, "size,{}{}algorithm,run,seconds", shape, distribution)?;
+        Some(file)
+    } else {
+        None
+    };
+
+    match config.format {
+        OutputFormat::Csv => {
+            // Write format header and CSV header
+            writeln!(raw_data_file, "{}", text_header("benchmark"))?;
+            write!(raw_data_file, "size")?;
+            if is_rectangular(config) {
+                write!(raw_data_file, ",other_size,aspect_ratio")?;
+            }
+            if sweeps_distributions(config) {
+                write!(raw_data_file, ",distribution")?;
+            }
+            for name in &names {
+                write!(raw_data_file, ",{}", name)?;
+                for statistic in ["min", "median", "std", "ci95"] {
+                    write!(raw_data_file, ",{}_{}", name, statistic)?;
+                }
+                if measures_memory(config) {
+                    write!(raw_data_file, ",{}_peak_bytes", name)?;
+                }
+                if counts_ops(config) {
+                    write!(raw_data_file, ",{0}_mults,{0}_adds", name)?;
+                }
+                if config.verify {
+                    write!(raw_data_file, ",{}_max_error", name)?;
+                }
+            }
+            writeln!(raw_data_file)?;
+        }
+        OutputFormat::Json => {
+            let seed = config.seed.map_or("null".to_string(), |s| s.to_string());
+            writeln!(raw_data_file, "{{")?;
+            writeln!(
+                raw_data_file,
+                "  \"format\": {}, \"coeff\": \"{}\", \"kind\": \"benchmark\", \"seed\": {},",
+                FORMAT_VERSION, COEFF_TYPE, seed
+            )?;
+            writeln!(raw_data_file, "  \"metadata\": {},", metadata.to_json())?;
+            write!(raw_data_file, "  \"results\": [")?;
+        }
+    }
+
+    let cells: Vec<(usize, usize, InputDistribution)> = (config.sizes.iter())
+        .flat_map(|&size| {
+            (config.aspect_ratios.iter()).flat_map(move |&ratio| {
+                (config.distributions.iter()).map(move |&distribution| (size, ratio, distribution))
+            })
+        })
+        .collect();
+    let runs: Vec<usize> = (cells.iter())
+        .map(|&(size, _, _)| runs_per_test(size))
+        .collect();
+    if runs.contains(&0) {
+        return Err(PolyError::InvalidArgument(
+            "number of runs per test must be positive".to_string(),
+        ));
+    }
+    let total_work = (cells.iter().zip(&runs))
+        .map(|(&(size, ratio, _), &runs)| runs as u64 * cell_work((size, ratio)))
+        .sum();
+    let progress = Progress::new(total_work);
+    let budget = TimeBudget::new(config.max_time_per_cell, algorithms.len());
+    // cells of the checkpoint with the same shape and number of runs are not measured again
+    let done: Vec<Option<SizeResult>> = (cells.iter().zip(&runs))
+        .map(|(&(size, aspect_ratio, distribution), &runs)| {
+            let saved = saved.as_ref()?;
+            (saved.cells.iter())
+                .find(|r| {
+                    (r.size, r.aspect_ratio, r.distribution, r.runs)
+                        == (size, aspect_ratio, distribution, runs)
+                })
+                .cloned()
+        })
+        .collect();
+    let checkpoint = match saved {
+        Some(_) => Checkpoint::append(&checkpoint_path)?,
+        None => Checkpoint::create(&checkpoint_path, seed, &names, &parameters)?,
+    };
+    #[cfg(feature = "sqlite")]
+    let mut store = match &config.database {
+        Some(path) => {
+            let store = crate::ResultStore::open(path)?;
+            let session = store.begin_session(config, seed)?;
+            Some((store, session))
+        }
+        None => None,
+    };
+    let measure = |i: usize| {
+        let (size, aspect_ratio, distribution) = cells[i];
+        if let Some(result) = &done[i] {
+            progress.advance(runs[i] as u64 * cell_work((size, aspect_ratio)));
+            return Ok(result.clone());
+        }
+        let shape = if is_rectangular(config) {
+            let other_size = (size / aspect_ratio).max(1);
+            format!("size {} x {}", size, other_size)
+        } else {
+            format!("size {}", size)
+        };
+        let label = if sweeps_distributions(config) {
+            format!("{} {} ({}/{})", shape, distribution, i + 1, cells.len())
+        } else {
+            format!("{} ({}/{})", shape, i + 1, cells.len())
+        };
+        let mut rng = StdRng::seed_from_u64(cell_seed(seed, i));
+        let cell = Cell {
+            size,
+            aspect_ratio,
+            distribution,
+            runs: runs[i],
+            label: &label,
+        };
+        let result = measure_cell(
+            config,
+            &algorithms,
+            &names,
+            cell,
+            &mut rng,
+            &progress,
+            &budget,
+        )?;
+        checkpoint.record(&result)?;
+        Ok(result)
+    };
+    // cells measured one after another are written as soon as they finish
+    let measured: Box<dyn Iterator<Item = Result<SizeResult, PolyError>>> =
+        match worker_threads(config) {
+            1 => Box::new((0..cells.len()).map(measure)),
+            threads => Box::new(
+                measure_parallel(cells.len(), threads, config.pin_threads, &measure).into_iter(),
+            ),
+        };
+    let mut results = Vec::with_capacity(cells.len());
+    for (i, result) in measured.enumerate() {
+        let result = result?;
+        write_cell(
+            config,
+            &names,
+            i,
+            &result,
+            &mut raw_data_file,
+            runs_file.as_mut(),
+        )?;
+        // resumed cells were stored by the interrupted run
+        #[cfg(feature = "sqlite")]
+        if let (Some((store, session)), None) = (&mut store, &done[i]) {
+            store.insert_cell(*session, &names, &result)?;
+        }
+        results.push(result);
+    }
+    if config.format == OutputFormat::Json {
+        writeln!(raw_data_file, "\n  ]\n}}")?;
+    }
+    progress.finish();
+    raw_data_file.flush()?;
+    if let Some(mut file) = runs_file {
+        file.flush()?;
+    }
+    if config.accuracy {
+        write_accuracy(config, &measure_accuracy(config, seed))?;
+    }
+    // a complete run leaves nothing to resume
+    drop(checkpoint);
+    std::fs::remove_file(&checkpoint_path)?;
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Invalid settings are reported before any file is written
+    #[test]
+    fn test_benchmark_invalid_config() {
+        let output_dir = std::env::temp_dir().join("mult_polynomial_benchmark_invalid_test");
+        let _ = std::fs::remove_dir_all(&output_dir);
+        let valid = BenchmarkConfig {
+            sizes: vec![4],
+            output_dir,
+            ..BenchmarkConfig::default()
+        };
+        let invalid = [
+            BenchmarkConfig {
+                sizes: vec![4, 0],
+                ..valid.clone()
+            },
+            BenchmarkConfig {
+                aspect_ratios: vec![0],
+                ..valid.clone()
+            },
+            BenchmarkConfig {
+                aggregation: Aggregation::TrimmedMean(0.5),
+                ..valid.clone()
+            },
+            BenchmarkConfig {
+                distributions: vec![InputDistribution::Sparse(0.0)],
+                ..valid.clone()
+            },
+            BenchmarkConfig {
+                max_time_per_cell: Some(Duration::ZERO),
+                ..valid.clone()
+            },
+        ];
+        for config in &invalid {
+            assert!(matches!(
+                run_comprehensive_benchmark(config, &|_| 1),
+                Err(PolyError::InvalidArgument(_))
+            ));
+        }
+        assert!(!valid.output_dir.exists());
+        assert!(matches!(
+            run_comprehensive_benchmark(&valid, &|_| 0),
+            Err(PolyError::InvalidArgument(_))
+        ));
+    }
+
+    /// Settings, algorithm expansion and errors of TOML benchmark files
+    #[cfg(feature = "config")]
+    #[test]
+    fn test_config_file() {
+        let (config, runs) = BenchmarkConfig::from_toml("").unwrap();
+        assert_eq!(config.sizes, BenchmarkConfig::default().sizes);
+        assert_eq!(config.algorithms, BenchmarkConfig::default().algorithms);
+        assert_eq!(runs, RunsPerSize::default());
+        assert_eq!(runs.runs(100), 1000);
+        assert_eq!(runs.runs(1 << 17), 10);
+
+        let text = r#"
+            sizes = [64, 256]
+            aspect_ratios = [1, 4]
+            distributions = ["uniform", "sparse:0.05", "huge-range"]
+            algorithms = ["naive", "thresholded", "hybrid:128:32"]
+            thresholds = [16, 32]
+            seed = 7
+            output_dir = "results/machine_a"
+            format = "json"
+            aggregation = "trimmed-mean"
+            trim = 0.2
+            verify = true
+            accuracy = true
+            evaluation_points = [1, 16]
+            exact_bits = [8, 32]
+            max_time_per_cell = 2.5
+
+            [runs]
+            min_runs = 3
+        "#;
+        let (config, runs) = BenchmarkConfig::from_toml(text).unwrap();
+        assert_eq!(config.sizes, vec![64, 256]);
+        assert_eq!(config.aspect_ratios, vec![1, 4]);
+        assert_eq!(
+            config.distributions,
+            vec![
+                InputDistribution::Uniform,
+                InputDistribution::Sparse(0.05),
+                InputDistribution::HugeRange
+            ]
+        );
+        assert_eq!(
+            config.algorithms,
+            vec![
+                PolynomialMultAlg::Naive,
+                PolynomialMultAlg::Thresholded(16),
+                PolynomialMultAlg::Thresholded(32),
+                "hybrid:128:32".parse().unwrap(),
+            ]
+        );
+        assert_eq!(config.seed, Some(7));
+        assert_eq!(config.output_dir, PathBuf::from("results/machine_a"));
+        assert_eq!(config.format, OutputFormat::Json);
+        assert_eq!(config.aggregation, Aggregation::TrimmedMean(0.2));
+        assert!(config.verify && config.accuracy && !config.raw_runs);
+        assert_eq!(config.evaluation_points, vec![1, 16]);
+        assert_eq!(config.exact_bits, vec![8, 32]);
+        assert_eq!(config.max_time_per_cell, Some(Duration::from_millis(2500)));
+        assert_eq!(runs.runs(64), 100000 / 64);
+        assert_eq!(RunsPerSize::fixed(3).runs(64), 3);
+
+        let (config, _) = BenchmarkConfig::from_toml("thresholds = [8]").unwrap();
+        assert_eq!(
+            config.algorithms,
+            vec![
+                PolynomialMultAlg::Naive,
+                PolynomialMultAlg::CookTooms,
+                PolynomialMultAlg::Thresholded(8)
+            ]
+        );
+
+        for (text, invalid_format) in [
+            ("sizes = [", true),
+            ("size = [4]", true),
+            ("format = \"xml\"", true),
+            ("max_time_per_cell = -1.0", true),
+            ("[runs]\nruns = 3", true),
+            ("algorithms = [\"fft\"]", false),
+            ("distributions = [\"cauchy\"]", false),
+            ("sizes = [0]", false),
+        ] {
+            let error = BenchmarkConfig::from_toml(text).unwrap_err();
+            assert_eq!(
+                matches!(error, PolyError::InvalidFormat(_)),
+                invalid_format,
+                "{}",
+                text
+            );
+        }
+        let error = BenchmarkConfig::from_toml_file("missing_bench.toml").unwrap_err();
+        assert!(matches!(error, PolyError::Io(_)));
+    }
+
+    /// A seeded run writes one column per algorithm and one row per size
+    #[test]
+    fn test_benchmark_csv_layout() {
+        let config = BenchmarkConfig {
+            sizes: vec![4, 16],
+            algorithms: vec![PolynomialMultAlg::Naive, PolynomialMultAlg::Thresholded(8)],
+            output_dir: std::env::temp_dir().join("mult_polynomial_benchmark_test"),
+            format: OutputFormat::Csv,
+            seed: Some(7),
+            ..BenchmarkConfig::default()
+        };
+        run_comprehensive_benchmark(&config, &|_| 2).unwrap();
+        let csv = std::fs::read_to_string(config.output_dir.join("all_results.csv")).unwrap();
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[1].starts_with("size,naive_time,naive_time_min,naive_time_median,"));
+        assert!(lines[1].ends_with(",threshold_8_std,threshold_8_ci95"));
+        assert!(lines[3].starts_with("16,"));
+        assert_eq!(lines[3].split(',').count(), 11);
+    }
+
+    /// The JSON results hold the time of every run of every algorithm
+    #[test]
+    fn test_benchmark_json_layout() {
+        let config = BenchmarkConfig {
+            sizes: vec![4, 16],
+            algorithms: vec![PolynomialMultAlg::Naive, PolynomialMultAlg::Thresholded(8)],
+            output_dir: std::env::temp_dir().join("mult_polynomial_benchmark_json_test"),
+            format: OutputFormat::Json,
+            ..BenchmarkConfig::default()
+        };
+        run_comprehensive_benchmark(&config, &|_| 3).unwrap();
+        let json = std::fs::read_to_string(config.output_dir.join("all_results.json")).unwrap();
+        assert!(json.contains("\"seed\": null"));
+        // the seed drawn for the run is recorded in the metadata, also next to the results
+        let metadata = std::fs::read_to_string(config.output_dir.join("metadata.json")).unwrap();
+        assert!(json.contains(&format!("  \"metadata\": {},", metadata.trim())));
+        assert!(metadata.contains("\"seed\": ") && !metadata.contains("\"seed\": null"));
+        assert!(json.contains("{\"size\": 16, \"runs\": 3, \"algorithms\": ["));
+        assert!(json.contains("{\"algorithm\": \"thresholded\", \"threshold\": 8, \"min\": "));
+        assert_eq!(json.matches("\"times\"").count(), 4);
+        assert_eq!(json.matches('{').count(), json.matches('}').count());
+    }
+
+    /// Rectangular mode pairs every size with shorter second operands
+    #[test]
+    fn test_benchmark_rectangular() {
+        let config = BenchmarkConfig {
+            sizes: vec![8, 64],
+            aspect_ratios: vec![1, 4, 16],
+            algorithms: vec![PolynomialMultAlg::Naive, PolynomialMultAlg::CookTooms],
+            output_dir: std::env::temp_dir().join("mult_polynomial_benchmark_rectangular_test"),
+            seed: Some(3),
+            ..BenchmarkConfig::default()
+        };
+        let results = run_comprehensive_benchmark(&config, &|_| 1).unwrap();
+        let shapes: Vec<_> = (results.iter())
+            .map(|r| (r.size, r.other_size, r.aspect_ratio))
+            .collect();
+        assert_eq!(
+            shapes,
+            [
+                (8, 8, 1),
+                (8, 2, 4),
+                (8, 1, 16),
+                (64, 64, 1),
+                (64, 16, 4),
+                (64, 4, 16)
+            ]
+        );
+        let csv = std::fs::read_to_string(config.output_dir.join("all_results.csv")).unwrap();
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines.len(), 8);
+        assert!(lines[1].starts_with("size,other_size,aspect_ratio,naive_time,"));
+        assert!(lines[6].starts_with("64,16,4,"));
+        assert_eq!(lines[6].split(',').count(), 13);
+    }
+
+    /// Every distribution is timed at every size, in its own row, and reported by name
+    #[test]
+    fn test_benchmark_distributions() {
+        let distributions = [
+            InputDistribution::Uniform,
+            InputDistribution::Gaussian,
+            InputDistribution::Sparse(0.05),
+            InputDistribution::HugeRange,
+            InputDistribution::Integer,
+        ];
+        for distribution in distributions {
+            let name = distribution.to_string();
+            assert_eq!(name.parse::<InputDistribution>().unwrap(), distribution);
+        }
+        assert_eq!(
+            "sparse".parse::<InputDistribution>().unwrap(),
+            InputDistribution::Sparse(0.1)
+        );
+        assert!("sparse:x".parse::<InputDistribution>().is_err());
+
+        let mut rng = StdRng::seed_from_u64(1);
+        let sparse = InputDistribution::Sparse(0.01).generate(1000, &mut rng);
+        assert_eq!(sparse.coeffs.len(), 1000);
+        assert!(sparse.coeffs.iter().filter(|&&c| c != 0.0).count() < 50);
+        let integer = InputDistribution::Integer.generate(1000, &mut rng);
+        assert!((integer.coeffs.iter()).all(|c| c.fract() == 0.0 && c.abs() <= 1000.0));
+        let huge = InputDistribution::HugeRange.generate(1000, &mut rng);
+        let magnitudes = huge.coeffs.iter().map(|c| c.abs());
+        assert!(magnitudes.clone().fold(f64::INFINITY, f64::min) < 1e-90);
+        assert!(magnitudes.fold(0.0, f64::max) > 1e90);
+
+        let config = BenchmarkConfig {
+            sizes: vec![8, 64],
+            distributions: distributions.to_vec(),
+            algorithms: vec![PolynomialMultAlg::Naive, PolynomialMultAlg::Thresholded(4)],
+            output_dir: std::env::temp_dir().join("mult_polynomial_benchmark_distributions_test"),
+            seed: Some(4),
+            verify: true,
+            raw_runs: true,
+            ..BenchmarkConfig::default()
+        };
+        let results = run_comprehensive_benchmark(&config, &|_| 2).unwrap();
+        assert_eq!(results.len(), 2 * 5);
+        assert_eq!(results[7].size, 64);
+        assert_eq!(results[7].distribution, InputDistribution::Sparse(0.05));
+        let csv = std::fs::read_to_string(config.output_dir.join("all_results.csv")).unwrap();
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines.len(), 2 + 10);
+        assert!(lines[1].starts_with("size,distribution,naive_time,"));
+        assert!(lines[5].starts_with("8,huge-range,"));
+        let runs = std::fs::read_to_string(config.output_dir.join("all_runs.csv")).unwrap();
+        assert!(runs
+            .lines()
+            .nth(1)
+            .unwrap()
+            .starts_with("size,distribution,"));
+        assert!(runs.contains("\n64,integer,threshold_4,1,"));
+
+        let fits = fit_complexity(&config, &results, 1);
+        assert_eq!(fits.len(), 2 * 5);
+        assert_eq!(fits[3].0, "threshold_4_gaussian");
+    }
+
+    /// An interrupted run resumes from its checkpoint, timing only the missing cells
+    #[test]
+    fn test_benchmark_resume() {
+        let output_dir = std::env::temp_dir().join("mult_polynomial_benchmark_resume_test");
+        let _ = std::fs::remove_dir_all(&output_dir);
+        let config = BenchmarkConfig {
+            sizes: vec![4, 16, 64],
+            algorithms: vec![PolynomialMultAlg::Naive, PolynomialMultAlg::Thresholded(8)],
+            output_dir,
+            seed: Some(7),
+            verify: true,
+            ..BenchmarkConfig::default()
+        };
+        let first = run_comprehensive_benchmark(&config, &|_| 2).unwrap();
+        let path = config.output_dir.join(CHECKPOINT_FILE);
+        assert!(!path.exists());
+        // interrupted while writing the third cell, with a recognizable time in the first
+        let names = column_names(&config);
+        let parameters = checkpoint_parameters(&config);
+        let checkpoint = Checkpoint::create(&path, 7, &names, &parameters).unwrap();
+        let mut first_cell = first[0].clone();
+        first_cell.times[0][0] = 123.0;
+        for cell in [&first_cell, &first[1], &first[2]] {
+            checkpoint.record(cell).unwrap();
+        }
+        drop(checkpoint);
+        let text = std::fs::read_to_string(&path).unwrap();
+        let last = text.trim_end().lines().last().unwrap();
+        std::fs::write(&path, &text[..text.len() - last.len() / 2]).unwrap();
+        assert_eq!(checkpoint::load(&path).unwrap().cells.len(), 2);
+
+        let resumed = BenchmarkConfig {
+            resume: true,
+            ..config.clone()
+        };
+        let results = run_comprehensive_benchmark(&resumed, &|_| 2).unwrap();
+        assert_eq!(results[0].times[0][0], 123.0);
+        assert_eq!(results[1].times, first[1].times);
+        assert_eq!(results[2].size, 64);
+        assert_ne!(results[2].times, first[2].times);
+        assert_eq!(results[2].max_discrepancy.len(), 2);
+        let csv = std::fs::read_to_string(config.output_dir.join("all_results.csv")).unwrap();
+        assert_eq!(csv.lines().count(), 2 + 3);
+        let mean: f64 = csv
+            .lines()
+            .nth(2)
+            .unwrap()
+            .split(',')
+            .nth(1)
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!(mean > 60.0);
+        assert!(!path.exists());
+
+        // another seed, other algorithms or other parameters would mix incomparable times
+        Checkpoint::create(&path, 7, &names, &parameters).unwrap();
+        for config in [
+            BenchmarkConfig {
+                seed: Some(8),
+                ..resumed.clone()
+            },
+            BenchmarkConfig {
+                algorithms: vec![PolynomialMultAlg::Naive],
+                ..resumed.clone()
+            },
+            BenchmarkConfig {
+                warmup_runs: resumed.warmup_runs + 1,
+                ..resumed.clone()
+            },
+            BenchmarkConfig {
+                verify: false,
+                ..resumed.clone()
+            },
+        ] {
+            assert!(matches!(
+                run_comprehensive_benchmark(&config, &|_| 2),
+                Err(PolyError::InvalidArgument(_))
+            ));
+        }
+    }
+
+    /// Aggregation of the runs and warm-up before timing
+    #[cfg(feature = "thread_rng")]
+    #[test]
+    fn test_aggregation() {
+        let times = [9.0, 1.0, 2.0, 3.0, 4.0, 100.0, 2.0, 3.0, 4.0, 2.0];
+        assert_eq!(Aggregation::Mean.apply(&times), 13.0);
+        assert_eq!(Aggregation::Min.apply(&times), 1.0);
+        // dropping one run at each end removes the outlier
+        assert_eq!(Aggregation::TrimmedMean(0.1).apply(&times), 29.0 / 8.0);
+        assert_eq!(Aggregation::TrimmedMean(0.0).apply(&times), 13.0);
+        assert_eq!(Aggregation::TrimmedMean(0.2).apply(&[5.0]), 5.0);
+
+        let p = Polynomial::random(-10.0, 10.0, 100);
+        let warm = benchmark_single_warm(&PolynomialMultAlg::CookTooms, &p, &p, 3);
+        assert!(warm > Duration::ZERO);
+        let config = BenchmarkConfig {
+            sizes: vec![16],
+            algorithms: vec![PolynomialMultAlg::Naive],
+            output_dir: std::env::temp_dir().join("mult_polynomial_benchmark_aggregation_test"),
+            warmup_runs: 2,
+            aggregation: Aggregation::Min,
+            ..BenchmarkConfig::default()
+        };
+        let results = run_comprehensive_benchmark(&config, &|_| 5).unwrap();
+        let csv = std::fs::read_to_string(config.output_dir.join("all_results.csv")).unwrap();
+        let row: Vec<_> = csv.lines().nth(2).unwrap().split(',').collect();
+        // the time column is the minimum, as is the `_min` column
+        assert_eq!(row[1], row[2]);
+        assert_eq!(results[0].times[0].len(), 5);
+    }
+
+    /// Verification reports the discrepancy of every algorithm and catches wrong products
+    #[test]
+    fn test_benchmark_verify() {
+        let config = BenchmarkConfig {
+            sizes: vec![16, 200],
+            algorithms: vec![PolynomialMultAlg::Naive, PolynomialMultAlg::CookTooms],
+            output_dir: std::env::temp_dir().join("mult_polynomial_benchmark_verify_test"),
+            verify: true,
+            ..BenchmarkConfig::default()
+        };
+        let results = run_comprehensive_benchmark(&config, &|_| 2).unwrap();
+        assert_eq!(results[1].max_discrepancy[0], 0.0);
+        assert!(results[1].max_discrepancy[1] > 0.0);
+        assert!(results[1].max_discrepancy[1] < VERIFY_TOLERANCE);
+        let csv = std::fs::read_to_string(config.output_dir.join("all_results.csv")).unwrap();
+        assert!(csv.contains(",cook_tooms_time_ci95,cook_tooms_time_max_error\n"));
+
+        let reference = Polynomial::new(vec![2.0, -4.0]);
+        let wrong = Polynomial::new(vec![2.0, -3.0, 1e-3]);
+        assert_eq!(relative_discrepancy(&wrong, &reference), 0.25);
+        assert_eq!(relative_discrepancy(&wrong, &Polynomial::new(vec![])), 3.0);
+    }
+
+    /// Custom algorithms get their own columns after the built-in ones and are verified too
+    #[test]
+    fn test_benchmark_custom_algorithms() {
+        /// Naive product with the last coefficient off by one
+        struct OffByOne;
+        impl MultiplyAlgorithm for OffByOne {
+            fn multiply(&self, a: &[f64], b: &[f64]) -> Vec<f64> {
+                let mut product = PolynomialMultAlg::Naive.multiply(a, b);
+                *product.last_mut().unwrap() += 1.0;
+                product
+            }
+            fn label(&self) -> String {
+                "off_by_one".to_string()
+            }
+        }
+        let mut config = BenchmarkConfig {
+            sizes: vec![16],
+            algorithms: vec![PolynomialMultAlg::Naive],
+            custom_algorithms: vec![Arc::new(PolynomialMultAlg::CookTooms), Arc::new(OffByOne)],
+            output_dir: std::env::temp_dir().join("mult_polynomial_benchmark_custom_test"),
+            format: OutputFormat::Json,
+            ..BenchmarkConfig::default()
+        };
+        let results = run_comprehensive_benchmark(&config, &|_| 2).unwrap();
+        assert_eq!(results[0].times.len(), 3);
+        let json = std::fs::read_to_string(config.output_dir.join("all_results.json")).unwrap();
+        assert!(json.contains("{\"algorithm\": \"off_by_one\", \"threshold\": null,"));
+        assert_eq!(
+            column_names(&config),
+            ["naive_time", "cook-tooms", "off_by_one"]
+        );
+
+        config.verify = true;
+        let error = run_comprehensive_benchmark(&config, &|_| 2).unwrap_err();
+        assert!(
+            matches!(error, PolyError::VerificationFailed { algorithm, .. } if algorithm == "off_by_one")
+        );
+    }
+
+    /// Algorithms stop at the time budget of a cell, and are skipped where one run would exceed it
+    #[test]
+    fn test_benchmark_time_budget() {
+        /// Naive product sleeping 50 µs per coefficient of the operands
+        struct Slow;
+        impl MultiplyAlgorithm for Slow {
+            fn multiply(&self, a: &[f64], b: &[f64]) -> Vec<f64> {
+                std::thread::sleep(Duration::from_micros(50 * (a.len() + b.len()) as u64));
+                PolynomialMultAlg::Naive.multiply(a, b)
+            }
+            fn label(&self) -> String {
+                "slow".to_string()
+            }
+        }
+        let mut config = BenchmarkConfig {
+            sizes: vec![8, 32, 128],
+            algorithms: vec![PolynomialMultAlg::Naive],
+            custom_algorithms: vec![Arc::new(Slow)],
+            output_dir: std::env::temp_dir().join("mult_polynomial_benchmark_budget_test"),
+            max_time_per_cell: Some(Duration::from_millis(5)),
+            warmup_runs: 0,
+            verify: true,
+            ..BenchmarkConfig::default()
+        };
+        // one run of `slow` takes 0.8 ms, 3.2 ms and 12.8 ms
+        let results = run_comprehensive_benchmark(&config, &|_| 10).unwrap();
+        let runs: Vec<(usize, usize)> = (results.iter())
+            .map(|r| (r.times[0].len(), r.times[1].len()))
+            .collect();
+        assert_eq!(runs[0].0, 10);
+        assert!((2..=7).contains(&runs[0].1), "{:?}", runs);
+        assert!((1..=2).contains(&runs[1].1), "{:?}", runs);
+        assert_eq!(runs[2], (10, 0));
+        assert!(results[2].skipped(1) && !results[2].skipped(0));
+        let csv = std::fs::read_to_string(config.output_dir.join("all_results.csv")).unwrap();
+        let last = csv.lines().last().unwrap();
+        assert!(last.ends_with(&",skipped".repeat(6)));
+        assert_eq!(last.split(',').count(), 1 + 2 * 6);
+        let fits = fit_complexity(&config, &results, 1);
+        assert_eq!(fits.len(), 2);
+
+        config.format = OutputFormat::Json;
+        run_comprehensive_benchmark(&config, &|_| 10).unwrap();
+        let json = std::fs::read_to_string(config.output_dir.join("all_results.json")).unwrap();
+        assert!(json.contains("\"threshold\": null, \"skipped\": true, \"times\": []}"));
+    }
+
+    /// Exponents are recovered from exact power laws and fitted per algorithm from the results
+    #[test]
+    fn test_complexity_fit() {
+        let points: Vec<(f64, f64)> = [16.0, 64.0, 256.0]
+            .iter()
+            .map(|&n: &f64| (n, 2e-8 * n.powf(5f64.log(3.0))))
+            .collect();
+        let fit = ComplexityFit::new(&points).unwrap();
+        assert!((fit.exponent - 1.465).abs() < 1e-3);
+        assert!((fit.coefficient - 2e-8).abs() < 1e-20);
+        assert!((fit.r_squared - 1.0).abs() < 1e-12);
+        // noise lowers the coefficient of determination
+        let noisy = [(10.0, 1.0), (100.0, 30.0), (1000.0, 1000.0)];
+        assert!(ComplexityFit::new(&noisy).unwrap().r_squared < 1.0);
+        assert_eq!(ComplexityFit::new(&[(10.0, 1.0), (10.0, 2.0)]), None);
+        assert_eq!(ComplexityFit::new(&[(10.0, 1.0), (20.0, 0.0)]), None);
+
+        let config = BenchmarkConfig {
+            sizes: vec![8, 64, 512],
+            aspect_ratios: vec![1, 4],
+            algorithms: vec![PolynomialMultAlg::Naive, PolynomialMultAlg::CookTooms],
+            output_dir: std::env::temp_dir().join("mult_polynomial_benchmark_complexity_test"),
+            ..BenchmarkConfig::default()
+        };
+        let results = run_comprehensive_benchmark(&config, &|_| 3).unwrap();
+        let fits = fit_complexity(&config, &results, 64);
+        assert_eq!(fits.len(), 2);
+        assert_eq!(fits[1].0, "cook_tooms_time");
+        assert!(fit_complexity(&config, &results, 512).is_empty());
+        write_complexity(&config, &fits).unwrap();
+        let csv = std::fs::read_to_string(config.output_dir.join("complexity.csv")).unwrap();
+        assert_eq!(
+            csv.lines().nth(1),
+            Some("algorithm,exponent,coefficient,r_squared")
+        );
+        assert!(csv.lines().nth(2).unwrap().starts_with("naive_time,"));
+    }
+
+    /// The raw runs file has one row per size, algorithm and run
+    #[test]
+    fn test_benchmark_raw_runs() {
+        let config = BenchmarkConfig {
+            sizes: vec![4, 16],
+            aspect_ratios: vec![1, 2],
+            algorithms: vec![PolynomialMultAlg::Naive, PolynomialMultAlg::Thresholded(8)],
+            output_dir: std::env::temp_dir().join("mult_polynomial_benchmark_runs_test"),
+            raw_runs: true,
+            ..BenchmarkConfig::default()
+        };
+        let results = run_comprehensive_benchmark(&config, &|_| 3).unwrap();
+        let csv = std::fs::read_to_string(config.output_dir.join("all_runs.csv")).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], text_header("runs"));
+        assert_eq!(
+            lines[1],
+            "size,other_size,aspect_ratio,algorithm,run,seconds"
+        );
+        assert_eq!(lines.len(), 2 + 4 * 2 * 3);
+        let last = results[3].times[1][2];
+        assert_eq!(lines[25], format!("16,8,2,threshold_8,2,{}", last));
+    }
+
+    /// The evaluation file has one row per size and number of points, with every strategy
+    #[cfg(feature = "thread_rng")]
+    #[test]
+    fn test_evaluation_benchmark() {
+        let config = BenchmarkConfig {
+            sizes: vec![10, 200],
+            evaluation_points: vec![3, 50],
+            output_dir: std::env::temp_dir().join("mult_polynomial_benchmark_evaluation_test"),
+            aggregation: Aggregation::Min,
+            seed: Some(5),
+            ..BenchmarkConfig::default()
+        };
+        let results = run_evaluation_benchmark(&config, &|size| 1 + size / 100).unwrap();
+        assert_eq!(results.len(), 4);
+        assert!(results.iter().all(|r| r.times.len() == 4));
+        assert_eq!(results[1].times[2].len(), 1);
+        assert_eq!(results[3].times[0].len(), 3);
+        let csv = std::fs::read_to_string(config.output_dir.join("evaluation.csv")).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], text_header("evaluation"));
+        assert_eq!(
+            lines[1],
+            "size,points,horner_time,estrin_time,multipoint_time,slice_time"
+        );
+        assert_eq!(lines.len(), 2 + 4);
+        assert_eq!(
+            lines[5],
+            format!(
+                "200,50,{},{},{},{}",
+                results[3].aggregate(0, Aggregation::Min),
+                results[3].aggregate(1, Aggregation::Min),
+                results[3].aggregate(2, Aggregation::Min),
+                results[3].aggregate(3, Aggregation::Min)
+            )
+        );
+
+        // the strategies agree on the values
+        let p = Polynomial::random(-1.0, 1.0, 500);
+        let points: Vec<f64> = (0..80).map(|i| i as f64 / 80.0 - 0.5).collect();
+        let horner = EvaluationStrategy::Horner.evaluate(&p, &points);
+        for strategy in &EvaluationStrategy::ALL[1..] {
+            for (x, y) in strategy.evaluate(&p, &points).iter().zip(&horner) {
+                assert!((x - y).abs() < 1e-12);
+            }
+        }
+        let invalid = BenchmarkConfig {
+            evaluation_points: vec![0],
+            ..config
+        };
+        assert!(run_evaluation_benchmark(&invalid, &|_| 1).is_err());
+    }
+
+    /// The exact file has one row per size and number of bits, and the strategies agree
+    #[test]
+    fn test_exact_benchmark() {
+        let config = BenchmarkConfig {
+            sizes: vec![10, 300],
+            exact_bits: vec![1, 50],
+            output_dir: std::env::temp_dir().join("mult_polynomial_benchmark_exact_test"),
+            aggregation: Aggregation::Min,
+            seed: Some(5),
+            ..BenchmarkConfig::default()
+        };
+        let results = run_exact_benchmark(&config, &|size| 1 + size / 100).unwrap();
+        assert_eq!(results.len(), 4);
+        assert!(results.iter().all(|r| r.times.len() == 3));
+        assert_eq!(results[3].times[0].len(), 4);
+        let csv = std::fs::read_to_string(config.output_dir.join("exact.csv")).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], text_header("exact"));
+        assert_eq!(
+            lines[1],
+            "size,bits,schoolbook_time,crt_time,kronecker_time"
+        );
+        assert_eq!(lines.len(), 2 + 4);
+        assert!(lines[5].starts_with("300,50,"));
+
+        let a: Vec<i64> = (0..200).map(|i| (i * 7919 % 2001) - 1000).collect();
+        let b: Vec<i64> = (0..90).map(|i| (i * 104729 % 3001) - 1500).collect();
+        let schoolbook = ExactStrategy::Schoolbook.multiply(&a, &b).unwrap();
+        for strategy in [ExactStrategy::Crt, ExactStrategy::Kronecker] {
+            assert_eq!(strategy.multiply(&a, &b).unwrap(), schoolbook);
+        }
+        // products beyond i128 fail instead of overflowing
+        let overflowing = BenchmarkConfig {
+            sizes: vec![64],
+            exact_bits: vec![62],
+            ..config.clone()
+        };
+        assert!(run_exact_benchmark(&overflowing, &|_| 1).is_err());
+        let invalid = BenchmarkConfig {
+            exact_bits: vec![63],
+            ..config
+        };
+        assert!(run_exact_benchmark(&invalid, &|_| 1).is_err());
+    }
+
+    /// The accuracy file has one row per size and algorithm, with errors against the exact product
+    #[test]
+    fn test_benchmark_accuracy() {
+        let big = (1i128 << 60) + 1; // not representable as a float
+        assert_eq!(
+            relative_error(&[(1u64 << 60) as f64], &[big]),
+            1.0 / big as f64
+        );
+        assert_eq!(relative_error(&[3.0, 1.0], &[4]), 0.25);
+
+        let config = BenchmarkConfig {
+            sizes: vec![1, 40, 300],
+            algorithms: vec![
+                PolynomialMultAlg::Naive,
+                PolynomialMultAlg::CookTooms,
+                PolynomialMultAlg::Thresholded(8),
+            ],
+            output_dir: std::env::temp_dir().join("mult_polynomial_benchmark_accuracy_test"),
+            seed: Some(3),
+            accuracy: true,
+            ..BenchmarkConfig::default()
+        };
+        run_comprehensive_benchmark(&config, &|_| 1).unwrap();
+        let accuracy = measure_accuracy(&config, 3);
+        assert!(accuracy.iter().all(|r| r.max_relative_error.len() == 3));
+        assert_eq!(accuracy[1].max_relative_error[0], 0.0);
+        for result in &accuracy {
+            assert!(result.max_relative_error.iter().all(|&e| e < 1e-12));
+        }
+        let csv = std::fs::read_to_string(config.output_dir.join("accuracy.csv")).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], text_header("accuracy"));
+        assert_eq!(lines[1], "size,algorithm,max_relative_error");
+        assert_eq!(lines.len(), 2 + 3 * 3);
+        assert_eq!(
+            lines[10],
+            format!("300,threshold_8,{:e}", accuracy[2].max_relative_error[2])
+        );
+    }
+
+    /// Seeded inputs are the same whether the cells are measured on one thread or several
+    #[test]
+    fn test_benchmark_threads() {
+        let run = |threads, dir: &str| {
+            let config = BenchmarkConfig {
+                sizes: vec![10, 40, 100, 27],
+                aspect_ratios: vec![1, 3],
+                algorithms: vec![PolynomialMultAlg::CookTooms],
+                output_dir: std::env::temp_dir().join(dir),
+                seed: Some(7),
+                verify: true,
+                threads,
+                pin_threads: true,
+                ..BenchmarkConfig::default()
+            };
+            run_comprehensive_benchmark(&config, &|_| 2).unwrap()
+        };
+        let sequential = run(1, "mult_polynomial_benchmark_sequential_test");
+        let parallel = run(3, "mult_polynomial_benchmark_parallel_test");
+        assert_eq!(parallel.len(), 8);
+        for (s, p) in sequential.iter().zip(&parallel) {
+            assert_eq!((s.size, s.aspect_ratio), (p.size, p.aspect_ratio));
+            assert_eq!(s.max_discrepancy, p.max_discrepancy);
+        }
+        assert_ne!(sequential[0].max_discrepancy, sequential[2].max_discrepancy);
+    }
+
+    /// Summary statistics of a known sample
+    #[test]
+    fn test_summary() {
+        let summary = Summary::new(&[4.0, 1.0, 3.0, 2.0]);
+        assert_eq!(summary.min, 1.0);
+        assert_eq!(summary.median, 2.5);
+        assert_eq!(summary.mean, 2.5);
+        assert!((summary.std_dev - (5.0f64 / 3.0).sqrt()).abs() < 1e-12);
+        assert!((summary.ci95 - 3.182 * summary.std_dev / 2.0).abs() < 1e-12);
+        assert_eq!(Summary::new(&[7.0]).ci95, 0.0);
+    }
+}
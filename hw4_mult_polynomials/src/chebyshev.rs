@@ -0,0 +1,212 @@
+//! Polynomials in the Chebyshev basis `T₀, T₁, …` with `Tₖ(cos θ) = cos kθ`
+
+use crate::polynomial::multiply_impl;
+use crate::{Normalization, Polynomial, PolynomialMultAlg};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Represents a polynomial `Σ cₖ·Tₖ(x)` in the Chebyshev basis
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChebyshevPolynomial {
+    /// Chebyshev coefficients, from `T₀` to the highest degree
+    pub coeffs: Vec<f64>,
+}
+
+impl ChebyshevPolynomial {
+    /// Create a new polynomial from a vector of Chebyshev coefficients
+    ///
+    /// Trailing coefficients are trimmed the same way as in [`Polynomial::new`].
+    pub fn new(coeffs: Vec<f64>) -> Self {
+        ChebyshevPolynomial {
+            coeffs: Normalization::DEFAULT.apply(coeffs),
+        }
+    }
+
+    /// Convert a polynomial from the monomial basis
+    ///
+    /// Uses Horner's scheme with the recurrence `x·Tₖ = (Tₖ₊₁ + Tₖ₋₁) / 2`, in O(n²).
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::{ChebyshevPolynomial, Polynomial};
+    /// // x² = (T₀ + T₂) / 2
+    /// let p = ChebyshevPolynomial::from_monomial(&Polynomial::new(vec![0.0, 0.0, 1.0]));
+    /// assert_eq!(p.coeffs, vec![0.5, 0.0, 0.5]);
+    /// ```
+    pub fn from_monomial(p: &Polynomial) -> Self {
+        let mut result: Vec<f64> = Vec::with_capacity(p.coeffs.len());
+        for &a in p.coeffs.iter().rev() {
+            // result ← x·result + a
+            let mut shifted = vec![0.0; result.len() + 1];
+            for (k, &c) in result.iter().enumerate() {
+                if k == 0 {
+                    shifted[1] += c;
+                } else {
+                    shifted[k + 1] += 0.5 * c;
+                    shifted[k - 1] += 0.5 * c;
+                }
+            }
+            shifted[0] += a;
+            result = shifted;
+        }
+        ChebyshevPolynomial::new(result)
+    }
+
+    /// Convert to the monomial basis
+    ///
+    /// Expands every `Tₖ` with the recurrence `Tₖ₊₁ = 2x·Tₖ - Tₖ₋₁`, in O(n²).
+    pub fn to_monomial(&self) -> Polynomial {
+        let n = self.coeffs.len();
+        let mut result = vec![0.0; n];
+        let mut prev = vec![0.0; n];
+        let mut curr = vec![0.0; n];
+        if n > 0 {
+            curr[0] = 1.0;
+        }
+        for (k, &c) in self.coeffs.iter().enumerate() {
+            for (r, t) in result.iter_mut().zip(&curr[..=k]) {
+                *r += c * t;
+            }
+            if k + 1 < n {
+                // prev ← 2x·curr - prev (T₁ = x·T₀), then swap so that curr holds Tₖ₊₁
+                let factor = if k == 0 { 1.0 } else { 2.0 };
+                for j in (0..=k + 1).rev() {
+                    let shifted = if j > 0 { factor * curr[j - 1] } else { 0.0 };
+                    prev[j] = shifted - prev[j];
+                }
+                core::mem::swap(&mut prev, &mut curr);
+            }
+        }
+        Polynomial::new(result)
+    }
+
+    /// Evaluate the polynomial at a given point x with Clenshaw's algorithm
+    pub fn evaluate(&self, x: f64) -> f64 {
+        let (mut b1, mut b2) = (0.0, 0.0);
+        for &c in self.coeffs.iter().skip(1).rev() {
+            (b1, b2) = (c + 2.0 * x * b1 - b2, b1);
+        }
+        self.coeffs.first().copied().unwrap_or(0.0) + x * b1 - b2
+    }
+
+    /// Multiply with another polynomial in Chebyshev form using the given algorithm
+    ///
+    /// Uses `Tᵢ·Tⱼ = (Tᵢ₊ⱼ + T|ᵢ₋ⱼ|) / 2`: the first sum is an ordinary product
+    /// and the second one a product with the reversed operand,
+    /// so both go through the chosen monomial multiplier.
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::{ChebyshevPolynomial, PolynomialMultAlg};
+    /// // T₁·T₂ = (T₁ + T₃) / 2
+    /// let t1 = ChebyshevPolynomial::new(vec![0.0, 1.0]);
+    /// let t2 = ChebyshevPolynomial::new(vec![0.0, 0.0, 1.0]);
+    /// let product = t1.multiply(&t2, PolynomialMultAlg::CookTooms);
+    /// assert_eq!(product.coeffs, vec![0.0, 0.5, 0.0, 0.5]);
+    /// ```
+    pub fn multiply(
+        &self,
+        other: &ChebyshevPolynomial,
+        algorithm: PolynomialMultAlg,
+    ) -> ChebyshevPolynomial {
+        let (a, b) = (&self.coeffs, &other.coeffs);
+        if a.is_empty() || b.is_empty() {
+            return ChebyshevPolynomial::new(vec![]);
+        }
+        let m = b.len();
+        let mut result = multiply_impl(a, b, algorithm);
+        // reversed[m - 1 + d] = Σ_{i - j = d} a_i·b_j
+        let b_reversed: Vec<f64> = b.iter().rev().copied().collect();
+        let reversed = multiply_impl(a, &b_reversed, algorithm);
+        for (k, r) in result.iter_mut().enumerate() {
+            let mut diff = reversed.get(m - 1 + k).copied().unwrap_or(0.0);
+            if k > 0 && k < m {
+                diff += reversed[m - 1 - k];
+            }
+            *r = 0.5 * (*r + diff);
+        }
+        ChebyshevPolynomial::new(result)
+    }
+}
+
+impl Polynomial {
+    /// Convert to the Chebyshev basis, see [`ChebyshevPolynomial::from_monomial`]
+    pub fn to_chebyshev(&self) -> ChebyshevPolynomial {
+        ChebyshevPolynomial::from_monomial(self)
+    }
+}
+
+#[cfg(all(test, feature = "thread_rng"))]
+mod tests {
+    use super::*;
+
+    /// Test the basis conversions on the first Chebyshev polynomials
+    #[test]
+    fn test_conversion() {
+        // T₃ = 4x³ - 3x, T₄ = 8x⁴ - 8x² + 1
+        let t3 = ChebyshevPolynomial::new(vec![0.0, 0.0, 0.0, 1.0]);
+        assert_eq!(t3.to_monomial(), Polynomial::new(vec![0.0, -3.0, 0.0, 4.0]));
+        let t4 = Polynomial::new(vec![1.0, 0.0, -8.0, 0.0, 8.0]);
+        assert_eq!(t4.to_chebyshev().coeffs, vec![0.0, 0.0, 0.0, 0.0, 1.0]);
+        // round trip
+        let p = Polynomial::random(-1.0, 1.0, 30);
+        assert!(p.to_chebyshev().to_monomial().approx_eq(&p, 1e-9, 1e-9));
+        assert!(ChebyshevPolynomial::new(vec![])
+            .to_monomial()
+            .coeffs
+            .is_empty());
+    }
+
+    /// Test Clenshaw evaluation against the monomial form and `Tₖ(cos θ) = cos kθ`
+    #[test]
+    fn test_evaluate() {
+        let p = Polynomial::random(-1.0, 1.0, 20);
+        let c = p.to_chebyshev();
+        for x in [-1.0, -0.3, 0.0, 0.7, 1.0] {
+            assert!((c.evaluate(x) - p.evaluate(x)).abs() < 1e-9);
+        }
+        let t7 = ChebyshevPolynomial::new(vec![0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0]);
+        let theta: f64 = 0.4;
+        assert!((t7.evaluate(theta.cos()) - (7.0 * theta).cos()).abs() < 1e-12);
+    }
+
+    /// Test multiplication in Chebyshev form against the product identity and the monomial product
+    #[test]
+    fn test_multiply() {
+        let c1 = ChebyshevPolynomial::new(Polynomial::random(-1.0, 1.0, 40).coeffs);
+        let c2 = ChebyshevPolynomial::new(Polynomial::random(-1.0, 1.0, 25).coeffs);
+        let mut expected = vec![0.0; 64];
+        for (i, a) in c1.coeffs.iter().enumerate() {
+            for (j, b) in c2.coeffs.iter().enumerate() {
+                expected[i + j] += 0.5 * a * b;
+                expected[i.abs_diff(j)] += 0.5 * a * b;
+            }
+        }
+        let expected = Polynomial::new(expected);
+        for alg in [
+            PolynomialMultAlg::Naive,
+            PolynomialMultAlg::CookTooms,
+            PolynomialMultAlg::Thresholded(8),
+        ] {
+            let product = c1.multiply(&c2, alg);
+            assert!(Polynomial::new(product.coeffs).approx_eq(&expected, 1e-12, 1e-12));
+        }
+        // the product of the values is the value of the product
+        let product = c1.multiply(&c2, PolynomialMultAlg::Naive);
+        for x in [-0.9, -0.2, 0.5, 1.0] {
+            assert!((product.evaluate(x) - c1.evaluate(x) * c2.evaluate(x)).abs() < 1e-9);
+        }
+        // small degrees agree with multiplying in the monomial basis
+        let (p1, p2) = (
+            Polynomial::new(vec![1.0, -2.0, 0.5]),
+            Polynomial::new(vec![3.0, 1.0]),
+        );
+        let product = p1
+            .to_chebyshev()
+            .multiply(&p2.to_chebyshev(), PolynomialMultAlg::Naive);
+        assert_eq!(product.to_monomial(), p1.multiply_naive(&p2));
+        let empty = ChebyshevPolynomial::new(vec![]);
+        assert!(c1
+            .multiply(&empty, PolynomialMultAlg::Naive)
+            .coeffs
+            .is_empty());
+    }
+}
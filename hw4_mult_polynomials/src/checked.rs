@@ -0,0 +1,109 @@
+//! Multiplication rejecting NaN and infinite coefficients
+//!
+//! A single NaN coefficient spreads through the Toom-3 evaluation and interpolation
+//! to most of the product, so the checked variants locate it instead.
+
+use crate::{Coefficient, Operand, PolyError, Polynomial, PolynomialMultAlg};
+
+/// Index and value of the first NaN or infinite coefficient
+fn first_non_finite<T: Coefficient>(coeffs: &[T]) -> Option<(usize, T)> {
+    // `c * 0` is zero for every finite `c`, and NaN for NaN and infinities
+    (coeffs.iter().copied().enumerate()).find(|&(_, c)| c * T::ZERO != T::ZERO)
+}
+
+/// Fail with [`PolyError::NonFinite`] on the first non-finite coefficient of `operand`
+fn check<T: Coefficient>(coeffs: &[T], operand: Operand) -> Result<(), PolyError> {
+    match first_non_finite(coeffs) {
+        Some((index, value)) => Err(PolyError::NonFinite {
+            operand,
+            index,
+            value: value.to_f64(),
+        }),
+        None => Ok(()),
+    }
+}
+
+impl<T: Coefficient> Polynomial<T> {
+    /// Whether all coefficients are finite (neither NaN nor infinite)
+    pub fn is_finite(&self) -> bool {
+        first_non_finite(&self.coeffs).is_none()
+    }
+
+    /// Multiply using the given algorithm, failing with [`PolyError::NonFinite`] if an operand
+    /// has a NaN or infinite coefficient, or if the product overflows
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::{Operand, PolyError, Polynomial, PolynomialMultAlg};
+    /// let p = Polynomial::new(vec![1.0, f64::NAN, 3.0]);
+    /// let q = Polynomial::new(vec![1.0, 2.0]);
+    /// assert!(matches!(
+    ///     q.multiply_checked(&p, PolynomialMultAlg::CookTooms),
+    ///     Err(PolyError::NonFinite { operand: Operand::Second, index: 1, .. })
+    /// ));
+    /// ```
+    pub fn multiply_checked(
+        &self,
+        other: &Polynomial<T>,
+        algorithm: PolynomialMultAlg,
+    ) -> Result<Polynomial<T>, PolyError> {
+        check(&self.coeffs, Operand::First)?;
+        check(&other.coeffs, Operand::Second)?;
+        let product = self.multiply(other, algorithm);
+        check(&product.coeffs, Operand::Product)?;
+        Ok(product)
+    }
+
+    /// Multiply using [`PolynomialMultAlg::auto_for`], checking for non-finite coefficients
+    /// as [`Polynomial::multiply_checked`] does
+    pub fn multiply_auto_checked(&self, other: &Polynomial<T>) -> Result<Polynomial<T>, PolyError> {
+        let algorithm = PolynomialMultAlg::auto_for::<T>(self.coeffs.len(), other.coeffs.len());
+        self.multiply_checked(other, algorithm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    /// Non-finite inputs are located, overflowing products are reported
+    #[test]
+    fn test_multiply_checked() {
+        let mut coeffs = vec![1.0; 1000];
+        let p = Polynomial::new(coeffs.clone());
+        assert!(p.is_finite());
+        let expected = p.multiply_naive(&p);
+        assert_eq!(p.multiply_auto_checked(&p), Ok(expected));
+
+        coeffs[537] = f64::NAN;
+        let nan = Polynomial::new(coeffs.clone());
+        assert!(!nan.is_finite());
+        let error = nan
+            .multiply_checked(&p, PolynomialMultAlg::Thresholded(16))
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            PolyError::NonFinite { operand: Operand::First, index: 537, value } if value.is_nan()
+        ));
+        coeffs[537] = f64::NEG_INFINITY;
+        assert_eq!(
+            p.multiply_auto_checked(&Polynomial::new(coeffs)),
+            Err(PolyError::NonFinite {
+                operand: Operand::Second,
+                index: 537,
+                value: f64::NEG_INFINITY
+            })
+        );
+
+        let huge = Polynomial::new(vec![1.0, f64::MAX]);
+        assert!(matches!(
+            huge.multiply_checked(&huge, PolynomialMultAlg::Naive),
+            Err(PolyError::NonFinite {
+                operand: Operand::Product,
+                ..
+            })
+        ));
+        let small = Polynomial::new(vec![1.0f32, f32::INFINITY]);
+        assert!(small.multiply_auto_checked(&small).is_err());
+    }
+}
@@ -0,0 +1,286 @@
+//! Checkpoints of benchmark sweeps, to resume an interrupted run
+//!
+//! [`run_comprehensive_benchmark`](crate::benchmark::run_comprehensive_benchmark) appends every
+//! measured cell to `checkpoint.csv` in the output directory as soon as it finishes, synced to disk,
+//! and with [`BenchmarkConfig::resume`](crate::benchmark::BenchmarkConfig::resume) reads the cells
+//! back instead of timing them again. After the header line, the file holds the seed of the inputs,
+//! the result columns and the parameters that change the measured values (see
+//! [`checkpoint_parameters`](crate::benchmark::checkpoint_parameters)), then one line per cell:
+//! `size;other_size;aspect_ratio;distribution;runs;times;peak_bytes;op_counts;max_discrepancy`,
+//! with the values of one algorithm separated by spaces and the algorithms by commas
+//! (no times for a skipped algorithm, so the times always have one field per column).
+//! A line cut off by the interruption is skipped. The file is removed once the run completes.
+
+use crate::benchmark::SizeResult;
+use crate::serialization::{check_text_header, text_header};
+use crate::{OpCounts, PolyError};
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::sync::{Mutex, PoisonError};
+
+/// Name of the checkpoint file in the output directory
+pub(crate) const CHECKPOINT_FILE: &str = "checkpoint.csv";
+
+/// Cells of an interrupted run read back from its checkpoint
+pub(crate) struct SavedRun {
+    /// Seed of the inputs of the run
+    pub(crate) seed: u64,
+    /// Result columns, see [`column_names`](crate::benchmark::column_names)
+    pub(crate) names: Vec<String>,
+    /// Parameters of the run, see [`checkpoint_parameters`](crate::benchmark::checkpoint_parameters)
+    pub(crate) parameters: String,
+    /// Measured cells, in the order in which they finished
+    pub(crate) cells: Vec<SizeResult>,
+}
+
+/// Read the checkpoint at `path`
+pub(crate) fn load(path: &Path) -> Result<SavedRun, PolyError> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| PolyError::Io(format!("{}: {}", path.display(), e)))?;
+    let invalid = || PolyError::InvalidFormat(format!("{}: invalid checkpoint", path.display()));
+    let mut lines = text.lines();
+    check_text_header(lines.next().unwrap_or_default(), "checkpoint")?;
+    let seed = (lines.next())
+        .and_then(|line| line.strip_prefix("seed="))
+        .and_then(|seed| seed.parse().ok())
+        .ok_or_else(invalid)?;
+    let names: Vec<String> = (lines.next())
+        .and_then(|line| line.strip_prefix("columns="))
+        .ok_or_else(invalid)?
+        .split(',')
+        .map(str::to_string)
+        .collect();
+    let parameters = (lines.next())
+        .and_then(|line| line.strip_prefix("parameters="))
+        .ok_or_else(invalid)?
+        .to_string();
+    let cells = lines
+        .filter_map(|line| parse_cell(line, names.len()))
+        .collect();
+    Ok(SavedRun {
+        seed,
+        names,
+        parameters,
+        cells,
+    })
+}
+
+/// Values of one algorithm per comma-separated field, none for an empty field
+fn split_columns(field: &str) -> impl Iterator<Item = &str> {
+    field.split(',').filter(|_| !field.is_empty())
+}
+
+/// Cell of a checkpoint line with `columns` algorithms, `None` if the line is incomplete
+fn parse_cell(line: &str, columns: usize) -> Option<SizeResult> {
+    let fields: Vec<&str> = line.split(';').collect();
+    let &[size, other_size, aspect_ratio, distribution, runs, times, peak_bytes, op_counts, max_discrepancy] =
+        fields.as_slice()
+    else {
+        return None;
+    };
+    // every algorithm has a (possibly empty) field of times, even a single skipped one
+    let times = (times.split(','))
+        .map(|runs| {
+            (runs.split(' '))
+                .filter(|_| !runs.is_empty())
+                .map(|t| t.parse().ok())
+                .collect()
+        })
+        .collect::<Option<Vec<Vec<f64>>>>()?;
+    let peak_bytes = (split_columns(peak_bytes))
+        .map(|b| b.parse().ok())
+        .collect::<Option<Vec<usize>>>()?;
+    let op_counts = (split_columns(op_counts))
+        .map(|ops| {
+            let (mults, adds) = ops.split_once(' ')?;
+            Some(OpCounts {
+                mults: mults.parse().ok()?,
+                adds: adds.parse().ok()?,
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let max_discrepancy = (split_columns(max_discrepancy))
+        .map(|d| d.parse().ok())
+        .collect::<Option<Vec<f64>>>()?;
+    let complete = |len: usize| len == 0 || len == columns;
+    if times.len() != columns
+        || !complete(peak_bytes.len())
+        || !complete(op_counts.len())
+        || !complete(max_discrepancy.len())
+    {
+        return None;
+    }
+    Some(SizeResult {
+        size: size.parse().ok()?,
+        other_size: other_size.parse().ok()?,
+        aspect_ratio: aspect_ratio.parse().ok()?,
+        distribution: distribution.parse().ok()?,
+        runs: runs.parse().ok()?,
+        times,
+        peak_bytes,
+        op_counts,
+        max_discrepancy,
+    })
+}
+
+/// Checkpoint line of a measured cell, ending with a line break
+fn format_cell(result: &SizeResult) -> String {
+    let join = |values: Vec<String>| values.join(",");
+    let times = (result.times.iter())
+        .map(|runs| {
+            let runs: Vec<String> = runs.iter().map(f64::to_string).collect();
+            runs.join(" ")
+        })
+        .collect();
+    let peak_bytes = result.peak_bytes.iter().map(usize::to_string).collect();
+    let op_counts = (result.op_counts.iter())
+        .map(|ops| format!("{} {}", ops.mults, ops.adds))
+        .collect();
+    let max_discrepancy = result.max_discrepancy.iter().map(f64::to_string).collect();
+    format!(
+        "{};{};{};{};{};{};{};{};{}\n",
+        result.size,
+        result.other_size,
+        result.aspect_ratio,
+        result.distribution,
+        result.runs,
+        join(times),
+        join(peak_bytes),
+        join(op_counts),
+        join(max_discrepancy)
+    )
+}
+
+/// Checkpoint file open for appending cells, from any worker thread
+pub(crate) struct Checkpoint {
+    file: Mutex<File>,
+}
+
+impl Checkpoint {
+    /// Start a new checkpoint at `path` for inputs drawn from `seed`, with the result columns `names`
+    /// and the run `parameters`
+    pub(crate) fn create(
+        path: &Path,
+        seed: u64,
+        names: &[String],
+        parameters: &str,
+    ) -> Result<Checkpoint, PolyError> {
+        let mut file = File::create(path)?;
+        writeln!(file, "{}", text_header("checkpoint"))?;
+        writeln!(file, "seed={}", seed)?;
+        writeln!(file, "columns={}", names.join(","))?;
+        writeln!(file, "parameters={}", parameters)?;
+        file.sync_data()?;
+        Ok(Checkpoint {
+            file: Mutex::new(file),
+        })
+    }
+
+    /// Continue the checkpoint at `path` after its last complete line
+    pub(crate) fn append(path: &Path) -> Result<Checkpoint, PolyError> {
+        let mut file = OpenOptions::new().read(true).append(true).open(path)?;
+        // end a line cut off by the interruption, so that it stays a single invalid line
+        let mut last = [b'\n'];
+        if file.seek(SeekFrom::End(0))? > 0 {
+            file.seek(SeekFrom::End(-1))?;
+            file.read_exact(&mut last)?;
+        }
+        if last[0] != b'\n' {
+            file.write_all(b"\n")?;
+        }
+        Ok(Checkpoint {
+            file: Mutex::new(file),
+        })
+    }
+
+    /// Append a measured cell, synced to disk before returning
+    pub(crate) fn record(&self, result: &SizeResult) -> Result<(), PolyError> {
+        let mut file = self.file.lock().unwrap_or_else(PoisonError::into_inner);
+        file.write_all(format_cell(result).as_bytes())?;
+        file.sync_data()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmark::InputDistribution;
+
+    /// Test the round trip of cells, including a line cut off by an interruption
+    #[test]
+    fn test_checkpoint() {
+        let dir = std::env::temp_dir().join("mult_polynomial_checkpoint_test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(CHECKPOINT_FILE);
+        let names = ["naive_time".to_string(), "threshold_8".to_string()];
+        let cell = SizeResult {
+            size: 64,
+            other_size: 16,
+            aspect_ratio: 4,
+            distribution: InputDistribution::Sparse(0.05),
+            runs: 2,
+            times: vec![vec![1e-5, 0.1 + 0.2], vec![]],
+            peak_bytes: Vec::new(),
+            op_counts: vec![
+                OpCounts { mults: 5, adds: 6 },
+                OpCounts { mults: 7, adds: 8 },
+            ],
+            max_discrepancy: vec![0.0, 1e-17],
+        };
+        let checkpoint = Checkpoint::create(&path, u64::MAX, &names, "warmup_runs=1").unwrap();
+        checkpoint.record(&cell).unwrap();
+        drop(checkpoint);
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"128;128;1;uniform;2;0.5 0.").unwrap();
+        drop(file);
+
+        let saved = load(&path).unwrap();
+        assert_eq!((saved.seed, saved.names.as_slice()), (u64::MAX, &names[..]));
+        assert_eq!(saved.parameters, "warmup_runs=1");
+        assert_eq!(saved.cells.len(), 1);
+        let loaded = &saved.cells[0];
+        assert_eq!(loaded.times, cell.times);
+        assert_eq!(loaded.distribution, cell.distribution);
+        assert_eq!(loaded.op_counts, cell.op_counts);
+        assert_eq!(loaded.max_discrepancy, cell.max_discrepancy);
+        assert!(loaded.peak_bytes.is_empty());
+
+        let checkpoint = Checkpoint::append(&path).unwrap();
+        checkpoint.record(&cell).unwrap();
+        assert_eq!(load(&path).unwrap().cells.len(), 2);
+        assert!(parse_cell("64;16;4;uniform;2;1 2;;;", 2).is_none());
+        std::fs::write(&path, "seed=1\n").unwrap();
+        assert!(matches!(load(&path), Err(PolyError::InvalidFormat(_))));
+    }
+
+    /// Test the round trip of cells with every algorithm skipped, with one and with two columns
+    #[test]
+    fn test_checkpoint_skipped() {
+        let dir = std::env::temp_dir().join("mult_polynomial_checkpoint_skipped_test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(CHECKPOINT_FILE);
+        for columns in 1..=2 {
+            let names = vec!["naive_time".to_string(); columns];
+            let cell = SizeResult {
+                size: 1 << 20,
+                other_size: 1 << 20,
+                aspect_ratio: 1,
+                distribution: InputDistribution::Uniform,
+                runs: 5,
+                times: vec![vec![]; columns],
+                peak_bytes: Vec::new(),
+                op_counts: Vec::new(),
+                max_discrepancy: vec![0.0; columns],
+            };
+            let checkpoint = Checkpoint::create(&path, 1, &names, "").unwrap();
+            checkpoint.record(&cell).unwrap();
+            drop(checkpoint);
+            let saved = load(&path).unwrap();
+            assert_eq!(saved.cells.len(), 1, "columns = {}", columns);
+            assert_eq!(saved.cells[0].times, cell.times);
+            assert_eq!(saved.cells[0].max_discrepancy, cell.max_discrepancy);
+        }
+    }
+}
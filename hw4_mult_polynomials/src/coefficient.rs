@@ -0,0 +1,199 @@
+//! Floating-point types usable as polynomial coefficients
+
+use core::fmt::Debug;
+use core::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// Floating-point type the polynomial arithmetic is generic over
+///
+/// Implemented for `f64` (the default) and `f32`. The constants carry the settings
+/// that depend on the precision, so the same kernels serve every width.
+/// ## Example
+/// ```
+/// use mult_polynomial::Polynomial;
+/// let p = Polynomial::<f32>::new(vec![1.0, 2.0, 3.0]);
+/// let q = Polynomial::<f32>::new(vec![4.0, 5.0, 6.0]);
+/// assert_eq!(p.multiply_cook_tooms_k3(&q).coeffs, vec![4.0, 13.0, 28.0, 27.0, 18.0]);
+/// ```
+pub trait Coefficient:
+    Copy
+    + Default
+    + Debug
+    + PartialOrd
+    + Send
+    + Sync
+    + 'static
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+{
+    /// Additive identity
+    const ZERO: Self;
+    /// Multiplicative identity
+    const ONE: Self;
+    /// Absolute tolerance of `==` on polynomials and default trimming epsilon of [`crate::Polynomial::new`]
+    ///
+    /// About 4500 ulps of 1, so that rounding errors of moderately sized products compare equal.
+    const TOLERANCE: f64;
+    /// Veltkamp splitting constant `2^⌈p/2⌉ + 1` for a `p`-bit significand
+    const SPLITTER: Self;
+    /// Threshold used by [`crate::PolynomialMultAlg::auto_for`] for switching to the naive algorithm
+    const AUTO_THRESHOLD: usize;
+
+    /// Convert from `f64`, rounding to the nearest value
+    fn from_f64(x: f64) -> Self;
+    /// Convert to `f64`
+    fn to_f64(self) -> f64;
+    /// Absolute value
+    fn abs(self) -> Self;
+    /// Compute `self * b + c` with a single rounding (for `f32` and `f64` only with the `std` feature)
+    fn fused_mul_add(self, b: Self, c: Self) -> Self;
+}
+
+macro_rules! impl_coefficient {
+    ($t:ty, $tolerance:expr, $splitter:expr, $auto_threshold:expr) => {
+        impl Coefficient for $t {
+            const ZERO: Self = 0.0;
+            const ONE: Self = 1.0;
+            const TOLERANCE: f64 = $tolerance;
+            const SPLITTER: Self = $splitter;
+            const AUTO_THRESHOLD: usize = $auto_threshold;
+
+            #[inline(always)]
+            fn from_f64(x: f64) -> Self {
+                x as $t
+            }
+
+            #[inline(always)]
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+
+            #[inline(always)]
+            fn abs(self) -> Self {
+                if self < 0.0 {
+                    -self
+                } else {
+                    self
+                }
+            }
+
+            #[inline(always)]
+            fn fused_mul_add(self, b: Self, c: Self) -> Self {
+                #[cfg(feature = "std")]
+                return self.mul_add(b, c);
+                #[cfg(not(feature = "std"))]
+                return self * b + c;
+            }
+        }
+    };
+}
+
+// splitters 2^27 + 1 and 2^12 + 1; the naive kernel runs on twice as many `f32` lanes,
+// which moves the crossover of the `f32` threshold up
+impl_coefficient!(f64, 1e-12, 134217729.0, 64);
+impl_coefficient!(f32, 5e-4, 4097.0, 128);
+
+#[cfg(feature = "f256")]
+impl Coefficient for f256::f256 {
+    const ZERO: Self = f256::f256::ZERO;
+    const ONE: Self = f256::f256::ONE;
+    const TOLERANCE: f64 = 4e-68;
+    // 2^119 + 1: biased exponent of 2^119 and the fraction bit of 2^-119
+    const SPLITTER: Self = f256::f256::from_bits(((F256_BIAS + 119) << 108, 1 << 117));
+    // software arithmetic makes multiplications relatively more expensive than in hardware
+    const AUTO_THRESHOLD: usize = 32;
+
+    #[inline(always)]
+    fn from_f64(x: f64) -> Self {
+        f256::f256::from(x)
+    }
+
+    /// Round to the 63 leading significand bits, then to `f64`
+    fn to_f64(self) -> f64 {
+        let (hi, _) = self.to_bits();
+        let sign = if hi >> 127 == 1 { -1.0 } else { 1.0 };
+        let exponent = ((hi >> 108) & 0x7ffff) as i32;
+        if exponent == 0x7ffff {
+            return if self.is_nan() {
+                f64::NAN
+            } else {
+                sign * f64::INFINITY
+            };
+        }
+        if exponent == 0 {
+            // subnormal values of `f256` are far below the range of `f64`
+            return sign * 0.0;
+        }
+        let significand = (1 << 63) | ((hi >> 45) as u64 & ((1 << 63) - 1));
+        sign * scale_by_pow2(significand as f64, exponent - F256_BIAS as i32 - 63)
+    }
+
+    #[inline(always)]
+    fn abs(self) -> Self {
+        f256::f256::abs(&self)
+    }
+
+    #[inline(always)]
+    fn fused_mul_add(self, b: Self, c: Self) -> Self {
+        self.mul_add(b, c)
+    }
+}
+
+/// Exponent bias of the 256-bit interchange format
+#[cfg(feature = "f256")]
+const F256_BIAS: u128 = 262143;
+
+/// Compute `x·2ᵏ` without the standard library, saturating to infinity or zero
+#[cfg(feature = "f256")]
+fn scale_by_pow2(mut x: f64, mut k: i32) -> f64 {
+    let pow2 = |k: i32| f64::from_bits(((k + 1023) as u64) << 52);
+    while k > 1023 && x.is_finite() {
+        x *= pow2(1023);
+        k -= 1023;
+    }
+    while k < -1022 && x != 0.0 {
+        x *= pow2(-1022);
+        k += 1022;
+    }
+    x * pow2(k.clamp(-1022, 1023))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that the splitting constants split significands into exact halves
+    #[test]
+    fn test_splitter() {
+        assert_eq!(
+            f64::SPLITTER,
+            (1u64 << (f64::MANTISSA_DIGITS.div_ceil(2))) as f64 + 1.0
+        );
+        assert_eq!(
+            f32::SPLITTER,
+            (1u32 << (f32::MANTISSA_DIGITS.div_ceil(2))) as f32 + 1.0
+        );
+    }
+
+    /// Test the `f256` constants and the conversion to `f64`
+    #[test]
+    #[cfg(feature = "f256")]
+    fn test_f256() {
+        use f256::f256;
+        let splitter = f256::from((1u128 << 119) as f64) + f256::ONE;
+        assert_eq!(f256::SPLITTER, splitter);
+        for x in [1.0, -0.1, 1e300, -3e-300, 5e-324, f64::MAX, 0.0] {
+            assert_eq!(Coefficient::to_f64(f256::from(x)), x);
+        }
+        // 1/3 rounds to the nearest f64
+        assert_eq!((f256::ONE / f256::from(3.0)).to_f64(), 1.0 / 3.0);
+        assert_eq!((f256::from(f64::MAX) * f256::TWO).to_f64(), f64::INFINITY);
+        assert_eq!((f256::from(5e-324) / f256::TWO).to_f64(), 0.0);
+        assert!(f256::NAN.to_f64().is_nan());
+    }
+}
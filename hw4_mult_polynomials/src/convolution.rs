@@ -0,0 +1,226 @@
+//! Cyclic and negacyclic convolutions built on the polynomial multipliers,
+//! and linear convolutions and correlations of plain slices
+
+use crate::polynomial::{multiply_impl, thresholded_autocorrelation_impl};
+use crate::{Coefficient, Polynomial, PolynomialMultAlg};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Part of the full convolution returned by [`conv`] and [`correlate`], as in NumPy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConvolveMode {
+    /// Every overlap, `n + m - 1` values
+    #[default]
+    Full,
+    /// The centered `max(n, m)` values
+    Same,
+    /// The `max(n, m) - min(n, m) + 1` values where the shorter input overlaps completely
+    Valid,
+}
+
+/// Linear convolution `c[k] = Σ a[i]·b[k - i]` of two signals, computed by the fast multiplier
+///
+/// Unlike [`Polynomial`] products, nothing is trimmed, so trailing zeros of the inputs
+/// keep their place in the output. Empty inputs give an empty output.
+/// ## Example
+/// ```
+/// use mult_polynomial::{conv, ConvolveMode};
+/// let (a, b) = ([1.0, 2.0, 3.0], [0.0, 1.0, 0.5]);
+/// assert_eq!(conv(&a, &b, ConvolveMode::Full), vec![0.0, 1.0, 2.5, 4.0, 1.5]);
+/// assert_eq!(conv(&a, &b, ConvolveMode::Same), vec![1.0, 2.5, 4.0]);
+/// assert_eq!(conv(&a, &b, ConvolveMode::Valid), vec![2.5]);
+/// ```
+pub fn conv<T: Coefficient>(a: &[T], b: &[T], mode: ConvolveMode) -> Vec<T> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let full = multiply_impl(a, b, PolynomialMultAlg::auto_for::<T>(a.len(), b.len()));
+    let (short, long) = (a.len().min(b.len()), a.len().max(b.len()));
+    let (start, len) = match mode {
+        ConvolveMode::Full => return full,
+        ConvolveMode::Same => ((short - 1) / 2, long),
+        ConvolveMode::Valid => (short - 1, long - short + 1),
+    };
+    full[start..start + len].to_vec()
+}
+
+/// Cross-correlation `c[k] = Σ a[n + k]·v[n]` of two signals, i.e. [`conv`] of `a` and `v` reversed
+///
+/// The full output covers the lags `k = -(v.len() - 1) ..= a.len() - 1` in increasing order.
+/// ## Example
+/// ```
+/// use mult_polynomial::{correlate, ConvolveMode};
+/// let (a, v) = ([1.0, 2.0, 3.0], [0.0, 1.0, 0.5]);
+/// assert_eq!(correlate(&a, &v, ConvolveMode::Full), vec![0.5, 2.0, 3.5, 3.0, 0.0]);
+/// assert_eq!(correlate(&a, &v, ConvolveMode::Valid), vec![3.5]);
+/// ```
+pub fn correlate<T: Coefficient>(a: &[T], v: &[T], mode: ConvolveMode) -> Vec<T> {
+    let reversed: Vec<T> = v.iter().rev().copied().collect();
+    conv(a, &reversed, mode)
+}
+
+/// Autocorrelation `r[k] = Σ a[n + k]·a[n]` of a signal at the lags `k = 0 .. a.len() - 1`
+///
+/// The negative lags mirror these, `r[-k] = r[k]`, so the result is the second half of
+/// [`correlate`]`(a, a, Full)`. The symmetry carries over to the Toom-3 recursion, which
+/// takes about 2/3 of the work of a general product, and half in the naive base case.
+/// ## Example
+/// ```
+/// use mult_polynomial::autocorrelate;
+/// assert_eq!(autocorrelate(&[1.0, 2.0, 3.0]), vec![14.0, 8.0, 3.0]);
+/// ```
+pub fn autocorrelate<T: Coefficient>(a: &[T]) -> Vec<T> {
+    let full = thresholded_autocorrelation_impl(a, T::AUTO_THRESHOLD);
+    full[a.len().saturating_sub(1)..].to_vec()
+}
+
+/// Reduce coefficients modulo `xⁿ - sign`, i.e. wrap every coefficient of degree `k ≥ n`
+/// onto degree `k mod n`, multiplied by `sign` once per wrap
+fn wrap(coeffs: &[f64], n: usize, sign: f64) -> Vec<f64> {
+    let mut result = vec![0.0; n];
+    for (block, chunk) in coeffs.chunks(n).enumerate() {
+        let factor = if block % 2 == 1 { sign } else { 1.0 };
+        for (r, c) in result.iter_mut().zip(chunk) {
+            *r += factor * c;
+        }
+    }
+    result
+}
+
+impl Polynomial {
+    /// Cyclic convolution of length `n`, i.e. the product modulo `xⁿ - 1`
+    ///
+    /// Operands longer than `n` are reduced first, so the multiplication works on length `n`.
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// let p = Polynomial::new(vec![1.0, 2.0, 3.0]);
+    /// let q = Polynomial::new(vec![0.0, 1.0]); // x rotates the coefficients
+    /// assert_eq!(p.convolve_cyclic(&q, 3), Polynomial::new(vec![3.0, 1.0, 2.0]));
+    /// ```
+    pub fn convolve_cyclic(&self, other: &Polynomial, n: usize) -> Polynomial {
+        self.convolve_wrapped(other, n, 1.0)
+    }
+
+    /// Negacyclic convolution of length `n`, i.e. the product modulo `xⁿ + 1`
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// let p = Polynomial::new(vec![1.0, 2.0, 3.0]);
+    /// let q = Polynomial::new(vec![0.0, 1.0]); // x rotates and negates the wrapped coefficient
+    /// assert_eq!(p.convolve_negacyclic(&q, 3), Polynomial::new(vec![-3.0, 1.0, 2.0]));
+    /// ```
+    pub fn convolve_negacyclic(&self, other: &Polynomial, n: usize) -> Polynomial {
+        self.convolve_wrapped(other, n, -1.0)
+    }
+
+    /// Product modulo `xⁿ - sign`
+    fn convolve_wrapped(&self, other: &Polynomial, n: usize, sign: f64) -> Polynomial {
+        assert!(n > 0, "convolution length must be positive");
+        let a = self.with_coeffs(wrap(&self.coeffs, n, sign));
+        let b = other.with_coeffs(wrap(&other.coeffs, n, sign));
+        let product = a.multiply_auto(&b);
+        self.with_coeffs(wrap(&product.coeffs, n, sign))
+    }
+}
+
+#[cfg(all(test, feature = "thread_rng"))]
+mod tests {
+    use super::*;
+
+    /// Test the three modes of the slice convolution and correlation against their definitions
+    #[test]
+    fn test_conv_and_correlate() {
+        let a = Polynomial::random(-10.0, 10.0, 120).coeffs;
+        let mut b = Polynomial::random(-10.0, 10.0, 45).coeffs;
+        b.extend([0.0, 0.0]); // trailing zeros are samples, not trimmed
+        let (n, m) = (a.len(), b.len());
+        let mut full = vec![0.0; n + m - 1];
+        for (i, x) in a.iter().enumerate() {
+            for (j, y) in b.iter().enumerate() {
+                full[i + j] += x * y;
+            }
+        }
+        let close = |x: &[f64], y: &[f64]| {
+            x.len() == y.len() && x.iter().zip(y).all(|(x, y)| (x - y).abs() < 1e-9)
+        };
+        for (first, second) in [(&a, &b), (&b, &a)] {
+            assert!(close(&conv(first, second, ConvolveMode::Full), &full));
+            assert!(close(
+                &conv(first, second, ConvolveMode::Same),
+                &full[23..23 + n]
+            ));
+            assert!(close(
+                &conv(first, second, ConvolveMode::Valid),
+                &full[m - 1..n]
+            ));
+        }
+        // correlation by its definition, lag k = index - (m - 1)
+        let correlation = correlate(&a, &b, ConvolveMode::Full);
+        assert_eq!(correlation.len(), n + m - 1);
+        for (index, c) in correlation.iter().enumerate() {
+            let k = index as isize - (m as isize - 1);
+            let expected: f64 = (0..m as isize)
+                .filter(|&i| (0..n as isize).contains(&(i + k)))
+                .map(|i| a[(i + k) as usize] * b[i as usize])
+                .sum();
+            assert!((c - expected).abs() < 1e-9);
+        }
+        assert!(conv(&a, &[], ConvolveMode::Same).is_empty());
+        assert_eq!(
+            conv(&[2.0f32], &[3.0, 0.0], ConvolveMode::Full),
+            vec![6.0, 0.0]
+        );
+    }
+
+    /// Test the autocorrelation against the general correlation, through several Toom-3 levels
+    #[test]
+    fn test_autocorrelate() {
+        for n in [1, 2, 7, 64, 100, 301, 1000] {
+            let a = Polynomial::random(-10.0, 10.0, n).coeffs;
+            let expected = correlate(&a, &a, ConvolveMode::Full);
+            let full = thresholded_autocorrelation_impl(&a, 5);
+            assert_eq!(full.len(), expected.len());
+            for (x, y) in full.iter().zip(&expected) {
+                assert!((x - y).abs() < 1e-8 * n as f64, "n = {}", n);
+            }
+            // symmetric up to rounding, exactly in the naive base case
+            assert!(full
+                .iter()
+                .zip(full.iter().rev())
+                .all(|(x, y)| (x - y).abs() < 1e-8 * n as f64));
+            assert!(n >= 5 || full.iter().eq(full.iter().rev()));
+            let lags = autocorrelate(&a);
+            assert_eq!(lags.len(), n);
+            for (x, y) in lags.iter().zip(&expected[n - 1..]) {
+                assert!((x - y).abs() < 1e-8 * n as f64, "n = {}", n);
+            }
+        }
+        assert!(autocorrelate::<f64>(&[]).is_empty());
+        assert_eq!(autocorrelate(&[3.0f32, 0.0]), vec![9.0, 0.0]);
+    }
+
+    /// Test the convolutions against reducing the full product by hand
+    #[test]
+    fn test_cyclic_and_negacyclic() {
+        let n = 100;
+        let p1 = Polynomial::random(-10.0, 10.0, 130);
+        let p2 = Polynomial::random(-10.0, 10.0, 90);
+        let full = p1.multiply_naive(&p2);
+
+        for (sign, result) in [
+            (1.0, p1.convolve_cyclic(&p2, n)),
+            (-1.0, p1.convolve_negacyclic(&p2, n)),
+        ] {
+            let mut expected = vec![0.0; n];
+            for (k, c) in full.coeffs.iter().enumerate() {
+                // xᵏ = x^(k mod n) · (xⁿ)^(k / n) ≡ x^(k mod n) · sign^(k / n)
+                expected[k % n] += if (k / n) % 2 == 1 { sign * c } else { *c };
+            }
+            assert_eq!(result.coeffs.len(), n);
+            for (r, e) in result.coeffs.iter().zip(&expected) {
+                assert!((r - e).abs() < 1e-8);
+            }
+        }
+    }
+}
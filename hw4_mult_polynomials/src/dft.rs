@@ -0,0 +1,120 @@
+//! Evaluation at the roots of unity and interpolation back, i.e. the discrete Fourier transform
+//!
+//! Toom-3 evaluates at the five points `0, 1, -1, 2, ∞`; evaluating at the `n`-th roots of unity
+//! instead gives the DFT, whose fast version (the FFT) leads to O(n log n) multiplication.
+//! The transforms here are the direct O(n²) ones, meant to make the picture explicit.
+
+use crate::Polynomial;
+use num_complex::Complex64;
+use std::f64::consts::TAU;
+
+/// The `n`-th roots of unity `ωᵏ = e^{2πik/n}`, each computed directly for accuracy
+fn roots_of_unity(n: usize) -> Vec<Complex64> {
+    (0..n)
+        .map(|k| Complex64::from_polar(1.0, TAU * k as f64 / n as f64))
+        .collect()
+}
+
+impl Polynomial {
+    /// Evaluate the polynomial at the `n`-th roots of unity `ω⁰, ω¹, …, ωⁿ⁻¹` with `ω = e^{2πi/n}`
+    ///
+    /// The values determine the polynomial if `n` is at least the number of coefficients,
+    /// see [`Polynomial::interpolate_from_roots_of_unity`].
+    /// Multiplying the values of two polynomials pointwise gives the values of their product,
+    /// as long as `n` is at least the length of the product.
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// let p = Polynomial::new(vec![1.0, 2.0]);
+    /// let q = Polynomial::new(vec![3.0, 4.0]);
+    /// let n = 4;
+    /// let values: Vec<_> = p
+    ///     .evaluate_at_roots_of_unity(n)
+    ///     .iter()
+    ///     .zip(q.evaluate_at_roots_of_unity(n))
+    ///     .map(|(x, y)| x * y)
+    ///     .collect();
+    /// let product = Polynomial::interpolate_from_roots_of_unity(&values);
+    /// assert_eq!(product, p.multiply_naive(&q));
+    /// ```
+    pub fn evaluate_at_roots_of_unity(&self, n: usize) -> Vec<Complex64> {
+        assert!(n > 0);
+        let roots = roots_of_unity(n);
+        (0..n)
+            .map(|k| {
+                // Σ cⱼ·ωᵏʲ, with ωᵏʲ read from the table as ω^(kj mod n)
+                self.coeffs
+                    .iter()
+                    .enumerate()
+                    .map(|(j, &c)| c * roots[(k * j) % n])
+                    .sum()
+            })
+            .collect()
+    }
+
+    /// Recover the polynomial from its values at the `n`-th roots of unity (the inverse DFT)
+    ///
+    /// The result has at most `n = values.len()` coefficients. Only the real parts are kept,
+    /// so the values should come from a real polynomial.
+    pub fn interpolate_from_roots_of_unity(values: &[Complex64]) -> Polynomial {
+        let n = values.len();
+        let roots = roots_of_unity(n);
+        let coeffs = (0..n)
+            .map(|j| {
+                let sum: Complex64 = values
+                    .iter()
+                    .enumerate()
+                    .map(|(k, &v)| v * roots[(n - (k * j) % n) % n])
+                    .sum();
+                sum.re / n as f64
+            })
+            .collect();
+        Polynomial::new(coeffs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test the values against direct evaluation at `±1` and `±i`
+    #[test]
+    fn test_evaluate_at_roots_of_unity() {
+        // 1 + 2x + 3x²
+        let p = Polynomial::new(vec![1.0, 2.0, 3.0]);
+        let values = p.evaluate_at_roots_of_unity(4);
+        let expected = [
+            Complex64::new(6.0, 0.0),
+            Complex64::new(-2.0, 2.0),
+            Complex64::new(2.0, 0.0),
+            Complex64::new(-2.0, -2.0),
+        ];
+        for (v, e) in values.iter().zip(expected) {
+            assert!((v - e).norm() < 1e-12);
+        }
+        // fewer points than coefficients wrap the coefficients around (cyclic convolution)
+        let values = p.evaluate_at_roots_of_unity(2);
+        let wrapped = Polynomial::interpolate_from_roots_of_unity(&values);
+        assert_eq!(wrapped, Polynomial::new(vec![4.0, 2.0]));
+    }
+
+    /// Test the round trip and the evaluate, pointwise multiply, interpolate scheme
+    #[cfg(feature = "thread_rng")]
+    #[test]
+    fn test_round_trip_and_multiply() {
+        let p1 = Polynomial::random(-10.0, 10.0, 50);
+        let p2 = Polynomial::random(-10.0, 10.0, 30);
+        let round_trip =
+            Polynomial::interpolate_from_roots_of_unity(&p1.evaluate_at_roots_of_unity(64));
+        assert!(round_trip.approx_eq(&p1, 1e-10, 1e-10));
+        let n = 79;
+        let values: Vec<Complex64> = p1
+            .evaluate_at_roots_of_unity(n)
+            .iter()
+            .zip(p2.evaluate_at_roots_of_unity(n))
+            .map(|(x, y)| x * y)
+            .collect();
+        let product = Polynomial::interpolate_from_roots_of_unity(&values);
+        assert!(product.approx_eq(&p1.multiply_naive(&p2), 1e-8, 1e-8));
+    }
+}
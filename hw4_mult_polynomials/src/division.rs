@@ -0,0 +1,221 @@
+//! Polynomial division with remainder and modular exponentiation
+
+use crate::series::{inverse_series, multiply_low};
+use crate::{PolyError, Polynomial, PolynomialRing};
+use alloc::vec;
+use alloc::vec::Vec;
+
+impl Polynomial {
+    /// Divide by `divisor`, returning the quotient and the remainder
+    ///
+    /// The remainder has a lower degree than the divisor, and `self = quotient·divisor + remainder`.
+    /// Uses long division in O((n - m)·m), see [`Polynomial::div_rem_fast`] for large quotients
+    /// and divisors. Trailing zeros of the divisor kept by [`Normalization::Keep`](crate::Normalization::Keep)
+    /// are ignored, and a divisor without nonzero coefficients fails with [`PolyError::DivisionByZero`].
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// // x³ - 1 = (x² + x + 1)(x - 1)
+    /// let p = Polynomial::new(vec![-1.0, 0.0, 0.0, 1.0]);
+    /// let (q, r) = p.div_rem(&Polynomial::new(vec![-1.0, 1.0])).unwrap();
+    /// assert_eq!(q, Polynomial::new(vec![1.0, 1.0, 1.0]));
+    /// assert!(r.coeffs.is_empty());
+    /// ```
+    pub fn div_rem(&self, divisor: &Polynomial) -> Result<(Polynomial, Polynomial), PolyError> {
+        let d = divisor.significant_coeffs();
+        let Some(&lead) = d.last() else {
+            return Err(PolyError::DivisionByZero);
+        };
+        let m = d.len();
+        let mut remainder = self.coeffs.clone();
+        if remainder.len() < m {
+            return Ok((self.with_coeffs(vec![]), self.with_coeffs(remainder)));
+        }
+
+        let mut quotient = vec![0.0; remainder.len() - m + 1];
+        for k in (0..quotient.len()).rev() {
+            let q = remainder[k + m - 1] / lead;
+            quotient[k] = q;
+            for (r, c) in remainder[k..k + m].iter_mut().zip(d) {
+                *r -= q * c;
+            }
+        }
+        remainder.truncate(m - 1);
+        Ok((self.with_coeffs(quotient), self.with_coeffs(remainder)))
+    }
+
+    /// Divide by `divisor` like [`Polynomial::div_rem`], in O(M(n)) for a multiplication cost M(n)
+    ///
+    /// Reversing the coefficients turns the quotient into the first `n - m + 1` coefficients
+    /// of the power series `rev(self) / rev(divisor)`, computed with [`Polynomial::inverse_mod_xn`];
+    /// the remainder then takes one truncated product. The rounding errors grow with the
+    /// condition of the divisor's reversal, so prefer long division for small or ill-conditioned inputs.
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// let p = Polynomial::new(vec![-1.0, 0.0, 0.0, 1.0]);
+    /// let (q, r) = p.div_rem_fast(&Polynomial::new(vec![-1.0, 1.0])).unwrap();
+    /// assert_eq!(q, Polynomial::new(vec![1.0, 1.0, 1.0]));
+    /// assert!(r.coeffs.is_empty());
+    /// ```
+    pub fn div_rem_fast(
+        &self,
+        divisor: &Polynomial,
+    ) -> Result<(Polynomial, Polynomial), PolyError> {
+        let d = divisor.significant_coeffs();
+        if d.is_empty() {
+            return Err(PolyError::DivisionByZero);
+        }
+        let (n, m) = (self.coeffs.len(), d.len());
+        if n < m {
+            return Ok((self.with_coeffs(vec![]), self.clone()));
+        }
+        let k = n - m + 1;
+        let d_reversed: Vec<f64> = d.iter().rev().copied().collect();
+        let a_reversed: Vec<f64> = self.coeffs.iter().rev().take(k).copied().collect();
+        let mut quotient = multiply_low(&a_reversed, &inverse_series(&d_reversed, k), k);
+        quotient.resize(k, 0.0);
+        quotient.reverse();
+
+        let product = multiply_low(&quotient, d, m - 1);
+        let mut remainder = self.coeffs[..m - 1].to_vec();
+        for (r, p) in remainder.iter_mut().zip(&product) {
+            *r -= p;
+        }
+        Ok((self.with_coeffs(quotient), self.with_coeffs(remainder)))
+    }
+
+    /// Compute `selfᵉ mod modulus` by repeated squaring, see [`PolynomialRing::pow`]
+    ///
+    /// Each step reduces a product of degree below `2·deg(modulus)` with two multiplications
+    /// by a precomputed inverse of the reversed modulus, so the fast multipliers are used
+    /// throughout, e.g. to compute `xⁿ mod f` for large `n`.
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// // x¹⁰⁰⁰ mod (x² + 1) = (x²)⁵⁰⁰ = (-1)⁵⁰⁰ = 1
+    /// let x = Polynomial::new(vec![0.0, 1.0]);
+    /// let modulus = Polynomial::new(vec![1.0, 0.0, 1.0]);
+    /// assert_eq!(x.powmod(1000, &modulus).unwrap(), Polynomial::new(vec![1.0]));
+    /// ```
+    pub fn powmod(&self, e: u64, modulus: &Polynomial) -> Result<Polynomial, PolyError> {
+        let ring = PolynomialRing::new(modulus)?;
+        Ok(ring.pow(self, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Normalization;
+
+    /// Test division against multiplication, including the degenerate cases
+    #[cfg(feature = "thread_rng")]
+    #[test]
+    fn test_div_rem() {
+        let p = Polynomial::random(-10.0, 10.0, 300);
+        for m in [1, 2, 50, 299, 300] {
+            // a dominant leading coefficient keeps the quotient from growing
+            let mut d = Polynomial::random(-1.0, 1.0, m);
+            d.coeffs[m - 1] = m as f64 + 1.0;
+            let (q, r) = p.div_rem(&d).unwrap();
+            assert_eq!(q.coeffs.len(), 301 - m);
+            assert!(r.coeffs.len() < m);
+            let mut back = q.multiply_naive(&d).coeffs;
+            for (b, c) in back.iter_mut().zip(&r.coeffs) {
+                *b += c;
+            }
+            assert!(Polynomial::new(back).approx_eq(&p, 1e-9, 1e-9));
+        }
+        let (q, r) = Polynomial::new(vec![1.0, 2.0]).div_rem(&p).unwrap();
+        assert!(q.coeffs.is_empty());
+        assert_eq!(r, Polynomial::new(vec![1.0, 2.0]));
+        assert_eq!(
+            p.div_rem(&Polynomial::new(vec![])),
+            Err(PolyError::DivisionByZero)
+        );
+    }
+
+    /// Test that trailing zeros kept in a divisor are ignored, and that all-zero divisors are rejected
+    #[test]
+    fn test_div_rem_kept_zeros() {
+        let p = Polynomial::new(vec![-1.0, 0.0, 0.0, 1.0]);
+        let d = Polynomial::with_normalization(vec![-1.0, 1.0, 0.0, 0.0], Normalization::Keep);
+        let expected = Polynomial::new(vec![1.0, 1.0, 1.0]);
+        for (q, r) in [p.div_rem(&d).unwrap(), p.div_rem_fast(&d).unwrap()] {
+            assert!(q.approx_eq(&expected, 1e-12, 1e-12));
+            assert!(r.coeffs.iter().all(|&c| c.abs() < 1e-12));
+        }
+        for zero in [vec![0.0], vec![0.0; 3]] {
+            let zero = Polynomial::with_normalization(zero, Normalization::Keep);
+            assert_eq!(p.div_rem(&zero), Err(PolyError::DivisionByZero));
+            assert_eq!(p.div_rem_fast(&zero), Err(PolyError::DivisionByZero));
+        }
+    }
+
+    /// Test the fast division against long division, up to large sizes
+    #[cfg(feature = "thread_rng")]
+    #[test]
+    fn test_div_rem_fast() {
+        let p = Polynomial::random(-10.0, 10.0, 300);
+        for m in [1, 2, 50, 151, 299, 300, 301] {
+            let mut d = Polynomial::random(-1.0, 1.0, m);
+            d.coeffs[m - 1] = m as f64 + 1.0;
+            let (q, r) = p.div_rem_fast(&d).unwrap();
+            let (q_long, r_long) = p.div_rem(&d).unwrap();
+            assert!(q.approx_eq(&q_long, 1e-9, 1e-9), "m = {}", m);
+            assert!(r.approx_eq(&r_long, 1e-9, 1e-9), "m = {}", m);
+        }
+        // a quotient and a divisor of 20000 coefficients
+        let mut d = Polynomial::random(-1.0, 1.0, 20_000);
+        d.coeffs[19_999] = 20_000.0;
+        let q = Polynomial::random(-1.0, 1.0, 20_000);
+        let r = Polynomial::random(-1.0, 1.0, 19_999);
+        let mut p = q.multiply_auto(&d).coeffs;
+        for (c, r) in p.iter_mut().zip(&r.coeffs) {
+            *c += r;
+        }
+        let p = Polynomial::new(p);
+        let (quotient, remainder) = p.div_rem_fast(&d).unwrap();
+        assert!(quotient.approx_eq(&q, 1e-8, 1e-8));
+        // the remainder cancels against product coefficients of magnitude ~ 10⁴
+        let height = q.multiply_auto(&d).height();
+        assert!(remainder.approx_eq(&r, 1e-10 * height, 0.0));
+        assert_eq!(
+            p.div_rem_fast(&Polynomial::new(vec![])),
+            Err(PolyError::DivisionByZero)
+        );
+    }
+
+    /// Test modular exponentiation against repeated multiplication and long division
+    #[test]
+    fn test_powmod() {
+        let p = Polynomial::new(vec![0.5, -0.25, 0.75]);
+        // a monic modulus whose powers of x stay bounded: x⁸⁰ - x⁷⁹/2 - 1/2
+        let mut modulus = vec![0.0; 81];
+        (modulus[0], modulus[79], modulus[80]) = (-0.5, -0.5, 1.0);
+        let modulus = Polynomial::new(modulus);
+        let mut expected = Polynomial::new(vec![1.0]);
+        for e in 0..=100u64 {
+            let result = p.powmod(e, &modulus).unwrap();
+            assert!(result.coeffs.len() < 81);
+            assert!(result.approx_eq(&expected, 1e-9, 1e-9), "e = {}", e);
+            expected = expected.multiply_naive(&p).div_rem(&modulus).unwrap().1;
+        }
+        // xⁿ mod (xᵏ - 1) = x^(n mod k)
+        let x = Polynomial::new(vec![0.0, 1.0]);
+        let cyclic = Polynomial::new(vec![-1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0]);
+        let result = x.powmod(1_000_000_007, &cyclic).unwrap();
+        assert!(result.approx_eq(&(&x << 5), 1e-12, 0.0));
+        // everything vanishes modulo a constant
+        assert!(x
+            .powmod(3, &Polynomial::new(vec![2.0]))
+            .unwrap()
+            .coeffs
+            .is_empty());
+        assert_eq!(
+            x.powmod(3, &Polynomial::new(vec![])),
+            Err(PolyError::DivisionByZero)
+        );
+    }
+}
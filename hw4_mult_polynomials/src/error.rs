@@ -0,0 +1,137 @@
+//! Error type of the crate
+
+use alloc::string::String;
+use core::fmt;
+use core::time::Duration;
+
+/// Errors reported by the polynomial library
+#[derive(Debug, Clone, PartialEq)]
+pub enum PolyError {
+    /// A multiplication task did not finish before its deadline
+    DeadlineExceeded {
+        /// How long after the deadline the task was abandoned
+        overdue: Duration,
+    },
+    /// Reading or writing a file failed
+    Io(String),
+    /// A file is not in the expected format
+    InvalidFormat(String),
+    /// A file was written by a newer, incompatible version of the crate
+    UnsupportedVersion {
+        /// Format version found in the file
+        found: u32,
+        /// Newest format version understood by this build
+        supported: u32,
+    },
+    /// A file stores coefficients of a different type
+    CoefficientTypeMismatch {
+        /// Coefficient type tag found in the file
+        found: String,
+        /// Coefficient type expected by the reader
+        expected: String,
+    },
+    /// Division by the zero polynomial
+    DivisionByZero,
+    /// An algorithm name could not be parsed
+    UnknownAlgorithm(String),
+    /// A benchmarked algorithm disagrees with the naive product
+    VerificationFailed {
+        /// Column name of the algorithm
+        algorithm: String,
+        /// Number of coefficients of the first operand
+        size: usize,
+        /// Largest deviation relative to the largest coefficient of the naive product
+        discrepancy: f64,
+    },
+    /// A function was called with arguments outside of its domain, e.g. an empty range
+    InvalidArgument(String),
+    /// A backend is not available on this machine, e.g. no GPU with 64-bit floats
+    Unavailable(String),
+    /// A coefficient of an operand or of the product is NaN or infinite
+    NonFinite {
+        /// Polynomial holding the coefficient
+        operand: Operand,
+        /// Index of the first non-finite coefficient
+        index: usize,
+        /// Its value
+        value: f64,
+    },
+}
+
+/// Polynomial of a multiplication, as reported by [`PolyError::NonFinite`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand {
+    /// The polynomial the multiplication is called on
+    First,
+    /// The other factor
+    Second,
+    /// The product (after an overflow)
+    Product,
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Operand::First => "first operand",
+            Operand::Second => "second operand",
+            Operand::Product => "product",
+        })
+    }
+}
+
+impl fmt::Display for PolyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolyError::DeadlineExceeded { overdue } => {
+                write!(f, "deadline exceeded by {:?}", overdue)
+            }
+            PolyError::Io(message) => write!(f, "I/O error: {}", message),
+            PolyError::InvalidFormat(message) => write!(f, "invalid file format: {}", message),
+            PolyError::UnsupportedVersion { found, supported } => write!(
+                f,
+                "file format version {} is not supported (newest supported version is {}); \
+                 it was probably written by a newer version of the crate",
+                found, supported
+            ),
+            PolyError::CoefficientTypeMismatch { found, expected } => write!(
+                f,
+                "file stores coefficients of type `{}`, expected `{}`",
+                found, expected
+            ),
+            PolyError::DivisionByZero => write!(f, "division by the zero polynomial"),
+            PolyError::UnknownAlgorithm(name) => write!(f, "unknown algorithm `{}`", name),
+            PolyError::InvalidArgument(message) => write!(f, "invalid argument: {}", message),
+            PolyError::Unavailable(message) => write!(f, "unavailable: {}", message),
+            PolyError::VerificationFailed {
+                algorithm,
+                size,
+                discrepancy,
+            } => write!(
+                f,
+                "`{}` deviates from the naive product by {:e} (relative) at size {}",
+                algorithm, discrepancy, size
+            ),
+            PolyError::NonFinite {
+                operand,
+                index,
+                value,
+            } => write!(f, "coefficient {} of the {} is {}", index, operand, value),
+        }
+    }
+}
+
+impl core::error::Error for PolyError {}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for PolyError {
+    fn from(error: std::io::Error) -> Self {
+        PolyError::Io(error.to_string())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl From<rusqlite::Error> for PolyError {
+    fn from(error: rusqlite::Error) -> Self {
+        PolyError::Io(alloc::format!("SQLite: {}", error))
+    }
+}
@@ -0,0 +1,209 @@
+//! Forward-error estimates for floating-point polynomial products
+
+use crate::polynomial::{karatsuba_shape, recursion_shape};
+use crate::{Polynomial, PolynomialMultAlg};
+use alloc::vec::Vec;
+
+/// Growth of the largest coefficient of a pointwise product at the Toom-3 points 0, 1, -1, 2 and ∞
+///
+/// Evaluating a three-part operand at `x` scales its largest coefficient by up to `Σ|x|ⁱ`,
+/// i.e. 1, 3, 3, 7 and 1, and the product of two evaluations by the square of that.
+const TOOM3_POINT_GROWTH: [f64; 5] = [1.0, 9.0, 9.0, 49.0, 1.0];
+
+/// Absolute weights of the point values in the interpolation of `r1`, `-r(0)/2 + r(1) - r(-1)/3 - r(2)/6 + 2r(∞)`
+///
+/// This is the row of the inverse Vandermonde matrix of the points that amplifies
+/// [`TOOM3_POINT_GROWTH`] the most (the other rows give 1, 11, 50/3 and 1).
+const TOOM3_INTERPOLATION_WEIGHTS: [f64; 5] = [1.0 / 2.0, 1.0, 1.0 / 3.0, 1.0 / 6.0, 2.0];
+
+/// Worst-case growth of the error per Toom-3 level, `Σ weight·growth` over the points (68/3)
+const TOOM3_ERROR_GROWTH: f64 = {
+    let mut growth = 0.0;
+    let mut i = 0;
+    while i < TOOM3_POINT_GROWTH.len() {
+        growth += TOOM3_INTERPOLATION_WEIGHTS[i] * TOOM3_POINT_GROWTH[i];
+        i += 1;
+    }
+    growth
+};
+
+/// Rounding operations added per Toom-3 level (evaluation and interpolation)
+const TOOM3_OPS_PER_LEVEL: usize = 8;
+
+/// Product of two polynomials together with a forward-error bound
+#[derive(Debug, Clone)]
+pub struct BoundedProduct {
+    /// The computed product
+    pub product: Polynomial,
+    /// Bound on the absolute error of each product coefficient, from lowest to highest degree
+    ///
+    /// Always `n + m - 1` entries for operands of `n` and `m` coefficients (none if one is empty):
+    /// the bounds are not trimmed with the product, so `product` may have fewer coefficients
+    /// under its normalization policy, and the missing ones are zero.
+    pub error_bounds: Vec<f64>,
+}
+
+impl BoundedProduct {
+    /// Largest per-coefficient error bound
+    pub fn max_error(&self) -> f64 {
+        self.error_bounds.iter().cloned().fold(0.0, f64::max)
+    }
+}
+
+/// The constant `γ_k = k·u / (1 - k·u)` of the standard rounding error analysis
+fn gamma(k: usize) -> f64 {
+    let ku = k as f64 * f64::EPSILON / 2.0;
+    if ku >= 1.0 {
+        f64::INFINITY
+    } else {
+        ku / (1.0 - ku)
+    }
+}
+
+impl Polynomial {
+    /// Multiply with another polynomial and estimate the forward error of each coefficient
+    ///
+    /// The bound for coefficient `k` is `γ · G^d · t_k · ‖a‖∞ · ‖b‖∞`, where `t_k` is the
+    /// number of terms contributing to `k`, `d` the Toom-3 recursion depth, `G` the per-level
+    /// error growth, and `γ` accounts for the rounding in the base case and every level.
+    /// It is computed in O(n + m) and is a worst-case estimate; observed errors are usually
+    /// orders of magnitude smaller.
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::{Polynomial, PolynomialMultAlg};
+    /// let p = Polynomial::random(-10.0, 10.0, 100);
+    /// let bounded = p.multiply_with_error_bound(&p, PolynomialMultAlg::CookTooms);
+    /// let exact = p.multiply_naive(&p);
+    /// for (k, bound) in bounded.error_bounds.iter().enumerate() {
+    ///     // both products are trimmed, so their leading coefficients may be missing
+    ///     let computed = bounded.product.coeffs.get(k).unwrap_or(&0.0);
+    ///     let expected = exact.coeffs.get(k).unwrap_or(&0.0);
+    ///     assert!((computed - expected).abs() <= 2.0 * bound);
+    /// }
+    /// ```
+    pub fn multiply_with_error_bound(
+        &self,
+        other: &Polynomial,
+        algorithm: PolynomialMultAlg,
+    ) -> BoundedProduct {
+        let product = self.multiply(other, algorithm);
+
+        let n = self.coeffs.len();
+        let m = other.coeffs.len();
+        if n == 0 || m == 0 {
+            return BoundedProduct {
+                product,
+                error_bounds: Vec::new(),
+            };
+        }
+
+        let norm_a = self.coeffs.iter().fold(0.0, |acc: f64, c| acc.max(c.abs()));
+        let norm_b = other
+            .coeffs
+            .iter()
+            .fold(0.0, |acc: f64, c| acc.max(c.abs()));
+        let terms = |k: usize| {
+            // number of pairs (i, j) with i + j = k
+            (k.min(n - 1) + 1 - k.saturating_sub(m - 1)) as f64
+        };
+
+        if algorithm == PolynomialMultAlg::NaiveCompensated {
+            // Dot2 bound: u·|c_k| + γ_t² · Σ|a_i b_j|
+            let error_bounds = (0..n + m - 1)
+                .map(|k| {
+                    let computed = product.coeffs.get(k).copied().unwrap_or(0.0);
+                    let t = terms(k);
+                    f64::EPSILON * computed.abs()
+                        + gamma(t as usize) * gamma(t as usize) * t * norm_a * norm_b
+                })
+                .collect();
+            return BoundedProduct {
+                product,
+                error_bounds,
+            };
+        }
+
+        // Toom-3 depth and operand length once the recursion reaches the base case
+        let (depth, base_terms) = match algorithm {
+            PolynomialMultAlg::Naive | PolynomialMultAlg::NaiveCompensated => (0, n.min(m)),
+            PolynomialMultAlg::CookTooms => recursion_shape(n, m, 5),
+            PolynomialMultAlg::Thresholded(threshold) => recursion_shape(n, m, threshold),
+            PolynomialMultAlg::Hybrid(config) => {
+                // Karatsuba levels grow the error less than Toom-3 levels, so count them as such
+                let (depth, len) = recursion_shape(n, m, config.toom3_threshold);
+                let (karatsuba_depth, len) = karatsuba_shape(len, config.karatsuba_threshold);
+                (depth + karatsuba_depth, len)
+            }
+        };
+        let growth = (0..depth).fold(1.0, |g, _| g * TOOM3_ERROR_GROWTH);
+        let scale = gamma(base_terms + TOOM3_OPS_PER_LEVEL * depth) * growth;
+        let error_bounds = (0..n + m - 1)
+            .map(|k| scale * terms(k) * norm_a * norm_b)
+            .collect();
+
+        BoundedProduct {
+            product,
+            error_bounds,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "thread_rng"))]
+mod tests {
+    use super::*;
+    use crate::{HybridConfig, Normalization};
+
+    /// Test that the computed products stay within the estimated bounds
+    #[test]
+    fn test_error_bound_holds() {
+        let p1 = Polynomial::random(-10.0, 10.0, 300);
+        let p2 = Polynomial::random(-10.0, 10.0, 200);
+        let reference = p1.multiply_with_error_bound(&p2, PolynomialMultAlg::NaiveCompensated);
+
+        assert!((TOOM3_ERROR_GROWTH - 68.0 / 3.0).abs() < 1e-12);
+        for algorithm in [
+            PolynomialMultAlg::Naive,
+            PolynomialMultAlg::NaiveCompensated,
+            PolynomialMultAlg::CookTooms,
+            PolynomialMultAlg::Thresholded(32),
+            PolynomialMultAlg::Hybrid(HybridConfig {
+                toom3_threshold: 64,
+                karatsuba_threshold: 8,
+            }),
+        ] {
+            let bounded = p1.multiply_with_error_bound(&p2, algorithm);
+            assert_eq!(
+                bounded.error_bounds.len(),
+                p1.coeffs.len() + p2.coeffs.len() - 1
+            );
+            assert!(bounded.max_error() > 0.0);
+            for (k, bound) in bounded.error_bounds.iter().enumerate() {
+                let coefficient = |p: &Polynomial| *p.coeffs.get(k).unwrap_or(&0.0);
+                let diff = (coefficient(&bounded.product) - coefficient(&reference.product)).abs();
+                // the reference product carries its own (tiny) error
+                assert!(
+                    diff <= bound + reference.error_bounds[k],
+                    "coefficient {}: {} > {}",
+                    k,
+                    diff,
+                    bound
+                );
+            }
+        }
+
+        // the bounds keep the coefficients trimmed from the product
+        let coarse = Polynomial::with_normalization(vec![1.0, 1e-2], Normalization::Trim(1e-3));
+        let bounded = coarse.multiply_with_error_bound(&coarse, PolynomialMultAlg::Naive);
+        assert_eq!(bounded.product.coeffs.len(), 2);
+        assert_eq!(bounded.error_bounds.len(), 3);
+
+        // Deeper recursion yields looser bounds
+        let naive = p1.multiply_with_error_bound(&p2, PolynomialMultAlg::Naive);
+        let toom = p1.multiply_with_error_bound(&p2, PolynomialMultAlg::CookTooms);
+        assert!(toom.max_error() > naive.max_error());
+
+        // Compensated accumulation yields tighter bounds
+        let compensated = p1.multiply_with_error_bound(&p2, PolynomialMultAlg::NaiveCompensated);
+        assert!(compensated.max_error() < naive.max_error());
+    }
+}
@@ -0,0 +1,661 @@
+//! Square-free decomposition and factorization over the rationals
+//!
+//! Integer polynomials are factored by the big-prime variant of Zassenhaus' algorithm.
+//! A prime `p` is picked that is more than twice a bound `B` on the coefficients of every factor.
+//! The polynomial is factored modulo `p`: distinct-degree factorization, then Cantor–Zassenhaus
+//! splitting of the equal-degree parts. Products of subsets of the modular factors are lifted to
+//! the symmetric range `(-p/2, p/2)`, and a product is kept as a true factor when the 1-norms of
+//! the lifted factor and cofactor multiply to at most `B`. Then their product is congruent to
+//! the polynomial and smaller than `p/2`, so it equals it. Every exact division is checked in
+//! the same way, so the arithmetic stays within `u128` and never needs big integers.
+
+use crate::modular::{add_mod, sub_mod, Montgomery};
+use crate::{PolyError, Polynomial};
+use alloc::format;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Primes just below `2⁶²`, tried in turn until one keeps the factors of the polynomial apart
+const FACTOR_PRIMES: [u64; 4] = [
+    (1 << 62) - 57,
+    (1 << 62) - 87,
+    (1 << 62) - 117,
+    (1 << 62) - 143,
+];
+
+/// Arithmetic modulo an odd prime below `2⁶²`, on polynomials stored from lowest to highest degree
+/// without trailing zeros
+///
+/// Residues are kept in the Montgomery form `x·R mod p` of [`Montgomery`], converted by
+/// [`Field::reduce`] and [`Field::lift`], so products need no division.
+#[derive(Debug, Clone, Copy)]
+struct Field {
+    modulus: Montgomery,
+    /// Montgomery form of 1
+    one: u64,
+}
+
+impl Field {
+    fn new(p: u64) -> Field {
+        let modulus = Montgomery::new(p);
+        Field {
+            modulus,
+            one: modulus.to_montgomery(1),
+        }
+    }
+
+    fn p(self) -> u64 {
+        self.modulus.p
+    }
+
+    fn add(self, a: u64, b: u64) -> u64 {
+        add_mod(a, b, self.p())
+    }
+
+    fn sub(self, a: u64, b: u64) -> u64 {
+        sub_mod(a, b, self.p())
+    }
+
+    fn mul(self, a: u64, b: u64) -> u64 {
+        self.modulus.mul(a, b)
+    }
+
+    fn inverse(self, a: u64) -> u64 {
+        self.modulus.pow_montgomery(a, self.p() - 2)
+    }
+
+    /// Residue of an integer
+    fn reduce(self, c: i128) -> u64 {
+        self.modulus
+            .to_montgomery(c.rem_euclid(self.p() as i128) as u64)
+    }
+
+    /// Representative of a residue in `(-p/2, p/2]`
+    fn lift(self, a: u64) -> i128 {
+        let a = self.modulus.mul(a, 1);
+        if a > self.p() / 2 {
+            a as i128 - self.p() as i128
+        } else {
+            a as i128
+        }
+    }
+
+    /// Integer polynomial of the representatives of the coefficients in `(-p/2, p/2]`
+    fn lift_poly(self, a: &[u64]) -> Vec<i128> {
+        a.iter().map(|&c| self.lift(c)).collect()
+    }
+
+    /// Residues of the coefficients of an integer polynomial
+    fn poly(self, coeffs: &[i128]) -> Vec<u64> {
+        let mut residues = coeffs.iter().map(|&c| self.reduce(c)).collect();
+        trim(&mut residues);
+        residues
+    }
+
+    fn scale(self, a: &[u64], factor: u64) -> Vec<u64> {
+        let mut scaled = a.iter().map(|&c| self.mul(c, factor)).collect();
+        trim(&mut scaled);
+        scaled
+    }
+
+    fn monic(self, a: &[u64]) -> Vec<u64> {
+        match a.last() {
+            Some(&lead) => self.scale(a, self.inverse(lead)),
+            None => vec![],
+        }
+    }
+
+    fn sub_poly(self, a: &[u64], b: &[u64]) -> Vec<u64> {
+        let mut difference: Vec<u64> = (0..a.len().max(b.len()))
+            .map(|k| {
+                self.sub(
+                    a.get(k).copied().unwrap_or(0),
+                    b.get(k).copied().unwrap_or(0),
+                )
+            })
+            .collect();
+        trim(&mut difference);
+        difference
+    }
+
+    fn mul_poly(self, a: &[u64], b: &[u64]) -> Vec<u64> {
+        if a.is_empty() || b.is_empty() {
+            return vec![];
+        }
+        let mut product = vec![0; a.len() + b.len() - 1];
+        for (i, &x) in a.iter().enumerate() {
+            for (c, &y) in product[i..].iter_mut().zip(b) {
+                *c = self.add(*c, self.mul(x, y));
+            }
+        }
+        trim(&mut product);
+        product
+    }
+
+    fn derivative(self, a: &[u64]) -> Vec<u64> {
+        let mut derivative = (a.iter().enumerate().skip(1))
+            .map(|(k, &c)| self.mul(self.reduce(k as i128), c))
+            .collect();
+        trim(&mut derivative);
+        derivative
+    }
+
+    /// Quotient and remainder of the division by a nonzero `divisor`
+    fn div_rem(self, a: &[u64], divisor: &[u64]) -> (Vec<u64>, Vec<u64>) {
+        let m = divisor.len();
+        if a.len() < m {
+            return (vec![], a.to_vec());
+        }
+        let inverse = self.inverse(divisor[m - 1]);
+        let mut remainder = a.to_vec();
+        let mut quotient = vec![0; a.len() - m + 1];
+        for k in (0..quotient.len()).rev() {
+            let q = self.mul(remainder[k + m - 1], inverse);
+            quotient[k] = q;
+            for (r, &d) in remainder[k..k + m].iter_mut().zip(divisor) {
+                *r = self.sub(*r, self.mul(q, d));
+            }
+        }
+        remainder.truncate(m - 1);
+        trim(&mut remainder);
+        (quotient, remainder)
+    }
+
+    /// Monic greatest common divisor, by the Euclidean algorithm
+    fn gcd(self, a: &[u64], b: &[u64]) -> Vec<u64> {
+        let (mut a, mut b) = (a.to_vec(), b.to_vec());
+        while !b.is_empty() {
+            let (_, remainder) = self.div_rem(&a, &b);
+            (a, b) = (b, remainder);
+        }
+        self.monic(&a)
+    }
+
+    /// `base^exp` modulo a nonconstant `modulus`
+    fn pow_mod(self, base: &[u64], mut exp: u64, modulus: &[u64]) -> Vec<u64> {
+        let mut base = self.div_rem(base, modulus).1;
+        let mut result = vec![self.one];
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = self.div_rem(&self.mul_poly(&result, &base), modulus).1;
+            }
+            base = self.div_rem(&self.mul_poly(&base, &base), modulus).1;
+            exp >>= 1;
+        }
+        result
+    }
+
+    /// Monic irreducible factors of a monic square-free polynomial, by distinct-degree factorization
+    /// and Cantor–Zassenhaus splitting
+    fn factor_squarefree(self, f: &[u64]) -> Vec<Vec<u64>> {
+        let mut factors = Vec::new();
+        let mut rest = f.to_vec();
+        let x = [0, self.one];
+        // x^(pᵈ) modulo the rest
+        let mut frobenius = x.to_vec();
+        let mut state = 0x9e37_79b9_7f4a_7c15;
+        let mut d = 1;
+        while 2 * d < rest.len() {
+            frobenius = self.pow_mod(&frobenius, self.p(), &rest);
+            let product = self.gcd(&rest, &self.sub_poly(&frobenius, &x));
+            if product.len() > 1 {
+                self.split(&product, d, &mut state, &mut factors);
+                rest = self.div_rem(&rest, &product).0;
+                frobenius = self.div_rem(&frobenius, &rest).1;
+            }
+            d += 1;
+        }
+        if rest.len() > 1 {
+            factors.push(rest);
+        }
+        factors
+    }
+
+    /// Split a monic product of irreducible factors of degree `d` into them, drawing the
+    /// random polynomials of Cantor–Zassenhaus from the xorshift `state`
+    fn split(self, g: &[u64], d: usize, state: &mut u64, factors: &mut Vec<Vec<u64>>) {
+        if g.len() == d + 1 {
+            factors.push(g.to_vec());
+            return;
+        }
+        loop {
+            let mut random = (0..g.len() - 1)
+                .map(|_| {
+                    *state ^= *state << 13;
+                    *state ^= *state >> 7;
+                    *state ^= *state << 17;
+                    *state % self.p()
+                })
+                .collect();
+            trim(&mut random);
+            // r^((pᵈ - 1)/2) = (r·r^p·…·r^(pᵈ⁻¹))^((p - 1)/2), ±1 modulo about half of the factors
+            let mut conjugate = random.clone();
+            let mut norm = random;
+            for _ in 1..d {
+                conjugate = self.pow_mod(&conjugate, self.p(), g);
+                norm = self.div_rem(&self.mul_poly(&norm, &conjugate), g).1;
+            }
+            let half = self.pow_mod(&norm, (self.p() - 1) / 2, g);
+            let divisor = self.gcd(g, &self.sub_poly(&half, &[self.one]));
+            if divisor.len() > 1 && divisor.len() < g.len() {
+                self.split(&divisor, d, state, factors);
+                self.split(&self.div_rem(g, &divisor).0, d, state, factors);
+                return;
+            }
+        }
+    }
+
+    /// Quotient `a / divisor` if `divisor` divides `a` over the integers
+    ///
+    /// Exact as long as `a` and `divisor` times the quotient stay within `bound < p/2`,
+    /// which holds for divisors of the polynomial the bound was computed for.
+    fn divide(self, a: &[i128], divisor: &[i128], bound: u128) -> Option<Vec<i128>> {
+        let (quotient, remainder) = self.div_rem(&self.poly(a), &self.poly(divisor));
+        if !remainder.is_empty() {
+            return None;
+        }
+        let quotient = self.lift_poly(&quotient);
+        (norm_l1(divisor).saturating_mul(norm_l1(&quotient)) <= bound).then_some(quotient)
+    }
+}
+
+/// Drop the trailing zero coefficients
+fn trim(p: &mut Vec<u64>) {
+    while p.last() == Some(&0) {
+        p.pop();
+    }
+}
+
+/// Sum of the absolute values of the coefficients, saturating
+fn norm_l1(p: &[i128]) -> u128 {
+    (p.iter()).fold(0u128, |sum, c| sum.saturating_add(c.unsigned_abs()))
+}
+
+/// Polynomial divided by the gcd of its coefficients, with a positive leading coefficient
+fn primitive(p: &[i128]) -> Vec<i128> {
+    let content = (p.iter()).fold(0u128, |g, c| gcd_u128(g, c.unsigned_abs()));
+    let sign = if p.last().is_some_and(|&c| c < 0) {
+        -1
+    } else {
+        1
+    };
+    p.iter().map(|&c| sign * c / content as i128).collect()
+}
+
+fn gcd_u128(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Advance `indices` to the next subset of the same size of `0..n` in lexicographic order,
+/// `false` after the last one
+fn next_subset(indices: &mut [usize], n: usize) -> bool {
+    let k = indices.len();
+    let Some(i) = (0..k).rev().find(|&i| indices[i] < n - k + i) else {
+        return false;
+    };
+    indices[i] += 1;
+    for j in i + 1..k {
+        indices[j] = indices[j - 1] + 1;
+    }
+    true
+}
+
+/// Irreducible factors of a primitive square-free `f` over the integers, from its monic factors
+/// modulo `p`, by trying products of subsets from the smallest on
+fn recombine(
+    field: Field,
+    f: Vec<i128>,
+    mut modular: Vec<Vec<u64>>,
+    bound: u128,
+) -> Vec<Vec<i128>> {
+    let mut factors = Vec::new();
+    let mut rest = f;
+    let mut size = 1;
+    'sizes: while 2 * size <= modular.len() {
+        let lead = field.reduce(*rest.last().expect("the rest is nonconstant"));
+        let mut indices: Vec<usize> = (0..size).collect();
+        loop {
+            // the leading coefficient times the product of the modular factors at `subset`
+            let lifted = |subset: &[usize]| {
+                let product = (subset.iter()).fold(vec![lead], |product, &i| {
+                    field.mul_poly(&product, &modular[i])
+                });
+                field.lift_poly(&product)
+            };
+            let (inside, outside): (Vec<_>, Vec<_>) =
+                (0..modular.len()).partition(|i| indices.contains(i));
+            let (factor, cofactor) = (lifted(&inside), lifted(&outside));
+            if norm_l1(&factor).saturating_mul(norm_l1(&cofactor)) <= bound {
+                factors.push(primitive(&factor));
+                rest = primitive(&cofactor);
+                modular = outside.iter().map(|&i| modular[i].clone()).collect();
+                continue 'sizes;
+            }
+            if !next_subset(&mut indices, modular.len()) {
+                break;
+            }
+        }
+        size += 1;
+    }
+    factors.push(rest);
+    factors
+}
+
+/// Factorization of an integer polynomial into irreducible polynomials over the rationals
+///
+/// Coefficients are from lowest to highest degree. Returns the content `c` and the distinct
+/// irreducible factors `fᵢ` with their multiplicities `eᵢ`, so that the input is `c·∏ fᵢ^eᵢ`.
+/// Every factor is primitive with a positive leading coefficient, and the factors are sorted by
+/// degree, then coefficients. By Gauss' lemma this is also the factorization over the rationals;
+/// multiply a polynomial with rational coefficients by the common denominator first.
+///
+/// The square-free part is split off with a modular greatest common divisor, then factored by
+/// Zassenhaus' algorithm modulo a prime below `2⁶²`. Recombining the modular factors tries
+/// subsets of them, which is exponential in the worst case but fast for most inputs. Fails with
+/// [`PolyError::InvalidArgument`] for the zero polynomial, and if the bound
+/// `(n + 1)²·2ⁿ·max|aᵢ|·|aₙ|` on the factors of the degree-`n` primitive part reaches `2⁶¹`,
+/// e.g. past degree 43 with coefficients of one digit.
+/// ## Example
+/// ```
+/// use mult_polynomial::factor_exact;
+/// // 3x⁴ - 6x³ + 6x² - 6x + 3 = 3·(x - 1)²·(x² + 1)
+/// let (content, factors) = factor_exact(&[3, -6, 6, -6, 3]).unwrap();
+/// assert_eq!(content, 3);
+/// assert_eq!(factors, vec![(vec![-1, 1], 2), (vec![1, 0, 1], 1)]);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn factor_exact(coeffs: &[i64]) -> Result<(i64, Vec<(Vec<i64>, usize)>), PolyError> {
+    let len = coeffs.len() - coeffs.iter().rev().take_while(|&&c| c == 0).count();
+    let coeffs = &coeffs[..len];
+    let Some(&lead) = coeffs.last() else {
+        return Err(PolyError::InvalidArgument(
+            "the zero polynomial has no factorization".into(),
+        ));
+    };
+    let content = (coeffs.iter()).fold(0u128, |g, c| gcd_u128(g, c.unsigned_abs() as u128));
+    let content = if lead < 0 {
+        -(content as i128)
+    } else {
+        content as i128
+    };
+    let f: Vec<i128> = coeffs.iter().map(|&c| c as i128 / content).collect();
+    let content = i64::try_from(content).expect("the content divides the leading coefficient");
+    let n = f.len() - 1;
+    if n == 0 {
+        return Ok((content, vec![]));
+    }
+
+    let height = f.iter().map(|c| c.unsigned_abs()).max().unwrap_or(0);
+    let bound = ((n as u128 + 1).pow(2))
+        .checked_mul(height)
+        .and_then(|b| b.checked_mul(f[n] as u128))
+        .and_then(|b| b.checked_shl(n as u32).filter(|&s| s >> n == b))
+        .filter(|&b| b < 1 << 61)
+        .ok_or_else(|| {
+            PolyError::InvalidArgument(format!(
+                "the factors of a polynomial of degree {} with coefficients up to {} \
+                 may exceed the bound of 2^61",
+                n, height
+            ))
+        })?;
+    let derivative: Vec<i128> = (f.iter().enumerate().skip(1))
+        .map(|(k, &c)| k as i128 * c)
+        .collect();
+
+    for p in FACTOR_PRIMES {
+        let field = Field::new(p);
+        if f[n] % p as i128 == 0 {
+            continue;
+        }
+        // the gcd with the derivative, scaled to the leading coefficient of f and checked to divide both
+        let gcd = field.gcd(&field.poly(&f), &field.poly(&derivative));
+        let gcd = primitive(&field.lift_poly(&field.scale(&gcd, field.reduce(f[n]))));
+        let Some(squarefree) = field.divide(&f, &gcd, bound) else {
+            continue;
+        };
+        if field.divide(&derivative, &gcd, bound).is_none() {
+            continue;
+        }
+        let modular = field.poly(&squarefree);
+        if field.gcd(&modular, &field.derivative(&modular)).len() > 1 {
+            continue;
+        }
+        let modular_factors = field.factor_squarefree(&field.monic(&modular));
+        let mut factors = Vec::new();
+        let mut rest = f.clone();
+        for factor in recombine(field, squarefree, modular_factors, bound) {
+            let mut multiplicity = 0;
+            while let Some(quotient) = field.divide(&rest, &factor, bound) {
+                (rest, multiplicity) = (quotient, multiplicity + 1);
+            }
+            let factor: Vec<i64> = factor.iter().map(|&c| c as i64).collect();
+            factors.push((factor, multiplicity));
+        }
+        factors.sort_by(|(a, _), (b, _)| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+        return Ok((content, factors));
+    }
+    Err(PolyError::Unavailable(
+        "no prime keeps the factors of the polynomial apart".into(),
+    ))
+}
+
+impl Polynomial {
+    /// Square-free decomposition `lc·∏ aᵢⁱ` by Yun's algorithm, as the monic factors `aᵢ`
+    /// of positive degree with their multiplicities `i`
+    ///
+    /// The `aᵢ` are square-free and pairwise coprime, with the roots of multiplicity `i` as roots.
+    /// Repeated roots are detected by [`Polynomial::gcd`] with the derivative, so roots closer
+    /// than its tolerance allows count as one; constants and the zero polynomial have no factors.
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// // (x - 1)·(x + 2)³
+    /// let p = Polynomial::from_roots(&[1.0, -2.0, -2.0, -2.0]);
+    /// let factors = p.squarefree_decomposition();
+    /// assert_eq!(factors.len(), 2);
+    /// assert!(factors[0].0.approx_eq(&Polynomial::from_roots(&[1.0]), 1e-9, 1e-9));
+    /// assert!(factors[1].0.approx_eq(&Polynomial::from_roots(&[-2.0]), 1e-9, 1e-9));
+    /// assert_eq!((factors[0].1, factors[1].1), (1, 3));
+    /// ```
+    pub fn squarefree_decomposition(&self) -> Vec<(Polynomial, usize)> {
+        let quotient = |a: &Polynomial, b: &Polynomial| {
+            a.div_rem(b)
+                .expect("a gcd of nonzero polynomials is nonzero")
+                .0
+        };
+        let mut factors = Vec::new();
+        if self.degree() == 0 {
+            return factors;
+        }
+        let derivative = self.derivative();
+        let gcd = self.gcd(&derivative);
+        let mut b = quotient(self, &gcd);
+        let c = quotient(&derivative, &gcd);
+        let mut d = sub(&c, &b.derivative());
+        let mut multiplicity = 1;
+        while b.degree() > 0 {
+            let a = b.gcd(&d);
+            b = quotient(&b, &a);
+            let c = quotient(&d, &a);
+            d = sub(&c, &b.derivative());
+            if a.degree() > 0 {
+                factors.push((a, multiplicity));
+            }
+            multiplicity += 1;
+        }
+        factors
+    }
+}
+
+/// Coefficient-wise difference
+fn sub(a: &Polynomial, b: &Polynomial) -> Polynomial {
+    let len = a.coeffs.len().max(b.coeffs.len());
+    let coeffs = (0..len)
+        .map(|k| a.coeffs.get(k).unwrap_or(&0.0) - b.coeffs.get(k).unwrap_or(&0.0))
+        .collect();
+    a.with_coeffs(coeffs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modular::schoolbook_exact;
+
+    /// Product `content·∏ fᵢ^eᵢ` of a factorization
+    fn expand(content: i64, factors: &[(Vec<i64>, usize)]) -> Vec<i128> {
+        let mut product = vec![content as i128];
+        for (factor, multiplicity) in factors {
+            for _ in 0..*multiplicity {
+                let narrow: Vec<i64> = product.iter().map(|&c| c as i64).collect();
+                product = schoolbook_exact(&narrow, factor);
+            }
+        }
+        product
+    }
+
+    /// Test the modular factorization of products of known irreducible polynomials
+    #[test]
+    fn test_factor_squarefree_mod() {
+        let field = Field::new(101);
+        // x² + 2 (−2 is not a square modulo 101), x + 5, x³ + x + 1 irreducible modulo 101
+        let irreducible = [&[2, 0, 1][..], &[5, 1], &[3, 1], &[1, 1, 0, 1]].map(|u| field.poly(u));
+        // factors of different degrees come out of the distinct-degree step directly
+        let mut factors =
+            field.factor_squarefree(&field.mul_poly(&irreducible[2], &irreducible[3]));
+        factors.sort();
+        let mut expected = irreducible[2..]
+            .iter()
+            .map(|u| field.monic(u))
+            .collect::<Vec<_>>();
+        expected.sort();
+        assert_eq!(factors, expected);
+        let f = (irreducible[..3].iter()).fold(vec![field.one], |f, u| field.mul_poly(&f, u));
+        let mut factors = field.factor_squarefree(&f);
+        factors.sort();
+        let mut expected = irreducible[..3].to_vec();
+        expected.sort();
+        assert_eq!(factors, expected);
+        for (p, n) in [(FACTOR_PRIMES[0], 12), (101, 9)] {
+            let field = Field::new(p);
+            // xⁿ - 1 is square-free, and the product of its factors gives it back
+            let mut cyclic = vec![0; n + 1];
+            (cyclic[0], cyclic[n]) = (-1, 1);
+            let cyclic = field.poly(&cyclic);
+            let factors = field.factor_squarefree(&cyclic);
+            let product = (factors.iter()).fold(vec![field.one], |f, u| field.mul_poly(&f, u));
+            assert_eq!(product, cyclic);
+            assert!(factors.iter().all(|u| u.last() == Some(&field.one)));
+        }
+        let mut indices = vec![0, 1];
+        let mut count = 1;
+        while next_subset(&mut indices, 5) {
+            count += 1;
+        }
+        assert_eq!((count, indices), (10, vec![3, 4]));
+    }
+
+    /// Test cyclotomic and Swinnerton-Dyer polynomials, multiplicities, contents and products
+    /// of known irreducible polynomials
+    #[test]
+    fn test_factor_exact() {
+        // x⁸ - 1 = (x - 1)(x + 1)(x² + 1)(x⁴ + 1)
+        let (content, factors) = factor_exact(&[-1, 0, 0, 0, 0, 0, 0, 0, 1]).unwrap();
+        assert_eq!(content, 1);
+        assert_eq!(
+            factors,
+            vec![
+                (vec![-1, 1], 1),
+                (vec![1, 1], 1),
+                (vec![1, 0, 1], 1),
+                (vec![1, 0, 0, 0, 1], 1)
+            ]
+        );
+        // x⁴ - 10x² + 1 is irreducible but splits into linear or quadratic factors modulo every prime
+        assert_eq!(
+            factor_exact(&[1, 0, -10, 0, 1]).unwrap(),
+            (1, vec![(vec![1, 0, -10, 0, 1], 1)])
+        );
+        // -6x³·(2x + 3)²·(x² - 2)·(x³ - x - 1), with trailing zeros
+        let irreducible = [vec![0, 1], vec![3, 2], vec![-2, 0, 1], vec![-1, -1, 0, 1]];
+        let multiplicities = [3, 2, 1, 1];
+        let factors: Vec<(Vec<i64>, usize)> =
+            irreducible.iter().cloned().zip(multiplicities).collect();
+        let mut input: Vec<i64> = expand(-6, &factors).iter().map(|&c| c as i64).collect();
+        input.extend([0, 0]);
+        assert_eq!(factor_exact(&input).unwrap(), (-6, factors));
+
+        // products of pairs of random cubics, which are irreducible or not
+        let mut state = 7u64;
+        let mut random = |len: usize| -> Vec<i64> {
+            (0..len)
+                .map(|_| {
+                    state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+                    (state >> 60) as i64 - 8
+                })
+                .collect()
+        };
+        for _ in 0..20 {
+            let (mut a, mut b) = (random(4), random(4));
+            (a[3], b[3]) = (1, b[3].abs() + 1);
+            let input: Vec<i64> = schoolbook_exact(&a, &b).iter().map(|&c| c as i64).collect();
+            let (content, factors) = factor_exact(&input).unwrap();
+            assert_eq!(expand(content, &factors), schoolbook_exact(&input, &[1]));
+            let degrees: usize = factors.iter().map(|(f, e)| (f.len() - 1) * e).sum();
+            assert_eq!(degrees, 6);
+            assert!(factors
+                .iter()
+                .all(|(f, _)| f.last().is_some_and(|&c| c > 0)));
+        }
+
+        assert_eq!(factor_exact(&[-4, 0]).unwrap(), (-4, vec![]));
+        assert!(matches!(
+            factor_exact(&[0, 0]),
+            Err(PolyError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            factor_exact(&[]),
+            Err(PolyError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            factor_exact(&[1 << 40, 3, 1 << 40]),
+            Err(PolyError::InvalidArgument(_))
+        ));
+        let mut long = vec![1; 61];
+        long[0] = -1;
+        assert!(factor_exact(&long).is_err());
+    }
+
+    /// Test multiplicities up to 4, coprime factors and constants
+    #[test]
+    fn test_squarefree_decomposition() {
+        let p = Polynomial::from_roots(&[0.5, -1.0, -1.0, 2.0, 2.0, 2.0, 2.0, 3.0, 3.0]);
+        let scaled = p.with_coeffs(p.coeffs.iter().map(|c| -2.5 * c).collect());
+        let factors = scaled.squarefree_decomposition();
+        let expected = [
+            (Polynomial::from_roots(&[0.5]), 1),
+            (Polynomial::from_roots(&[-1.0, 3.0]), 2),
+            (Polynomial::from_roots(&[2.0]), 4),
+        ];
+        assert_eq!(factors.len(), expected.len());
+        for ((factor, multiplicity), (expected, expected_multiplicity)) in
+            factors.iter().zip(&expected)
+        {
+            assert!(factor.approx_eq(expected, 1e-6, 1e-6), "{:?}", factor);
+            assert_eq!(multiplicity, expected_multiplicity);
+        }
+        let squarefree = Polynomial::from_roots(&[1.0, 2.0, 4.0]);
+        assert_eq!(
+            squarefree.squarefree_decomposition(),
+            vec![(squarefree.clone(), 1)]
+        );
+        assert!(Polynomial::new(vec![3.0])
+            .squarefree_decomposition()
+            .is_empty());
+        assert!(Polynomial::new(vec![])
+            .squarefree_decomposition()
+            .is_empty());
+    }
+}
@@ -0,0 +1,200 @@
+//! C bindings
+//!
+//! Enabled with the `ffi` feature. The functions work on caller-owned coefficient buffers,
+//! from lowest to highest degree, and write the product into `out`, which must hold at least
+//! `poly_product_len(a_len, b_len)` values. The matching header is `include/mult_polynomial.h`,
+//! regenerated with `cbindgen --config cbindgen.toml --output include/mult_polynomial.h`;
+//! a test checks that it declares exactly the constants and functions of this module.
+//! ```c
+//! #include "mult_polynomial.h"
+//! double a[] = {1, 2, 3}, b[] = {4, 5}, out[4];
+//! int status = poly_multiply_toom3(a, 3, b, 2, out, 4); // POLY_OK
+//! ```
+
+use crate::polynomial::multiply_impl;
+use crate::PolynomialMultAlg;
+
+/// The product was written to `out`
+pub const POLY_OK: i32 = 0;
+/// An input or output pointer was null while its length was nonzero
+pub const POLY_ERR_NULL: i32 = -1;
+/// `out_len` is smaller than `poly_product_len(a_len, b_len)`
+pub const POLY_ERR_OUT_TOO_SMALL: i32 = -2;
+
+/// Number of coefficients in the product of polynomials with `a_len` and `b_len` coefficients
+#[no_mangle]
+pub extern "C" fn poly_product_len(a_len: usize, b_len: usize) -> usize {
+    if a_len == 0 || b_len == 0 {
+        0
+    } else {
+        a_len + b_len - 1
+    }
+}
+
+/// Multiply using the naive O(n²) algorithm
+///
+/// Returns `POLY_OK` on success, or a negative error code.
+///
+/// # Safety
+/// `a` and `b` must point to `a_len` and `b_len` readable values and `out` to `out_len`
+/// writable values; `out` must not overlap the inputs.
+#[no_mangle]
+pub unsafe extern "C" fn poly_multiply_naive(
+    a: *const f64,
+    a_len: usize,
+    b: *const f64,
+    b_len: usize,
+    out: *mut f64,
+    out_len: usize,
+) -> i32 {
+    multiply_raw(a, a_len, b, b_len, out, out_len, PolynomialMultAlg::Naive)
+}
+
+/// Multiply using the Cook-Tooms algorithm with k=3
+///
+/// Returns `POLY_OK` on success, or a negative error code.
+///
+/// # Safety
+/// Same requirements as [`poly_multiply_naive`].
+#[no_mangle]
+pub unsafe extern "C" fn poly_multiply_toom3(
+    a: *const f64,
+    a_len: usize,
+    b: *const f64,
+    b_len: usize,
+    out: *mut f64,
+    out_len: usize,
+) -> i32 {
+    multiply_raw(
+        a,
+        a_len,
+        b,
+        b_len,
+        out,
+        out_len,
+        PolynomialMultAlg::CookTooms,
+    )
+}
+
+/// Multiply using Toom-3, switching to the naive algorithm below `threshold` coefficients
+///
+/// Returns `POLY_OK` on success, or a negative error code.
+///
+/// # Safety
+/// Same requirements as [`poly_multiply_naive`].
+#[no_mangle]
+pub unsafe extern "C" fn poly_multiply_thresholded(
+    a: *const f64,
+    a_len: usize,
+    b: *const f64,
+    b_len: usize,
+    out: *mut f64,
+    out_len: usize,
+    threshold: usize,
+) -> i32 {
+    let algorithm = PolynomialMultAlg::Thresholded(threshold);
+    multiply_raw(a, a_len, b, b_len, out, out_len, algorithm)
+}
+
+/// Validate the raw buffers and multiply into `out`
+unsafe fn multiply_raw(
+    a: *const f64,
+    a_len: usize,
+    b: *const f64,
+    b_len: usize,
+    out: *mut f64,
+    out_len: usize,
+    algorithm: PolynomialMultAlg,
+) -> i32 {
+    let product_len = poly_product_len(a_len, b_len);
+    if out_len < product_len {
+        return POLY_ERR_OUT_TOO_SMALL;
+    }
+    if product_len == 0 {
+        return POLY_OK;
+    }
+    if a.is_null() || b.is_null() || out.is_null() {
+        return POLY_ERR_NULL;
+    }
+    let a = core::slice::from_raw_parts(a, a_len);
+    let b = core::slice::from_raw_parts(b, b_len);
+    let out = core::slice::from_raw_parts_mut(out, product_len);
+    out.copy_from_slice(&multiply_impl(a, b, algorithm));
+    POLY_OK
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+    use alloc::{format, vec};
+    use core::ptr;
+
+    /// All three entry points agree with the safe API
+    #[test]
+    fn test_ffi_multiply() {
+        let a = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0];
+        let b = [7.0, -1.0, 0.5];
+        let expected = crate::Polynomial::new(a.to_vec())
+            .multiply_naive(&crate::Polynomial::new(b.to_vec()))
+            .coeffs;
+        let mut out = vec![0.0; poly_product_len(a.len(), b.len())];
+        let (ap, bp, op, len) = (a.as_ptr(), b.as_ptr(), out.as_mut_ptr(), out.len());
+        unsafe {
+            assert_eq!(poly_multiply_naive(ap, 7, bp, 3, op, len), POLY_OK);
+            assert_eq!(out, expected);
+            assert_eq!(poly_multiply_toom3(ap, 7, bp, 3, op, len), POLY_OK);
+            assert!(out.iter().zip(&expected).all(|(x, y)| (x - y).abs() < 1e-9));
+            assert_eq!(poly_multiply_thresholded(ap, 7, bp, 3, op, len, 4), POLY_OK);
+            assert!(out.iter().zip(&expected).all(|(x, y)| (x - y).abs() < 1e-9));
+        }
+    }
+
+    /// Invalid buffers are reported instead of dereferenced
+    #[test]
+    fn test_ffi_errors() {
+        let a = [1.0, 2.0];
+        let mut out = [0.0; 2];
+        unsafe {
+            let status = poly_multiply_naive(a.as_ptr(), 2, a.as_ptr(), 2, out.as_mut_ptr(), 2);
+            assert_eq!(status, POLY_ERR_OUT_TOO_SMALL);
+            let status = poly_multiply_naive(ptr::null(), 2, a.as_ptr(), 2, out.as_mut_ptr(), 3);
+            assert_eq!(status, POLY_ERR_NULL);
+            let status = poly_multiply_naive(ptr::null(), 0, a.as_ptr(), 2, ptr::null_mut(), 0);
+            assert_eq!(status, POLY_OK);
+        }
+    }
+
+    /// The checked-in header declares exactly the constants and functions of this module
+    #[test]
+    fn test_header_in_sync() {
+        let header = include_str!("../include/mult_polynomial.h");
+        let source = include_str!("ffi.rs");
+
+        let defines: Vec<&str> = (header.lines())
+            .filter_map(|line| line.strip_prefix("#define "))
+            .filter(|define| *define != "MULT_POLYNOMIAL_H")
+            .collect();
+        let constants: Vec<String> = (source.lines())
+            .filter_map(|line| line.strip_prefix("pub const "))
+            .map(|line| {
+                let (name, value) = line.split_once(':').unwrap();
+                let value = value.split_once("= ").unwrap().1;
+                format!("{} {}", name, value.trim_end_matches(';'))
+            })
+            .collect();
+        assert_eq!(defines, constants);
+
+        let declared: Vec<&str> = (header.lines())
+            .filter(|line| line.starts_with(|c: char| c.is_ascii_alphabetic()))
+            .filter_map(|line| line.split_once('(')?.0.rsplit(['*', ' ']).next())
+            .collect();
+        let functions: Vec<&str> = (source.lines())
+            .filter(|line| line.starts_with("pub "))
+            .filter_map(|line| line.split_once("extern \"C\" fn ")?.1.split_once('('))
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(declared, functions);
+    }
+}
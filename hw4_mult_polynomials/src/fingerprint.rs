@@ -0,0 +1,373 @@
+//! Stable fingerprints of polynomials, randomized fingerprints over a prime field,
+//! and a bounded product cache keyed by them
+
+use crate::Polynomial;
+#[cfg(feature = "std")]
+use crate::PolynomialMultAlg;
+#[cfg(feature = "std")]
+use std::collections::{BTreeMap, HashMap};
+use xxhash_rust::xxh3::Xxh3;
+
+impl Polynomial {
+    /// Compute a fast, stable 64-bit hash of the coefficients
+    ///
+    /// The hash is XXH3 over the little-endian bits of the coefficients, after mapping
+    /// `-0.0` to `0.0` and every NaN to the canonical NaN, so it is identical across
+    /// platforms and runs. Polynomials differing by the tolerance of `==` hash differently.
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// let p = Polynomial::new(vec![1.0, -0.0, 3.0]);
+    /// let q = Polynomial::new(vec![1.0, 0.0, 3.0]);
+    /// assert_eq!(p.fingerprint(), q.fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = Xxh3::new();
+        hasher.update(&(self.coeffs.len() as u64).to_le_bytes());
+        for &c in &self.coeffs {
+            hasher.update(&canonical_bits(c).to_le_bytes());
+        }
+        hasher.digest()
+    }
+
+    /// Evaluate the polynomial exactly over the prime field of order `p = 2⁶¹ - 1`
+    /// at a random point derived from `seed`
+    ///
+    /// Every finite coefficient `m·2ᵉ` maps exactly to `m·2ᵉ mod p` (2 is invertible, and
+    /// `2⁶¹ ≡ 1`), so `-0.0` and `0.0` agree, trailing zeros do not matter, and two different
+    /// polynomials collide for at most `degree / p` of all seeds, about `10⁻¹³` at degree 10⁶.
+    /// Unlike [`Polynomial::fingerprint`] the map respects sums and products of dyadic
+    /// rationals, so the fingerprint of an exact product is the product of the fingerprints,
+    /// see [`verify_exact_product`](crate::verify_exact_product). NaN and infinities map to
+    /// fixed elements of the field. Takes `O(n)` time and compares without tolerances.
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// let p = Polynomial::new(vec![0.5, -3.0, 1.0]);
+    /// let mut q = p.clone();
+    /// assert_eq!(p.field_fingerprint(7), q.field_fingerprint(7));
+    /// q.coeffs[1] = f64::from_bits((-3.0f64).to_bits() + 1); // one ulp apart
+    /// assert_ne!(p.field_fingerprint(7), q.field_fingerprint(7));
+    /// ```
+    pub fn field_fingerprint(&self, seed: u64) -> u64 {
+        field_evaluate(&self.coeffs, field_point(seed))
+    }
+}
+
+/// Bits of a coefficient as hashed by [`Polynomial::fingerprint`], with `-0.0` as `0.0`
+/// and every NaN as the canonical NaN
+fn canonical_bits(c: f64) -> u64 {
+    if c.is_nan() {
+        f64::NAN.to_bits()
+    } else if c == 0.0 {
+        0
+    } else {
+        c.to_bits()
+    }
+}
+
+/// Whether two polynomials have the same fingerprinted coefficients, bit for bit
+#[cfg(feature = "std")]
+fn same_coeffs(a: &Polynomial, b: &Polynomial) -> bool {
+    a.coeffs.len() == b.coeffs.len()
+        && (a.coeffs.iter().zip(&b.coeffs)).all(|(&x, &y)| canonical_bits(x) == canonical_bits(y))
+}
+
+/// Order of the prime field of [`Polynomial::field_fingerprint`], the Mersenne prime `2⁶¹ - 1`
+const FIELD_PRIME: u64 = (1 << 61) - 1;
+
+/// Reduce `x < 2⁶⁴` modulo `2⁶¹ - 1`
+fn field_reduce(x: u64) -> u64 {
+    let x = (x & FIELD_PRIME) + (x >> 61);
+    if x >= FIELD_PRIME {
+        x - FIELD_PRIME
+    } else {
+        x
+    }
+}
+
+/// Product of two field elements
+pub(crate) fn field_mul(x: u64, y: u64) -> u64 {
+    let product = x as u128 * y as u128;
+    field_reduce((product as u64 & FIELD_PRIME) + (product >> 61) as u64)
+}
+
+/// Exact image of a float in the field: `m·2ᵉ` maps to `m·2^(e mod 61)`, as `2⁶¹ ≡ 1`
+fn field_element(c: f64) -> u64 {
+    if !c.is_finite() {
+        // arbitrary fixed elements, distinct from those of small integers
+        return match c {
+            c if c.is_nan() => FIELD_PRIME - 3,
+            c if c > 0.0 => FIELD_PRIME - 5,
+            _ => FIELD_PRIME - 7,
+        };
+    }
+    let bits = c.to_bits();
+    let (biased, fraction) = ((bits >> 52) & 0x7ff, bits & ((1 << 52) - 1));
+    let (mantissa, exponent) = match biased {
+        0 => (fraction, -1074), // subnormal numbers and zero
+        _ => (fraction | (1 << 52), biased as i64 - 1075),
+    };
+    let value = field_mul(mantissa, 1 << exponent.rem_euclid(61));
+    if c.is_sign_negative() && value != 0 {
+        FIELD_PRIME - value
+    } else {
+        value
+    }
+}
+
+/// Evaluation point of a seed: SplitMix64 of the seed, reduced into the field
+pub(crate) fn field_point(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    field_reduce(z ^ (z >> 31))
+}
+
+/// Evaluate the field images of `coeffs` at `point` with Horner's scheme
+pub(crate) fn field_evaluate(coeffs: &[f64], point: u64) -> u64 {
+    (coeffs.iter().rev()).fold(0, |acc, &c| {
+        field_reduce(field_mul(acc, point) + field_element(c))
+    })
+}
+
+/// Key of a cached product: operand fingerprints and the algorithm used
+#[cfg(feature = "std")]
+type ProductKey = (u64, u64, PolynomialMultAlg);
+
+/// Cached product with its operands, to tell fingerprint collisions apart
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+struct CachedProduct {
+    lhs: Polynomial,
+    rhs: Polynomial,
+    product: Polynomial,
+    /// Time of the last use
+    last_used: u64,
+}
+
+/// Bounded LRU memo table of polynomial products keyed by operand fingerprints
+///
+/// When full, the least recently used product is evicted.
+/// Products are looked up by the 64-bit [`Polynomial::fingerprint`] of the operands, and the
+/// operands are kept alongside to compare on every hit, so a fingerprint collision only costs
+/// a recomputation and never returns the product of other operands.
+/// ## Example
+/// ```
+/// use mult_polynomial::{Polynomial, ProductCache};
+/// let mut cache = ProductCache::new(16);
+/// let p = Polynomial::random(-10.0, 10.0, 100);
+/// let first = p.multiply_auto_cached(&p, &mut cache);
+/// let second = p.multiply_auto_cached(&p, &mut cache);
+/// assert_eq!(first, second);
+/// assert_eq!(cache.hits(), 1);
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct ProductCache {
+    capacity: usize,
+    /// Cached products with their operands and the time of their last use
+    products: HashMap<ProductKey, CachedProduct>,
+    /// Keys ordered by the time of their last use
+    recency: BTreeMap<u64, ProductKey>,
+    /// Logical clock incremented on every access
+    clock: u64,
+    hits: u64,
+    misses: u64,
+}
+
+#[cfg(feature = "std")]
+impl ProductCache {
+    /// Create an empty cache holding at most `capacity` products
+    pub fn new(capacity: usize) -> Self {
+        ProductCache {
+            capacity,
+            products: HashMap::with_capacity(capacity),
+            recency: BTreeMap::new(),
+            clock: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Multiply `lhs` by `rhs`, returning the cached product if it was computed before
+    pub fn multiply(
+        &mut self,
+        lhs: &Polynomial,
+        rhs: &Polynomial,
+        algorithm: PolynomialMultAlg,
+    ) -> Polynomial {
+        let key = (lhs.fingerprint(), rhs.fingerprint(), algorithm);
+        self.clock += 1;
+
+        match self.products.get_mut(&key) {
+            Some(cached) if same_coeffs(&cached.lhs, lhs) && same_coeffs(&cached.rhs, rhs) => {
+                self.recency.remove(&cached.last_used);
+                self.recency.insert(self.clock, key);
+                cached.last_used = self.clock;
+                self.hits += 1;
+                return cached.product.clone();
+            }
+            // a collision: the entry of the other operands is replaced
+            Some(cached) => {
+                self.recency.remove(&cached.last_used);
+                self.products.remove(&key);
+            }
+            None => {}
+        }
+
+        self.misses += 1;
+        let product = lhs.multiply(rhs, algorithm);
+        if self.capacity > 0 {
+            if self.products.len() == self.capacity {
+                if let Some((_, oldest)) = self.recency.pop_first() {
+                    self.products.remove(&oldest);
+                }
+            }
+            let cached = CachedProduct {
+                lhs: lhs.clone(),
+                rhs: rhs.clone(),
+                product: product.clone(),
+                last_used: self.clock,
+            };
+            self.products.insert(key, cached);
+            self.recency.insert(self.clock, key);
+        }
+        product
+    }
+
+    /// Number of cached products
+    pub fn len(&self) -> usize {
+        self.products.len()
+    }
+
+    /// Whether the cache holds no products
+    pub fn is_empty(&self) -> bool {
+        self.products.is_empty()
+    }
+
+    /// Number of products served from the cache
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Number of products that had to be computed
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// Remove all cached products
+    pub fn clear(&mut self) {
+        self.products.clear();
+        self.recency.clear();
+    }
+}
+
+#[cfg(feature = "std")]
+impl Polynomial {
+    /// Multiply with another polynomial using the automatically chosen algorithm,
+    /// consulting `cache` for previously computed products
+    pub fn multiply_auto_cached(&self, other: &Polynomial, cache: &mut ProductCache) -> Polynomial {
+        let algorithm = PolynomialMultAlg::auto(self.coeffs.len(), other.coeffs.len());
+        cache.multiply(self, other, algorithm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    /// Test that fingerprints are stable and distinguish different polynomials
+    #[test]
+    fn test_fingerprint() {
+        let p = Polynomial::new(vec![1.0, 2.0, 3.0]);
+        assert_eq!(p.fingerprint(), p.clone().fingerprint());
+        assert_ne!(
+            p.fingerprint(),
+            Polynomial::new(vec![1.0, 2.0, 3.5]).fingerprint()
+        );
+        assert_ne!(
+            p.fingerprint(),
+            Polynomial::new(vec![3.0, 2.0, 1.0]).fingerprint()
+        );
+        assert_ne!(
+            Polynomial::new(vec![]).fingerprint(),
+            Polynomial::new(vec![0.0, 1.0]).fingerprint()
+        );
+    }
+
+    /// Field fingerprints are exact images: they respect dyadic products and tell 1 ulp apart
+    #[test]
+    fn test_field_fingerprint() {
+        assert_eq!(field_element(0.5), field_mul(field_element(0.25), 2));
+        assert_eq!(field_element(-0.0), 0);
+        assert_eq!(
+            field_element(f64::MIN_POSITIVE / 4.0),
+            1 << (-1024i64).rem_euclid(61)
+        );
+        assert_eq!(field_reduce(field_element(-3.0) + 3), 0);
+        let a = Polynomial::new(vec![0.5, -1.25, 3.0, 1e10]);
+        let b = Polynomial::new(vec![-2.0, 0.0, 0.75]);
+        let c = a.multiply_naive(&b); // exact in floating point
+        for seed in 0..10 {
+            let product = field_mul(a.field_fingerprint(seed), b.field_fingerprint(seed));
+            assert_eq!(product, c.field_fingerprint(seed));
+        }
+        let mut padded = c.clone();
+        padded.coeffs.extend([0.0, -0.0]);
+        assert_eq!(padded.field_fingerprint(3), c.field_fingerprint(3));
+        let mut next = c.clone();
+        next.coeffs[2] = f64::from_bits(c.coeffs[2].to_bits() + 1);
+        assert_ne!(next.field_fingerprint(3), c.field_fingerprint(3));
+        assert_ne!(a.field_fingerprint(1), a.field_fingerprint(2));
+    }
+
+    /// Test that the product cache stays bounded and returns the memoized products
+    #[cfg(feature = "thread_rng")]
+    #[test]
+    fn test_product_cache() {
+        let mut cache = ProductCache::new(2);
+        let p1 = Polynomial::random(-10.0, 10.0, 20);
+        let p2 = Polynomial::random(-10.0, 10.0, 20);
+        let p3 = Polynomial::random(-10.0, 10.0, 20);
+        let alg = PolynomialMultAlg::CookTooms;
+
+        assert_eq!(cache.multiply(&p1, &p2, alg), p1.multiply(&p2, alg));
+        assert_eq!(cache.multiply(&p1, &p2, alg), p1.multiply(&p2, alg));
+        assert_eq!(cache.len(), 1);
+
+        cache.multiply(&p1, &p3, alg);
+        assert_eq!((cache.hits(), cache.misses()), (1, 2));
+
+        // p1·p2 was used more recently than p1·p3 and survives the eviction
+        cache.multiply(&p1, &p2, alg);
+        cache.multiply(&p2, &p3, alg);
+        assert_eq!(cache.len(), 2);
+        cache.multiply(&p1, &p2, alg);
+        assert_eq!((cache.hits(), cache.misses()), (3, 3));
+        cache.multiply(&p1, &p3, alg);
+        assert_eq!((cache.hits(), cache.misses()), (3, 4));
+
+        // a forged fingerprint collision is recomputed instead of served
+        let key = (p1.fingerprint(), p2.fingerprint(), alg);
+        let forged = CachedProduct {
+            lhs: p3.clone(),
+            rhs: p2.clone(),
+            product: p3.multiply(&p2, alg),
+            last_used: cache.products[&key].last_used,
+        };
+        cache.products.insert(key, forged);
+        assert_eq!(cache.multiply(&p1, &p2, alg), p1.multiply(&p2, alg));
+        assert_eq!((cache.hits(), cache.misses()), (3, 5));
+        assert_eq!(cache.len(), 2);
+        assert!(same_coeffs(&cache.products[&key].lhs, &p1));
+        assert!(same_coeffs(
+            &Polynomial::new(vec![-0.0, 1.0]),
+            &Polynomial::new(vec![0.0, 1.0])
+        ));
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+}
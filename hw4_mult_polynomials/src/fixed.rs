@@ -0,0 +1,170 @@
+//! Polynomials with a number of coefficients fixed at compile time, multiplied without allocation
+
+use crate::fp::mul_add;
+use crate::{Coefficient, Polynomial};
+
+/// Operand length below which [`karatsuba_into`] multiplies naively
+const KARATSUBA_CUTOFF: usize = 4;
+
+/// Polynomial with `N` coefficients stored in an array, from lowest to highest degree
+///
+/// Nothing is trimmed, so products of `FixedPolynomial<N>`s have `2N - 1` coefficients;
+/// their length `M` is a second parameter, usually inferred from the binding,
+/// and a shorter one fails to compile. The multiplications only use the stack,
+/// for embedded targets and hot loops over many small products.
+/// ## Example
+/// ```
+/// use mult_polynomial::FixedPolynomial;
+/// let p = FixedPolynomial::new([1.0, 2.0]);
+/// let q = FixedPolynomial::new([3.0, 4.0]);
+/// let product: FixedPolynomial<3> = p.multiply(&q);
+/// assert_eq!(product.coeffs, [3.0, 10.0, 8.0]);
+/// assert_eq!(p.multiply_karatsuba::<3>(&q), product);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FixedPolynomial<const N: usize, T = f64> {
+    /// Coefficients from lowest to highest degree
+    pub coeffs: [T; N],
+}
+
+impl<const N: usize, T: Coefficient> FixedPolynomial<N, T> {
+    /// Create a new polynomial from an array of coefficients
+    pub const fn new(coeffs: [T; N]) -> Self {
+        FixedPolynomial { coeffs }
+    }
+
+    /// Evaluate the polynomial at a given point x with Horner's scheme
+    pub fn evaluate(&self, x: T) -> T {
+        (self.coeffs.iter().rev()).fold(T::ZERO, |acc, &c| mul_add(acc, x, c))
+    }
+
+    /// Multiply with another polynomial using the naive O(N²) algorithm
+    ///
+    /// The product has `M ≥ 2N - 1` coefficients, the ones past `2N - 1` are zero.
+    pub fn multiply<const M: usize>(&self, other: &FixedPolynomial<N, T>) -> FixedPolynomial<M, T> {
+        const { assert!(M + 1 >= 2 * N, "the product needs 2N - 1 coefficients") };
+        let mut result = [T::ZERO; M];
+        naive_into(&self.coeffs, &other.coeffs, &mut result);
+        FixedPolynomial::new(result)
+    }
+
+    /// Multiply with another polynomial using Karatsuba's algorithm
+    ///
+    /// The temporaries live in a stack array of `3M` coefficients;
+    /// below 4 coefficients the halves are multiplied naively.
+    pub fn multiply_karatsuba<const M: usize>(
+        &self,
+        other: &FixedPolynomial<N, T>,
+    ) -> FixedPolynomial<M, T> {
+        const { assert!(M + 1 >= 2 * N, "the product needs 2N - 1 coefficients") };
+        let mut result = [T::ZERO; M];
+        let mut scratch = [[T::ZERO; M]; 3];
+        let len = (2 * N).saturating_sub(1);
+        karatsuba_into(
+            &self.coeffs,
+            &other.coeffs,
+            &mut result[..len],
+            scratch.as_flattened_mut(),
+        );
+        FixedPolynomial::new(result)
+    }
+
+    /// Copy the coefficients into a heap-allocated polynomial, trimming as in [`Polynomial::new`]
+    pub fn to_polynomial(&self) -> Polynomial<T> {
+        Polynomial::new(self.coeffs.to_vec())
+    }
+}
+
+impl<const N: usize, T: Coefficient> From<[T; N]> for FixedPolynomial<N, T> {
+    fn from(coeffs: [T; N]) -> Self {
+        FixedPolynomial::new(coeffs)
+    }
+}
+
+/// Naive multiplication adding `a·b` to the zeroed `result`
+fn naive_into<T: Coefficient>(a: &[T], b: &[T], result: &mut [T]) {
+    for (i, &a_i) in a.iter().enumerate() {
+        for (r, &b_j) in result[i..].iter_mut().zip(b) {
+            *r = mul_add(a_i, b_j, *r);
+        }
+    }
+}
+
+/// Karatsuba multiplication of operands of equal length `n`, overwriting the `2n - 1`
+/// coefficients of `result` and using about `4n` coefficients of `scratch`
+fn karatsuba_into<T: Coefficient>(a: &[T], b: &[T], result: &mut [T], scratch: &mut [T]) {
+    let n = a.len();
+    if n < KARATSUBA_CUTOFF {
+        result.fill(T::ZERO);
+        naive_into(a, b, result);
+        return;
+    }
+
+    // a = a0 + x^half·a1 with a1 no longer than a0
+    let half = n.div_ceil(2);
+    let (a0, a1) = a.split_at(half);
+    let (b0, b1) = b.split_at(half);
+    let (sums, scratch) = scratch.split_at_mut(2 * half);
+    let (z1, scratch) = scratch.split_at_mut(2 * half - 1);
+
+    // z0 and z2 go straight to their places, z1 = (a0 + a1)(b0 + b1) - z0 - z2 next to them
+    karatsuba_into(a0, b0, &mut result[..2 * half - 1], scratch);
+    result[2 * half - 1] = T::ZERO;
+    karatsuba_into(a1, b1, &mut result[2 * half..], scratch);
+    let (a_sum, b_sum) = sums.split_at_mut(half);
+    a_sum.copy_from_slice(a0);
+    b_sum.copy_from_slice(b0);
+    for (s, &h) in a_sum.iter_mut().zip(a1) {
+        *s += h;
+    }
+    for (s, &h) in b_sum.iter_mut().zip(b1) {
+        *s += h;
+    }
+    karatsuba_into(a_sum, b_sum, z1, scratch);
+    for (k, z) in z1.iter_mut().enumerate() {
+        *z -= result[k] + result.get(2 * half + k).copied().unwrap_or(T::ZERO);
+    }
+    for (r, &z) in result[half..].iter_mut().zip(&*z1) {
+        *r += z;
+    }
+}
+
+#[cfg(all(test, feature = "thread_rng"))]
+mod tests {
+    use super::*;
+
+    /// Test both multiplications against the heap-allocated naive product
+    #[test]
+    fn test_fixed_products() {
+        fn check<const N: usize, const M: usize>() {
+            let p = Polynomial::random(-10.0, 10.0, N);
+            let q = Polynomial::random(-10.0, 10.0, N);
+            let (mut a, mut b) = ([0.0; N], [0.0; N]);
+            a[..p.coeffs.len()].copy_from_slice(&p.coeffs);
+            b[..q.coeffs.len()].copy_from_slice(&q.coeffs);
+            let (a, b) = (FixedPolynomial::new(a), FixedPolynomial::new(b));
+            let naive: FixedPolynomial<M> = a.multiply(&b);
+            let karatsuba: FixedPolynomial<M> = a.multiply_karatsuba(&b);
+            assert_eq!(naive.to_polynomial(), p.multiply_naive(&q), "N = {}", N);
+            assert!(karatsuba
+                .to_polynomial()
+                .approx_eq(&naive.to_polynomial(), 1e-12, 1e-12));
+            assert!(naive.coeffs[(2 * N).saturating_sub(1)..]
+                .iter()
+                .all(|&c| c == 0.0));
+            assert!((naive.evaluate(0.7) - a.evaluate(0.7) * b.evaluate(0.7)).abs() < 1e-9);
+        }
+        check::<1, 1>();
+        check::<3, 5>();
+        check::<8, 15>();
+        check::<8, 16>();
+        check::<13, 25>();
+        check::<64, 127>();
+
+        let p = FixedPolynomial::<4, f32>::from([1.0, 0.0, 0.0, 1.0]);
+        let square: FixedPolynomial<7, f32> = p.multiply_karatsuba(&p);
+        assert_eq!(square.coeffs, [1.0, 0.0, 0.0, 2.0, 0.0, 0.0, 1.0]);
+        let empty = FixedPolynomial::<0>::new([]);
+        assert_eq!(empty.multiply_karatsuba::<0>(&empty).coeffs, [0.0; 0]);
+    }
+}
@@ -0,0 +1,25 @@
+//! Floating-point primitives whose rounding depends on the `fast_fma` feature
+
+use crate::Coefficient;
+
+/// Whether fused multiply-add is used by the kernels
+#[cfg(feature = "std")]
+const USE_FMA: bool = cfg!(all(
+    feature = "fast_fma",
+    any(target_feature = "fma", target_arch = "aarch64"),
+    not(feature = "strict_fp")
+));
+
+/// Compute `a * b + c`
+///
+/// The product and the sum are rounded separately, which gives bit-identical results on every platform.
+/// With `fast_fma` on a target with hardware FMA this is a single fused operation (one rounding) instead,
+/// unless `strict_fp` is enabled too.
+#[inline(always)]
+pub(crate) fn mul_add<T: Coefficient>(a: T, b: T, c: T) -> T {
+    #[cfg(feature = "std")]
+    if USE_FMA {
+        return a.fused_mul_add(b, c);
+    }
+    a * b + c
+}
@@ -0,0 +1,249 @@
+//! Greatest common divisors by the Euclidean and the half-GCD algorithm
+//!
+//! The half-GCD algorithm finds the quotients of the first half of the remainder sequence
+//! from the high halves of the operands alone, recursively, and jumps there with one
+//! 2×2 polynomial matrix product. With the fast multipliers this takes O(M(n) log n)
+//! instead of the O(n²) of the Euclidean remainder sequence.
+//!
+//! In floating point, leading coefficients cancel only up to rounding errors, so coefficients
+//! below [`GCD_TOLERANCE`] relative to the largest coefficient of the operands count as zero.
+
+use crate::polynomial::multiply_impl;
+use crate::{Normalization, Polynomial, PolynomialMultAlg};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Coefficients below this fraction of the largest coefficient of the operands count as zero
+pub const GCD_TOLERANCE: f64 = 1e-9;
+
+/// Degree from which [`Polynomial::gcd`] switches to the half-GCD algorithm
+const HALF_GCD_THRESHOLD: usize = 64;
+
+/// Transformation `(a, b) ↦ (m₀₀·a + m₀₁·b, m₁₀·a + m₁₁·b)` of a pair of remainders
+struct Matrix([[Vec<f64>; 2]; 2]);
+
+impl Matrix {
+    fn identity() -> Self {
+        Matrix([[vec![1.0], vec![]], [vec![], vec![1.0]]])
+    }
+
+    /// One Euclidean step `(a, b) ↦ (b, a - q·b)`
+    fn step(quotient: Vec<f64>) -> Self {
+        let negated = quotient.iter().map(|c| -c).collect();
+        Matrix([[vec![], vec![1.0]], [vec![1.0], negated]])
+    }
+
+    /// Matrix product `self · other`, i.e. `other` applied first
+    fn compose(&self, other: &Matrix) -> Matrix {
+        let entry = |i: usize, j: usize| {
+            add(
+                &multiply(&self.0[i][0], &other.0[0][j]),
+                &multiply(&self.0[i][1], &other.0[1][j]),
+            )
+        };
+        Matrix([[entry(0, 0), entry(0, 1)], [entry(1, 0), entry(1, 1)]])
+    }
+
+    /// Apply to `(a, b)`, dropping the coefficients that cancel up to `tolerance`
+    fn apply(&self, a: &[f64], b: &[f64], tolerance: f64) -> (Vec<f64>, Vec<f64>) {
+        let row = |i: usize| {
+            let mut r = add(&multiply(&self.0[i][0], a), &multiply(&self.0[i][1], b));
+            trim(&mut r, tolerance);
+            r
+        };
+        (row(0), row(1))
+    }
+}
+
+/// Product with the algorithm chosen by [`PolynomialMultAlg::auto`]
+pub(crate) fn multiply(a: &[f64], b: &[f64]) -> Vec<f64> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    multiply_impl(a, b, PolynomialMultAlg::auto(a.len(), b.len()))
+}
+
+/// Coefficient-wise sum
+pub(crate) fn add(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut sum = long.to_vec();
+    for (s, c) in sum.iter_mut().zip(short) {
+        *s += c;
+    }
+    sum
+}
+
+/// Drop the trailing coefficients up to `tolerance` in absolute value
+fn trim(p: &mut Vec<f64>, tolerance: f64) {
+    while p.last().is_some_and(|c| c.abs() <= tolerance) {
+        p.pop();
+    }
+}
+
+/// Quotient and remainder of `a` divided by a nonzero `b`, by fast division for large quotients
+///
+/// The fast quotient is checked to cancel the high coefficients of `a` up to `tolerance`,
+/// falling back to long division when the divisor's reversal is too ill-conditioned.
+fn div_rem(a: &[f64], b: &[f64], tolerance: f64) -> (Vec<f64>, Vec<f64>) {
+    let keep = |p: &[f64]| Polynomial::with_normalization(p.to_vec(), Normalization::Keep);
+    let (a, b) = (keep(a), keep(b));
+    let fast = a.coeffs.len() >= b.coeffs.len() + HALF_GCD_THRESHOLD
+        && b.coeffs.len() >= HALF_GCD_THRESHOLD;
+    let fast_division = || {
+        let (quotient, remainder) = a.div_rem_fast(&b).ok()?;
+        let product = multiply(&quotient.coeffs, &b.coeffs);
+        (b.coeffs.len() - 1..a.coeffs.len())
+            .all(|k| (a.coeffs[k] - product[k]).abs() <= tolerance)
+            .then_some((quotient, remainder))
+    };
+    let division = match fast.then(fast_division).flatten() {
+        Some(division) => Ok(division),
+        None => a.div_rem(&b),
+    };
+    let (quotient, remainder) = match division {
+        Ok(division) => division,
+        Err(_) => unreachable!("the divisor is not zero"),
+    };
+    let mut remainder = remainder.coeffs;
+    trim(&mut remainder, tolerance);
+    (quotient.coeffs, remainder)
+}
+
+/// Matrix reducing `(a, b)`, with `deg a > deg b`, to the consecutive remainders around
+/// half the degree of `a`: the second one has a degree below `⌈deg a / 2⌉`
+fn half_gcd(a: &[f64], b: &[f64], tolerance: f64) -> Matrix {
+    let m = a.len() / 2;
+    if b.len() <= m {
+        return Matrix::identity();
+    }
+    // the quotients of the high parts are those of the full operands down to degree m
+    let high = half_gcd(&a[m..], &b[m..], tolerance);
+    let (a, b) = high.apply(a, b, tolerance);
+    // the degrees drop by at least one per step, unless rounding errors kept them from cancelling
+    if b.len() <= m || b.len() >= a.len() || b.len() > 2 * m {
+        return high;
+    }
+    let (quotient, remainder) = div_rem(&a, &b, tolerance);
+    let k = 2 * m + 1 - b.len();
+    let low = half_gcd(&b[k..], &remainder[k.min(remainder.len())..], tolerance);
+    low.compose(&Matrix::step(quotient)).compose(&high)
+}
+
+/// Greatest common divisor up to a constant factor, by half-GCD steps from degree `threshold`
+fn gcd_impl(a: &[f64], b: &[f64], threshold: usize) -> Vec<f64> {
+    let height = a.iter().chain(b).fold(0.0f64, |h, c| h.max(c.abs()));
+    let tolerance = GCD_TOLERANCE * height;
+    let (mut a, mut b) = (a.to_vec(), b.to_vec());
+    trim(&mut a, tolerance);
+    trim(&mut b, tolerance);
+    if a.len() < b.len() {
+        (a, b) = (b, a);
+    }
+    while !b.is_empty() {
+        if a.len() > b.len() && a.len() > threshold && 2 * b.len() > a.len() {
+            let (c, d) = half_gcd(&a, &b, tolerance).apply(&a, &b, tolerance);
+            // rounding errors beyond the tolerance can keep the degrees from dropping
+            if c.len() <= a.len() && d.len() < b.len() && d.len() < c.len() {
+                (a, b) = (c, d);
+                continue;
+            }
+        }
+        let (_, remainder) = div_rem(&a, &b, tolerance);
+        (a, b) = (b, remainder);
+    }
+    a
+}
+
+impl Polynomial {
+    /// Monic greatest common divisor, the zero polynomial if both are zero
+    ///
+    /// Uses the half-GCD algorithm with the fast multipliers from degree 64 on, so even
+    /// degrees in the hundreds of thousands take softly linear time. Floating-point remainder
+    /// sequences can be ill-conditioned, so the result is only as good as the inputs' separation
+    /// from polynomials with a larger common factor, see [`GCD_TOLERANCE`]. Steps where rounding
+    /// errors keep the degrees from dropping, e.g. fast divisions by a divisor whose reversal is
+    /// ill-conditioned, fall back to long division and single Euclidean steps.
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// let a = Polynomial::from_roots(&[1.0, 2.0, 3.0]);
+    /// let b = Polynomial::from_roots(&[2.0, 3.0, 5.0]);
+    /// assert_eq!(a.gcd(&b), Polynomial::from_roots(&[2.0, 3.0]));
+    /// ```
+    pub fn gcd(&self, other: &Polynomial) -> Polynomial {
+        self.monic_gcd(gcd_impl(&self.coeffs, &other.coeffs, HALF_GCD_THRESHOLD))
+    }
+
+    /// Normalize a greatest common divisor to a leading coefficient of 1
+    fn monic_gcd(&self, gcd: Vec<f64>) -> Polynomial {
+        match gcd.last() {
+            Some(&lead) => self.with_coeffs(gcd.iter().map(|c| c / lead).collect()),
+            None => self.with_coeffs(gcd),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `xⁿ - 1`
+    fn cyclic(n: usize) -> Polynomial {
+        let mut coeffs = vec![0.0; n + 1];
+        (coeffs[0], coeffs[n]) = (-1.0, 1.0);
+        Polynomial::new(coeffs)
+    }
+
+    /// Test small inputs, coprime inputs and the zero polynomial
+    #[test]
+    fn test_gcd() {
+        let a = Polynomial::from_roots(&[1.0, -2.0, 0.5, 3.0]);
+        let b = Polynomial::from_roots(&[0.5, 4.0, -2.0]);
+        assert_eq!(a.gcd(&b), Polynomial::from_roots(&[0.5, -2.0]));
+        assert_eq!(b.gcd(&a), Polynomial::from_roots(&[0.5, -2.0]));
+        let scaled = Polynomial::new(a.coeffs.iter().map(|c| 3.0 * c).collect());
+        assert_eq!(scaled.gcd(&a), a);
+        assert_eq!(
+            a.gcd(&Polynomial::from_roots(&[7.0])),
+            Polynomial::new(vec![1.0])
+        );
+        let zero = Polynomial::new(vec![]);
+        assert_eq!(scaled.gcd(&zero), a);
+        assert!(zero.gcd(&zero).coeffs.is_empty());
+    }
+
+    /// gcd(xⁿ - 1, xᵐ - 1) = x^gcd(n, m) - 1 by the half-GCD and the Euclidean algorithm
+    #[test]
+    fn test_half_gcd() {
+        for (n, m, expected) in [
+            (3000, 1800, 600),
+            (1001, 700, 7),
+            (4096, 4095, 1),
+            (97, 5, 1),
+        ] {
+            let (a, b) = (cyclic(n), cyclic(m));
+            for threshold in [1, 16, usize::MAX] {
+                let gcd = a.monic_gcd(gcd_impl(&a.coeffs, &b.coeffs, threshold));
+                assert!(
+                    gcd.approx_eq(&cyclic(expected), 1e-9, 0.0),
+                    "n = {}, m = {}, threshold = {}",
+                    n,
+                    m,
+                    threshold
+                );
+            }
+        }
+        // a common factor, with roots inside the unit circle to keep the fast division stable
+        let g = Polynomial::from_roots(&[0.5, -0.3, 0.2]);
+        let a = g.multiply_auto(&cyclic(8000));
+        let b = g.multiply_auto(&cyclic(6000));
+        let expected = g.multiply_auto(&cyclic(2000)).monic().unwrap();
+        assert!(a.gcd(&b).approx_eq(&expected, 1e-9, 1e-12));
+        // a general common factor at a moderate degree
+        let g = Polynomial::new(vec![2.0, -1.0, 0.5, 1.0]);
+        let a = g.multiply_naive(&cyclic(300));
+        let b = g.multiply_naive(&cyclic(200));
+        let expected = g.multiply_naive(&cyclic(100));
+        assert!(a.gcd(&b).approx_eq(&expected, 1e-9, 0.0));
+    }
+}
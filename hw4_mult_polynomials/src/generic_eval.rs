@@ -0,0 +1,108 @@
+//! Evaluation at complex numbers, matrices and other ring elements
+
+use crate::{Coefficient, Polynomial};
+use core::ops::{Add, Mul};
+use num_traits::One;
+
+impl<T: Coefficient> Polynomial<T> {
+    /// Evaluate the polynomial at `x` of any type with a multiplicative identity,
+    /// products and scaling by coefficients, with Horner's scheme
+    ///
+    /// The constant coefficient is taken as a multiple of the identity, so `x` may be a complex number,
+    /// a square matrix (e.g. to check Cayley-Hamilton, `χ_A(A) = 0`) or the plain coefficient type.
+    /// Own types implement [`num_traits::One`] together with the arithmetic operators.
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::{Complex64, Polynomial};
+    /// // x² + 1 vanishes at i
+    /// let p = Polynomial::new(vec![1.0, 0.0, 1.0]);
+    /// assert_eq!(p.evaluate_generic(Complex64::new(0.0, 1.0)), Complex64::new(0.0, 0.0));
+    /// assert_eq!(p.evaluate_generic(2.0), p.evaluate(2.0));
+    /// ```
+    pub fn evaluate_generic<X>(&self, x: X) -> X
+    where
+        X: Clone + One + Add<Output = X> + Mul<Output = X> + Mul<T, Output = X>,
+    {
+        let mut coeffs = self.coeffs.iter().rev();
+        let leading = coeffs.next().copied().unwrap_or(T::ZERO);
+        coeffs.fold(X::one() * leading, |acc, &c| acc * x.clone() + X::one() * c)
+    }
+}
+
+#[cfg(all(test, feature = "thread_rng"))]
+mod tests {
+    use super::*;
+    use num_complex::Complex64;
+
+    /// Square matrix of order 3, just enough arithmetic to evaluate polynomials at it
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Matrix([[f64; 3]; 3]);
+
+    impl One for Matrix {
+        fn one() -> Self {
+            Matrix([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]])
+        }
+    }
+
+    impl Add for Matrix {
+        type Output = Matrix;
+        fn add(self, other: Matrix) -> Matrix {
+            Matrix(core::array::from_fn(|i| {
+                core::array::from_fn(|j| self.0[i][j] + other.0[i][j])
+            }))
+        }
+    }
+
+    impl Mul for Matrix {
+        type Output = Matrix;
+        fn mul(self, other: Matrix) -> Matrix {
+            Matrix(core::array::from_fn(|i| {
+                core::array::from_fn(|j| (0..3).map(|k| self.0[i][k] * other.0[k][j]).sum())
+            }))
+        }
+    }
+
+    impl Mul<f64> for Matrix {
+        type Output = Matrix;
+        fn mul(self, c: f64) -> Matrix {
+            Matrix(self.0.map(|row| row.map(|a| a * c)))
+        }
+    }
+
+    /// Test evaluation at complex numbers and Cayley-Hamilton for a matrix
+    #[test]
+    fn test_evaluate_generic() {
+        let p = Polynomial::random(-1.0, 1.0, 30);
+        for z in [Complex64::new(0.3, -0.8), Complex64::new(-1.1, 0.2)] {
+            let expected: Complex64 = (p.coeffs.iter().enumerate())
+                .map(|(k, &c)| z.powu(k as u32) * c)
+                .sum();
+            assert!((p.evaluate_generic(z) - expected).norm() < 1e-12);
+        }
+        // real points agree with the plain evaluation
+        assert!((p.evaluate_generic(0.7) - p.evaluate(0.7)).abs() < 1e-14);
+
+        // characteristic polynomial λ³ - tr(A)λ² + (sum of principal minors)λ - det(A)
+        let a = Matrix([[2.0, 1.0, 0.0], [-1.0, 3.0, 4.0], [0.5, 0.0, -2.0]]);
+        let m = a.0;
+        let trace = m[0][0] + m[1][1] + m[2][2];
+        let minors = (m[0][0] * m[1][1] - m[0][1] * m[1][0])
+            + (m[0][0] * m[2][2] - m[0][2] * m[2][0])
+            + (m[1][1] * m[2][2] - m[1][2] * m[2][1]);
+        let det = m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0]);
+        let characteristic = Polynomial::new(vec![-det, minors, -trace, 1.0]);
+        let value = characteristic.evaluate_generic(a);
+        assert!(value.0.iter().flatten().all(|v| v.abs() < 1e-12));
+        // a constant is a multiple of the identity
+        assert_eq!(
+            Polynomial::new(vec![2.0]).evaluate_generic(a),
+            Matrix::one() * 2.0
+        );
+        assert_eq!(
+            Polynomial::<f64>::new(vec![]).evaluate_generic(a),
+            Matrix::one() * 0.0
+        );
+    }
+}
@@ -0,0 +1,418 @@
+//! GPU backend for very large products
+//!
+//! Enabled with the `gpu` feature. [`GpuMultiplier`] implements [`MultiplyAlgorithm`], so it runs
+//! through [`Polynomial::multiply_with`](crate::Polynomial::multiply_with) and can be added to
+//! [`BenchmarkConfig::custom_algorithms`](crate::benchmark::BenchmarkConfig::custom_algorithms).
+
+use crate::polynomial::{multiply_impl, toom3_evaluate_into, toom3_interpolate_into};
+use crate::{MultiplyAlgorithm, PolyError, PolynomialMultAlg};
+
+/// Naive products of `count` operand pairs of `len` coefficients each, one output coefficient
+/// per invocation, summing the terms in the same order as the naive algorithm
+const SHADER: &str = r#"
+struct Params {
+    len: u32,
+    count: u32,
+}
+
+@group(0) @binding(0) var<storage, read> a: array<f64>;
+@group(0) @binding(1) var<storage, read> b: array<f64>;
+@group(0) @binding(2) var<storage, read_write> c: array<f64>;
+@group(0) @binding(3) var<uniform> params: Params;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>, @builtin(num_workgroups) groups: vec3<u32>) {
+    let product_len = 2u * params.len - 1u;
+    let index = id.x + id.y * groups.x * 64u;
+    if (index >= product_len * params.count) {
+        return;
+    }
+    let pair = index / product_len;
+    let k = index % product_len;
+    let offset = pair * params.len;
+    let i_min = select(0u, k + 1u - params.len, k >= params.len);
+    let i_max = min(k, params.len - 1u);
+    var sum = 0.0lf;
+    for (var i = i_min; i <= i_max; i++) {
+        sum += a[offset + i] * b[offset + k - i];
+    }
+    c[index] = sum;
+}
+"#;
+
+/// Invocations per workgroup, as declared in [`SHADER`]
+const WORKGROUP_SIZE: usize = 64;
+
+/// Multiplication running the base cases of Toom-3 on the GPU through `wgpu`
+///
+/// Operands shorter than [`GpuMultiplier::min_size`] stay on the CPU with
+/// [`PolynomialMultAlg::auto_for`]. Longer ones are zero-padded to `b·3ᵏ` coefficients with
+/// `b ≤` [`GpuMultiplier::base_len`], and split by `k` levels of Toom-3 on the CPU: depth first
+/// while the evaluations would not fit into the GPU buffers, then breadth first, so the `5ʲ`
+/// base products of the last `j` levels go to the GPU in a single dispatch. The GPU computes
+/// them naively in double precision, and the CPU interpolates the levels back up.
+///
+/// Needs an adapter supporting 64-bit floats in shaders (`SHADER_F64`, e.g. Vulkan or DX12 on
+/// desktop GPUs; Metal has none). The defaults are a starting point, not tuned for any GPU.
+/// ## Panics
+/// [`MultiplyAlgorithm::multiply`] panics if the device is lost during a multiplication.
+/// ## Example
+/// ```no_run
+/// use mult_polynomial::{GpuMultiplier, Polynomial};
+/// let gpu = GpuMultiplier::new().unwrap().min_size(1 << 20);
+/// let p = Polynomial::random(-1.0, 1.0, 20_000_000);
+/// let product = p.multiply_with(&p, &gpu);
+/// assert_eq!(product.degree(), 2 * p.degree());
+/// ```
+pub struct GpuMultiplier {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    pipeline: wgpu::ComputePipeline,
+    adapter_name: String,
+    min_size: usize,
+    base_len: usize,
+    /// Most coefficients of all operands of one dispatch, so the products fit into a buffer
+    batch_len: usize,
+    /// Most workgroups along one dimension of a dispatch
+    max_groups: usize,
+}
+
+impl GpuMultiplier {
+    /// Default of [`GpuMultiplier::min_size`]
+    pub const DEFAULT_MIN_SIZE: usize = 1 << 17;
+    /// Default of [`GpuMultiplier::base_len`]
+    pub const DEFAULT_BASE_LEN: usize = 512;
+
+    /// Open the fastest GPU supporting 64-bit floats in shaders
+    ///
+    /// Fails with [`PolyError::Unavailable`] if there is no such adapter.
+    pub fn new() -> Result<GpuMultiplier, PolyError> {
+        pollster::block_on(Self::request())
+    }
+
+    async fn request() -> Result<GpuMultiplier, PolyError> {
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::from_env_or_default());
+        let adapter = (instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::HighPerformance,
+            ..Default::default()
+        }))
+        .await
+        .map_err(|e| PolyError::Unavailable(format!("no GPU adapter: {}", e)))?;
+        let adapter_name = adapter.get_info().name;
+        if !adapter.features().contains(wgpu::Features::SHADER_F64) {
+            return Err(PolyError::Unavailable(format!(
+                "GPU `{}` does not support 64-bit floats in shaders",
+                adapter_name
+            )));
+        }
+        let limits = adapter.limits();
+        let (device, queue) = (adapter.request_device(&wgpu::DeviceDescriptor {
+            label: Some("mult_polynomial"),
+            required_features: wgpu::Features::SHADER_F64,
+            required_limits: limits.clone(),
+            ..Default::default()
+        }))
+        .await
+        .map_err(|e| PolyError::Unavailable(format!("GPU `{}`: {}", adapter_name, e)))?;
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("naive products"),
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("naive products"),
+            layout: None,
+            module: &module,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+        let buffer_bytes =
+            (limits.max_buffer_size).min(limits.max_storage_buffer_binding_size as u64);
+        Ok(GpuMultiplier {
+            device,
+            queue,
+            pipeline,
+            adapter_name,
+            min_size: Self::DEFAULT_MIN_SIZE,
+            base_len: Self::DEFAULT_BASE_LEN,
+            // the products are about twice as long as the operands
+            batch_len: (buffer_bytes / 16).min(u32::MAX as u64 / 2) as usize,
+            max_groups: limits.max_compute_workgroups_per_dimension as usize,
+        })
+    }
+
+    /// Name of the GPU, as reported by the driver
+    pub fn adapter_name(&self) -> &str {
+        &self.adapter_name
+    }
+
+    /// Multiply operands with fewer coefficients than `min_size` on the CPU
+    pub fn min_size(mut self, min_size: usize) -> Self {
+        self.min_size = min_size;
+        self
+    }
+
+    /// Stop the Toom-3 splits at base products of at most `base_len` coefficients
+    pub fn base_len(mut self, base_len: usize) -> Self {
+        self.base_len = base_len.max(1);
+        self
+    }
+
+    /// Naive products of the operand pairs of `len` coefficients each, stored one after another
+    fn multiply_batch(&self, xs: &[f64], ys: &[f64], len: usize) -> Vec<f64> {
+        use wgpu::BufferUsages as Usage;
+        let count = xs.len() / len;
+        let product_len = 2 * len - 1;
+        let bytes = (count * product_len * 8) as u64;
+        let storage = |label: &str, values: &[f64]| {
+            let buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(label),
+                size: (values.len() * 8) as u64,
+                usage: Usage::STORAGE | Usage::COPY_DST,
+                mapped_at_creation: false,
+            });
+            let data: Vec<u8> = values.iter().flat_map(|v| v.to_ne_bytes()).collect();
+            self.queue.write_buffer(&buffer, 0, &data);
+            buffer
+        };
+        let (a, b) = (storage("a", xs), storage("b", ys));
+        let c = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("c"),
+            size: bytes,
+            usage: Usage::STORAGE | Usage::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let staging = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("staging"),
+            size: bytes,
+            usage: Usage::MAP_READ | Usage::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let params = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("params"),
+            size: 16,
+            usage: Usage::UNIFORM | Usage::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let header: Vec<u8> = [len as u32, count as u32, 0, 0]
+            .iter()
+            .flat_map(|v| v.to_ne_bytes())
+            .collect();
+        self.queue.write_buffer(&params, 0, &header);
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &self.pipeline.get_bind_group_layout(0),
+            entries: &[&a, &b, &c, &params]
+                .iter()
+                .enumerate()
+                .map(|(binding, buffer)| wgpu::BindGroupEntry {
+                    binding: binding as u32,
+                    resource: buffer.as_entire_binding(),
+                })
+                .collect::<Vec<_>>(),
+        });
+
+        // a 2D grid of workgroups, as one dimension is limited to `max_groups`
+        let groups = (count * product_len).div_ceil(WORKGROUP_SIZE);
+        let groups_x = groups.min(self.max_groups);
+        let mut encoder = self.device.create_command_encoder(&Default::default());
+        {
+            let mut pass = encoder.begin_compute_pass(&Default::default());
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(groups_x as u32, groups.div_ceil(groups_x) as u32, 1);
+        }
+        encoder.copy_buffer_to_buffer(&c, 0, &staging, 0, bytes);
+        self.queue.submit([encoder.finish()]);
+
+        let slice = staging.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |result| {
+            result.expect("mapping the GPU products failed")
+        });
+        (self.device.poll(wgpu::PollType::Wait)).expect("GPU device lost");
+        let products = (slice.get_mapped_range().chunks_exact(8))
+            .map(|bytes| f64::from_ne_bytes(bytes.try_into().unwrap()))
+            .collect();
+        staging.unmap();
+        products
+    }
+}
+
+impl MultiplyAlgorithm for GpuMultiplier {
+    fn multiply(&self, a: &[f64], b: &[f64]) -> Vec<f64> {
+        let (n, m) = (a.len(), b.len());
+        if n.min(m) < self.min_size.max(1) {
+            return multiply_impl(a, b, PolynomialMultAlg::auto_for::<f64>(n, m));
+        }
+        let mut base = |xs: &[f64], ys: &[f64], len: usize| self.multiply_batch(xs, ys, len);
+        padded_toom3(a, b, self.base_len, self.batch_len, &mut base)
+    }
+
+    fn label(&self) -> String {
+        "gpu_time".to_string()
+    }
+}
+
+/// Product of `a` and `b`, zero-padded to `b·3ᵏ` coefficients with `b ≤ base_len`,
+/// by [`batched_toom3`]
+fn padded_toom3<F>(
+    a: &[f64],
+    b: &[f64],
+    base_len: usize,
+    batch_len: usize,
+    base: &mut F,
+) -> Vec<f64>
+where
+    F: FnMut(&[f64], &[f64], usize) -> Vec<f64>,
+{
+    let len = a.len().max(b.len());
+    let (mut levels, mut scale) = (0, 1);
+    while len.div_ceil(scale) > base_len {
+        levels += 1;
+        scale *= 3;
+    }
+    let padded = len.div_ceil(scale) * scale;
+    let pad = |x: &[f64]| {
+        let mut x = x.to_vec();
+        x.resize(padded, 0.0);
+        x
+    };
+    let mut product = batched_toom3(&pad(a), &pad(b), levels, batch_len, base);
+    product.truncate(a.len() + b.len() - 1);
+    product
+}
+
+/// Product of `a` and `b` of equal length `b·3ᵏ` by `k` levels of Toom-3, down to naive products
+/// of `b` coefficients computed by `base` on all operand pairs of a batch at once
+///
+/// Levels are split depth first until the evaluations of the remaining ones hold at most
+/// `batch_len` coefficients per operand, then breadth first.
+fn batched_toom3<F>(a: &[f64], b: &[f64], levels: u32, batch_len: usize, base: &mut F) -> Vec<f64>
+where
+    F: FnMut(&[f64], &[f64], usize) -> Vec<f64>,
+{
+    // each level turns 3 coefficients into 5
+    let expanded = a.len() / 3usize.pow(levels) * 5usize.pow(levels);
+    if expanded <= batch_len {
+        return breadth_first_toom3(a, b, levels, base);
+    }
+    let n_chunk = a.len() / 3;
+    let (mut points_a, mut points_b) = (vec![0.0; 3 * n_chunk], vec![0.0; 3 * n_chunk]);
+    let values_a = toom3_evaluate_into(a, n_chunk, &mut points_a);
+    let values_b = toom3_evaluate_into(b, n_chunk, &mut points_b);
+    let mut products = Vec::with_capacity(5 * (2 * n_chunk - 1));
+    for (x, y) in values_a.iter().zip(&values_b) {
+        products.extend(batched_toom3(x, y, levels - 1, batch_len, base));
+    }
+    let mut result = vec![0.0; 2 * a.len() - 1];
+    toom3_interpolate_into(&products, n_chunk, &mut result);
+    result
+}
+
+/// Evaluate the operands level by level, multiply all base pairs with one call of `base`,
+/// and interpolate the products level by level
+fn breadth_first_toom3<F>(a: &[f64], b: &[f64], levels: u32, base: &mut F) -> Vec<f64>
+where
+    F: FnMut(&[f64], &[f64], usize) -> Vec<f64>,
+{
+    let (mut xs, mut ys, mut len) = (a.to_vec(), b.to_vec(), a.len());
+    for _ in 0..levels {
+        (xs, ys) = (evaluate_all(&xs, len), evaluate_all(&ys, len));
+        len /= 3;
+    }
+    let mut products = base(&xs, &ys, len);
+    for _ in 0..levels {
+        let (n_chunk, product_len) = (len, 2 * len - 1);
+        len *= 3;
+        products = (products.chunks_exact(5 * product_len))
+            .flat_map(|group| {
+                let mut result = vec![0.0; 2 * len - 1];
+                toom3_interpolate_into(group, n_chunk, &mut result);
+                result
+            })
+            .collect();
+    }
+    products
+}
+
+/// Values at the 5 points of Toom-3 of every operand of `len` coefficients, one after another
+fn evaluate_all(operands: &[f64], len: usize) -> Vec<f64> {
+    let n_chunk = len / 3;
+    let mut points = vec![0.0; 3 * n_chunk];
+    let mut values = Vec::with_capacity(operands.len() / 3 * 5);
+    for operand in operands.chunks_exact(len) {
+        for part in toom3_evaluate_into(operand, n_chunk, &mut points) {
+            values.extend_from_slice(part);
+        }
+    }
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polynomial::naive_multiply_impl;
+    use crate::Polynomial;
+
+    /// Naive products of every operand pair, as computed by the shader
+    fn naive_batch(xs: &[f64], ys: &[f64], len: usize) -> Vec<f64> {
+        (xs.chunks_exact(len).zip(ys.chunks_exact(len)))
+            .flat_map(|(x, y)| naive_multiply_impl(x, y))
+            .collect()
+    }
+
+    /// Test the Toom-3 splits around the GPU kernel with a CPU kernel, breadth and depth first
+    #[test]
+    fn test_batched_toom3() {
+        for (n, m, base_len, batch_len) in [
+            (1000, 1000, 16, usize::MAX),
+            (1000, 1000, 16, 2000),
+            (2500, 700, 40, 5000),
+            (100, 1, 512, usize::MAX),
+        ] {
+            let p = Polynomial::random(-1.0, 1.0, n);
+            let q = Polynomial::random(-1.0, 1.0, m);
+            let mut dispatches = 0;
+            let mut base = |xs: &[f64], ys: &[f64], len: usize| {
+                dispatches += 1;
+                assert!(len <= base_len && xs.len() <= batch_len);
+                naive_batch(xs, ys, len)
+            };
+            let product = padded_toom3(&p.coeffs, &q.coeffs, base_len, batch_len, &mut base);
+            let expected = p.multiply_naive(&q);
+            assert_eq!(product.len(), n + m - 1);
+            assert!(Polynomial::new(product).approx_eq(&expected, 1e-9, 0.0));
+            assert!(batch_len < usize::MAX || dispatches == 1);
+        }
+    }
+
+    /// Test that the shader parses and validates with 64-bit floats, without needing a GPU
+    #[test]
+    fn test_shader_validates() {
+        use wgpu::naga;
+        let module = naga::front::wgsl::parse_str(SHADER).expect("shader does not parse");
+        let mut validator = naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::FLOAT64,
+        );
+        validator
+            .validate(&module)
+            .expect("shader does not validate");
+    }
+
+    /// Test a product on the GPU against the CPU, if a GPU with 64-bit floats is present
+    #[test]
+    fn test_gpu_multiply() {
+        let gpu = match GpuMultiplier::new() {
+            Ok(gpu) => gpu.min_size(100).base_len(32),
+            Err(PolyError::Unavailable(_)) => return,
+            Err(e) => panic!("{}", e),
+        };
+        let p = Polynomial::random(-1.0, 1.0, 3000);
+        let q = Polynomial::random(-1.0, 1.0, 2000);
+        assert!(p
+            .multiply_with(&q, &gpu)
+            .approx_eq(&p.multiply_naive(&q), 1e-9, 0.0));
+        assert_eq!(gpu.multiply(&[2.0], &[3.0]), vec![6.0]);
+    }
+}
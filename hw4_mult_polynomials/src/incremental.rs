@@ -0,0 +1,155 @@
+//! Products kept up to date while single coefficients of the factors change
+
+use crate::fp::mul_add;
+use crate::op_count::record;
+use crate::polynomial::multiply_impl;
+use crate::{Coefficient, Polynomial, PolynomialMultAlg};
+use alloc::vec::Vec;
+
+/// Product `c = a·b` updated in place when one coefficient of `a` or `b` changes
+///
+/// Changing `aᵢ` by `δ` changes `c` by `δ·xⁱ·b`, so [`IncrementalProduct::set_a`] costs `O(m)`
+/// for `b` with `m` coefficients instead of a full multiplication, and likewise
+/// [`IncrementalProduct::set_b`] costs `O(n)`. Setting a coefficient past the end of a factor
+/// extends it with zeros. Every update adds its own rounding error, so after many updates
+/// [`IncrementalProduct::refresh`] recomputes the product from scratch.
+/// ## Example
+/// ```
+/// use mult_polynomial::{IncrementalProduct, Polynomial};
+/// let a = Polynomial::new(vec![1.0, 2.0]);
+/// let b = Polynomial::new(vec![3.0, 4.0]);
+/// let mut product = IncrementalProduct::new(&a, &b);
+/// assert_eq!(product.product(), &[3.0, 10.0, 8.0]);
+/// product.set_a(0, 5.0); // (5 + 2x)(3 + 4x)
+/// assert_eq!(product.product(), &[15.0, 26.0, 8.0]);
+/// product.set_b(2, 1.0); // (5 + 2x)(3 + 4x + x²)
+/// assert_eq!(product.to_polynomial(), Polynomial::new(vec![15.0, 26.0, 13.0, 2.0]));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct IncrementalProduct<T = f64> {
+    a: Vec<T>,
+    b: Vec<T>,
+    product: Vec<T>,
+    updates: usize,
+}
+
+impl<T: Coefficient> IncrementalProduct<T> {
+    /// Multiply `a` and `b` with [`PolynomialMultAlg::auto_for`] once, to be updated afterwards
+    pub fn new(a: &Polynomial<T>, b: &Polynomial<T>) -> Self {
+        let (a, b) = (a.coeffs.clone(), b.coeffs.clone());
+        let mut incremental = IncrementalProduct {
+            a,
+            b,
+            product: Vec::new(),
+            updates: 0,
+        };
+        incremental.refresh();
+        incremental
+    }
+
+    /// Coefficients of the first factor, from lowest to highest degree
+    pub fn a(&self) -> &[T] {
+        &self.a
+    }
+
+    /// Coefficients of the second factor, from lowest to highest degree
+    pub fn b(&self) -> &[T] {
+        &self.b
+    }
+
+    /// Coefficients of the product, `a.len() + b.len() - 1` of them (none if a factor is empty),
+    /// without trimming
+    pub fn product(&self) -> &[T] {
+        &self.product
+    }
+
+    /// The product as a polynomial, trimmed like [`Polynomial::new`]
+    pub fn to_polynomial(&self) -> Polynomial<T> {
+        Polynomial::new(self.product.clone())
+    }
+
+    /// Number of updates since the product was last computed from scratch
+    pub fn updates(&self) -> usize {
+        self.updates
+    }
+
+    /// Set the coefficient of `xⁱ` of the first factor to `value`, updating the product in `O(m)`
+    pub fn set_a(&mut self, i: usize, value: T) {
+        let IncrementalProduct { a, b, product, .. } = self;
+        Self::update(a, b, product, i, value);
+        self.updates += 1;
+    }
+
+    /// Set the coefficient of `xʲ` of the second factor to `value`, updating the product in `O(n)`
+    pub fn set_b(&mut self, j: usize, value: T) {
+        let IncrementalProduct { a, b, product, .. } = self;
+        Self::update(b, a, product, j, value);
+        self.updates += 1;
+    }
+
+    /// Recompute the product from scratch, dropping the rounding errors of the updates
+    pub fn refresh(&mut self) {
+        let algorithm = PolynomialMultAlg::auto_for::<T>(self.a.len(), self.b.len());
+        self.product = multiply_impl(&self.a, &self.b, algorithm);
+        self.updates = 0;
+    }
+
+    /// Set `changed[i]` to `value` and add the difference times `xⁱ·other` to `product`
+    fn update(changed: &mut Vec<T>, other: &[T], product: &mut Vec<T>, i: usize, value: T) {
+        if i >= changed.len() {
+            changed.resize(i + 1, T::ZERO);
+            if !other.is_empty() {
+                product.resize(changed.len() + other.len() - 1, T::ZERO);
+            }
+        }
+        let delta = value - changed[i];
+        changed[i] = value;
+        for (c, &y) in product[i..].iter_mut().zip(other) {
+            *c = mul_add(delta, y, *c);
+        }
+        record(other.len(), other.len());
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    /// Random updates of both factors match the product recomputed from scratch
+    #[test]
+    fn test_incremental_product() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let a = Polynomial::random_with_rng(-1.0, 1.0, 300, &mut rng);
+        let b = Polynomial::random_with_rng(-1.0, 1.0, 200, &mut rng);
+        let mut incremental = IncrementalProduct::new(&a, &b);
+        for _ in 0..500 {
+            let value = rng.gen_range(-1.0..1.0);
+            if rng.gen_bool(0.5) {
+                incremental.set_a(rng.gen_range(0..300), value);
+            } else {
+                incremental.set_b(rng.gen_range(0..200), value);
+            }
+        }
+        assert_eq!(incremental.updates(), 500);
+        let expected = Polynomial::new(incremental.a().to_vec())
+            .multiply_naive(&Polynomial::new(incremental.b().to_vec()));
+        assert_eq!(incremental.product().len(), 499);
+        assert!(incremental.to_polynomial().approx_eq(&expected, 1e-10, 0.0));
+        incremental.refresh();
+        assert_eq!(incremental.updates(), 0);
+        assert!(incremental.to_polynomial().approx_eq(&expected, 1e-12, 0.0));
+
+        // extending a factor, and factors starting empty
+        incremental.set_a(310, 2.0);
+        assert_eq!(incremental.a().len(), 311);
+        assert_eq!(incremental.product().len(), 510);
+        assert_eq!(incremental.product()[509], 2.0 * incremental.b()[199]);
+        let mut empty = IncrementalProduct::new(&Polynomial::<f32>::new(vec![]), &b.cast());
+        assert!(empty.product().is_empty());
+        empty.set_a(1, 3.0);
+        assert_eq!(empty.product().len(), 201);
+        assert_eq!(empty.product()[0], 0.0);
+        assert_eq!(empty.product()[1], 3.0 * empty.b()[0]);
+    }
+}
@@ -0,0 +1,170 @@
+//! Indexing and iteration over the coefficients
+//!
+//! Reading past the degree gives zero, and polynomials built or extended from iterators
+//! are trimmed like those of [`Polynomial::new`].
+//! [`Polynomial::terms`] and [`Polynomial::from_terms`] go through the nonzero terms only.
+
+use crate::{Coefficient, Polynomial};
+use alloc::vec;
+use alloc::vec::{IntoIter, Vec};
+use core::ops::{Index, IndexMut};
+use core::slice::Iter;
+
+/// Degrees and values of the nonzero coefficients, in increasing degree
+pub(crate) fn nonzero_terms<T: Coefficient>(coeffs: &[T]) -> impl Iterator<Item = (usize, T)> + '_ {
+    (coeffs.iter().enumerate())
+        .filter(|(_, &c)| c != T::ZERO)
+        .map(|(k, &c)| (k, c))
+}
+
+impl<T: Coefficient> Polynomial<T> {
+    /// Iterator over the nonzero terms as `(degree, coefficient)`, in increasing degree
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// let p = Polynomial::new(vec![1.0, 0.0, 0.0, -2.0]);
+    /// assert_eq!(p.terms().collect::<Vec<_>>(), vec![(0, 1.0), (3, -2.0)]);
+    /// ```
+    pub fn terms(&self) -> impl Iterator<Item = (usize, T)> + '_ {
+        nonzero_terms(&self.coeffs)
+    }
+
+    /// Polynomial from `(degree, coefficient)` terms in any order, trimmed as by [`Polynomial::new`]
+    ///
+    /// Coefficients of a repeated degree add up.
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// let p = Polynomial::from_terms([(3, -2.0), (0, 1.0), (3, 1.0)]);
+    /// assert_eq!(p, Polynomial::new(vec![1.0, 0.0, 0.0, -1.0]));
+    /// assert_eq!(Polynomial::from_terms(p.terms()), p);
+    /// ```
+    pub fn from_terms<I: IntoIterator<Item = (usize, T)>>(terms: I) -> Self {
+        let mut coeffs = vec![];
+        for (k, c) in terms {
+            if k >= coeffs.len() {
+                coeffs.resize(k + 1, T::ZERO);
+            }
+            coeffs[k] += c;
+        }
+        Polynomial::new(coeffs)
+    }
+}
+
+impl Index<usize> for Polynomial {
+    type Output = f64;
+
+    /// Coefficient of `xᵏ`, zero past the degree
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// let mut p = Polynomial::new(vec![1.0, 2.0]);
+    /// assert_eq!((p[1], p[5]), (2.0, 0.0));
+    /// p[3] = 4.0;
+    /// assert_eq!(p, Polynomial::new(vec![1.0, 2.0, 0.0, 4.0]));
+    /// ```
+    fn index(&self, k: usize) -> &f64 {
+        self.coeffs.get(k).unwrap_or(&0.0)
+    }
+}
+
+impl IndexMut<usize> for Polynomial {
+    /// Mutable coefficient of `xᵏ`, extending the polynomial with zeros past the degree
+    ///
+    /// Zeroing the leading coefficient keeps it until [`Polynomial::normalize`].
+    fn index_mut(&mut self, k: usize) -> &mut f64 {
+        if k >= self.coeffs.len() {
+            self.coeffs.resize(k + 1, 0.0);
+        }
+        &mut self.coeffs[k]
+    }
+}
+
+impl IntoIterator for Polynomial {
+    type Item = f64;
+    type IntoIter = IntoIter<f64>;
+
+    /// Coefficients from lowest to highest degree
+    fn into_iter(self) -> IntoIter<f64> {
+        self.coeffs.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Polynomial {
+    type Item = &'a f64;
+    type IntoIter = Iter<'a, f64>;
+
+    /// Coefficients from lowest to highest degree
+    fn into_iter(self) -> Iter<'a, f64> {
+        self.coeffs.iter()
+    }
+}
+
+impl FromIterator<f64> for Polynomial {
+    /// Polynomial of the coefficients from lowest to highest degree, trimmed as by [`Polynomial::new`]
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// let p: Polynomial = (1..=3).map(f64::from).collect();
+    /// assert_eq!(p, Polynomial::new(vec![1.0, 2.0, 3.0]));
+    /// ```
+    fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Self {
+        Polynomial::new(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl Extend<f64> for Polynomial {
+    /// Append coefficients of higher degrees, then trim according to the normalization policy
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, iter: I) {
+        self.coeffs.extend(iter);
+        self.normalize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    /// Test indexing past the degree, iteration, collection and extension
+    #[test]
+    fn test_index_and_iter() {
+        let mut p = Polynomial::new(vec![1.0, 2.0, 3.0]);
+        assert_eq!((p[0], p[2], p[3], p[100]), (1.0, 3.0, 0.0, 0.0));
+        p[1] = -2.0;
+        p[4] = 5.0;
+        assert_eq!(p.coeffs, vec![1.0, -2.0, 3.0, 0.0, 5.0]);
+        p[4] = 0.0;
+        assert_eq!(p.degree(), 4);
+        p.normalize();
+        assert_eq!(p.degree(), 2);
+
+        assert_eq!((&p).into_iter().sum::<f64>(), 2.0);
+        let doubled: Polynomial = p.clone().into_iter().map(|c| 2.0 * c).collect();
+        assert_eq!(doubled, Polynomial::new(vec![2.0, -4.0, 6.0]));
+        let trimmed: Polynomial = [1.0, 0.0, 0.0].into_iter().collect();
+        assert_eq!(trimmed.coeffs, vec![1.0]);
+
+        p.extend([0.0, 7.0, 0.0]);
+        assert_eq!(p.coeffs, vec![1.0, -2.0, 3.0, 0.0, 7.0]);
+        let mut zero = Polynomial::new(vec![]);
+        zero.extend([0.0, 0.0]);
+        assert!(zero.coeffs.is_empty());
+    }
+
+    /// Test the nonzero terms and the round trip through them, including the zero polynomial
+    #[test]
+    fn test_terms() {
+        let p = Polynomial::new(vec![0.0, 2.5, 0.0, 0.0, -1.0, 3.0]);
+        let terms: Vec<_> = p.terms().collect();
+        assert_eq!(terms, vec![(1, 2.5), (4, -1.0), (5, 3.0)]);
+        assert_eq!(Polynomial::from_terms(terms.into_iter().rev()), p);
+        assert_eq!(Polynomial::<f64>::new(vec![]).terms().count(), 0);
+        assert!(Polynomial::<f64>::from_terms([]).coeffs.is_empty());
+        // cancelling terms are trimmed
+        let cancelled = Polynomial::from_terms([(0, 1.0), (7, 2.0), (7, -2.0)]);
+        assert_eq!(cancelled.coeffs, vec![1.0]);
+        let single: Polynomial<f32> = Polynomial::from_terms([(2, 1.5f32)]);
+        assert_eq!(single.terms().collect::<Vec<_>>(), vec![(2, 1.5f32)]);
+    }
+}
@@ -0,0 +1,216 @@
+//! Kronecker substitution: integer polynomials multiplied as one big integer each
+//!
+//! Evaluating a polynomial at `x = 2ʷ` packs its coefficients into the `w`-bit slots of a big
+//! integer. If every coefficient of the product fits in a slot, the product of the two integers
+//! holds the product polynomial, read back slot by slot. Negative coefficients borrow from the
+//! next slot, so slots are read as balanced digits in `(-2ʷ⁻¹, 2ʷ⁻¹]`, carrying into the next one.
+//! The big integers are multiplied through their 16-bit digits by [`multiply_exact`].
+
+use crate::modular::{multiply_exact, product_bound};
+use crate::PolyError;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Bits of the digits in which the packed integers are multiplied, so that the digit products
+/// stay below `2⁶⁰` and [`multiply_exact`] needs a single prime
+const DIGIT_BITS: usize = 16;
+
+/// Sum of `|c|·2^(w·i)` over the coefficients `c` of one sign, as little-endian `u64` limbs
+///
+/// The slots do not overlap as long as every `|c|` is below `2ʷ`.
+fn pack(coeffs: &[i64], w: usize, negative: bool) -> Vec<u64> {
+    let mut limbs = vec![0; (coeffs.len() * w).div_ceil(64) + 1];
+    for (i, &c) in coeffs.iter().enumerate() {
+        if c == 0 || (c < 0) != negative {
+            continue;
+        }
+        let (limb, shift) = (i * w / 64, i * w % 64);
+        limbs[limb] |= c.unsigned_abs() << shift;
+        if shift > 0 {
+            limbs[limb + 1] |= c.unsigned_abs() >> (64 - shift);
+        }
+    }
+    limbs
+}
+
+/// Difference `x - y` of big integers of the same number of limbs, for `x ≥ y`
+fn subtract(x: &[u64], y: &[u64]) -> Vec<u64> {
+    let mut borrow = false;
+    (x.iter().zip(y))
+        .map(|(&u, &v)| {
+            let (d, b1) = u.overflowing_sub(v);
+            let (d, b2) = d.overflowing_sub(borrow as u64);
+            borrow = b1 || b2;
+            d
+        })
+        .collect()
+}
+
+/// Value of the polynomial at `2ʷ` as a sign (negative or not) and a magnitude
+fn pack_signed(coeffs: &[i64], w: usize) -> (bool, Vec<u64>) {
+    let (positive, negative) = (pack(coeffs, w, false), pack(coeffs, w, true));
+    if positive.iter().rev().ge(negative.iter().rev()) {
+        (false, subtract(&positive, &negative))
+    } else {
+        (true, subtract(&negative, &positive))
+    }
+}
+
+/// Product of big integers, multiplying their 16-bit digits as polynomials and propagating carries
+fn multiply_big(x: &[u64], y: &[u64]) -> Vec<u64> {
+    let per_limb = 64 / DIGIT_BITS;
+    let mask = (1 << DIGIT_BITS) - 1;
+    let digits = |limbs: &[u64]| -> Vec<i64> {
+        (limbs.iter())
+            .flat_map(|&l| (0..per_limb).map(move |k| ((l >> (DIGIT_BITS * k)) & mask) as i64))
+            .collect()
+    };
+    let product = multiply_exact(&digits(x), &digits(y))
+        .expect("products of 16-bit digits are far below the bound");
+    let mut limbs = vec![0; x.len() + y.len()];
+    let mut carry = 0u128;
+    for (k, &c) in product.iter().enumerate() {
+        let t = carry + c as u128;
+        limbs[k / per_limb] |= ((t as u64) & mask) << (DIGIT_BITS * (k % per_limb));
+        carry = t >> DIGIT_BITS;
+    }
+    // the product is below 2^(64·limbs), so the last carry fills the last digit
+    let k = product.len();
+    limbs[k / per_limb] |= (carry as u64) << (DIGIT_BITS * (k % per_limb));
+    limbs
+}
+
+/// Slot `i` of `w ≤ 128` bits of a big integer
+fn slot(limbs: &[u64], i: usize, w: usize) -> u128 {
+    let (limb, shift) = (i * w / 64, (i * w % 64) as u32);
+    let word = |k: usize| limbs.get(k).copied().unwrap_or(0) as u128;
+    let mut value = (word(limb) | word(limb + 1) << 64) >> shift;
+    if shift > 0 {
+        value |= word(limb + 2) << (128 - shift);
+    }
+    if w == 128 {
+        value
+    } else {
+        value & ((1 << w) - 1)
+    }
+}
+
+/// Exact product of integer polynomials by Kronecker substitution
+///
+/// Both operands are evaluated at `2ʷ`, with `w` one bit more than the coefficient bound
+/// `min(n, m)·max|a|·max|b|` of the product, the two big integers are multiplied once,
+/// and the coefficients are read back from the `w`-bit slots of the result.
+/// Coefficients are from lowest to highest degree. Small coefficients give short integers,
+/// which is where the packing pays off against [`multiply_exact`] on the coefficients directly.
+/// Fails with [`PolyError::InvalidArgument`] if the bound reaches `2¹²⁷`, as for [`multiply_exact`].
+/// ## Example
+/// ```
+/// use mult_polynomial::multiply_kronecker;
+/// // (3 - 2x)·(1 + x) = 3 + x - 2x²
+/// assert_eq!(multiply_kronecker(&[3, -2], &[1, 1]).unwrap(), vec![3, 1, -2]);
+/// ```
+pub fn multiply_kronecker(a: &[i64], b: &[i64]) -> Result<Vec<i128>, PolyError> {
+    if a.is_empty() || b.is_empty() {
+        return Ok(vec![]);
+    }
+    let bound = product_bound(a, b)?;
+    let result_len = a.len() + b.len() - 1;
+    if bound == 0 {
+        return Ok(vec![0; result_len]);
+    }
+    // every |c| ≤ bound < 2ʷ⁻¹, and so is every operand coefficient as the other one is nonzero
+    let w = (u128::BITS - bound.leading_zeros() + 1) as usize;
+    let (a_negative, a_packed) = pack_signed(a, w);
+    let (b_negative, b_packed) = pack_signed(b, w);
+    let product = multiply_big(&a_packed, &b_packed);
+
+    let half = 1u128 << (w - 1);
+    let mut carry = 0;
+    Ok((0..result_len)
+        .map(|i| {
+            let (t, overflow) = slot(&product, i, w).overflowing_add(carry);
+            let borrowed = overflow || t >= half;
+            carry = borrowed as u128;
+            // t - 2ʷ, wrapping to t for w = 128
+            let digit = if borrowed {
+                t.wrapping_sub(half << 1)
+            } else {
+                t
+            } as i128;
+            if a_negative != b_negative {
+                -digit
+            } else {
+                digit
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "std")]
+    use crate::modular::schoolbook_exact;
+    #[cfg(feature = "std")]
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    /// Test packing and the big integer product against `u128` arithmetic
+    #[test]
+    fn test_big_integers() {
+        // 5 - 2⁸ = -251
+        let (negative, packed) = pack_signed(&[5, -1], 8);
+        assert_eq!((negative, packed[0]), (true, 251));
+        let (negative, packed) = pack_signed(&[-5, 3], 8);
+        assert_eq!((negative, packed[0]), (false, 3 * 256 - 5));
+        let (negative, packed) = pack_signed(&[5, -3], 8);
+        assert_eq!((negative, packed[0]), (true, 3 * 256 - 5));
+
+        let x = [0xdead_beef_1234_5678u64, 0x0fed];
+        let y = [0x0002_2222_3333_4444u64];
+        let product = multiply_big(&x, &y);
+        let full = (x[0] as u128 | (x[1] as u128) << 64) * y[0] as u128; // x < 2⁷⁶, y < 2⁵⁰
+        assert_eq!(product[0] as u128 | (product[1] as u128) << 64, full);
+        assert_eq!(product[2], 0);
+        assert_eq!(slot(&product, 1, 48), (full >> 48) & ((1 << 48) - 1));
+    }
+
+    /// Test against the schoolbook product from tiny to nearly `2¹²⁷` coefficients
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_multiply_kronecker() {
+        let mut rng = StdRng::seed_from_u64(11);
+        for (magnitude, n, m) in [
+            (1i64, 50, 50),
+            (1 << 10, 100, 37),
+            (1 << 30, 64, 300),
+            (1 << 55, 20, 45),
+            (1 << 61, 31, 90),
+        ] {
+            let mut operand = |len: usize| -> Vec<i64> {
+                (0..len)
+                    .map(|_| rng.gen_range(-magnitude..=magnitude))
+                    .collect()
+            };
+            let (a, b) = (operand(n), operand(m));
+            let product = multiply_kronecker(&a, &b).unwrap();
+            assert_eq!(product, schoolbook_exact(&a, &b), "magnitude {}", magnitude);
+        }
+        // all of one sign, and coefficients close to -2¹²⁷ with w = 128
+        let a = vec![1 << 61; 31];
+        let b = vec![-(1 << 61); 40];
+        assert_eq!(
+            multiply_kronecker(&a, &b).unwrap(),
+            schoolbook_exact(&a, &b)
+        );
+        assert_eq!(
+            multiply_kronecker(&a, &a).unwrap(),
+            schoolbook_exact(&a, &a)
+        );
+        assert_eq!(multiply_kronecker(&[0, 0], &[7]).unwrap(), vec![0, 0]);
+        assert!(multiply_kronecker(&[], &[7]).unwrap().is_empty());
+        assert!(matches!(
+            multiply_kronecker(&[i64::MIN; 2], &[i64::MIN; 64]),
+            Err(PolyError::InvalidArgument(_))
+        ));
+    }
+}
@@ -0,0 +1,168 @@
+//! Laurent polynomials, which also have negative powers of x (e.g. `z⁻¹` delays of a filter)
+
+use crate::polynomial::multiply_impl;
+use crate::resultant::powu;
+use crate::{Normalization, Polynomial, PolynomialMultAlg};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Represents a Laurent polynomial `Σ cₖ·x^(min_exponent + k)`
+///
+/// Only the coefficients from the lowest to the highest exponent are stored,
+/// so products go through the dense multipliers and only add up the minimum exponents.
+/// ## Example
+/// ```
+/// use mult_polynomial::{LaurentPolynomial, PolynomialMultAlg};
+/// // (x⁻¹ + 1)(1 - x) = x⁻¹ - x
+/// let p = LaurentPolynomial::new(vec![1.0, 1.0], -1);
+/// let q = LaurentPolynomial::new(vec![1.0, -1.0], 0);
+/// let product = p.multiply(&q, PolynomialMultAlg::Naive);
+/// assert_eq!(product, LaurentPolynomial::new(vec![1.0, 0.0, -1.0], -1));
+/// assert_eq!(product.evaluate(2.0), -1.5);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct LaurentPolynomial {
+    /// Coefficients from the lowest to the highest exponent
+    pub coeffs: Vec<f64>,
+    /// Exponent of the first coefficient (0 for the zero polynomial)
+    pub min_exponent: i64,
+}
+
+impl LaurentPolynomial {
+    /// Create a new Laurent polynomial whose first coefficient belongs to `x^min_exponent`
+    ///
+    /// Coefficients at either end are trimmed the same way as in [`Polynomial::new`],
+    /// raising the minimum exponent for each one dropped at the low end.
+    pub fn new(coeffs: Vec<f64>, min_exponent: i64) -> Self {
+        let mut coeffs = Normalization::DEFAULT.apply(coeffs);
+        let leading = coeffs.iter().take_while(|c| c.abs() < 1e-12).count();
+        coeffs.drain(..leading);
+        let min_exponent = if coeffs.is_empty() {
+            0
+        } else {
+            min_exponent + leading as i64
+        };
+        LaurentPolynomial {
+            coeffs,
+            min_exponent,
+        }
+    }
+
+    /// Highest exponent with a nonzero coefficient, `None` for the zero polynomial
+    pub fn max_exponent(&self) -> Option<i64> {
+        (!self.coeffs.is_empty()).then(|| self.min_exponent + self.coeffs.len() as i64 - 1)
+    }
+
+    /// Whether this is the zero polynomial
+    pub fn is_zero(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    /// Convert to an ordinary polynomial, `None` if there is a negative exponent
+    pub fn to_polynomial(&self) -> Option<Polynomial> {
+        let shift = usize::try_from(self.min_exponent).ok()?;
+        let mut coeffs = vec![0.0; shift];
+        coeffs.extend_from_slice(&self.coeffs);
+        Some(Polynomial::new(coeffs))
+    }
+
+    /// Multiply by `xᵏ`, which only moves the exponents
+    pub fn mul_xk(&self, k: i64) -> LaurentPolynomial {
+        LaurentPolynomial::new(self.coeffs.clone(), self.min_exponent + k)
+    }
+
+    /// Evaluate the polynomial at a given point x, which must not be 0 if there are negative exponents
+    pub fn evaluate(&self, x: f64) -> f64 {
+        let value = self.coeffs.iter().rev().fold(0.0, |acc, &c| acc * x + c);
+        let e = self.min_exponent;
+        if e >= 0 {
+            value * powu(x, e as usize)
+        } else {
+            value / powu(x, e.unsigned_abs() as usize)
+        }
+    }
+
+    /// Multiply with another Laurent polynomial using the given algorithm
+    pub fn multiply(
+        &self,
+        other: &LaurentPolynomial,
+        algorithm: PolynomialMultAlg,
+    ) -> LaurentPolynomial {
+        if self.is_zero() || other.is_zero() {
+            return LaurentPolynomial::new(vec![], 0);
+        }
+        LaurentPolynomial::new(
+            multiply_impl(&self.coeffs, &other.coeffs, algorithm),
+            self.min_exponent + other.min_exponent,
+        )
+    }
+
+    /// Multiply with another Laurent polynomial using the algorithm chosen by [`PolynomialMultAlg::auto`]
+    pub fn multiply_auto(&self, other: &LaurentPolynomial) -> LaurentPolynomial {
+        let algorithm = PolynomialMultAlg::auto(self.coeffs.len(), other.coeffs.len());
+        self.multiply(other, algorithm)
+    }
+}
+
+impl From<Polynomial> for LaurentPolynomial {
+    fn from(p: Polynomial) -> Self {
+        LaurentPolynomial::new(p.coeffs, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test the trimming at both ends and the conversions from and to ordinary polynomials
+    #[test]
+    fn test_new_and_conversion() {
+        let p = LaurentPolynomial::new(vec![0.0, 1e-15, 2.0, 0.0, 3.0, 0.0], -3);
+        assert_eq!(p.coeffs, vec![2.0, 0.0, 3.0]);
+        assert_eq!((p.min_exponent, p.max_exponent()), (-1, Some(1)));
+        assert_eq!(p.to_polynomial(), None);
+        assert_eq!(
+            p.mul_xk(2).to_polynomial(),
+            Some(Polynomial::new(vec![0.0, 2.0, 0.0, 3.0]))
+        );
+        let q = Polynomial::new(vec![0.0, 0.0, 1.0, 4.0]);
+        let laurent = LaurentPolynomial::from(q.clone());
+        assert_eq!((laurent.min_exponent, laurent.max_exponent()), (2, Some(3)));
+        assert_eq!(laurent.to_polynomial(), Some(q));
+        let zero = LaurentPolynomial::new(vec![0.0, 0.0], -5);
+        assert!(zero.is_zero());
+        assert_eq!((zero.min_exponent, zero.max_exponent()), (0, None));
+        assert_eq!(zero.evaluate(0.5), 0.0);
+    }
+
+    /// Test multiplication against shifting both operands into ordinary polynomials
+    #[cfg(feature = "thread_rng")]
+    #[test]
+    fn test_multiply() {
+        let p = LaurentPolynomial::new(Polynomial::random(-1.0, 1.0, 200).coeffs, -150);
+        let q = LaurentPolynomial::new(Polynomial::random(-1.0, 1.0, 90).coeffs, 7);
+        let product = p.multiply_auto(&q);
+        assert_eq!(product.min_exponent, -143);
+        assert_eq!(product.max_exponent(), Some(-143 + 288));
+        // x¹⁵⁰·p times q, then divided by x¹⁵⁰
+        let shifted = p.mul_xk(150).to_polynomial().unwrap();
+        let expected = shifted.multiply_naive(&q.to_polynomial().unwrap());
+        let back = product.mul_xk(150).to_polynomial().unwrap();
+        assert!(back.approx_eq(&expected, 1e-9, 1e-12));
+        for algorithm in [PolynomialMultAlg::Naive, PolynomialMultAlg::Thresholded(16)] {
+            let coeffs = p.multiply(&q, algorithm).coeffs;
+            assert!(Polynomial::new(coeffs).approx_eq(
+                &Polynomial::new(product.coeffs.clone()),
+                1e-9,
+                1e-12
+            ));
+        }
+        // the value of the product is the product of the values
+        for x in [-1.3, -0.8, 0.9, 1.1] {
+            let value = p.evaluate(x) * q.evaluate(x);
+            assert!((product.evaluate(x) - value).abs() <= 1e-9 * value.abs().max(1.0));
+        }
+        let zero = LaurentPolynomial::new(vec![], 3);
+        assert!(p.multiply_auto(&zero).is_zero());
+    }
+}
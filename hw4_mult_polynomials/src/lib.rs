@@ -1,18 +1,178 @@
 //! # Polynomial Multiplication Algorithms
 //!
 //! ## Implementation
-//! Three polynomial multiplication algorithms are implemented:
-//! 1. Naive algorithm
+//! Four polynomial multiplication algorithms are implemented:
+//! 1. Naive algorithm (optionally with compensated summation for accuracy)
 //! 2. Cook-Tooms algorithm
 //! 3. Thresholded Cook-Tooms algorithm
+//! 4. Hybrid algorithm: Toom-3 for long operands, Karatsuba for medium ones and naive for short ones
 //!
 //! Internally, the Cook-Tooms algorithm is implemented as a special case of the thresholded version.
 //! The threshold is set as 5 to keep the algorithm working in a simple way.
 //! As such, any threshold equal or less than 5 will be the same as the Cook-Tooms algorithm.
 //!
+//! Coefficients are `f64` by default; `Polynomial<f32>` runs the same kernels in single precision,
+//! with the precision-dependent settings taken from the [`Coefficient`] trait.
+//!
+//! Other algorithms plug in by implementing [`MultiplyAlgorithm`], which the built-in ones implement too;
+//! they run through [`Polynomial::multiply_with`] and can be registered with the benchmark.
+//!
+//! ## Floating-point determinism
+//! The kernels use a fixed evaluation order and never reassociate sums,
+//! so results only depend on whether fused multiply-add is used.
+//! By default products and sums are rounded separately, making every result bit-identical across platforms.
+//! Enable the `fast_fma` feature to use FMA on targets that have it in hardware
+//! (e.g. `aarch64`, or x86-64 with `-C target-feature=+fma`); `strict_fp` turns it off again.
+//!
+//! ## `no_std` support
+//! The polynomial arithmetic only needs `alloc`. Disable the default `std` feature to build
+//! it under `no_std`; threads, timing, file I/O and random generation require `std`.
+//!
+//! ## Random number generators
+//! Everything random in the crate takes the generator from the caller (e.g.
+//! [`Polynomial::random_with_rng`], [`RandomPolynomial::generate_with_rng`],
+//! [`verify_product_with_rng`]), so a seeded `StdRng` or a small deterministic generator gives
+//! reproducible results. The default `thread_rng` feature adds the conveniences drawing from
+//! `rand::thread_rng` instead: [`Polynomial::random`], [`RandomPolynomial::generate`] and
+//! [`verify_product`].
+//!
 //! ## Author
 //! Written by [Wuqiong Zhao](https://wqzhao.org).
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+mod algorithm;
+#[cfg(feature = "async")]
+mod async_api;
+#[cfg(feature = "baselines")]
+mod baseline;
+#[cfg(feature = "std")]
+mod batch;
+#[cfg(feature = "std")]
+pub mod benchmark;
+mod chebyshev;
+mod checked;
+#[cfg(feature = "std")]
+mod checkpoint;
+mod coefficient;
+mod convolution;
+#[cfg(feature = "std")]
+mod dft;
+mod division;
+mod error;
+mod error_bound;
+mod factor;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod fingerprint;
+mod fixed;
+mod fp;
+pub mod gcd;
+mod generic_eval;
+#[cfg(feature = "gpu")]
+mod gpu;
+mod incremental;
+mod iter;
+mod kronecker;
+mod laurent;
+#[cfg(feature = "mem_profile")]
+pub mod memory;
+#[cfg(feature = "std")]
+mod metadata;
+mod middle;
+mod modular;
+#[cfg(feature = "std")]
+mod multiplier;
+mod multipoint;
+#[cfg(feature = "npy")]
+mod npy;
+mod op_count;
+#[cfg(feature = "plot")]
+pub mod plot;
 mod polynomial;
-pub use polynomial::thresholded_multiply_impl;
-pub use polynomial::{Polynomial, PolynomialMultAlg};
+#[cfg(feature = "python")]
+mod python;
+#[cfg(feature = "std")]
+mod random;
+mod rational;
+mod resultant;
+mod ring;
+#[cfg(feature = "std")]
+pub mod serialization;
+mod series;
+mod shift;
+#[cfg(feature = "smallvec")]
+mod small;
+mod sparse;
+#[cfg(feature = "sqlite")]
+mod store;
+#[cfg(feature = "std")]
+mod streaming;
+pub mod testing;
+mod trace;
+mod tree;
+#[cfg(feature = "std")]
+pub mod tune;
+mod verify;
+mod view;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub use algorithm::MultiplyAlgorithm;
+#[cfg(feature = "async")]
+pub use async_api::{
+    multiply_async, multiply_batch_with_deadlines_async, multiply_thresholded_with_progress_async,
+};
+#[cfg(feature = "baselines")]
+pub use baseline::{baselines, PolynomialCrate, RustFft};
+#[cfg(feature = "std")]
+pub use batch::{
+    multiply_batch, multiply_batch_parallel, multiply_batch_with_deadlines, MultiplyTask,
+};
+pub use chebyshev::ChebyshevPolynomial;
+pub use coefficient::Coefficient;
+pub use convolution::{autocorrelate, conv, correlate, ConvolveMode};
+pub use error::{Operand, PolyError};
+pub use error_bound::BoundedProduct;
+#[cfg(feature = "f256")]
+pub use f256::f256;
+pub use factor::factor_exact;
+#[cfg(feature = "std")]
+pub use fingerprint::ProductCache;
+pub use fixed::FixedPolynomial;
+#[cfg(feature = "gpu")]
+pub use gpu::GpuMultiplier;
+pub use incremental::IncrementalProduct;
+pub use kronecker::multiply_kronecker;
+pub use laurent::LaurentPolynomial;
+pub use modular::multiply_exact;
+#[cfg(feature = "std")]
+pub use multiplier::{Multiplier, MultiplierBuilder};
+#[cfg(feature = "std")]
+pub use num_complex::Complex64;
+#[cfg(feature = "op_count")]
+pub use op_count::count_ops;
+pub use op_count::OpCounts;
+pub use polynomial::{
+    hybrid_multiply_impl, thresholded_multiply_impl, thresholded_multiply_iterative,
+    thresholded_multiply_with_progress, thresholded_multiply_with_stats,
+    thresholded_multiply_with_tree, thresholded_square_impl,
+};
+pub use polynomial::{HybridConfig, MultiplyStats, Normalization, Polynomial, PolynomialMultAlg};
+#[cfg(feature = "std")]
+pub use random::{CoefficientDistribution, RandomPolynomial};
+pub use rational::RationalFunction;
+pub use ring::PolynomialRing;
+#[cfg(feature = "smallvec")]
+pub use small::{SmallCoeffs, SmallPolynomial, SMALL_INLINE};
+#[cfg(feature = "sqlite")]
+pub use store::ResultStore;
+#[cfg(feature = "std")]
+pub use streaming::multiply_streaming;
+pub use tree::{NodeAlgorithm, RecursionNode, RecursionTree};
+pub use verify::verify_exact_product;
+#[cfg(feature = "thread_rng")]
+pub use verify::verify_product;
+pub use verify::verify_product_with_rng;
+pub use view::PolynomialView;
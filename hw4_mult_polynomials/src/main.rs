@@ -1,14 +1,680 @@
 //! Benchmarking code for polynomial multiplication algorithms
 //! Run with `cargo run --release` to get accurate results
+//!
+//! The benchmark settings can be changed on the command line, e.g.
+//! `cargo run --release -- --sizes 256,1024 --algorithms naive,thresholded --thresholds 32,64 --seed 1`,
+//! or read from a TOML file with `--config bench.toml`, which the other options override
+//!
+//! The `multiply` subcommand multiplies two polynomials stored in files (binary, `.f64`, `.csv` or `.npy`):
+//! `cargo run --release -- multiply a.csv b.csv -o c.csv --alg toom3 --threshold 64`
+//!
+//! The `tune` subcommand searches for the fastest threshold of the thresholded algorithm:
+//! `cargo run --release -- tune --sizes 1024,4096,16384`
+//!
+//! The `tree` subcommand prints the recursion tree of the thresholded algorithm for teaching:
+//! `cargo run --release -- tree --size 300 --threshold 16 | dot -Tsvg > tree.svg`
+//!
+//! The `verify` subcommand cross-checks every pair of algorithms on all sizes up to a bound:
+//! `cargo run --release -- verify --max-size 200`
 
-use mult_polynomial::{Polynomial, PolynomialMultAlg};
+use clap::{Args, Parser, Subcommand};
+use mult_polynomial::benchmark::{
+    fit_complexity, run_comprehensive_benchmark, run_evaluation_benchmark, run_exact_benchmark,
+    write_complexity, Aggregation, BenchmarkConfig, InputDistribution, OutputFormat, RunMetadata,
+    RunsPerSize,
+};
+use mult_polynomial::testing::{cross_check, ALGORITHMS};
+use mult_polynomial::tune::{tune_threshold, Search, TuneConfig};
+use mult_polynomial::{thresholded_multiply_with_tree, PolyError, Polynomial, PolynomialMultAlg};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 
+#[cfg(feature = "mem_profile")]
+#[global_allocator]
+static ALLOCATOR: mult_polynomial::memory::CountingAllocator =
+    mult_polynomial::memory::CountingAllocator;
+
+/// Polynomial multiplication benchmarks and tools
+#[derive(Parser)]
+#[command(version, about, args_conflicts_with_subcommands = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[command(flatten)]
+    bench: Box<BenchArgs>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the comprehensive benchmark (default)
+    Bench(Box<BenchArgs>),
+    /// Multiply two polynomials stored in files, e.g. `multiply a.csv b.csv -o c.csv`:
+    /// `.csv` and `.txt` for text, `.f64` for the compact binary format,
+    /// `.npy` for NumPy arrays (with the `npy` feature), the versioned binary format otherwise
+    Multiply {
+        /// The two input files
+        #[arg(num_args = 2, required = true, value_names = ["A", "B"])]
+        inputs: Vec<PathBuf>,
+        /// Output file for the product
+        #[arg(short, long)]
+        out: PathBuf,
+        /// Algorithm: auto, naive, naive-compensated, cook-tooms, thresholded (or toom3) or hybrid,
+        /// optionally with thresholds such as thresholded:32 or hybrid:256:64
+        #[arg(long, default_value = "auto")]
+        alg: String,
+        /// Threshold for the thresholded algorithm, or the Toom-3 threshold of the hybrid one
+        #[arg(long, default_value_t = PolynomialMultAlg::AUTO_THRESHOLD)]
+        threshold: usize,
+    },
+    /// Search for the fastest threshold of the thresholded algorithm over a set of sizes,
+    /// printing a comparison table and writing it to tune.csv (with tune_metadata.json)
+    Tune(TuneArgs),
+    /// Print the recursion tree of the thresholded algorithm as Graphviz DOT or JSON,
+    /// e.g. `tree --size 300 --threshold 16 | dot -Tsvg > tree.svg`
+    Tree {
+        /// Number of coefficients of the first operand
+        #[arg(long, value_parser = parse_positive)]
+        size: usize,
+        /// Number of coefficients of the second operand [default: the same as the first]
+        #[arg(long, value_parser = parse_positive)]
+        other_size: Option<usize>,
+        /// Threshold of the thresholded algorithm
+        #[arg(long, default_value_t = PolynomialMultAlg::AUTO_THRESHOLD)]
+        threshold: usize,
+        /// Output format: dot or json
+        #[arg(long, default_value = "dot", value_parser = ["dot", "json"])]
+        format: String,
+        /// Output file [default: standard output]
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Cross-check every pair of algorithms on all sizes from 0 up to a bound, with random,
+    /// all-zero, huge, tiny and mixed-magnitude coefficients, printing a pass/fail matrix
+    Verify {
+        /// Largest number of coefficients of the operands
+        #[arg(long, default_value_t = 200)]
+        max_size: usize,
+        /// Largest discrepancy accepted, relative to the largest coefficient of |a|·|b|
+        #[arg(long, default_value_t = 1e-10)]
+        tolerance: f64,
+        /// Seed of the random coefficients
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+}
+
+/// Settings of the threshold search
+#[derive(Args)]
+struct TuneArgs {
+    /// Comma-separated input sizes the threshold is tuned for
+    #[arg(long, value_delimiter = ',', value_parser = parse_positive, default_value = "1024,4096,16384")]
+    sizes: Vec<usize>,
+    /// Smallest threshold considered
+    #[arg(long, default_value_t = 2, value_parser = parse_positive)]
+    min_threshold: usize,
+    /// Largest threshold considered
+    #[arg(long, default_value_t = 1 << 15, value_parser = parse_positive)]
+    max_threshold: usize,
+    /// Search strategy: golden (golden-section search on the logarithm of the threshold)
+    /// or grid (geometrically spaced thresholds)
+    #[arg(long, default_value = "golden", value_parser = ["golden", "grid"])]
+    search: String,
+    /// Number of thresholds of the grid search
+    #[arg(long, default_value_t = 16, value_parser = parse_positive)]
+    grid_points: usize,
+    /// Timed runs of every threshold at every size, the fastest one counting
+    #[arg(long, default_value_t = 5, value_parser = parse_positive)]
+    runs: usize,
+    /// Untimed warm-up multiplications before the runs of every threshold and size
+    #[arg(long, default_value_t = 1)]
+    warmup: usize,
+    /// Seed for the random inputs, for reproducible searches [default: drawn from the operating system]
+    #[arg(long)]
+    seed: Option<u64>,
+    /// Directory for tune.csv
+    #[arg(long, default_value = "output_data")]
+    output_dir: PathBuf,
+}
+
+/// Settings of the comprehensive benchmark
+#[derive(Args)]
+struct BenchArgs {
+    /// TOML file with the benchmark settings, see `BenchmarkConfig::from_toml`;
+    /// the options below override it
+    #[arg(long)]
+    config: Option<PathBuf>,
+    /// Comma-separated input sizes [default: powers of two up to 2^17 and midpoints]
+    #[arg(long, value_delimiter = ',', value_parser = parse_positive)]
+    sizes: Vec<usize>,
+    /// Comma-separated ratios n / m of the operand lengths, pairing each size n
+    /// with a second operand of n / ratio coefficients [default: 1]
+    #[arg(long, value_delimiter = ',', value_parser = parse_positive)]
+    aspect_ratios: Vec<usize>,
+    /// Comma-separated coefficient distributions of the operands, each timed at every size:
+    /// uniform, gaussian, sparse:<density>, huge-range (1e-100 to 1e100) and integer [default: uniform]
+    #[arg(long, value_delimiter = ',', value_parser = parse_distribution)]
+    distributions: Vec<InputDistribution>,
+    /// Comma-separated thresholds for the thresholded algorithm [default: 2,4,...,32768]
+    #[arg(long, value_delimiter = ',')]
+    thresholds: Vec<usize>,
+    /// Comma-separated algorithms: naive, naive-compensated, cook-tooms, thresholded and hybrid
+    /// [default: naive,cook-tooms,thresholded]
+    #[arg(long, value_delimiter = ',', value_parser = PolynomialMultAlg::NAMES)]
+    algorithms: Vec<String>,
+    /// Runs per size [default: max(10, 100000 / size)]
+    #[arg(long, value_parser = parse_positive)]
+    runs: Option<usize>,
+    /// Directory for the CSV results [default: output_data]
+    #[arg(long)]
+    output_dir: Option<PathBuf>,
+    /// Results format: csv (mean per size and algorithm) or json (every run) [default: csv]
+    #[arg(long, value_parser = ["csv", "json"])]
+    format: Option<String>,
+    /// Seed for the random inputs, for reproducible benchmarks
+    #[arg(long)]
+    seed: Option<u64>,
+    /// Check every algorithm against the naive product of each input pair (untimed)
+    /// and record the largest relative discrepancy
+    #[arg(long)]
+    verify: bool,
+    /// Also measure the error of every algorithm against the exact product of integer inputs
+    /// at each size and write it to accuracy.csv
+    #[arg(long)]
+    accuracy: bool,
+    /// Comma-separated numbers of points at which Horner, Estrin, multipoint and blocked evaluation
+    /// are also timed for every size, written to evaluation.csv
+    #[arg(long, value_delimiter = ',', value_parser = parse_positive)]
+    evaluation_points: Vec<usize>,
+    /// Comma-separated coefficient sizes in bits at which exact integer multiplication
+    /// (schoolbook, multi-modular CRT and Kronecker substitution) is also timed for every size,
+    /// written to exact.csv
+    #[arg(long, value_delimiter = ',')]
+    exact_bits: Vec<u32>,
+    /// Untimed warm-up multiplications of every algorithm before the runs of each size [default: 1]
+    #[arg(long)]
+    warmup: Option<usize>,
+    /// How the runs are combined into the time column: mean, trimmed-mean or min [default: mean]
+    #[arg(long, value_parser = ["mean", "trimmed-mean", "min"])]
+    aggregate: Option<String>,
+    /// Fraction of the fastest and of the slowest runs dropped by the trimmed mean [default: 0.1]
+    #[arg(long, value_parser = parse_trim)]
+    trim: Option<f64>,
+    /// Also record the peak heap usage of each algorithm
+    #[cfg(feature = "mem_profile")]
+    #[arg(long)]
+    memory: bool,
+    /// Also record the floating-point operations of each algorithm
+    #[cfg(feature = "op_count")]
+    #[arg(long)]
+    ops: bool,
+    /// Also write the time of every run to all_runs.csv (size, algorithm, run, seconds)
+    #[arg(long)]
+    raw_runs: bool,
+    /// Time budget in seconds of every algorithm at each size: its runs stop once they add up
+    /// to it, and it is skipped ("skipped" in the results) where a single run would exceed it
+    #[arg(long, value_parser = parse_seconds)]
+    max_time_per_cell: Option<Duration>,
+    /// Continue an interrupted run from checkpoint.csv in the output directory,
+    /// timing only the cells it has not finished
+    #[arg(long)]
+    resume: bool,
+    /// Threads measuring different sizes at the same time (0 for one per core) [default: 1]
+    #[arg(long)]
+    threads: Option<usize>,
+    /// Pin each benchmark thread to its own core
+    #[arg(long)]
+    pin_threads: bool,
+    /// Smallest size included in the fitted complexity exponents
+    #[arg(long, default_value_t = 64)]
+    fit_min_size: usize,
+    /// Also time the third-party baselines (rustfft and the polynomial crate)
+    #[cfg(feature = "baselines")]
+    #[arg(long)]
+    baselines: bool,
+    /// SQLite database to append every run to, with the machine and the seed
+    #[cfg(feature = "sqlite")]
+    #[arg(long)]
+    database: Option<PathBuf>,
+    /// Machine name recorded in the database [default: the host name]
+    #[cfg(feature = "sqlite")]
+    #[arg(long)]
+    machine: Option<String>,
+    /// Also time the GPU backend (fails if there is no GPU with 64-bit floats)
+    #[cfg(feature = "gpu")]
+    #[arg(long)]
+    gpu: bool,
+}
+
 /// Run a comprehensive benchmark for polynomial multiplication algorithms
 fn main() {
+    let cli = Cli::parse();
+    let result = match cli.command.unwrap_or(Command::Bench(cli.bench)) {
+        Command::Bench(args) => run_bench(*args),
+        Command::Multiply {
+            inputs,
+            out,
+            alg,
+            threshold,
+        } => run_multiply(&inputs[0], &inputs[1], &out, &alg, threshold),
+        Command::Tune(args) => run_tune(args),
+        Command::Tree {
+            size,
+            other_size,
+            threshold,
+            format,
+            out,
+        } => run_tree(
+            size,
+            other_size.unwrap_or(size),
+            threshold,
+            &format,
+            out.as_deref(),
+        ),
+        Command::Verify {
+            max_size,
+            tolerance,
+            seed,
+        } => run_verify(max_size, tolerance, seed),
+    };
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+/// Run the comprehensive benchmark with the settings of the configuration file,
+/// overridden by those given on the command line
+fn run_bench(args: BenchArgs) -> Result<(), PolyError> {
+    let (mut config, mut runs) = match &args.config {
+        Some(path) => BenchmarkConfig::from_toml_file(path)?,
+        None => (BenchmarkConfig::default(), RunsPerSize::default()),
+    };
+    if !args.sizes.is_empty() {
+        config.sizes = args.sizes;
+    }
+    if !args.aspect_ratios.is_empty() {
+        config.aspect_ratios = args.aspect_ratios;
+    }
+    if !args.distributions.is_empty() {
+        config.distributions = args.distributions;
+    }
+    if !args.algorithms.is_empty() || !args.thresholds.is_empty() {
+        // keep the configured algorithms or thresholds unless both are given
+        let mut names: Vec<String> = Vec::new();
+        for alg in &config.algorithms {
+            if !names.iter().any(|name| name == alg.name()) {
+                names.push(alg.name().to_string());
+            }
+        }
+        let thresholds: Vec<usize> = if args.thresholds.is_empty() {
+            (config.algorithms.iter())
+                .filter_map(|alg| match alg {
+                    PolynomialMultAlg::Thresholded(threshold) => Some(*threshold),
+                    _ => None,
+                })
+                .collect()
+        } else {
+            args.thresholds
+        };
+        if !args.algorithms.is_empty() {
+            names = args.algorithms;
+        }
+        config.algorithms = bench_algorithms(&names, &thresholds);
+    }
+    if let Some(output_dir) = args.output_dir {
+        config.output_dir = output_dir;
+    }
+    match args.format.as_deref() {
+        Some("json") => config.format = OutputFormat::Json,
+        Some(_) => config.format = OutputFormat::Csv,
+        None => {}
+    }
+    config.seed = args.seed.or(config.seed);
+    config.threads = args.threads.unwrap_or(config.threads);
+    config.pin_threads |= args.pin_threads;
+    config.warmup_runs = args.warmup.unwrap_or(config.warmup_runs);
+    config.verify |= args.verify;
+    config.accuracy |= args.accuracy;
+    if !args.evaluation_points.is_empty() {
+        config.evaluation_points = args.evaluation_points;
+    }
+    if !args.exact_bits.is_empty() {
+        config.exact_bits = args.exact_bits;
+    }
+    config.raw_runs |= args.raw_runs;
+    config.resume |= args.resume;
+    config.max_time_per_cell = args.max_time_per_cell.or(config.max_time_per_cell);
+    let trim = match config.aggregation {
+        Aggregation::TrimmedMean(fraction) => args.trim.unwrap_or(fraction),
+        _ => args.trim.unwrap_or(0.1),
+    };
+    config.aggregation = match args.aggregate.as_deref() {
+        Some("trimmed-mean") => Aggregation::TrimmedMean(trim),
+        Some("min") => Aggregation::Min,
+        Some(_) => Aggregation::Mean,
+        None => match config.aggregation {
+            Aggregation::TrimmedMean(_) => Aggregation::TrimmedMean(trim),
+            aggregation => aggregation,
+        },
+    };
+    #[cfg(feature = "mem_profile")]
+    {
+        config.measure_memory |= args.memory;
+    }
+    #[cfg(feature = "op_count")]
+    {
+        config.count_ops |= args.ops;
+    }
+    #[cfg(feature = "sqlite")]
+    {
+        config.database = args.database.or(config.database);
+        config.machine = args.machine.or(config.machine);
+    }
+    #[cfg(feature = "baselines")]
+    if args.baselines {
+        config.custom_algorithms = mult_polynomial::baselines();
+    }
+    #[cfg(feature = "gpu")]
+    if args.gpu {
+        let gpu = mult_polynomial::GpuMultiplier::new()?;
+        println!("Using GPU {}", gpu.adapter_name());
+        config.custom_algorithms.push(std::sync::Arc::new(gpu));
+    }
+    if let Some(fixed) = args.runs {
+        runs = RunsPerSize::fixed(fixed);
+    }
     println!("Running comprehensive benchmarks...");
-    run_comprehensive_benchmark(|size| std::cmp::max(10, 100000 / size));
+    let results = run_comprehensive_benchmark(&config, &|size| runs.runs(size))?;
+    let fits = fit_complexity(&config, &results, args.fit_min_size);
+    for (name, fit) in &fits {
+        println!(
+            "{}: time ~ n^{:.3} (R² = {:.4})",
+            name, fit.exponent, fit.r_squared
+        );
+    }
+    write_complexity(&config, &fits)?;
+    #[cfg(feature = "plot")]
+    mult_polynomial::plot::plot_results(&config, &results)?;
+    #[cfg(not(feature = "plot"))]
+    drop(results);
+    if !config.evaluation_points.is_empty() {
+        println!("Running evaluation benchmarks...");
+        let evaluation = run_evaluation_benchmark(&config, &|size| runs.runs(size))?;
+        #[cfg(feature = "plot")]
+        mult_polynomial::plot::plot_evaluation(&config, &evaluation)?;
+        #[cfg(not(feature = "plot"))]
+        drop(evaluation);
+    }
+    if !config.exact_bits.is_empty() {
+        println!("Running exact integer benchmarks...");
+        run_exact_benchmark(&config, &|size| runs.runs(size))?;
+    }
+    Ok(())
+}
+
+/// Search for the fastest threshold and print the timings of every threshold tried
+fn run_tune(args: TuneArgs) -> Result<(), PolyError> {
+    let config = TuneConfig {
+        sizes: args.sizes,
+        min_threshold: args.min_threshold,
+        max_threshold: args.max_threshold,
+        search: match args.search.as_str() {
+            "grid" => Search::Grid {
+                points: args.grid_points,
+            },
+            _ => Search::GoldenSection,
+        },
+        runs: args.runs,
+        warmup_runs: args.warmup,
+        seed: args.seed.unwrap_or_else(|| StdRng::from_entropy().gen()),
+        ..TuneConfig::default()
+    };
+    println!(
+        "Searching thresholds {} to {}...",
+        args.min_threshold, args.max_threshold
+    );
+    let result = tune_threshold(&config)?;
+    let best = result.best_timing().score;
+    print!("{:>10}", "threshold");
+    for size in &result.sizes {
+        print!("{:>14}", format!("n = {}", size));
+    }
+    println!("{:>14}{:>10}", "geo. mean", "relative");
+    for timing in &result.timings {
+        let marker = if timing.threshold == result.best {
+            " *"
+        } else {
+            ""
+        };
+        print!("{:>10}", timing.threshold);
+        for time in &timing.times {
+            print!("{:>14}", format!("{:.3e}", time));
+        }
+        println!(
+            "{:>14}{:>10}{}",
+            format!("{:.3e}", timing.score),
+            format!("{:.3}", timing.score / best),
+            marker
+        );
+    }
+    println!("Best threshold: {}", result.best);
+    std::fs::create_dir_all(&args.output_dir)?;
+    RunMetadata::collect(result.seed).write(args.output_dir.join("tune_metadata.json"))?;
+    result.write_csv(args.output_dir.join("tune.csv"))
+}
+
+/// Write the recursion tree of a product of operands with `n` and `m` coefficients
+fn run_tree(
+    n: usize,
+    m: usize,
+    threshold: usize,
+    format: &str,
+    out: Option<&Path>,
+) -> Result<(), PolyError> {
+    // the shape of the recursion only depends on the lengths
+    let (_, tree) = thresholded_multiply_with_tree(&vec![1.0; n], &vec![1.0; m], threshold);
+    let text = match format {
+        "json" => tree.to_json() + "\n",
+        _ => tree.to_dot(),
+    };
+    match out {
+        Some(path) => std::fs::write(path, text)?,
+        None => print!("{}", text),
+    }
+    Ok(())
+}
+
+/// Cross-check all algorithms on sizes up to `max_size` and print the pass/fail matrix
+fn run_verify(max_size: usize, tolerance: f64, seed: u64) -> Result<(), PolyError> {
+    let check = cross_check(max_size, seed);
+    println!(
+        "{} operand pairs with up to {} coefficients",
+        check.cases, max_size
+    );
+    print!("{}", check.report(tolerance));
+    if check.passed(tolerance) {
+        return Ok(());
+    }
+    // report the worst deviation from the naive product, or the worst pair if all are within
+    let from_naive = (1..ALGORITHMS.len())
+        .filter(|&j| check.worst[0][j].is_nan() || check.worst[0][j] > tolerance)
+        .max_by(|&i, &j| check.worst[0][i].total_cmp(&check.worst[0][j]))
+        .and_then(|j| Some((j, check.worst[0][j], check.worst_case[0][j]?)));
+    let (j, discrepancy, case) = match (from_naive, check.worst_pair()) {
+        (Some(failure), _) => failure,
+        (None, Some((_, j, discrepancy, case))) => (j, discrepancy, case),
+        (None, None) => return Ok(()),
+    };
+    Err(PolyError::VerificationFailed {
+        algorithm: ALGORITHMS[j].to_string(),
+        size: case.n,
+        discrepancy,
+    })
+}
+
+/// Parse a positive integer command-line value
+fn parse_positive(value: &str) -> Result<usize, String> {
+    match value.parse::<usize>() {
+        Ok(0) => Err("must be positive".to_string()),
+        Ok(n) => Ok(n),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Parse an input distribution of the benchmark, see `InputDistribution`
+fn parse_distribution(value: &str) -> Result<InputDistribution, String> {
+    value.parse().map_err(|e: PolyError| e.to_string())
+}
+
+/// Parse a positive duration in seconds
+fn parse_seconds(value: &str) -> Result<Duration, String> {
+    match value.parse::<f64>() {
+        Ok(seconds) if seconds > 0.0 => {
+            Duration::try_from_secs_f64(seconds).map_err(|e| e.to_string())
+        }
+        Ok(_) => Err("must be positive".to_string()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Parse the trimmed fraction of the trimmed mean
+fn parse_trim(value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(fraction) if (0.0..0.5).contains(&fraction) => Ok(fraction),
+        Ok(_) => Err("must be in [0, 0.5)".to_string()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Expand the benchmarked algorithm names, with one entry per threshold for `thresholded`
+/// and the default thresholds for `hybrid`
+fn bench_algorithms(names: &[String], thresholds: &[usize]) -> Vec<PolynomialMultAlg> {
+    (names.iter())
+        .flat_map(|name| PolynomialMultAlg::all(thresholds).filter(move |alg| alg.name() == name))
+        .collect()
+}
+
+/// Parse an algorithm given on the command line (`None` stands for automatic selection)
+///
+/// A bare `thresholded` or `hybrid` takes `threshold`, e.g. `thresholded:32` overrides it.
+fn parse_algorithm(name: &str, threshold: usize) -> Result<Option<PolynomialMultAlg>, PolyError> {
+    if name == "auto" {
+        return Ok(None);
+    }
+    let algorithm: PolynomialMultAlg = name.parse()?;
+    Ok(Some(if name.contains(':') {
+        algorithm
+    } else {
+        algorithm.with_threshold(threshold)
+    }))
+}
+
+/// File format of a polynomial, chosen by the extension of its path
+fn file_format(path: &Path) -> &'static str {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("csv" | "txt") => "csv",
+        Some("f64") => "compact",
+        Some("npy") => "npy",
+        _ => "binary",
+    }
+}
+
+/// Read a polynomial in the format given by the extension of `path`
+fn read_polynomial(path: &Path) -> Result<Polynomial, PolyError> {
+    match file_format(path) {
+        "csv" => Polynomial::from_csv_file(path),
+        "compact" => Polynomial::from_compact_file(path),
+        "npy" => read_npy(path),
+        _ => {
+            let file = std::fs::File::open(path)
+                .map_err(|e| PolyError::Io(format!("{}: {}", path.display(), e)))?;
+            Polynomial::read_binary(std::io::BufReader::new(file))
+        }
+    }
+}
+
+/// Write a polynomial in the format given by the extension of `path`
+fn write_polynomial(polynomial: &Polynomial, path: &Path) -> Result<(), PolyError> {
+    match file_format(path) {
+        "csv" => polynomial.to_csv_file(path),
+        "compact" => polynomial.to_compact_file(path),
+        "npy" => write_npy(polynomial, path),
+        _ => {
+            let file = std::fs::File::create(path)
+                .map_err(|e| PolyError::Io(format!("{}: {}", path.display(), e)))?;
+            let mut writer = std::io::BufWriter::new(file);
+            polynomial.write_binary(&mut writer)?;
+            writer.flush()?;
+            Ok(())
+        }
+    }
+}
+
+/// Read a `.npy` file, which needs the `npy` feature
+fn read_npy(path: &Path) -> Result<Polynomial, PolyError> {
+    #[cfg(feature = "npy")]
+    return Polynomial::from_npy(path);
+    #[cfg(not(feature = "npy"))]
+    Err(npy_unavailable(path))
+}
+
+/// Write a `.npy` file, which needs the `npy` feature
+#[allow(unused_variables)]
+fn write_npy(polynomial: &Polynomial, path: &Path) -> Result<(), PolyError> {
+    #[cfg(feature = "npy")]
+    return polynomial.to_npy(path);
+    #[cfg(not(feature = "npy"))]
+    Err(npy_unavailable(path))
+}
+
+/// Error for a `.npy` path in a build without the `npy` feature
+#[cfg(not(feature = "npy"))]
+fn npy_unavailable(path: &Path) -> PolyError {
+    PolyError::Unavailable(format!(
+        "{}: .npy files need the `npy` feature (e.g. `cargo run --release --features npy`)",
+        path.display()
+    ))
+}
+
+/// Multiply the polynomials in two files and write the product, printing timing stats
+fn run_multiply(
+    a_path: &Path,
+    b_path: &Path,
+    out_path: &Path,
+    alg: &str,
+    threshold: usize,
+) -> Result<(), PolyError> {
+    let algorithm = parse_algorithm(alg, threshold)?;
+
+    let timer = Instant::now();
+    let a = read_polynomial(a_path)?;
+    let b = read_polynomial(b_path)?;
+    let read_time = timer.elapsed();
+
+    let algorithm =
+        algorithm.unwrap_or_else(|| PolynomialMultAlg::auto(a.coeffs.len(), b.coeffs.len()));
+    let timer = Instant::now();
+    let product = a.multiply_checked(&b, algorithm)?;
+    let multiply_time = timer.elapsed();
+
+    let timer = Instant::now();
+    write_polynomial(&product, out_path)?;
+    let write_time = timer.elapsed();
+
+    println!(
+        "Multiplied {} x {} coefficients into {} coefficients using {}",
+        a.coeffs.len(),
+        b.coeffs.len(),
+        product.coeffs.len(),
+        algorithm
+    );
+    println!("  read:     {:?}", read_time);
+    println!("  multiply: {:?}", multiply_time);
+    println!("  write:    {:?}", write_time);
+    Ok(())
 }
 
 /// Test the algorithms with small inputs to verify correctness
@@ -40,92 +706,44 @@ fn test_correctness() {
     assert_eq!(cook_tooms_result, expected);
 }
 
-/// Run a single benchmark for a specific algorithm and input size
-fn benchmark_single(algorithm: PolynomialMultAlg, p1: &Polynomial, p2: &Polynomial) -> Duration {
-    let timer = Instant::now();
-
-    match algorithm {
-        PolynomialMultAlg::Naive => {
-            p1.multiply_naive(&p2);
-        }
-        PolynomialMultAlg::CookTooms => {
-            p1.multiply_cook_tooms_k3(&p2);
-        }
-        PolynomialMultAlg::Thresholded(threshold) => {
-            p1.multiply_thresholded(&p2, threshold);
-        }
-    }
-
-    timer.elapsed()
+/// Test parsing of the algorithm names of the `multiply` subcommand
+#[test]
+fn test_parse_algorithm() {
+    assert_eq!(parse_algorithm("auto", 64), Ok(None));
+    assert_eq!(
+        parse_algorithm("thresholded", 32),
+        Ok(Some(PolynomialMultAlg::Thresholded(32)))
+    );
+    assert_eq!(
+        parse_algorithm("thresholded:16", 32),
+        Ok(Some(PolynomialMultAlg::Thresholded(16)))
+    );
+    assert_eq!(
+        parse_algorithm("hybrid", 128),
+        Ok(Some(PolynomialMultAlg::Hybrid(
+            mult_polynomial::HybridConfig {
+                toom3_threshold: 128,
+                karatsuba_threshold: mult_polynomial::HybridConfig::default().karatsuba_threshold
+            }
+        )))
+    );
+    assert!(parse_algorithm("fft", 64).is_err());
 }
 
-/// Run a comprehensive benchmark with different input sizes and characteristics
-fn run_comprehensive_benchmark(runs_per_test: fn(usize) -> usize) {
-    // Create a directory for the output data files
-    std::fs::create_dir_all("output_data").unwrap_or_else(|_| {
-        println!("Warning: Could not create output_data directory");
-    });
-
-    // Settings for the benchmark
-    let thresholds = (1..16).map(|i| 1 << i).collect::<Vec<_>>();
-
-    // Generate a range of sizes including powers of 2 and intermediate values
-    let mut sizes = Vec::new();
-    for i in 0..=17 {
-        let power_of_two = 1 << i;
-        sizes.push(power_of_two);
-
-        // Add an intermediate size between powers of 2
-        if i > 3 {
-            sizes.push(power_of_two + (power_of_two / 2));
-        }
-    }
-
-    let raw_data_file = std::fs::File::create("output_data/all_results.csv").unwrap();
-    let mut raw_data_file = std::io::BufWriter::new(raw_data_file);
-
-    // Write CSV header
-    writeln!(
-        raw_data_file,
-        "size,naive_time,cook_tooms_time,{}",
-        thresholds
-            .iter()
-            .map(|t| format!("threshold_{}", t))
-            .collect::<Vec<_>>()
-            .join(",")
-    )
-    .unwrap();
-
-    for (i, &size) in sizes.iter().enumerate() {
-        let mut naive_time = 0.0;
-        let mut cook_tooms_time = 0.0;
-        let mut threshold_times = vec![0.0; thresholds.len()];
-        let runs = runs_per_test(size);
-        assert!(runs > 0, "Number of runs per test must be positive");
-        for _i in 0..runs {
-            let p1 = Polynomial::random(-10.0, 10.0, size);
-            let p2 = Polynomial::random(-10.0, 10.0, size);
-
-            naive_time +=
-                benchmark_single(PolynomialMultAlg::Naive, &p1, &p2).as_secs_f64() / runs as f64;
-            cook_tooms_time += benchmark_single(PolynomialMultAlg::CookTooms, &p1, &p2)
-                .as_secs_f64()
-                / runs as f64;
-            thresholds.iter().enumerate().for_each(|(j, &threshold)| {
-                threshold_times[j] +=
-                    benchmark_single(PolynomialMultAlg::Thresholded(threshold), &p1, &p2)
-                        .as_secs_f64()
-                        / runs as f64;
-            });
-        }
-        println!("Simulated size {} ({}/{})", size, i + 1, sizes.len());
-
-        // Write to CSV data file
-        write!(raw_data_file, "{},{}", size, naive_time).unwrap();
-        write!(raw_data_file, ",{}", cook_tooms_time).unwrap();
-        for time in &threshold_times {
-            write!(raw_data_file, ",{}", time).unwrap();
-        }
-        writeln!(raw_data_file).unwrap();
-    }
+/// Test expansion of the benchmarked algorithms
+#[test]
+fn test_bench_algorithms() {
+    let names = ["naive", "thresholded", "hybrid"].map(String::from);
+    assert_eq!(
+        bench_algorithms(&names, &[16, 32]),
+        vec![
+            PolynomialMultAlg::Naive,
+            PolynomialMultAlg::Thresholded(16),
+            PolynomialMultAlg::Thresholded(32),
+            PolynomialMultAlg::Hybrid(mult_polynomial::HybridConfig::default())
+        ]
+    );
+    assert_eq!(parse_positive("0"), Err("must be positive".to_string()));
+    assert_eq!(parse_trim("0.25"), Ok(0.25));
+    assert!(parse_trim("0.5").is_err());
 }
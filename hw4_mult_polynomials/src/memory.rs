@@ -0,0 +1,111 @@
+//! Heap usage measurement with a counting global allocator
+//!
+//! Enabled with the `mem_profile` feature. Install [`CountingAllocator`] as the global
+//! allocator of the executable, then measure closures with [`peak_during`]:
+//! ```
+//! use mult_polynomial::memory::{peak_during, CountingAllocator};
+//!
+//! #[global_allocator]
+//! static ALLOCATOR: CountingAllocator = CountingAllocator;
+//!
+//! let bytes = peak_during(|| vec![0u8; 1 << 20]);
+//! assert!(bytes >= 1 << 20);
+//! ```
+//! The counters are process wide, so allocations of other threads are included.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Bytes currently allocated
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+/// Largest value of `CURRENT` since the last [`reset_peak`]
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// System allocator that keeps track of the current and peak heap usage
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Add an allocation to the current usage and update the peak
+fn record_alloc(size: usize) {
+    let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(current, Ordering::Relaxed);
+}
+
+/// Bytes currently allocated through [`CountingAllocator`]
+pub fn current_bytes() -> usize {
+    CURRENT.load(Ordering::Relaxed)
+}
+
+/// Peak number of bytes allocated since the last [`reset_peak`]
+pub fn peak_bytes() -> usize {
+    PEAK.load(Ordering::Relaxed)
+}
+
+/// Reset the peak to the current usage and return it
+pub fn reset_peak() -> usize {
+    let current = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(current, Ordering::Relaxed);
+    current
+}
+
+/// Run `f` and return its peak heap usage in bytes above the usage before the call
+///
+/// The result of `f` is dropped before returning, but counted in the peak.
+/// Always zero unless [`CountingAllocator`] is the global allocator.
+pub fn peak_during<T>(f: impl FnOnce() -> T) -> usize {
+    let baseline = reset_peak();
+    drop(f());
+    peak_bytes().saturating_sub(baseline)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Polynomial;
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    /// The peak of a multiplication includes at least the product
+    #[test]
+    fn test_peak_during_multiplication() {
+        let p1 = Polynomial::new(vec![1.0; 1000]);
+        let p2 = Polynomial::new(vec![1.0; 1000]);
+        let naive = peak_during(|| p1.multiply_naive(&p2));
+        assert!(naive >= 1999 * std::mem::size_of::<f64>());
+        // Toom-3 allocates evaluation buffers on top of the product
+        let toom3 = peak_during(|| p1.multiply_cook_tooms_k3(&p2));
+        assert!(toom3 > naive);
+    }
+}
@@ -0,0 +1,229 @@
+//! Machine, build and seed of a benchmark run, written next to its results
+
+use crate::PolyError;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Crate features changing the measured times
+const TIMING_FEATURES: [(&str, bool); 5] = [
+    ("fast_fma", cfg!(feature = "fast_fma")),
+    ("strict_fp", cfg!(feature = "strict_fp")),
+    ("op_count", cfg!(feature = "op_count")),
+    ("mem_profile", cfg!(feature = "mem_profile")),
+    ("tracing", cfg!(feature = "tracing")),
+];
+
+/// Where, when and how a benchmark ran
+///
+/// [`run_comprehensive_benchmark`](crate::benchmark::run_comprehensive_benchmark) writes it to
+/// `metadata.json` next to the results (and into `all_results.json`), and the `tune` command to
+/// `tune_metadata.json`, so results of different machines and builds can be told apart.
+/// ## Example
+/// ```
+/// use mult_polynomial::benchmark::RunMetadata;
+/// let metadata = RunMetadata::collect(42);
+/// assert!(metadata.cores >= 1);
+/// assert!(metadata.rustc.starts_with("rustc"));
+/// assert!(metadata.to_json().contains("\"seed\": 42"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunMetadata {
+    /// CPU model as reported by the operating system (`unknown` if it is not available)
+    pub cpu_model: String,
+    /// Number of logical cores available to the process
+    pub cores: usize,
+    /// Operating system, e.g. `linux` or `macos`
+    pub os: String,
+    /// CPU architecture, e.g. `x86_64` or `aarch64`
+    pub arch: String,
+    /// Version of the compiler that built the crate
+    pub rustc: String,
+    /// Version of the crate
+    pub crate_version: String,
+    /// Git commit of the build, with `-dirty` for uncommitted changes (`None` outside of git)
+    pub commit: Option<String>,
+    /// Cargo profile, `release` or `debug`
+    pub profile: String,
+    /// Optimization level of the build
+    pub opt_level: String,
+    /// Enabled target features, e.g. `fma` and `avx2` with `-C target-cpu=native`
+    pub target_features: Vec<String>,
+    /// Extra flags passed to the compiler (`RUSTFLAGS`)
+    pub rustflags: String,
+    /// Enabled crate features changing the times, e.g. `strict_fp`
+    pub features: Vec<String>,
+    /// Seed of the random inputs
+    pub seed: u64,
+    /// Start of the run, in seconds since the Unix epoch
+    pub timestamp: u64,
+}
+
+impl RunMetadata {
+    /// Describe this machine and build, for a run with inputs drawn from `seed` starting now
+    pub fn collect(seed: u64) -> RunMetadata {
+        let list = |text: &str| -> Vec<String> {
+            (text.split(',').filter(|s| !s.is_empty()))
+                .map(str::to_string)
+                .collect()
+        };
+        let commit = env!("MULT_POLYNOMIAL_COMMIT");
+        RunMetadata {
+            cpu_model: cpu_model().unwrap_or_else(|| "unknown".to_string()),
+            cores: std::thread::available_parallelism().map_or(1, |n| n.get()),
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            rustc: env!("MULT_POLYNOMIAL_RUSTC").to_string(),
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            commit: (!commit.is_empty()).then(|| commit.to_string()),
+            profile: env!("MULT_POLYNOMIAL_PROFILE").to_string(),
+            opt_level: env!("MULT_POLYNOMIAL_OPT_LEVEL").to_string(),
+            target_features: list(env!("MULT_POLYNOMIAL_TARGET_FEATURES")),
+            rustflags: env!("MULT_POLYNOMIAL_RUSTFLAGS").to_string(),
+            features: (TIMING_FEATURES.iter())
+                .filter(|(_, enabled)| *enabled)
+                .map(|(name, _)| name.to_string())
+                .collect(),
+            seed,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+        }
+    }
+
+    /// Start of the run as an ISO 8601 date and time in UTC, e.g. `2024-03-01T12:00:00Z`
+    pub fn date_time(&self) -> String {
+        let (days, seconds) = (self.timestamp / 86400, self.timestamp % 86400);
+        let (year, month, day) = civil_from_days(days as i64);
+        format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+            year,
+            month,
+            day,
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        )
+    }
+
+    /// JSON object with every field, and the date and time next to the timestamp
+    pub fn to_json(&self) -> String {
+        let list = |items: &[String]| {
+            let items: Vec<String> = items.iter().map(|s| json_string(s)).collect();
+            format!("[{}]", items.join(", "))
+        };
+        format!(
+            "{{\"cpu_model\": {}, \"cores\": {}, \"os\": {}, \"arch\": {}, \"rustc\": {}, \
+             \"crate_version\": {}, \"commit\": {}, \"profile\": {}, \"opt_level\": {}, \
+             \"target_features\": {}, \"rustflags\": {}, \"features\": {}, \"seed\": {}, \
+             \"timestamp\": {}, \"date_time\": {}}}",
+            json_string(&self.cpu_model),
+            self.cores,
+            json_string(&self.os),
+            json_string(&self.arch),
+            json_string(&self.rustc),
+            json_string(&self.crate_version),
+            self.commit
+                .as_deref()
+                .map_or("null".to_string(), json_string),
+            json_string(&self.profile),
+            json_string(&self.opt_level),
+            list(&self.target_features),
+            json_string(&self.rustflags),
+            list(&self.features),
+            self.seed,
+            self.timestamp,
+            json_string(&self.date_time()),
+        )
+    }
+
+    /// Write [`RunMetadata::to_json`] to a file
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), PolyError> {
+        std::fs::write(path, self.to_json() + "\n")?;
+        Ok(())
+    }
+}
+
+/// JSON string literal of `text`
+fn json_string(text: &str) -> String {
+    let mut quoted = String::from('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Year, month and day of the date `days` after 1970-01-01 in the proleptic Gregorian calendar
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    // shift the epoch to 0000-03-01, so leap days end a 400 year era
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// CPU model from `/proc/cpuinfo`, `sysctl` on macOS or the environment on Windows
+fn cpu_model() -> Option<String> {
+    let model = if cfg!(target_os = "macos") {
+        let output = (std::process::Command::new("sysctl"))
+            .args(["-n", "machdep.cpu.brand_string"])
+            .output()
+            .ok()?;
+        String::from_utf8(output.stdout).ok()?
+    } else if cfg!(target_os = "windows") {
+        std::env::var("PROCESSOR_IDENTIFIER").ok()?
+    } else {
+        let cpuinfo = std::fs::read_to_string("/proc/cpuinfo").ok()?;
+        // `model name` on x86, `Model` or `Hardware` on some ARM boards
+        (cpuinfo.lines())
+            .filter_map(|line| line.split_once(':'))
+            .find(|(key, _)| matches!(key.trim(), "model name" | "Model" | "Hardware"))?
+            .1
+            .to_string()
+    };
+    Some(model.trim().to_string()).filter(|model| !model.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Dates around leap days and the epoch, and the JSON of every field
+    #[test]
+    fn test_run_metadata() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(11016), (2000, 2, 29));
+        assert_eq!(civil_from_days(19783), (2024, 3, 1));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+        let metadata = RunMetadata {
+            cpu_model: "CPU \"X\" @ 3GHz".to_string(),
+            commit: None,
+            timestamp: 1_709_294_400,
+            features: vec!["strict_fp".to_string()],
+            ..RunMetadata::collect(7)
+        };
+        assert_eq!(metadata.date_time(), "2024-03-01T12:00:00Z");
+        let json = metadata.to_json();
+        assert!(json.starts_with("{\"cpu_model\": \"CPU \\\"X\\\" @ 3GHz\", \"cores\": "));
+        assert!(json.contains("\"commit\": null"));
+        assert!(json.contains("\"features\": [\"strict_fp\"], \"seed\": 7"));
+        assert!(json.ends_with("\"date_time\": \"2024-03-01T12:00:00Z\"}"));
+        assert_eq!(json.matches('{').count(), json.matches('}').count());
+    }
+}
@@ -0,0 +1,180 @@
+//! Middle product: a window of the coefficients of a product at about the cost of one multiplication
+//!
+//! The balanced middle product of `a` with `2n - 1` coefficients and `b` with `n` coefficients
+//! is the `n` coefficients `n - 1 .. 2n - 1` of `a·b`. It is the transpose of the multiplication by `b`,
+//! so transposing Karatsuba's scheme computes it with three half-size middle products,
+//! in the time of one `n × n` product instead of the `2n × n` product it is cut from.
+//! Newton's iterations on power series only need such windows, as the low half of each product is known.
+
+use crate::fp::mul_add;
+use crate::Polynomial;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Length of `b` below which the balanced middle product is computed naively
+const MIDDLE_PRODUCT_THRESHOLD: usize = 32;
+
+/// Balanced middle product by the quadratic definition `c[k] = Σⱼ b[j]·a[n - 1 + k - j]`
+fn naive_middle(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let n = b.len();
+    (0..n)
+        .map(|k| {
+            (b.iter().enumerate()).fold(0.0, |sum, (j, &bj)| mul_add(bj, a[n - 1 + k - j], sum))
+        })
+        .collect()
+}
+
+/// Coefficients `n - 1 .. 2n - 1` of `a·b` for `n = b.len()` and `a.len() = 2n - 1`, by transposed Karatsuba
+///
+/// With `b = b₀ + xʰ·b₁` and the windows `a₀ = a[0..2h-1]`, `a₁ = a[h..3h-1]`, `a₂ = a[2h..4h-1]`,
+/// the low half is `MP(a₁, b₀ + b₁) + MP(a₀ - a₁, b₁)` and the high half `MP(a₁, b₀ + b₁) + MP(a₂ - a₁, b₀)`.
+/// Odd lengths shift `a` by one and pad both operands with a zero.
+fn balanced_middle(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let n = b.len();
+    debug_assert_eq!(a.len() + 1, 2 * n);
+    if n < MIDDLE_PRODUCT_THRESHOLD {
+        return naive_middle(a, b);
+    }
+    if n % 2 == 1 {
+        let mut padded_a = Vec::with_capacity(a.len() + 2);
+        padded_a.push(0.0);
+        padded_a.extend_from_slice(a);
+        padded_a.push(0.0);
+        let mut padded_b = b.to_vec();
+        padded_b.push(0.0);
+        let mut result = balanced_middle(&padded_a, &padded_b);
+        result.truncate(n);
+        return result;
+    }
+
+    let h = n / 2;
+    let (b0, b1) = b.split_at(h);
+    let (a0, a1, a2) = (&a[..2 * h - 1], &a[h..3 * h - 1], &a[2 * h..]);
+    let b_sum: Vec<f64> = b0.iter().zip(b1).map(|(x, y)| x + y).collect();
+    let a_low: Vec<f64> = a0.iter().zip(a1).map(|(x, y)| x - y).collect();
+    let a_high: Vec<f64> = a2.iter().zip(a1).map(|(x, y)| x - y).collect();
+    let alpha = balanced_middle(a1, &b_sum);
+    let beta = balanced_middle(&a_low, b1);
+    let gamma = balanced_middle(&a_high, b0);
+
+    let mut result = Vec::with_capacity(n);
+    result.extend(alpha.iter().zip(&beta).map(|(x, y)| x + y));
+    result.extend(alpha.iter().zip(&gamma).map(|(x, y)| x + y));
+    result
+}
+
+/// Coefficients `lo..hi` of `a·b`, exactly `hi - lo` of them (zeros beyond the product)
+///
+/// The window is cut into blocks of `s = min(hi - lo, shorter operand length)` coefficients,
+/// and the shorter operand into blocks of `s` too; each pair of blocks is one balanced middle product
+/// with the matching window of the longer operand.
+pub(crate) fn middle_product_impl(a: &[f64], b: &[f64], lo: usize, hi: usize) -> Vec<f64> {
+    if hi <= lo {
+        return vec![];
+    }
+    let mut result = vec![0.0; hi - lo];
+    if a.is_empty() || b.is_empty() {
+        return result;
+    }
+    let (a, b) = if a.len() < b.len() { (b, a) } else { (a, b) };
+    let hi_nonzero = hi.min(a.len() + b.len() - 1);
+    if hi_nonzero <= lo {
+        return result;
+    }
+    let s = (hi_nonzero - lo).min(b.len());
+    let mut window = vec![0.0; 2 * s - 1];
+    let mut b_block = vec![0.0; s];
+    for out in (lo..hi_nonzero).step_by(s) {
+        for start in (0..b.len()).step_by(s) {
+            let end = (start + s).min(b.len());
+            b_block[..end - start].copy_from_slice(&b[start..end]);
+            b_block[end - start..].fill(0.0);
+            // window[i] = a[out - start - (s - 1) + i], zero outside of `a`
+            for (i, w) in window.iter_mut().enumerate() {
+                *w = (out + i)
+                    .checked_sub(start + s - 1)
+                    .and_then(|k| a.get(k))
+                    .copied()
+                    .unwrap_or(0.0);
+            }
+            let block = balanced_middle(&window, &b_block);
+            for (r, c) in result[out - lo..].iter_mut().zip(block) {
+                *r += c;
+            }
+        }
+    }
+    result
+}
+
+impl Polynomial {
+    /// Middle product: the coefficients `lo..hi` of `self·other`, coefficient `lo` first
+    ///
+    /// Uses the balanced middle product (transposed Karatsuba), so a window of `n` coefficients
+    /// against an operand of `n` coefficients costs about one `n × n` multiplication
+    /// rather than the full product. Empty if `hi ≤ lo`.
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// // (1 + x + x² + x³)·(1 + 2x) = 1 + 3x + 3x² + 3x³ + 2x⁴
+    /// let p = Polynomial::new(vec![1.0, 1.0, 1.0, 1.0]);
+    /// let q = Polynomial::new(vec![1.0, 2.0]);
+    /// assert_eq!(p.middle_product(&q, 2, 4), Polynomial::new(vec![3.0, 3.0]));
+    /// assert_eq!(p.middle_product(&q, 4, 9), Polynomial::new(vec![2.0]));
+    /// ```
+    pub fn middle_product(&self, other: &Polynomial, lo: usize, hi: usize) -> Polynomial {
+        self.with_coeffs(middle_product_impl(&self.coeffs, &other.coeffs, lo, hi))
+    }
+}
+
+#[cfg(all(test, feature = "thread_rng"))]
+mod tests {
+    use super::*;
+    use crate::polynomial::naive_multiply_impl;
+
+    /// Test the transposed Karatsuba recursion against the quadratic definition on odd and even lengths
+    #[test]
+    fn test_balanced_middle() {
+        for n in [1, 2, 31, 32, 33, 64, 75, 200] {
+            let a = Polynomial::random(-1.0, 1.0, 2 * n - 1).coeffs;
+            let b = Polynomial::random(-1.0, 1.0, n).coeffs;
+            let (fast, naive) = (balanced_middle(&a, &b), naive_middle(&a, &b));
+            assert_eq!(fast.len(), n);
+            let error = (fast.iter().zip(&naive)).fold(0.0f64, |m, (x, y)| m.max((x - y).abs()));
+            assert!(error < 1e-12 * n as f64, "n = {}", n);
+        }
+    }
+
+    /// Test windows of unbalanced products against slices of the full product, including empty cases
+    #[test]
+    fn test_middle_product() {
+        let p = Polynomial::random(-10.0, 10.0, 150);
+        let q = Polynomial::random(-10.0, 10.0, 47);
+        let full = naive_multiply_impl(&p.coeffs, &q.coeffs);
+        for (lo, hi) in [
+            (0, 196),
+            (46, 150),
+            (100, 196),
+            (3, 20),
+            (0, 1),
+            (180, 250),
+            (47, 94),
+        ] {
+            for (x, y) in [(&p, &q), (&q, &p)] {
+                let window = x.middle_product(y, lo, hi);
+                let expected = Polynomial::new(full[lo.min(196)..hi.min(196)].to_vec());
+                assert!(window.approx_eq(&expected, 1e-9, 1e-9), "{}..{}", lo, hi);
+            }
+        }
+        assert!(p.middle_product(&q, 10, 10).coeffs.is_empty());
+        assert!(p.middle_product(&q, 20, 10).coeffs.is_empty());
+        assert!(p.middle_product(&q, 196, 300).coeffs.is_empty());
+        assert!(p
+            .middle_product(&Polynomial::new(vec![]), 0, 5)
+            .coeffs
+            .is_empty());
+        assert_eq!(
+            middle_product_impl(&[1.0], &[2.0], 0, 3),
+            vec![2.0, 0.0, 0.0]
+        );
+    }
+}
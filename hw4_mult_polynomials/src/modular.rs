@@ -0,0 +1,431 @@
+//! Exact products of integer polynomials by multi-modular number-theoretic transforms
+//!
+//! The product is computed modulo up to three primes `p = c·2⁴⁰ + 1` just below `2⁶²`,
+//! each by a number-theoretic transform (NTT) of length up to `2⁴⁰`, and the exact coefficients
+//! are reconstructed by the Chinese remainder theorem (Garner's mixed-radix form).
+//! A bound on the coefficients of the product picks how many primes are needed,
+//! so small coefficients take a single transform per operand.
+//! Residues are multiplied with Montgomery's reduction, which replaces the division of a
+//! 128-bit product by the prime with two word multiplications and a conditional subtraction.
+
+use crate::PolyError;
+use alloc::format;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Odd modulus `p` with the constants of Montgomery's reduction modulo `p` with `R = 2⁶⁴`
+///
+/// Also the arithmetic of the factorization modulo primes, which keeps its residues in the
+/// Montgomery form `x·R mod p` throughout.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Montgomery {
+    /// The modulus, below `2⁶²` so sums of two residues fit in a `u64`
+    pub(crate) p: u64,
+    /// `-p⁻¹ mod 2⁶⁴`
+    neg_inverse: u64,
+    /// `R² mod p`, to convert into the Montgomery form `x·R mod p`
+    r2: u64,
+}
+
+impl Montgomery {
+    /// Modulus with its Montgomery constants
+    pub(crate) const fn new(p: u64) -> Montgomery {
+        // an odd p is its own inverse modulo 2³, and each Newton step doubles the correct bits
+        let mut inverse = p;
+        let mut i = 0;
+        while i < 5 {
+            inverse = inverse.wrapping_mul(2u64.wrapping_sub(p.wrapping_mul(inverse)));
+            i += 1;
+        }
+        let r = (1u128 << 64) % p as u128;
+        Montgomery {
+            p,
+            neg_inverse: inverse.wrapping_neg(),
+            r2: (r * r % p as u128) as u64,
+        }
+    }
+
+    /// Montgomery reduction `t·R⁻¹ mod p` for `t < p·R`, with one multiplication and no division
+    #[inline(always)]
+    fn reduce(self, t: u128) -> u64 {
+        let m = (t as u64).wrapping_mul(self.neg_inverse);
+        // t + m·p is divisible by R and below 2p·R
+        let r = ((t + m as u128 * self.p as u128) >> 64) as u64;
+        if r >= self.p {
+            r - self.p
+        } else {
+            r
+        }
+    }
+
+    /// `a·b·R⁻¹ mod p` for reduced `a` and `b`: their product if one of them is in Montgomery form,
+    /// and in Montgomery form if both are
+    #[inline(always)]
+    pub(crate) fn mul(self, a: u64, b: u64) -> u64 {
+        self.reduce(a as u128 * b as u128)
+    }
+
+    /// Montgomery form `x·R mod p` of a reduced `x`
+    #[inline(always)]
+    pub(crate) fn to_montgomery(self, x: u64) -> u64 {
+        self.mul(x, self.r2)
+    }
+
+    /// `baseᵉ` in Montgomery form for a `base` in Montgomery form, by repeated squaring
+    pub(crate) fn pow_montgomery(self, mut base: u64, mut exponent: u64) -> u64 {
+        let mut result = self.to_montgomery(1);
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = self.mul(result, base);
+            }
+            base = self.mul(base, base);
+            exponent >>= 1;
+        }
+        result
+    }
+
+    /// `baseᵉ mod p` by repeated squaring in Montgomery form
+    fn pow(self, base: u64, exponent: u64) -> u64 {
+        self.mul(self.pow_montgomery(self.to_montgomery(base), exponent), 1)
+    }
+}
+
+/// Prime modulus `p` with a multiplicative generator, whose powers give the roots of unity
+#[derive(Debug, Clone, Copy)]
+struct NttPrime {
+    /// The prime, `c·2⁴⁰ + 1 < 2⁶²`, with its Montgomery constants
+    modulus: Montgomery,
+    /// Generator of the multiplicative group modulo `p`
+    generator: u64,
+}
+
+impl NttPrime {
+    const fn new(p: u64, generator: u64) -> NttPrime {
+        NttPrime {
+            modulus: Montgomery::new(p),
+            generator,
+        }
+    }
+}
+
+/// Primes of the transforms, largest first; their product exceeds `2¹⁸⁵`
+const PRIMES: [NttPrime; 3] = [
+    NttPrime::new(4611546380450660353, 5),
+    NttPrime::new(4611524390218104833, 3),
+    NttPrime::new(4611480409752993793, 10),
+];
+
+/// Base-2 logarithm of the longest transform, the power of 2 dividing every `p - 1`
+const MAX_LOG_LEN: u32 = 40;
+
+/// Length of the shorter operand below which the product is computed naively in `i128`
+const EXACT_NAIVE_THRESHOLD: usize = 32;
+
+/// `a + b mod p` for reduced `a` and `b`
+#[inline(always)]
+pub(crate) fn add_mod(a: u64, b: u64, p: u64) -> u64 {
+    let sum = a + b;
+    if sum >= p {
+        sum - p
+    } else {
+        sum
+    }
+}
+
+/// `a - b mod p` for reduced `a` and `b`
+#[inline(always)]
+pub(crate) fn sub_mod(a: u64, b: u64, p: u64) -> u64 {
+    if a >= b {
+        a - b
+    } else {
+        a + p - b
+    }
+}
+
+/// In-place NTT of a power-of-two length, or its inverse (including the division by the length)
+///
+/// Iterative Cooley-Tukey after a bit-reversal permutation, as for the complex FFT.
+/// The values stay reduced residues and the twiddle factors are kept in Montgomery form,
+/// so each butterfly takes one Montgomery multiplication.
+fn ntt(values: &mut [u64], prime: NttPrime, inverse: bool) {
+    let n = values.len();
+    let p = prime.modulus.p;
+    let bits = n.trailing_zeros();
+    for i in 0..n {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+    let one = prime.modulus.to_montgomery(1);
+    let mut len = 2;
+    while len <= n {
+        let mut root = prime.modulus.pow(prime.generator, (p - 1) / len as u64);
+        if inverse {
+            root = prime.modulus.pow(root, p - 2);
+        }
+        let root = prime.modulus.to_montgomery(root);
+        for block in values.chunks_exact_mut(len) {
+            let (low, high) = block.split_at_mut(len / 2);
+            let mut w = one;
+            for (x, y) in low.iter_mut().zip(high) {
+                let t = prime.modulus.mul(*y, w);
+                *y = sub_mod(*x, t, p);
+                *x = add_mod(*x, t, p);
+                w = prime.modulus.mul(w, root);
+            }
+        }
+        len *= 2;
+    }
+    if inverse {
+        let n_inv = prime
+            .modulus
+            .to_montgomery(prime.modulus.pow(n as u64, p - 2));
+        for x in values.iter_mut() {
+            *x = prime.modulus.mul(*x, n_inv);
+        }
+    }
+}
+
+/// Product of integer polynomials modulo `prime`, by NTTs of length `len ≥ a.len() + b.len() - 1`
+fn multiply_mod(a: &[i64], b: &[i64], prime: NttPrime, len: usize) -> Vec<u64> {
+    let reduce = |coeffs: &[i64]| {
+        let mut residues = vec![0; len];
+        for (r, &c) in residues.iter_mut().zip(coeffs) {
+            let magnitude = c.unsigned_abs() % prime.modulus.p;
+            *r = if c < 0 {
+                sub_mod(0, magnitude, prime.modulus.p)
+            } else {
+                magnitude
+            };
+        }
+        residues
+    };
+    let (mut x, mut y) = (reduce(a), reduce(b));
+    ntt(&mut x, prime, false);
+    ntt(&mut y, prime, false);
+    for (u, v) in x.iter_mut().zip(&y) {
+        *u = prime.modulus.mul(*u, prime.modulus.to_montgomery(*v));
+    }
+    ntt(&mut x, prime, true);
+    x.truncate(a.len() + b.len() - 1);
+    x
+}
+
+/// Inverses of the primes modulo each other: `pⱼ⁻¹ mod pᵢ` at `[i][j]` for `j < i`,
+/// in the Montgomery form modulo `pᵢ`
+fn garner_inverses() -> [[u64; PRIMES.len()]; PRIMES.len()] {
+    let mut inverses = [[0; PRIMES.len()]; PRIMES.len()];
+    for (i, row) in inverses.iter_mut().enumerate() {
+        let prime = PRIMES[i];
+        for (j, inverse) in row[..i].iter_mut().enumerate() {
+            let residue = PRIMES[j].modulus.p % prime.modulus.p;
+            *inverse = prime
+                .modulus
+                .to_montgomery(prime.modulus.pow(residue, prime.modulus.p - 2));
+        }
+    }
+    inverses
+}
+
+/// Integer with the given `residues` modulo the first primes, closest to zero
+///
+/// Garner's algorithm gives the mixed-radix digits `x = y₀ + p₀·y₁ + p₀p₁·y₂`; taking the last
+/// digit in `(-p/2, p/2]` gives the representative closest to zero, which is then the exact value
+/// as long as it fits in `i128`, and the sum is evaluated with wrapping arithmetic modulo `2¹²⁸`.
+fn reconstruct(residues: &[u64], inverses: &[[u64; PRIMES.len()]; PRIMES.len()]) -> i128 {
+    let mut digits = [0u64; PRIMES.len()];
+    for (i, &r) in residues.iter().enumerate() {
+        let prime = PRIMES[i];
+        let mut digit = r;
+        for (j, &y) in digits[..i].iter().enumerate() {
+            // the primes are within a factor of 2 of each other
+            let y = if y >= prime.modulus.p {
+                y - prime.modulus.p
+            } else {
+                y
+            };
+            digit = prime
+                .modulus
+                .mul(sub_mod(digit, y, prime.modulus.p), inverses[i][j]);
+        }
+        digits[i] = digit;
+    }
+    let last = residues.len() - 1;
+    let mut value: i128 = 0;
+    let mut radix: i128 = 1;
+    for (i, &digit) in digits[..=last].iter().enumerate() {
+        let p = PRIMES[i].modulus.p;
+        let digit = if i == last && digit > p / 2 {
+            digit as i128 - p as i128
+        } else {
+            digit as i128
+        };
+        value = value.wrapping_add(radix.wrapping_mul(digit));
+        radix = radix.wrapping_mul(p as i128);
+    }
+    value
+}
+
+/// Bound `min(n, m)·max|a|·max|b|` on the magnitude of the coefficients of `a·b`
+///
+/// Fails with [`PolyError::InvalidArgument`] from `2¹²⁷` on, as the product might not fit in `i128`.
+pub(crate) fn product_bound(a: &[i64], b: &[i64]) -> Result<u128, PolyError> {
+    let max_abs = |coeffs: &[i64]| coeffs.iter().map(|c| c.unsigned_abs()).max().unwrap_or(0);
+    (max_abs(a) as u128 * max_abs(b) as u128)
+        .checked_mul(a.len().min(b.len()) as u128)
+        .filter(|&bound| bound < 1 << 127)
+        .ok_or_else(|| {
+            PolyError::InvalidArgument("the coefficients of the product may not fit in i128".into())
+        })
+}
+
+/// Product of nonempty integer polynomials in `i128` by the schoolbook algorithm,
+/// exact for coefficients within [`product_bound`]
+pub(crate) fn schoolbook_exact(a: &[i64], b: &[i64]) -> Vec<i128> {
+    let mut product = vec![0i128; a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (c, &y) in product[i..].iter_mut().zip(b) {
+            *c += x as i128 * y as i128;
+        }
+    }
+    product
+}
+
+/// Exact product of integer polynomials, by NTTs modulo machine-word primes and the CRT
+///
+/// Coefficients are from lowest to highest degree. Each coefficient of the product is at most
+/// `min(n, m)·max|a|·max|b|` in magnitude; one, two or three primes below `2⁶²` are used depending on
+/// that bound, for `O(n log n)` word operations per prime instead of the `O(n²)` of schoolbook
+/// multiplication (used below 32 coefficients). Fails with [`PolyError::InvalidArgument`]
+/// if the bound reaches `2¹²⁷`, as the product might not fit in `i128`,
+/// or if the product is longer than `2⁴⁰` coefficients.
+/// ## Example
+/// ```
+/// use mult_polynomial::multiply_exact;
+/// // (2⁶⁰ + x)·(2⁶⁰ - x) = 2¹²⁰ - x², beyond the 53 bits of an f64
+/// let product = multiply_exact(&[1 << 60, 1], &[1 << 60, -1]).unwrap();
+/// assert_eq!(product, vec![1 << 120, 0, -1]);
+/// assert!(multiply_exact(&[i64::MIN; 4], &[i64::MIN; 4]).is_err());
+/// ```
+pub fn multiply_exact(a: &[i64], b: &[i64]) -> Result<Vec<i128>, PolyError> {
+    if a.is_empty() || b.is_empty() {
+        return Ok(vec![]);
+    }
+    let bound = product_bound(a, b)?;
+    let result_len = a.len() + b.len() - 1;
+    let len = result_len.next_power_of_two();
+    if len.trailing_zeros() > MAX_LOG_LEN {
+        return Err(PolyError::InvalidArgument(format!(
+            "a product of {} coefficients is longer than the transforms (2^{})",
+            result_len, MAX_LOG_LEN
+        )));
+    }
+
+    if a.len().min(b.len()) < EXACT_NAIVE_THRESHOLD {
+        return Ok(schoolbook_exact(a, b));
+    }
+
+    // the primes exceed 2⁶¹, so k of them determine any value of magnitude below 2^(61k - 1)
+    let primes = match bound {
+        _ if bound < 1 << 60 => 1,
+        _ if bound < 1 << 121 => 2,
+        _ => 3,
+    };
+    let residues: Vec<Vec<u64>> = (PRIMES[..primes].iter())
+        .map(|&prime| multiply_mod(a, b, prime, len))
+        .collect();
+    let inverses = garner_inverses();
+    Ok((0..result_len)
+        .map(|k| {
+            let mut column = [0u64; PRIMES.len()];
+            for (c, r) in column.iter_mut().zip(&residues) {
+                *c = r[k];
+            }
+            reconstruct(&column[..primes], &inverses)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "std")]
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    /// Test that the primes have roots of unity of order 2⁴⁰ and that a transform round-trips
+    #[test]
+    fn test_ntt() {
+        for prime in PRIMES {
+            let p = prime.modulus.p;
+            assert_eq!((p - 1) % (1 << MAX_LOG_LEN), 0);
+            let root = prime.modulus.pow(prime.generator, (p - 1) >> MAX_LOG_LEN);
+            assert_eq!(prime.modulus.pow(root, 1 << MAX_LOG_LEN), 1);
+            assert_ne!(prime.modulus.pow(root, 1 << (MAX_LOG_LEN - 1)), 1);
+            let values: Vec<u64> = (0..64).map(|i| (i * i * 7919) % p).collect();
+            let mut transformed = values.clone();
+            ntt(&mut transformed, prime, false);
+            assert_ne!(transformed, values);
+            ntt(&mut transformed, prime, true);
+            assert_eq!(transformed, values);
+        }
+    }
+
+    /// Test Montgomery multiplication against `u128` remainders, including the extremes
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_montgomery() {
+        let mut rng = StdRng::seed_from_u64(8);
+        for prime in PRIMES {
+            let p = prime.modulus.p;
+            assert_eq!(p.wrapping_mul(prime.modulus.neg_inverse), u64::MAX);
+            assert_eq!(prime.modulus.mul(prime.modulus.to_montgomery(1), 1), 1);
+            for _ in 0..1000 {
+                let (a, b) = (rng.gen_range(0..p), rng.gen_range(0..p));
+                let expected = (a as u128 * b as u128 % p as u128) as u64;
+                assert_eq!(
+                    prime.modulus.mul(a, prime.modulus.to_montgomery(b)),
+                    expected
+                );
+            }
+            assert_eq!(
+                prime.modulus.mul(p - 1, prime.modulus.to_montgomery(p - 1)),
+                1
+            );
+            assert_eq!(prime.modulus.pow(3, p - 1), 1);
+            assert_eq!(prime.modulus.pow(2, 10), 1024);
+        }
+    }
+
+    /// Test against the schoolbook product for each number of primes, with signs and extremes
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_multiply_exact() {
+        let mut rng = StdRng::seed_from_u64(3);
+        for (magnitude, n, m) in [
+            (1i64 << 10, 100, 37),
+            (1 << 28, 64, 64),
+            (1 << 55, 300, 200),
+            (1 << 61, 70, 31),
+            (1, 1000, 1),
+        ] {
+            let mut operand = |len: usize| -> Vec<i64> {
+                (0..len)
+                    .map(|_| rng.gen_range(-magnitude..=magnitude))
+                    .collect()
+            };
+            let (a, b) = (operand(n), operand(m));
+            let exact = multiply_exact(&a, &b).unwrap();
+            assert_eq!(exact, schoolbook_exact(&a, &b), "magnitude {}", magnitude);
+        }
+        // coefficients of the product close to -2¹²⁷
+        let a = vec![1 << 61; 31];
+        let b = vec![-(1 << 61); 40];
+        assert_eq!(multiply_exact(&a, &b).unwrap(), schoolbook_exact(&a, &b));
+        assert!(multiply_exact(&a, &[]).unwrap().is_empty());
+        assert!(matches!(
+            multiply_exact(&[i64::MIN; 2], &[i64::MIN; 64]),
+            Err(PolyError::InvalidArgument(_))
+        ));
+    }
+}
@@ -0,0 +1,296 @@
+//! Reusable multiplication engine configured once by a builder
+
+use crate::benchmark::{column_name, relative_discrepancy};
+use crate::polynomial::{
+    multiply_impl, thresholded_multiply_with_schedule, thresholded_multiply_with_scratch,
+    toom3_evaluate_into, toom3_interpolate_into, Scratch,
+};
+use crate::sparse::{prefers_sparse, sparse_multiply_impl};
+use crate::{verify_product_with_rng, PolyError, Polynomial, PolynomialMultAlg};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// Random points at which [`MultiplierBuilder::verify`] checks every product
+const VERIFY_TRIALS: usize = 3;
+
+/// Threshold per recursion depth, shared by the threads of a multiplier
+type Schedule = Arc<dyn Fn(usize) -> usize + Send + Sync>;
+
+/// How a [`Multiplier`] picks the algorithm of a product
+#[derive(Clone)]
+enum Strategy {
+    /// [`PolynomialMultAlg::auto_for`] on the operand lengths, or term by term for sparse operands
+    Auto,
+    /// The same algorithm for every product
+    Algorithm(PolynomialMultAlg),
+    /// Toom-3 with a threshold depending on the recursion depth
+    Schedule(Schedule),
+}
+
+/// Settings of a [`Multiplier`], created by [`Multiplier::builder`]
+///
+/// By default the multiplier picks the algorithm like [`Polynomial::multiply_auto`],
+/// runs on the calling thread, allocates its workspace on first use and does not verify.
+#[derive(Clone)]
+pub struct MultiplierBuilder {
+    strategy: Strategy,
+    threads: usize,
+    scratch_capacity: usize,
+    verify: bool,
+    seed: u64,
+}
+
+impl Default for MultiplierBuilder {
+    fn default() -> Self {
+        MultiplierBuilder {
+            strategy: Strategy::Auto,
+            threads: 1,
+            scratch_capacity: 0,
+            verify: false,
+            seed: 0,
+        }
+    }
+}
+
+impl MultiplierBuilder {
+    /// Use `algorithm` for every product, replacing an earlier [`MultiplierBuilder::schedule`]
+    pub fn algorithm(mut self, algorithm: PolynomialMultAlg) -> Self {
+        self.strategy = Strategy::Algorithm(algorithm);
+        self
+    }
+
+    /// Use Toom-3 with the threshold `schedule(depth)` after `depth` levels,
+    /// see [`Polynomial::multiply_thresholded_with_schedule`]; replaces an earlier algorithm
+    pub fn schedule(mut self, schedule: impl Fn(usize) -> usize + Send + Sync + 'static) -> Self {
+        self.strategy = Strategy::Schedule(Arc::new(schedule));
+        self
+    }
+
+    /// Compute the pointwise products of the top Toom-3 levels on up to `threads` threads
+    /// (0 for one per core, 1 by default); other algorithms run on the calling thread
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = threads;
+        self
+    }
+
+    /// Allocate a Toom-3 workspace of `capacity` coefficients up front, reused by every product
+    /// (it still grows when a product needs more)
+    pub fn scratch_capacity(mut self, capacity: usize) -> Self {
+        self.scratch_capacity = capacity;
+        self
+    }
+
+    /// Check every product at a few random points with
+    /// [`verify_product_with_rng`](crate::verify_product_with_rng),
+    /// failing with [`PolyError::VerificationFailed`] if it is wrong
+    pub fn verify(mut self, verify: bool) -> Self {
+        self.verify = verify;
+        self
+    }
+
+    /// Seed of the generator drawing the points of [`MultiplierBuilder::verify`] (0 by default)
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Create the multiplier
+    pub fn build(self) -> Multiplier {
+        let threads = match self.threads {
+            0 => std::thread::available_parallelism().map_or(1, |n| n.get()),
+            threads => threads,
+        };
+        Multiplier {
+            strategy: self.strategy,
+            threads,
+            verify: self.verify,
+            rng: Mutex::new(StdRng::seed_from_u64(self.seed)),
+            scratch: Mutex::new(Scratch::with_capacity(self.scratch_capacity)),
+        }
+    }
+}
+
+/// Polynomial multiplier holding its algorithm, parallelism, workspace and verification settings
+///
+/// Configured once with [`Multiplier::builder`] instead of passing a threshold and further options
+/// to every call. The Toom-3 workspace is kept between products, so repeated products of similar
+/// sizes stop allocating it; concurrent calls on a shared multiplier that find it in use allocate
+/// their own. With several threads the results are bit-identical to those on one thread.
+/// ## Example
+/// ```
+/// use mult_polynomial::{Multiplier, Polynomial, PolynomialMultAlg};
+/// let multiplier = Multiplier::builder()
+///     .algorithm(PolynomialMultAlg::Thresholded(32))
+///     .threads(4)
+///     .verify(true)
+///     .build();
+/// let p = Polynomial::random(-1.0, 1.0, 2000);
+/// let product = multiplier.multiply(&p, &p).unwrap();
+/// assert_eq!(product, p.multiply_thresholded(&p, 32));
+/// ```
+pub struct Multiplier {
+    strategy: Strategy,
+    threads: usize,
+    verify: bool,
+    rng: Mutex<StdRng>,
+    scratch: Mutex<Scratch>,
+}
+
+impl Multiplier {
+    /// Settings with the defaults of [`MultiplierBuilder`]
+    pub fn builder() -> MultiplierBuilder {
+        MultiplierBuilder::default()
+    }
+
+    /// Number of threads used for a product
+    pub fn threads(&self) -> usize {
+        self.threads
+    }
+
+    /// Coefficients the kept Toom-3 workspace holds without allocating
+    pub fn scratch_capacity(&self) -> usize {
+        self.scratch.lock().map_or(0, |scratch| scratch.capacity())
+    }
+
+    /// Multiply `a` and `b`, failing only with [`MultiplierBuilder::verify`] enabled
+    /// and a product that does not pass the check
+    pub fn multiply(&self, a: &Polynomial, b: &Polynomial) -> Result<Polynomial, PolyError> {
+        let (x, y) = (&a.coeffs[..], &b.coeffs[..]);
+        let algorithm = match &self.strategy {
+            Strategy::Auto if prefers_sparse(x, y) => None,
+            Strategy::Auto => Some(PolynomialMultAlg::auto_for::<f64>(x.len(), y.len())),
+            Strategy::Algorithm(algorithm) => Some(*algorithm),
+            Strategy::Schedule(_) => None,
+        };
+        let product = match (&self.strategy, algorithm) {
+            (Strategy::Schedule(schedule), _) => self.toom3(x, y, &**schedule),
+            (_, Some(PolynomialMultAlg::CookTooms)) => self.toom3(x, y, &|_| 5),
+            (_, Some(PolynomialMultAlg::Thresholded(threshold))) => {
+                self.toom3(x, y, &|_| threshold)
+            }
+            (_, Some(algorithm)) => multiply_impl(x, y, algorithm),
+            (_, None) => sparse_multiply_impl(x, y),
+        };
+        let product = a.with_coeffs(product);
+        if self.verify && !self.check(a, b, &product) {
+            return Err(PolyError::VerificationFailed {
+                algorithm: algorithm.map_or("schedule".to_string(), column_name),
+                size: x.len(),
+                discrepancy: relative_discrepancy(&product, &a.multiply_naive(b)),
+            });
+        }
+        Ok(product)
+    }
+
+    /// Check `product` at random points drawn from the seeded generator
+    fn check(&self, a: &Polynomial, b: &Polynomial, product: &Polynomial) -> bool {
+        let mut rng = self.rng.lock().unwrap_or_else(PoisonError::into_inner);
+        verify_product_with_rng(a, b, product, VERIFY_TRIALS, &mut *rng)
+    }
+
+    /// Toom-3 product with the threshold `schedule(depth)`, on the kept workspace
+    /// or split over the threads
+    fn toom3(&self, a: &[f64], b: &[f64], schedule: &(dyn Fn(usize) -> usize + Sync)) -> Vec<f64> {
+        if self.threads > 1 {
+            return parallel_toom3(a, b, schedule, 0, self.threads);
+        }
+        let schedule: &dyn Fn(usize) -> usize = schedule;
+        match self.scratch.try_lock() {
+            Ok(mut scratch) => {
+                thresholded_multiply_with_scratch(a, b, schedule(0), Some(schedule), &mut scratch)
+            }
+            Err(_) => thresholded_multiply_with_schedule(a, b, schedule),
+        }
+    }
+}
+
+/// Toom-3 product at recursion depth `depth` computing the 5 pointwise products on their own
+/// threads while more than one of `threads` is left, then on one thread each
+///
+/// Takes the same decisions and roundings as the sequential recursion: base cases and
+/// unbalanced operands, which the recursion splits into blocks, go to it directly.
+fn parallel_toom3(
+    a: &[f64],
+    b: &[f64],
+    schedule: &(dyn Fn(usize) -> usize + Sync),
+    depth: usize,
+    threads: usize,
+) -> Vec<f64> {
+    let (n, m) = (a.len(), b.len());
+    let thr = schedule(depth).max(5);
+    if threads <= 1 || n < thr || m < thr || n >= 2 * m || m >= 2 * n {
+        return thresholded_multiply_with_schedule(a, b, &|d| schedule(depth + d));
+    }
+    let n_chunk = n.max(m).div_ceil(3);
+    let (mut a_points, mut b_points) = (vec![0.0; 3 * n_chunk], vec![0.0; 3 * n_chunk]);
+    let a_at = toom3_evaluate_into(a, n_chunk, &mut a_points);
+    let b_at = toom3_evaluate_into(b, n_chunk, &mut b_points);
+    let child_threads = (threads / 5).max(1);
+    let values: Vec<Vec<f64>> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (a_at.iter().zip(&b_at))
+            .map(|(&x, &y)| {
+                scope.spawn(move || parallel_toom3(x, y, schedule, depth + 1, child_threads))
+            })
+            .collect();
+        (handles.into_iter())
+            .map(|handle| handle.join().unwrap())
+            .collect()
+    });
+    // zero-pad the shorter products, as the sequential recursion does
+    let mut products = vec![0.0; 5 * (2 * n_chunk - 1)];
+    for (product, value) in products.chunks_exact_mut(2 * n_chunk - 1).zip(&values) {
+        product[..value.len()].copy_from_slice(value);
+    }
+    let mut result = vec![0.0; n + m - 1];
+    toom3_interpolate_into(&products, n_chunk, &mut result);
+    result
+}
+
+#[cfg(all(test, feature = "thread_rng"))]
+mod tests {
+    use super::*;
+
+    /// Every strategy matches the corresponding method, on one thread and on several
+    #[test]
+    fn test_multiplier() {
+        let p = Polynomial::random(-1.0, 1.0, 3000);
+        let q = Polynomial::random(-1.0, 1.0, 2200);
+        let schedule = |depth: usize| [40, 20][depth.min(1)];
+        let expected = p.multiply_thresholded_with_schedule(&q, schedule);
+        for threads in [1, 3, 30] {
+            let multiplier = Multiplier::builder()
+                .schedule(schedule)
+                .threads(threads)
+                .verify(true)
+                .build();
+            assert_eq!(multiplier.multiply(&p, &q).unwrap().coeffs, expected.coeffs);
+        }
+
+        let multiplier = Multiplier::builder()
+            .algorithm(PolynomialMultAlg::CookTooms)
+            .scratch_capacity(100_000)
+            .build();
+        assert!(multiplier.scratch_capacity() >= 100_000);
+        let product = multiplier.multiply(&q, &p).unwrap();
+        assert_eq!(product.coeffs, q.multiply_cook_tooms_k3(&p).coeffs);
+        // unbalanced operands are split into blocks
+        let short = Polynomial::random(-1.0, 1.0, 500);
+        let parallel = Multiplier::builder().threads(0).build();
+        assert!(parallel.threads() >= 1);
+        let product = parallel.multiply(&p, &short).unwrap();
+        assert_eq!(product, p.multiply_auto(&short));
+
+        let naive = Multiplier::builder()
+            .algorithm(PolynomialMultAlg::Naive)
+            .build();
+        let empty = Polynomial::new(vec![]);
+        assert_eq!(naive.multiply(&p, &empty).unwrap(), empty);
+        let sparse = Polynomial::new(vec![1.0, 0.0, 0.0, 2.0]);
+        let auto = Multiplier::builder().verify(true).seed(42).build();
+        assert_eq!(
+            auto.multiply(&sparse, &sparse).unwrap(),
+            sparse.multiply_naive(&sparse)
+        );
+    }
+}
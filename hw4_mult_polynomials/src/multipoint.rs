@@ -0,0 +1,228 @@
+//! Evaluation at many points at once, with blocked Horner steps or a subproduct tree
+
+use crate::fp::mul_add;
+use crate::Polynomial;
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Points evaluated together by [`Polynomial::evaluate_slice`], one independent Horner chain each
+const LANES: usize = 16;
+
+/// Multiply-adds (points times coefficients) from which [`Polynomial::evaluate_slice`]
+/// spreads the points over one thread per available core
+#[cfg(feature = "std")]
+const PARALLEL_WORK: usize = 1 << 20;
+
+/// Most points of a leaf of the subproduct tree, evaluated one by one with Horner's scheme
+const LEAF_POINTS: usize = 16;
+
+/// Shortest quotient and divisor reduced with [`Polynomial::div_rem_fast`] instead of long division
+const FAST_DIVISION: usize = 64;
+
+/// Node of the subproduct tree: the monic polynomial vanishing on its points
+enum Node<'a> {
+    Leaf {
+        product: Polynomial,
+        points: &'a [f64],
+    },
+    Inner {
+        product: Polynomial,
+        children: Box<[Node<'a>; 2]>,
+    },
+}
+
+impl<'a> Node<'a> {
+    /// Tree over `points`, halved until at most [`LEAF_POINTS`] are left
+    fn new(points: &'a [f64]) -> Node<'a> {
+        if points.len() <= LEAF_POINTS {
+            return Node::Leaf {
+                product: Polynomial::from_roots(points),
+                points,
+            };
+        }
+        let (low, high) = points.split_at(points.len() / 2);
+        let children = Box::new([Node::new(low), Node::new(high)]);
+        let product = children[0].product().multiply_auto(children[1].product());
+        Node::Inner { product, children }
+    }
+
+    fn product(&self) -> &Polynomial {
+        match self {
+            Node::Leaf { product, .. } | Node::Inner { product, .. } => product,
+        }
+    }
+
+    /// Append the values at the points of the node of a polynomial reduced modulo its product
+    fn evaluate_into(&self, p: &Polynomial, values: &mut Vec<f64>) {
+        match self {
+            Node::Leaf { points, .. } => values.extend(points.iter().map(|&x| p.evaluate(x))),
+            Node::Inner { children, .. } => {
+                for child in children.iter() {
+                    child.evaluate_into(&remainder(p, child.product()), values);
+                }
+            }
+        }
+    }
+}
+
+/// Remainder of `p` modulo the monic `divisor`, by the faster division for its sizes
+fn remainder(p: &Polynomial, divisor: &Polynomial) -> Polynomial {
+    let (n, m) = (p.coeffs.len(), divisor.coeffs.len());
+    if n < m {
+        return p.clone();
+    }
+    let division = if (n - m + 1).min(m) >= FAST_DIVISION {
+        p.div_rem_fast(divisor)
+    } else {
+        p.div_rem(divisor)
+    };
+    // the divisor is monic, so never zero
+    division.map_or_else(|_| p.clone(), |(_, r)| r)
+}
+
+impl Polynomial {
+    /// Evaluate at every point of `points`, in the same order
+    ///
+    /// The points are the roots of the products in a balanced subproduct tree; the polynomial is
+    /// reduced modulo the product at the root and then modulo each child down the tree, so every
+    /// leaf of up to 16 points evaluates a remainder of low degree. With fast multiplication
+    /// and division this takes `O(M(n) log n)` for `n` points and coefficients instead of the
+    /// `O(n²)` of evaluating one point at a time.
+    ///
+    /// The remainders are only well-conditioned for points well inside `(-1, 1)`: at points up to
+    /// `±1/2` the values agree with [`Polynomial::evaluate`] to a few ulps, but with points near
+    /// `±1` and beyond a few dozen of them, high-degree remainders lose all accuracy.
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// let p = Polynomial::new(vec![1.0, -2.0, 0.5, 3.0]);
+    /// let points = [0.5, -1.0, 2.0];
+    /// let values = p.evaluate_multipoint(&points);
+    /// for (value, &x) in values.iter().zip(&points) {
+    ///     assert!((value - p.evaluate(x)).abs() < 1e-12);
+    /// }
+    /// ```
+    pub fn evaluate_multipoint(&self, points: &[f64]) -> Vec<f64> {
+        let tree = Node::new(points);
+        let mut values = Vec::with_capacity(points.len());
+        tree.evaluate_into(&remainder(self, tree.product()), &mut values);
+        values
+    }
+}
+
+/// Values at `xs` into `values`, by Horner's scheme on blocks of [`LANES`] points
+///
+/// Every step updates the whole block with the same coefficient, so the multiply-adds of a step
+/// are independent of each other and vectorize.
+fn evaluate_blocked(coeffs: &[f64], xs: &[f64], values: &mut [f64]) {
+    let Some((&leading, rest)) = coeffs.split_last() else {
+        return values.fill(0.0);
+    };
+    for (xs, values) in xs.chunks(LANES).zip(values.chunks_mut(LANES)) {
+        let mut x = [0.0; LANES];
+        x[..xs.len()].copy_from_slice(xs);
+        let mut acc = [leading; LANES];
+        for &c in rest.iter().rev() {
+            for (a, &x) in acc.iter_mut().zip(&x) {
+                *a = mul_add(*a, x, c);
+            }
+        }
+        values.copy_from_slice(&acc[..values.len()]);
+    }
+}
+
+impl Polynomial {
+    /// Values at each of `xs`, by Horner's scheme on blocks of 16 points at a time
+    ///
+    /// The 16 Horner chains of a block share every coefficient load and run side by side,
+    /// so the compiler vectorizes them, and with the `std` feature large inputs
+    /// (from about 10⁶ points times coefficients) are split over one thread per available core.
+    /// This is the way to sample a polynomial at many points, e.g. for plotting: unlike
+    /// [`Polynomial::evaluate_multipoint`] it costs `O(n)` per point but is accurate everywhere
+    /// and has no setup. Results agree with [`Polynomial::evaluate`] up to rounding.
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// let p = Polynomial::new(vec![1.0, -2.0, 0.5, 3.0]);
+    /// let xs: Vec<f64> = (0..100).map(|i| i as f64 / 10.0 - 5.0).collect();
+    /// let values = p.evaluate_slice(&xs);
+    /// for (value, &x) in values.iter().zip(&xs) {
+    ///     assert!((value - p.evaluate(x)).abs() < 1e-12 * p.evaluate(x).abs().max(1.0));
+    /// }
+    /// ```
+    pub fn evaluate_slice(&self, xs: &[f64]) -> Vec<f64> {
+        let mut values = vec![0.0; xs.len()];
+        #[cfg(feature = "std")]
+        if xs.len() * self.coeffs.len() >= PARALLEL_WORK {
+            let workers = std::thread::available_parallelism().map_or(1, |n| n.get());
+            let chunk = xs.len().div_ceil(workers).next_multiple_of(LANES);
+            std::thread::scope(|scope| {
+                for (xs, values) in xs.chunks(chunk).zip(values.chunks_mut(chunk)) {
+                    scope.spawn(|| evaluate_blocked(&self.coeffs, xs, values));
+                }
+            });
+            return values;
+        }
+        evaluate_blocked(&self.coeffs, xs, &mut values);
+        values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Values match Horner's scheme in order, across several levels of the tree
+    #[test]
+    fn test_evaluate_multipoint() {
+        let p = Polynomial::new(
+            (0..300)
+                .map(|k| ((k * 7) % 11) as f64 / 10.0 - 0.5)
+                .collect(),
+        );
+        let points: Vec<f64> = (0..100).map(|i| 0.5 * (i as f64 * 0.37).sin()).collect();
+        let values = p.evaluate_multipoint(&points);
+        assert_eq!(values.len(), points.len());
+        for (value, &x) in values.iter().zip(&points) {
+            let expected = p.evaluate(x);
+            assert!((value - expected).abs() < 1e-13 * expected.abs().max(1.0));
+        }
+        assert!(p.evaluate_multipoint(&[]).is_empty());
+        let line = Polynomial::new(vec![1.0, 2.0]);
+        assert_eq!(line.evaluate_multipoint(&[3.0; 20]), vec![7.0; 20]);
+    }
+
+    /// Values match Horner's scheme for partial blocks, the zero polynomial and the parallel split
+    #[cfg(feature = "thread_rng")]
+    #[test]
+    fn test_evaluate_slice() {
+        let p = Polynomial::random(-1.0, 1.0, 40);
+        for len in [0, 1, 15, 16, 17, 100] {
+            let xs: Vec<f64> = (0..len).map(|i| 1.5 * (i as f64 * 0.61).sin()).collect();
+            let values = p.evaluate_slice(&xs);
+            assert_eq!(values.len(), len);
+            for (value, &x) in values.iter().zip(&xs) {
+                let expected = p.evaluate(x);
+                assert!((value - expected).abs() < 1e-12 * expected.abs().max(1.0));
+            }
+        }
+        assert_eq!(
+            Polynomial::new(vec![]).evaluate_slice(&[1.0, 2.0]),
+            vec![0.0; 2]
+        );
+        assert_eq!(
+            Polynomial::new(vec![3.0]).evaluate_slice(&[f64::INFINITY]),
+            vec![3.0]
+        );
+
+        // 2000 coefficients at 1000 points are split over the threads
+        let q = Polynomial::random(-1.0, 1.0, 2000);
+        let xs: Vec<f64> = (0..1000).map(|i| i as f64 / 1000.0 - 0.5).collect();
+        let values = q.evaluate_slice(&xs);
+        let mut sequential = vec![0.0; xs.len()];
+        evaluate_blocked(&q.coeffs, &xs, &mut sequential);
+        assert_eq!(values, sequential);
+        assert!((values[999] - q.evaluate(xs[999])).abs() < 1e-12);
+    }
+}
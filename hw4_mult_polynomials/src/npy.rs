@@ -0,0 +1,265 @@
+//! NumPy `.npy` files of coefficients
+//!
+//! A `.npy` file holds the magic `\x93NUMPY`, the format version (two bytes), the length of the
+//! header (`u16` in version 1, `u32` in versions 2 and 3), and the header: a Python dict literal
+//! with the keys `descr` (dtype), `fortran_order` and `shape`, padded with spaces to a multiple of
+//! 64 bytes and ended by a line break. The array data follows.
+//!
+//! Polynomials are written as 1-D little-endian `f64` arrays (`<f8`), which `numpy.load` reads
+//! bit for bit. Reading also takes `f4` arrays of either byte order, converted exactly, and arrays
+//! of any shape with at most one dimension above 1 (e.g. row or column vectors).
+
+use crate::serialization::{create, open};
+use crate::{PolyError, Polynomial};
+use std::io::{Read, Write};
+use std::path::Path;
+
+/// Magic bytes at the start of `.npy` files
+const NPY_MAGIC: &[u8; 6] = b"\x93NUMPY";
+
+/// Alignment in bytes of the array data, i.e. of the header with the preamble
+const NPY_ALIGN: usize = 64;
+
+/// Value of `key` in the dict literal `header`, up to the end of the literal
+fn header_field<'a>(header: &'a str, key: &str) -> Result<&'a str, PolyError> {
+    let quoted = [format!("'{}'", key), format!("\"{}\"", key)];
+    let start = (quoted.iter())
+        .find_map(|k| header.find(k.as_str()).map(|i| i + k.len()))
+        .ok_or_else(|| PolyError::InvalidFormat(format!("missing `{}` in the .npy header", key)))?;
+    let rest = header[start..].trim_start();
+    let rest = rest.strip_prefix(':').unwrap_or(rest).trim_start();
+    Ok(rest)
+}
+
+/// Element type of a `.npy` array: (byte size, big-endian)
+fn parse_descr(header: &str) -> Result<(usize, bool), PolyError> {
+    let value = header_field(header, "descr")?;
+    let descr = (value.strip_prefix(['\'', '"']))
+        .and_then(|v| v.split(['\'', '"']).next())
+        .ok_or_else(|| PolyError::InvalidFormat("invalid dtype in the .npy header".to_string()))?;
+    match descr {
+        "<f8" | "=f8" => Ok((8, false)),
+        ">f8" => Ok((8, true)),
+        "<f4" | "=f4" => Ok((4, false)),
+        ">f4" => Ok((4, true)),
+        _ => Err(PolyError::CoefficientTypeMismatch {
+            found: descr.to_string(),
+            expected: "<f8".to_string(),
+        }),
+    }
+}
+
+/// Number of elements of the `shape` tuple of a `.npy` header, a vector in one dimension at most
+fn parse_shape(header: &str) -> Result<usize, PolyError> {
+    let value = header_field(header, "shape")?;
+    let invalid = || PolyError::InvalidFormat("invalid shape in the .npy header".to_string());
+    let tuple = (value.strip_prefix('('))
+        .and_then(|v| v.split(')').next())
+        .ok_or_else(invalid)?;
+    let dims = (tuple.split(','))
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+        .map(|d| d.parse::<usize>().map_err(|_| invalid()))
+        .collect::<Result<Vec<_>, _>>()?;
+    if dims.iter().filter(|&&d| d > 1).count() > 1 {
+        return Err(PolyError::InvalidFormat(format!(
+            "a .npy array of shape ({}) is not a vector of coefficients",
+            tuple.trim()
+        )));
+    }
+    Ok(dims.iter().product())
+}
+
+impl Polynomial {
+    /// Write the coefficients as a 1-D little-endian `f64` NumPy array (`.npy` format version 1.0)
+    ///
+    /// Requires the `npy` feature.
+    pub fn write_npy<W: Write>(&self, mut writer: W) -> Result<(), PolyError> {
+        let mut header = format!(
+            "{{'descr': '<f8', 'fortran_order': False, 'shape': ({},), }}",
+            self.coeffs.len()
+        );
+        // preamble: magic, version, u16 header length
+        let preamble = NPY_MAGIC.len() + 2 + 2;
+        let padded = (preamble + header.len() + 1).div_ceil(NPY_ALIGN) * NPY_ALIGN;
+        header.extend(std::iter::repeat_n(
+            ' ',
+            padded - preamble - header.len() - 1,
+        ));
+        header.push('\n');
+        writer.write_all(NPY_MAGIC)?;
+        writer.write_all(&[1, 0])?;
+        writer.write_all(&(header.len() as u16).to_le_bytes())?;
+        writer.write_all(header.as_bytes())?;
+        for c in &self.coeffs {
+            writer.write_all(&c.to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Read the coefficients of a NumPy array in the `.npy` format, from lowest to highest degree
+    ///
+    /// Requires the `npy` feature. Takes `f8` and `f4` arrays of either byte order
+    /// with at most one dimension above 1, and fails with [`PolyError::CoefficientTypeMismatch`]
+    /// for other dtypes.
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// let p = Polynomial::new(vec![0.1, -2.0, 3e300]);
+    /// let mut bytes = Vec::new();
+    /// p.write_npy(&mut bytes).unwrap();
+    /// assert_eq!(bytes.len(), 128 + 3 * 8);
+    /// assert_eq!(Polynomial::read_npy(bytes.as_slice()).unwrap(), p);
+    /// ```
+    pub fn read_npy<R: Read>(mut reader: R) -> Result<Polynomial, PolyError> {
+        let mut magic = [0; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic[..6] != NPY_MAGIC {
+            return Err(PolyError::InvalidFormat("missing .npy magic".to_string()));
+        }
+        let header_len = match magic[6] {
+            1 => {
+                let mut len = [0; 2];
+                reader.read_exact(&mut len)?;
+                u16::from_le_bytes(len) as usize
+            }
+            2 | 3 => {
+                let mut len = [0; 4];
+                reader.read_exact(&mut len)?;
+                u32::from_le_bytes(len) as usize
+            }
+            version => {
+                return Err(PolyError::UnsupportedVersion {
+                    found: version as u32,
+                    supported: 3,
+                })
+            }
+        };
+        let mut header = vec![0; header_len];
+        reader.read_exact(&mut header)?;
+        let header = String::from_utf8_lossy(&header);
+        let (size, big_endian) = parse_descr(&header)?;
+        let len = parse_shape(&header)?;
+
+        let mut coeffs = Vec::with_capacity(len.min(1 << 20));
+        let mut word = [0; 8];
+        for _ in 0..len {
+            let bytes = &mut word[..size];
+            reader.read_exact(bytes)?;
+            if big_endian {
+                bytes.reverse();
+            }
+            coeffs.push(match size {
+                4 => f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f64,
+                _ => f64::from_le_bytes(word),
+            });
+        }
+        Ok(Polynomial::new(coeffs))
+    }
+
+    /// Read a `.npy` file, e.g. written by `numpy.save("a.npy", a)`
+    ///
+    /// Requires the `npy` feature. See [`Polynomial::read_npy`].
+    pub fn from_npy(path: impl AsRef<Path>) -> Result<Polynomial, PolyError> {
+        Polynomial::read_npy(open(path.as_ref())?)
+    }
+
+    /// Write a `.npy` file, to be read by `numpy.load("a.npy")`
+    ///
+    /// Requires the `npy` feature. See [`Polynomial::write_npy`].
+    pub fn to_npy(&self, path: impl AsRef<Path>) -> Result<(), PolyError> {
+        let mut writer = create(path.as_ref())?;
+        self.write_npy(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `.npy` bytes with the given format version, header and data, as written by NumPy
+    fn npy_bytes(version: u8, header: &str, data: &[u8]) -> Vec<u8> {
+        let mut bytes = NPY_MAGIC.to_vec();
+        bytes.extend([version, 0]);
+        match version {
+            1 => bytes.extend((header.len() as u16).to_le_bytes()),
+            _ => bytes.extend((header.len() as u32).to_le_bytes()),
+        }
+        bytes.extend(header.as_bytes());
+        bytes.extend(data);
+        bytes
+    }
+
+    /// Test the round trip, the header alignment and files written by NumPy
+    #[test]
+    fn test_npy() {
+        for len in [0, 1, 100, 100000] {
+            let p = match len {
+                0 => Polynomial::new(vec![]),
+                _ => Polynomial::random(-10.0, 10.0, len),
+            };
+            let mut bytes = Vec::new();
+            p.write_npy(&mut bytes).unwrap();
+            assert_eq!((bytes.len() - 8 * len) % NPY_ALIGN, 0);
+            assert_eq!(bytes[bytes.len() - 8 * len - 1], b'\n');
+            assert_eq!(Polynomial::read_npy(bytes.as_slice()).unwrap(), p);
+        }
+        let path = std::env::temp_dir().join("mult_polynomial_npy_test.npy");
+        let p = Polynomial::new(vec![1.0, f64::MIN_POSITIVE, -1e300]);
+        p.to_npy(&path).unwrap();
+        assert_eq!(Polynomial::from_npy(&path).unwrap(), p);
+
+        // `numpy.save` of `np.array([1.5, -2.0], dtype=">f4").reshape(1, 2)`, in format 2.0
+        let header = "{'descr': '>f4', 'fortran_order': False, 'shape': (1, 2), }";
+        let data: Vec<u8> = [1.5f32, -2.0]
+            .iter()
+            .flat_map(|c| c.to_be_bytes())
+            .collect();
+        let p = Polynomial::read_npy(npy_bytes(2, header, &data).as_slice()).unwrap();
+        assert_eq!(p, Polynomial::new(vec![1.5, -2.0]));
+        // column vector of `<f8`, double quotes
+        let header = "{\"descr\": \"<f8\", \"fortran_order\": True, \"shape\": (2, 1)}\n";
+        let data: Vec<u8> = [3.0f64, 4.0].iter().flat_map(|c| c.to_le_bytes()).collect();
+        let p = Polynomial::read_npy(npy_bytes(1, header, &data).as_slice()).unwrap();
+        assert_eq!(p, Polynomial::new(vec![3.0, 4.0]));
+    }
+
+    /// Test the rejection of matrices, other dtypes, newer versions and truncated files
+    #[test]
+    fn test_npy_invalid() {
+        let data = [0; 32];
+        let matrix = "{'descr': '<f8', 'fortran_order': False, 'shape': (2, 2), }";
+        assert!(matches!(
+            Polynomial::read_npy(npy_bytes(1, matrix, &data).as_slice()),
+            Err(PolyError::InvalidFormat(message)) if message.contains("(2, 2)")
+        ));
+        let integers = "{'descr': '<i8', 'fortran_order': False, 'shape': (4,), }";
+        assert_eq!(
+            Polynomial::read_npy(npy_bytes(1, integers, &data).as_slice()),
+            Err(PolyError::CoefficientTypeMismatch {
+                found: "<i8".to_string(),
+                expected: "<f8".to_string()
+            })
+        );
+        let vector = "{'descr': '<f8', 'fortran_order': False, 'shape': (4,), }";
+        assert!(matches!(
+            Polynomial::read_npy(npy_bytes(4, vector, &data).as_slice()),
+            Err(PolyError::UnsupportedVersion { found: 4, .. })
+        ));
+        assert!(matches!(
+            Polynomial::read_npy(npy_bytes(1, vector, &data[..31]).as_slice()),
+            Err(PolyError::Io(_))
+        ));
+        assert!(matches!(
+            Polynomial::read_npy(&b"MPLY\x01\x00\x00\x00"[..]),
+            Err(PolyError::InvalidFormat(_))
+        ));
+        let shapeless = "{'descr': '<f8', 'fortran_order': False}";
+        assert!(matches!(
+            Polynomial::read_npy(npy_bytes(1, shapeless, &data).as_slice()),
+            Err(PolyError::InvalidFormat(_))
+        ));
+    }
+}
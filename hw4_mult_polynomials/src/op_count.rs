@@ -0,0 +1,96 @@
+//! Counting of the scalar floating-point operations performed by the kernels
+//!
+//! With the `op_count` feature, every kernel records how many multiplications
+//! (including divisions) and additions (including subtractions) it performs,
+//! and [`count_ops`] returns the totals of a closure. Without the feature the
+//! recording compiles to nothing.
+
+#[cfg(feature = "op_count")]
+use std::cell::Cell;
+
+/// Number of scalar floating-point operations
+///
+/// A fused multiply-add counts as one multiplication and one addition.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OpCounts {
+    /// Multiplications and divisions
+    pub mults: u64,
+    /// Additions and subtractions
+    pub adds: u64,
+}
+
+#[cfg(feature = "op_count")]
+std::thread_local! {
+    /// Operations recorded on this thread so far
+    static COUNTS: Cell<OpCounts> = const { Cell::new(OpCounts { mults: 0, adds: 0 }) };
+}
+
+/// Add `mults` multiplications and `adds` additions to the counts of the current thread
+#[inline(always)]
+pub(crate) fn record(mults: usize, adds: usize) {
+    #[cfg(feature = "op_count")]
+    COUNTS.with(|counts| {
+        let c = counts.get();
+        counts.set(OpCounts {
+            mults: c.mults + mults as u64,
+            adds: c.adds + adds as u64,
+        });
+    });
+    #[cfg(not(feature = "op_count"))]
+    let _ = (mults, adds);
+}
+
+/// Run `f` and count the floating-point operations it performs on the current thread
+///
+/// Requires the `op_count` feature. Work done on other threads
+/// (e.g. by [`crate::multiply_batch_parallel`]) is not included.
+/// ## Example
+/// ```
+/// use mult_polynomial::{count_ops, Polynomial};
+/// let p = Polynomial::new(vec![1.0; 100]);
+/// let (_, naive) = count_ops(|| p.multiply_naive(&p));
+/// assert_eq!(naive.mults, 100 * 100);
+/// let (_, toom3) = count_ops(|| p.multiply_cook_tooms_k3(&p));
+/// assert!(toom3.mults < naive.mults);
+/// ```
+#[cfg(feature = "op_count")]
+pub fn count_ops<T>(f: impl FnOnce() -> T) -> (T, OpCounts) {
+    let before = COUNTS.with(Cell::get);
+    let result = f();
+    let after = COUNTS.with(Cell::get);
+    let counts = OpCounts {
+        mults: after.mults - before.mults,
+        adds: after.adds - before.adds,
+    };
+    (result, counts)
+}
+
+#[cfg(all(test, feature = "op_count"))]
+mod tests {
+    use super::*;
+    use crate::Polynomial;
+    use alloc::vec;
+
+    /// Naive counts match n·m exactly and Toom-3 follows its 5-way recursion
+    #[test]
+    fn test_count_ops() {
+        let p1 = Polynomial::new(vec![1.0; 10]);
+        let p2 = Polynomial::new(vec![2.0; 7]);
+        let (_, counts) = count_ops(|| p1.multiply_naive(&p2));
+        assert_eq!(
+            counts,
+            OpCounts {
+                mults: 70,
+                adds: 70
+            }
+        );
+
+        // chunks of 27, 9 and 3 coefficients on 1, 5 and 25 nodes, then 125 base products of 3 x 3
+        let p = Polynomial::new(vec![1.0; 81]);
+        let (_, counts) = count_ops(|| p.multiply_thresholded(&p, 5));
+        let base = 125 * 9;
+        let evaluations = 2 * (27 + 5 * 9 + 25 * 3); // 2 operands, 1 scaling per coefficient
+        let interpolations = 4 * (53 + 5 * 17 + 25 * 5); // 4 scalings per product coefficient
+        assert_eq!(counts.mults, (base + evaluations + interpolations) as u64);
+    }
+}
@@ -0,0 +1,217 @@
+//! Log-log SVG plots of benchmark results via `plotters`
+//!
+//! Enabled with the `plot` feature. [`plot_results`] writes
+//! - `time_vs_size.svg`: aggregated time (see [`crate::benchmark::Aggregation`]) against input size, one line per algorithm
+//!   (and per aspect ratio in the rectangular mode, and per input distribution if several are swept)
+//! - `time_vs_threshold.svg`: aggregated time of the thresholded algorithm against the threshold,
+//!   one line per input size
+//!
+//! into the output directory of the benchmark, next to the CSV or JSON results, and
+//! [`plot_evaluation`] writes `evaluation_vs_size.svg` next to `evaluation.csv`.
+
+use crate::benchmark::{
+    column_names, is_rectangular, sweeps_distributions, BenchmarkConfig, EvaluationResult,
+    EvaluationStrategy, InputDistribution, SizeResult,
+};
+use crate::{PolyError, PolynomialMultAlg};
+use plotters::prelude::*;
+use std::path::Path;
+
+/// Size of the generated images in pixels
+const IMAGE_SIZE: (u32, u32) = (1024, 768);
+
+/// One line of a plot, as `(x, y)` points with positive coordinates
+type Series = (String, Vec<(f64, f64)>);
+
+/// Plot the benchmark results into the output directory of `config`
+///
+/// The threshold plot is only written if at least two thresholds were benchmarked.
+pub fn plot_results(config: &BenchmarkConfig, results: &[SizeResult]) -> Result<(), PolyError> {
+    let shapes: Vec<(usize, InputDistribution)> = (config.aspect_ratios.iter())
+        .flat_map(|&ratio| config.distributions.iter().map(move |&d| (ratio, d)))
+        .collect();
+    let by_algorithm: Vec<Series> = (column_names(config).into_iter().enumerate())
+        .flat_map(|(j, name)| {
+            shapes.iter().map(move |&(ratio, distribution)| {
+                let points = (results.iter())
+                    .filter(|r| r.aspect_ratio == ratio && r.distribution == distribution)
+                    .filter(|r| !r.skipped(j))
+                    .map(|r| (r.size as f64, r.aggregate(j, config.aggregation)))
+                    .collect();
+                let mut label = name.clone();
+                if is_rectangular(config) {
+                    label += &format!(" {}:1", ratio);
+                }
+                if sweeps_distributions(config) {
+                    label += &format!(" {}", distribution);
+                }
+                (label, points)
+            })
+        })
+        .collect();
+    draw(
+        &config.output_dir.join("time_vs_size.svg"),
+        "Time vs size",
+        "size",
+        &by_algorithm,
+    )?;
+
+    let thresholds: Vec<(usize, usize)> = (config.algorithms.iter().enumerate())
+        .filter_map(|(j, algorithm)| match algorithm {
+            PolynomialMultAlg::Thresholded(threshold) => Some((j, *threshold)),
+            _ => None,
+        })
+        .collect();
+    if thresholds.len() >= 2 {
+        let by_size: Vec<Series> = results
+            .iter()
+            .map(|r| {
+                let points = (thresholds.iter())
+                    .filter(|&&(j, _)| !r.skipped(j))
+                    .map(|&(j, threshold)| (threshold as f64, r.aggregate(j, config.aggregation)))
+                    .collect();
+                let mut label = if is_rectangular(config) {
+                    format!("size {} x {}", r.size, r.other_size)
+                } else {
+                    format!("size {}", r.size)
+                };
+                if sweeps_distributions(config) {
+                    label += &format!(" {}", r.distribution);
+                }
+                (label, points)
+            })
+            .collect();
+        draw(
+            &config.output_dir.join("time_vs_threshold.svg"),
+            "Time vs threshold",
+            "threshold",
+            &by_size,
+        )?;
+    }
+    Ok(())
+}
+
+/// Plot the times of the evaluation strategies against the size into `evaluation_vs_size.svg`,
+/// one line per strategy and number of points
+pub fn plot_evaluation(
+    config: &BenchmarkConfig,
+    results: &[EvaluationResult],
+) -> Result<(), PolyError> {
+    let series: Vec<Series> = (EvaluationStrategy::ALL.iter().enumerate())
+        .flat_map(|(j, strategy)| {
+            config.evaluation_points.iter().map(move |&k| {
+                let points = (results.iter())
+                    .filter(|r| r.points == k)
+                    .map(|r| (r.size as f64, r.aggregate(j, config.aggregation)))
+                    .collect();
+                (format!("{} {} points", strategy.column_name(), k), points)
+            })
+        })
+        .collect();
+    draw(
+        &config.output_dir.join("evaluation_vs_size.svg"),
+        "Evaluation time vs size",
+        "size",
+        &series,
+    )
+}
+
+/// Draw the series on log-log axes into an SVG file
+fn draw(path: &Path, caption: &str, x_label: &str, series: &[Series]) -> Result<(), PolyError> {
+    let plot_error =
+        |e: &dyn std::fmt::Display| PolyError::Io(format!("{}: {}", path.display(), e));
+    let points = || {
+        series
+            .iter()
+            .flat_map(|(_, p)| p)
+            .filter(|(x, y)| *x > 0.0 && *y > 0.0)
+    };
+    let (x_min, x_max) = points().fold((f64::MAX, f64::MIN), |(lo, hi), &(x, _)| {
+        (lo.min(x), hi.max(x))
+    });
+    let (y_min, y_max) = points().fold((f64::MAX, f64::MIN), |(lo, hi), &(_, y)| {
+        (lo.min(y), hi.max(y))
+    });
+    if x_min > x_max {
+        return Ok(()); // nothing to plot
+    }
+
+    let root = SVGBackend::new(path, IMAGE_SIZE).into_drawing_area();
+    root.fill(&WHITE).map_err(|e| plot_error(&e))?;
+    let mut chart = ChartBuilder::on(&root)
+        .caption(caption, ("sans-serif", 28))
+        .margin(16)
+        .x_label_area_size(48)
+        .y_label_area_size(80)
+        .build_cartesian_2d(
+            (x_min..x_max * 1.01).log_scale(),
+            (y_min * 0.9..y_max * 1.1).log_scale(),
+        )
+        .map_err(|e| plot_error(&e))?;
+    chart
+        .configure_mesh()
+        .x_desc(x_label)
+        .y_desc("time (s)")
+        .y_label_formatter(&|y| format!("{:.0e}", y))
+        .draw()
+        .map_err(|e| plot_error(&e))?;
+
+    for (i, (label, points)) in series.iter().enumerate() {
+        let color = Palette99::pick(i).to_rgba();
+        let points = points.iter().copied().filter(|(x, y)| *x > 0.0 && *y > 0.0);
+        chart
+            .draw_series(LineSeries::new(points, color.stroke_width(2)))
+            .map_err(|e| plot_error(&e))?
+            .label(label.as_str())
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 16, y)], color));
+    }
+    chart
+        .configure_series_labels()
+        .position(SeriesLabelPosition::UpperLeft)
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()
+        .map_err(|e| plot_error(&e))?;
+    root.present().map_err(|e| plot_error(&e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every plot is written next to the benchmark results
+    #[test]
+    fn test_plot_results() {
+        let config = BenchmarkConfig {
+            sizes: vec![8, 64],
+            algorithms: vec![
+                PolynomialMultAlg::Naive,
+                PolynomialMultAlg::Thresholded(8),
+                PolynomialMultAlg::Thresholded(16),
+            ],
+            output_dir: std::env::temp_dir().join("mult_polynomial_plot_test"),
+            ..BenchmarkConfig::default()
+        };
+        let results = crate::benchmark::run_comprehensive_benchmark(&config, &|_| 2).unwrap();
+        plot_results(&config, &results).unwrap();
+        for (name, label) in [
+            ("time_vs_size.svg", "threshold_16"),
+            ("time_vs_threshold.svg", "size 64"),
+        ] {
+            let svg = std::fs::read_to_string(config.output_dir.join(name)).unwrap();
+            assert!(svg.starts_with("<svg"));
+            assert!(svg.contains(label));
+        }
+
+        let config = BenchmarkConfig {
+            evaluation_points: vec![1, 20],
+            ..config
+        };
+        let results = crate::benchmark::run_evaluation_benchmark(&config, &|_| 2).unwrap();
+        plot_evaluation(&config, &results).unwrap();
+        let svg =
+            std::fs::read_to_string(config.output_dir.join("evaluation_vs_size.svg")).unwrap();
+        assert!(svg.contains("multipoint_time 20 points"));
+    }
+}
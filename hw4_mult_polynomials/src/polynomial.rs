@@ -1,209 +1,1812 @@
+use crate::fp::mul_add;
+use crate::op_count::record;
+use crate::sparse::prefers_sparse;
+use crate::trace::span;
+use crate::tree::{NodeAlgorithm, RecursionTree};
+use crate::{Coefficient, PolyError};
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
 use rand::Rng;
 
-/// Represents a polynomial with real coefficients
+/// Represents a polynomial with real coefficients of type `T` (`f64` unless specified)
+///
+/// Create it with [`Polynomial::new`], [`Polynomial::with_normalization`] or `From<Vec<T>>`:
+/// the private normalization policy means a struct literal `Polynomial { coeffs }` no longer compiles.
 #[derive(Debug, Clone)]
-pub struct Polynomial {
+pub struct Polynomial<T = f64> {
     /// Coefficients of the polynomial, from lowest to highest degree
-    pub coeffs: Vec<f64>,
+    pub coeffs: Vec<T>,
+    /// Trimming applied when the polynomial and the results computed from it are created
+    normalization: Normalization,
 }
 
-impl PartialEq for Polynomial {
+/// Policy for trimming the highest-degree coefficients of a polynomial
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Normalization {
+    /// Drop trailing coefficients whose absolute value is below the given epsilon
+    Trim(f64),
+    /// Keep all coefficients, including trailing zeros
+    Keep,
+}
+
+impl Normalization {
+    /// Trimming used by [`Polynomial::new`] for `f64` coefficients
+    pub const DEFAULT: Normalization = Normalization::Trim(1e-12);
+
+    /// Trim `coeffs` according to the policy
+    pub(crate) fn apply<T: Coefficient>(self, mut coeffs: Vec<T>) -> Vec<T> {
+        if let Normalization::Trim(epsilon) = self {
+            while coeffs.last().is_some_and(|c| c.abs().to_f64() < epsilon) {
+                coeffs.pop();
+            }
+        }
+        coeffs
+    }
+}
+
+impl Default for Normalization {
+    fn default() -> Self {
+        Normalization::DEFAULT
+    }
+}
+
+impl<T: Coefficient> PartialEq for Polynomial<T> {
     fn eq(&self, other: &Self) -> bool {
         // allow a threshold
-        let threshold = 1e-12;
+        let threshold = T::TOLERANCE;
         self.coeffs
             .iter()
             .zip(other.coeffs.iter())
-            .all(|(a, b)| (a - b).abs() < threshold)
+            .all(|(&a, &b)| (a - b).abs().to_f64() < threshold)
             && self.coeffs.len() == other.coeffs.len()
     }
 }
 
-impl Polynomial {
-    /// Create a new polynomial from a vector of coefficients
-    pub fn new(coeffs: Vec<f64>) -> Self {
-        // Remove trailing zeros
-        let mut result = coeffs;
-        while result.len() > 0 && result.last().unwrap_or(&0.0).abs() < 1e-12 {
-            result.pop();
+impl<T: Coefficient> From<Vec<T>> for Polynomial<T> {
+    /// Same as [`Polynomial::new`]
+    fn from(coeffs: Vec<T>) -> Self {
+        Polynomial::new(coeffs)
+    }
+}
+
+impl<T: Coefficient> Polynomial<T> {
+    /// Create a new polynomial from a vector of coefficients
+    ///
+    /// Trailing coefficients with absolute value below [`Coefficient::TOLERANCE`]
+    /// (`1e-12` for `f64`) are removed, see [`Polynomial::with_normalization`] to change this.
+    pub fn new(coeffs: Vec<T>) -> Self {
+        Polynomial::with_normalization(coeffs, Normalization::Trim(T::TOLERANCE))
+    }
+
+    /// Create a new polynomial with the given trimming policy
+    ///
+    /// The policy is also applied to every polynomial computed from this one
+    /// (products, squares, powers, convolutions, ...), taking the policy of the left operand.
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::{Normalization, Polynomial};
+    /// let tiny = Polynomial::with_normalization(vec![1.0, 1e-15], Normalization::Keep);
+    /// assert_eq!(tiny.degree(), 1);
+    /// assert_eq!(tiny.square().degree(), 2);
+    /// let trimmed = Polynomial::with_normalization(vec![1.0, 1e-3], Normalization::Trim(1e-2));
+    /// assert_eq!(trimmed.degree(), 0);
+    /// ```
+    pub fn with_normalization(coeffs: Vec<T>, normalization: Normalization) -> Self {
+        Polynomial {
+            coeffs: normalization.apply(coeffs),
+            normalization,
+        }
+    }
+
+    /// Trimming policy of the polynomial
+    pub fn normalization(&self) -> Normalization {
+        self.normalization
+    }
+
+    /// Trim the coefficients again according to the normalization policy,
+    /// e.g. after zeroing the leading coefficient through the public `coeffs` or indexing
+    pub fn normalize(&mut self) {
+        self.coeffs = self.normalization.apply(core::mem::take(&mut self.coeffs));
+    }
+
+    /// Polynomial with the given coefficients and the trimming policy of `self`
+    pub(crate) fn with_coeffs(&self, coeffs: Vec<T>) -> Polynomial<T> {
+        Polynomial::with_normalization(coeffs, self.normalization)
+    }
+
+    /// Compare with another polynomial coefficient by coefficient, up to rounding errors
+    ///
+    /// Coefficients `x` and `y` match if `|x - y| <= max(abs_tol, rel_tol * max(|x|, |y|))`.
+    /// Missing coefficients of the shorter polynomial count as zero.
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// let p = Polynomial::new(vec![0.1, 0.2, 0.3]);
+    /// let q = Polynomial::new(vec![0.1 + 1e-15, 0.2, 0.3 * (1.0 + 1e-10)]);
+    /// assert!(p.approx_eq(&q, 1e-12, 1e-9));
+    /// assert!(!p.approx_eq(&q, 1e-12, 1e-12));
+    /// ```
+    pub fn approx_eq(&self, other: &Polynomial<T>, abs_tol: f64, rel_tol: f64) -> bool {
+        let len = self.coeffs.len().max(other.coeffs.len());
+        (0..len).all(|k| {
+            let x = self.coeffs.get(k).map_or(0.0, |c| c.to_f64());
+            let y = other.coeffs.get(k).map_or(0.0, |c| c.to_f64());
+            (x - y).abs() <= abs_tol.max(rel_tol * x.abs().max(y.abs()))
+        })
+    }
+
+    /// Convert the coefficients to another floating-point type, keeping the trimming policy
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// let p = Polynomial::new(vec![0.5, 1.0 / 3.0]);
+    /// let q = p.cast::<f32>();
+    /// assert_eq!(q.coeffs, vec![0.5f32, 1.0 / 3.0]);
+    /// assert!(q.cast::<f64>().approx_eq(&p, 1e-7, 0.0));
+    /// ```
+    pub fn cast<U: Coefficient>(&self) -> Polynomial<U> {
+        Polynomial::with_normalization(
+            self.coeffs
+                .iter()
+                .map(|c| U::from_f64(c.to_f64()))
+                .collect(),
+            self.normalization,
+        )
+    }
+
+    /// Get the degree of the polynomial, the number of stored coefficients minus one
+    ///
+    /// By convention this is 0 for the zero polynomial, the same as for a nonzero constant,
+    /// and it counts trailing zeros kept by [`Normalization::Keep`]; use [`Polynomial::is_zero`]
+    /// or [`Polynomial::degree_checked`] to tell these cases apart.
+    pub fn degree(&self) -> usize {
+        if self.coeffs.is_empty() {
+            0
+        } else {
+            self.coeffs.len() - 1
+        }
+    }
+
+    /// Degree of the highest nonzero coefficient, `None` for the zero polynomial
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::{Normalization, Polynomial};
+    /// assert_eq!(Polynomial::new(vec![3.0]).degree_checked(), Some(0));
+    /// assert_eq!(Polynomial::new(vec![0.0]).degree_checked(), None);
+    /// let kept = Polynomial::with_normalization(vec![1.0, 2.0, 0.0], Normalization::Keep);
+    /// assert_eq!((kept.degree(), kept.degree_checked()), (2, Some(1)));
+    /// ```
+    pub fn degree_checked(&self) -> Option<usize> {
+        self.significant_coeffs().len().checked_sub(1)
+    }
+
+    /// Whether this is the zero polynomial, i.e. has no nonzero coefficient
+    ///
+    /// Unlike `degree() == 0`, this tells the zero polynomial apart from nonzero constants;
+    /// [`Polynomial::degree_checked`] returns `None` exactly in this case.
+    pub fn is_zero(&self) -> bool {
+        self.coeffs.iter().all(|&c| c == T::ZERO)
+    }
+
+    /// Number of stored coefficients, `degree() + 1` except for the (trimmed) zero polynomial
+    pub fn len(&self) -> usize {
+        self.coeffs.len()
+    }
+
+    /// Whether no coefficients are stored, which under trimming means the zero polynomial
+    pub fn is_empty(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    /// Number of nonzero coefficients
+    pub fn num_terms(&self) -> usize {
+        self.coeffs.iter().filter(|&&c| c != T::ZERO).count()
+    }
+
+    /// Coefficient of the highest degree, `None` for the zero polynomial
+    pub fn leading_coefficient(&self) -> Option<T> {
+        self.coeffs.iter().rev().copied().find(|&c| c != T::ZERO)
+    }
+
+    /// Coefficients up to the leading nonzero one, without the trailing zeros that
+    /// [`Normalization::Keep`] may store; empty for the zero polynomial
+    pub(crate) fn significant_coeffs(&self) -> &[T] {
+        let len = (self.coeffs.iter()).rposition(|&c| c != T::ZERO);
+        &self.coeffs[..len.map_or(0, |i| i + 1)]
+    }
+
+    /// Polynomial divided by its leading coefficient, failing on the zero polynomial
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// let p = Polynomial::new(vec![1.0, 2.0, 4.0]);
+    /// assert_eq!(p.leading_coefficient(), Some(4.0));
+    /// assert_eq!(p.monic().unwrap(), Polynomial::new(vec![0.25, 0.5, 1.0]));
+    /// assert!(Polynomial::new(vec![0.0]).monic().is_err());
+    /// ```
+    pub fn monic(&self) -> Result<Polynomial<T>, PolyError> {
+        let leading = self
+            .leading_coefficient()
+            .ok_or(PolyError::DivisionByZero)?;
+        Ok(self.with_coeffs(self.coeffs.iter().map(|&c| c / leading).collect()))
+    }
+
+    /// Sum of the absolute values of the coefficients (L1 norm)
+    pub fn norm_l1(&self) -> f64 {
+        self.coeffs.iter().map(|c| c.abs().to_f64()).sum()
+    }
+
+    /// Square root of the sum of the squared coefficients (L2 norm), without intermediate overflow
+    ///
+    /// Requires the `std` feature.
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// let p = Polynomial::new(vec![3.0, -4.0]);
+    /// assert_eq!((p.norm_l1(), p.norm_l2(), p.norm_inf()), (7.0, 5.0, 4.0));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn norm_l2(&self) -> f64 {
+        let height = self.height();
+        if height == 0.0 || !height.is_finite() {
+            return height;
+        }
+        let sum: f64 = (self.coeffs.iter())
+            .map(|c| (c.to_f64() / height).powi(2))
+            .sum();
+        height * sum.sqrt()
+    }
+
+    /// Largest absolute value of a coefficient (L∞ norm), same as [`Polynomial::height`]
+    pub fn norm_inf(&self) -> f64 {
+        self.height()
+    }
+
+    /// Height of the polynomial, the largest absolute value of a coefficient
+    pub fn height(&self) -> f64 {
+        (self.coeffs.iter()).fold(0.0, |height, c| height.max(c.abs().to_f64()))
+    }
+
+    /// Evaluate the polynomial at a given point x
+    pub fn evaluate(&self, x: T) -> T {
+        let mut result = T::ZERO;
+        let mut power = T::ONE;
+
+        for &coeff in &self.coeffs {
+            result += coeff * power;
+            power *= x;
+        }
+
+        result
+    }
+
+    /// Evaluate the polynomial at a given point x with Estrin's scheme on blocks of 8 coefficients
+    ///
+    /// Each block `c₀ + c₁x + … + c₇x⁷` is evaluated as a tree of independent multiply-adds
+    /// in `x`, `x²` and `x⁴`, and only the Horner steps in `x⁸` joining the blocks depend on each other,
+    /// so the processor overlaps (and the compiler can vectorize) most of the work, unlike
+    /// the single dependency chain of [`Polynomial::evaluate`]. Results agree up to rounding.
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// let p = Polynomial::new((1..=20).map(f64::from).collect());
+    /// assert_eq!(p.evaluate_fast(1.0), 210.0);
+    /// assert!((p.evaluate_fast(0.5) - p.evaluate(0.5)).abs() < 1e-12);
+    /// ```
+    pub fn evaluate_fast(&self, x: T) -> T {
+        let x2 = x * x;
+        let x4 = x2 * x2;
+        let x8 = x4 * x4;
+        let estrin = |c: &[T]| {
+            let p0 = mul_add(c[1], x, c[0]);
+            let p1 = mul_add(c[3], x, c[2]);
+            let p2 = mul_add(c[5], x, c[4]);
+            let p3 = mul_add(c[7], x, c[6]);
+            mul_add(mul_add(p3, x2, p2), x4, mul_add(p1, x2, p0))
+        };
+        let blocks = self.coeffs.chunks_exact(8);
+        // the highest coefficients that do not fill a block start the Horner steps
+        let top = (blocks.remainder().iter().rev()).fold(T::ZERO, |acc, &c| mul_add(acc, x, c));
+        blocks
+            .rev()
+            .fold(top, |acc, block| mul_add(acc, x8, estrin(block)))
+    }
+
+    /// Value and first derivative at `x` in a single pass of the extended Horner scheme
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// // one Newton step towards √2
+    /// let p = Polynomial::new(vec![-2.0, 0.0, 1.0]);
+    /// let (value, slope) = p.evaluate_with_derivative(1.5);
+    /// assert_eq!((value, slope), (0.25, 3.0));
+    /// assert!((1.5 - value / slope - 2f64.sqrt()).abs() < 3e-3);
+    /// ```
+    pub fn evaluate_with_derivative(&self, x: T) -> (T, T) {
+        let mut value = T::ZERO;
+        let mut derivative = T::ZERO;
+        for &c in self.coeffs.iter().rev() {
+            derivative = derivative * x + value;
+            value = value * x + c;
+        }
+        (value, derivative)
+    }
+
+    /// Value and derivatives at `x` up to the given order, `[p(x), p'(x), …, p⁽ᵒʳᵈᵉʳ⁾(x)]`,
+    /// in `O(n · order)` without building the derivative polynomials
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// let p = Polynomial::new(vec![1.0, 1.0, 1.0, 1.0]);
+    /// assert_eq!(p.evaluate_derivatives(2.0, 4), vec![15.0, 17.0, 14.0, 6.0, 0.0]);
+    /// ```
+    pub fn evaluate_derivatives(&self, x: T, order: usize) -> Vec<T> {
+        // repeated Horner division by `(y - x)` leaves the Taylor coefficients `p⁽ᵏ⁾(x) / k!`
+        let mut taylor = vec![T::ZERO; order + 1];
+        for &c in self.coeffs.iter().rev() {
+            for k in (1..=order).rev() {
+                taylor[k] = taylor[k] * x + taylor[k - 1];
+            }
+            taylor[0] = taylor[0] * x + c;
+        }
+        let mut factorial = T::ONE;
+        for (k, t) in taylor.iter_mut().enumerate().skip(1) {
+            factorial *= T::from_f64(k as f64);
+            *t *= factorial;
+        }
+        taylor
+    }
+
+    /// Derivative of the polynomial
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// let p = Polynomial::new(vec![1.0, 2.0, 3.0]);
+    /// assert_eq!(p.derivative(), Polynomial::new(vec![2.0, 6.0]));
+    /// ```
+    pub fn derivative(&self) -> Polynomial<T> {
+        self.with_coeffs(
+            (self.coeffs.iter().enumerate().skip(1))
+                .map(|(k, &c)| T::from_f64(k as f64) * c)
+                .collect(),
+        )
+    }
+
+    /// Antiderivative of the polynomial with zero constant term
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// let p = Polynomial::new(vec![2.0, 6.0]);
+    /// assert_eq!(p.integral(), Polynomial::new(vec![0.0, 2.0, 3.0]));
+    /// assert_eq!(p.integral().derivative(), p);
+    /// ```
+    pub fn integral(&self) -> Polynomial<T> {
+        if self.coeffs.is_empty() {
+            return self.clone();
+        }
+        let mut coeffs = vec![T::ZERO];
+        coeffs.extend(
+            (self.coeffs.iter().enumerate()).map(|(k, &c)| c / T::from_f64((k + 1) as f64)),
+        );
+        self.with_coeffs(coeffs)
+    }
+
+    /// Build the monic polynomial `(x - r₀)(x - r₁)…` with the given roots
+    ///
+    /// The linear factors are combined in a balanced product tree, so the large products
+    /// at the top of the tree go through the fast multiplier.
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// let p = Polynomial::from_roots(&[1.0, 2.0, 3.0]);
+    /// assert_eq!(p, Polynomial::new(vec![-6.0, 11.0, -6.0, 1.0]));
+    /// ```
+    pub fn from_roots(roots: &[T]) -> Polynomial<T> {
+        match roots {
+            [] => Polynomial::new(vec![T::ONE]),
+            [r] => Polynomial::new(vec![-*r, T::ONE]),
+            _ => {
+                let (low, high) = roots.split_at(roots.len() / 2);
+                Polynomial::from_roots(low).multiply_auto(&Polynomial::from_roots(high))
+            }
+        }
+    }
+
+    /// Multiply with another polynomial using the given algorithm
+    pub fn multiply(&self, other: &Polynomial<T>, algorithm: PolynomialMultAlg) -> Polynomial<T> {
+        self.with_coeffs(multiply_impl(&self.coeffs, &other.coeffs, algorithm))
+    }
+
+    /// Multiply with another polynomial using the algorithm chosen by [`PolynomialMultAlg::auto`],
+    /// or term by term with [`Polynomial::multiply_sparse`] if few coefficients are nonzero
+    pub fn multiply_auto(&self, other: &Polynomial<T>) -> Polynomial<T> {
+        if prefers_sparse(&self.coeffs, &other.coeffs) {
+            return self.multiply_sparse(other);
+        }
+        self.multiply(
+            other,
+            PolynomialMultAlg::auto_for::<T>(self.coeffs.len(), other.coeffs.len()),
+        )
+    }
+
+    /// Multiply with another polynomial using the naive O(n²) algorithm
+    pub fn multiply_naive(&self, other: &Polynomial<T>) -> Polynomial<T> {
+        self.with_coeffs(naive_multiply_impl(&self.coeffs, &other.coeffs))
+    }
+
+    /// Multiply with another polynomial using the naive O(n²) algorithm
+    /// with compensated (Kahan-style) accumulation of each coefficient
+    ///
+    /// Slower than [`Polynomial::multiply_naive`], but each coefficient is accurate
+    /// to a few ulps even for large degrees with mixed-sign coefficients.
+    pub fn multiply_naive_compensated(&self, other: &Polynomial<T>) -> Polynomial<T> {
+        self.with_coeffs(naive_compensated_multiply_impl(&self.coeffs, &other.coeffs))
+    }
+
+    /// Multiply with another polynomial using the Cook-Tooms algorithm with k=3
+    pub fn multiply_cook_tooms_k3(&self, other: &Polynomial<T>) -> Polynomial<T> {
+        self.with_coeffs(cook_tooms_k3_impl(&self.coeffs, &other.coeffs))
+    }
+
+    /// Multiply with another polynomial using a thresholded approach
+    pub fn multiply_thresholded(&self, other: &Polynomial<T>, threshold: usize) -> Polynomial<T> {
+        self.with_coeffs(thresholded_multiply_impl(
+            &self.coeffs,
+            &other.coeffs,
+            threshold,
+        ))
+    }
+
+    /// Multiply with another polynomial using Toom-3, Karatsuba and the naive algorithm
+    /// depending on the operand length, see [`HybridConfig`]
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::{HybridConfig, Polynomial};
+    /// let p = Polynomial::random(-10.0, 10.0, 1000);
+    /// let config = HybridConfig { toom3_threshold: 300, karatsuba_threshold: 20 };
+    /// assert!(p.multiply_hybrid(&p, config).approx_eq(&p.multiply_naive(&p), 1e-9, 1e-9));
+    /// ```
+    pub fn multiply_hybrid(&self, other: &Polynomial<T>, config: HybridConfig) -> Polynomial<T> {
+        self.with_coeffs(hybrid_multiply_impl(&self.coeffs, &other.coeffs, config))
+    }
+
+    /// Square the polynomial using the Cook-Tooms algorithm with k=3
+    ///
+    /// Exploits the symmetry of `a·a`: every level evaluates the operand only once,
+    /// and the base case computes each cross term `a_i·a_j` only once.
+    pub fn square(&self) -> Polynomial<T> {
+        self.square_thresholded(5)
+    }
+
+    /// Square the polynomial using a thresholded approach
+    pub fn square_thresholded(&self, threshold: usize) -> Polynomial<T> {
+        self.with_coeffs(thresholded_square_impl(&self.coeffs, threshold))
+    }
+
+    /// Raise the polynomial to the power `exponent` by repeated squaring
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// let p = Polynomial::new(vec![1.0, 1.0]);
+    /// assert_eq!(p.pow(3), Polynomial::new(vec![1.0, 3.0, 3.0, 1.0]));
+    /// ```
+    pub fn pow(&self, exponent: u32) -> Polynomial<T> {
+        let mut result = self.with_coeffs(vec![T::ONE]);
+        let mut base = self.clone();
+        let mut e = exponent;
+        while e > 0 {
+            if e & 1 == 1 {
+                result = result.multiply_cook_tooms_k3(&base);
+            }
+            e >>= 1;
+            if e > 0 {
+                base = base.square();
+            }
+        }
+        result
+    }
+
+    /// Multiply with another polynomial using the Cook-Tooms algorithm with k=3,
+    /// reporting progress as the fraction of the recursion tree completed
+    pub fn multiply_cook_tooms_k3_with_progress(
+        &self,
+        other: &Polynomial<T>,
+        progress: impl FnMut(f64),
+    ) -> Polynomial<T> {
+        self.multiply_thresholded_with_progress(other, 5, progress)
+    }
+
+    /// Multiply with another polynomial using a thresholded approach,
+    /// reporting progress as the fraction of the recursion tree completed
+    ///
+    /// The callback receives non-decreasing values in `(0, 1]` and always ends with `1.0`.
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// let p = Polynomial::random(-10.0, 10.0, 1000);
+    /// let mut last = 0.0;
+    /// p.multiply_thresholded_with_progress(&p, 16, |fraction| last = fraction);
+    /// assert_eq!(last, 1.0);
+    /// ```
+    pub fn multiply_thresholded_with_progress(
+        &self,
+        other: &Polynomial<T>,
+        threshold: usize,
+        mut progress: impl FnMut(f64),
+    ) -> Polynomial<T> {
+        self.with_coeffs(thresholded_multiply_with_progress(
+            &self.coeffs,
+            &other.coeffs,
+            threshold,
+            &mut progress,
+        ))
+    }
+
+    /// Multiply with another polynomial using a thresholded approach
+    /// where the threshold depends on the recursion depth
+    ///
+    /// `schedule(depth)` is the threshold for operands after `depth` Toom-3 levels,
+    /// e.g. a larger cutoff at the top of the recursion than deep inside it.
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// let p = Polynomial::random(-10.0, 10.0, 1000);
+    /// let thresholds = [32, 64, 128];
+    /// let schedule = |depth: usize| thresholds[depth.min(thresholds.len() - 1)];
+    /// let product = p.multiply_thresholded_with_schedule(&p, schedule);
+    /// assert!(product.approx_eq(&p.multiply_naive(&p), 1e-9, 1e-9));
+    /// ```
+    pub fn multiply_thresholded_with_schedule(
+        &self,
+        other: &Polynomial<T>,
+        schedule: impl Fn(usize) -> usize,
+    ) -> Polynomial<T> {
+        self.with_coeffs(thresholded_multiply_with_schedule(
+            &self.coeffs,
+            &other.coeffs,
+            &schedule,
+        ))
+    }
+
+    /// Multiply with another polynomial using a thresholded approach,
+    /// also returning statistics about the shape of the recursion
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// let p = Polynomial::new(vec![1.0; 81]);
+    /// let (_, stats) = p.multiply_thresholded_with_stats(&p, 5);
+    /// assert_eq!(stats.max_depth, 3); // chunks of 27, 9 and 3 coefficients
+    /// assert_eq!(stats.base_cases, 125);
+    /// ```
+    pub fn multiply_thresholded_with_stats(
+        &self,
+        other: &Polynomial<T>,
+        threshold: usize,
+    ) -> (Polynomial<T>, MultiplyStats) {
+        let (product, stats) =
+            thresholded_multiply_with_stats(&self.coeffs, &other.coeffs, threshold);
+        (self.with_coeffs(product), stats)
+    }
+}
+
+impl Polynomial {
+    /// Generate a random polynomial with given size from the thread-local random number generator
+    ///
+    /// Requires the `thread_rng` feature (on by default); [`Polynomial::random_with_rng`]
+    /// takes the generator instead.
+    /// See [`crate::RandomPolynomial`] for other distributions,
+    /// integer or sparse coefficients and a fixed leading coefficient.
+    ///
+    /// - `range_min`: Minimum value for coefficients
+    /// - `range_max`: Maximum value for coefficients
+    /// - `size`: Number of coefficients
+    ///
+    /// Panics on a zero size or an empty range, see [`Polynomial::try_random`].
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// let p = Polynomial::random(-10.0, 10.0, 5);
+    /// ```
+    #[cfg(feature = "thread_rng")]
+    pub fn random(range_min: f64, range_max: f64, size: usize) -> Polynomial {
+        Polynomial::try_random(range_min, range_max, size).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Generate a random polynomial with given size, failing with [`PolyError::InvalidArgument`]
+    /// on a zero size or a range that is empty or infinite
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::{PolyError, Polynomial};
+    /// assert!(Polynomial::try_random(-10.0, 10.0, 5).is_ok());
+    /// assert!(matches!(
+    ///     Polynomial::try_random(1.0, 1.0, 5),
+    ///     Err(PolyError::InvalidArgument(_))
+    /// ));
+    /// ```
+    #[cfg(feature = "thread_rng")]
+    pub fn try_random(
+        range_min: f64,
+        range_max: f64,
+        size: usize,
+    ) -> Result<Polynomial, PolyError> {
+        Polynomial::try_random_with_rng(range_min, range_max, size, &mut rand::thread_rng())
+    }
+
+    /// Generate a random polynomial with given size from the given random number generator
+    ///
+    /// Use a seeded generator for reproducible polynomials. Any [`Rng`] works, including
+    /// small deterministic ones, and unlike [`Polynomial::random`] this does not need `std`.
+    /// Panics on a zero size or an empty range, see [`Polynomial::try_random_with_rng`].
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// use rand::{rngs::mock::StepRng, rngs::StdRng, SeedableRng};
+    /// let p = Polynomial::random_with_rng(-10.0, 10.0, 5, &mut StdRng::seed_from_u64(42));
+    /// let q = Polynomial::random_with_rng(-10.0, 10.0, 5, &mut StdRng::seed_from_u64(42));
+    /// assert_eq!(p, q);
+    /// // a counter instead of random bits
+    /// let r = Polynomial::random_with_rng(-1.0, 1.0, 3, &mut StepRng::new(1 << 60, 1 << 61));
+    /// assert_eq!(r, Polynomial::random_with_rng(-1.0, 1.0, 3, &mut StepRng::new(1 << 60, 1 << 61)));
+    /// ```
+    pub fn random_with_rng<R: Rng + ?Sized>(
+        range_min: f64,
+        range_max: f64,
+        size: usize,
+        rng: &mut R,
+    ) -> Polynomial {
+        Polynomial::try_random_with_rng(range_min, range_max, size, rng)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Generate a random polynomial with given size from the given random number generator,
+    /// failing with [`PolyError::InvalidArgument`] on a zero size or a range that is empty or infinite
+    pub fn try_random_with_rng<R: Rng + ?Sized>(
+        range_min: f64,
+        range_max: f64,
+        size: usize,
+        rng: &mut R,
+    ) -> Result<Polynomial, PolyError> {
+        check_size(size)?;
+        check_range(range_min, range_max)?;
+        Ok(Polynomial::new(
+            (0..size)
+                .map(|_| rng.gen_range(range_min..range_max))
+                .collect(),
+        ))
+    }
+}
+
+/// Check that a random polynomial has at least one coefficient
+pub(crate) fn check_size(size: usize) -> Result<(), PolyError> {
+    if size == 0 {
+        return Err(PolyError::InvalidArgument(
+            "size must be positive".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Check that `min..max` is a nonempty, finite range to sample from
+pub(crate) fn check_range(min: f64, max: f64) -> Result<(), PolyError> {
+    if min < max && (max - min).is_finite() {
+        Ok(())
+    } else {
+        Err(PolyError::InvalidArgument(format!(
+            "range {}..{} is empty or infinite",
+            min, max
+        )))
+    }
+}
+
+/// Multiply two coefficient slices using the given algorithm
+pub(crate) fn multiply_impl<T: Coefficient>(
+    a: &[T],
+    b: &[T],
+    algorithm: PolynomialMultAlg,
+) -> Vec<T> {
+    match algorithm {
+        PolynomialMultAlg::Naive => naive_multiply_impl(a, b),
+        PolynomialMultAlg::NaiveCompensated => naive_compensated_multiply_impl(a, b),
+        PolynomialMultAlg::CookTooms => cook_tooms_k3_impl(a, b),
+        PolynomialMultAlg::Thresholded(threshold) => thresholded_multiply_impl(a, b, threshold),
+        PolynomialMultAlg::Hybrid(config) => hybrid_multiply_impl(a, b, config),
+    }
+}
+
+/// Basic naive implementation of polynomial multiplication
+pub(crate) fn naive_multiply_impl<T: Coefficient>(a: &[T], b: &[T]) -> Vec<T> {
+    let n = a.len();
+    let m = b.len();
+
+    if n == 0 || m == 0 {
+        return Vec::new();
+    }
+
+    let mut result = vec![T::ZERO; n + m - 1];
+    naive_multiply_into(a, b, &mut result);
+    result
+}
+
+/// Size in bytes of the tiles of the result accumulated by [`naive_multiply_into`],
+/// small enough for the tile and the matching coefficients of `b` to stay in the L1 cache
+const NAIVE_TILE_BYTES: usize = 16 * 1024;
+
+/// Naive multiplication overwriting `result`, which holds `a.len() + b.len() - 1` coefficients
+///
+/// Long products are computed one tile of [`NAIVE_TILE_BYTES`] at a time in a local buffer,
+/// so the partial sums stay in the L1 cache instead of streaming the whole result through it
+/// for every coefficient of `a`, and four coefficients of `a` are applied per pass over a tile.
+/// Each coefficient of the result still adds its terms in increasing order of the index into `a`,
+/// so the tiling leaves the result bit-for-bit unchanged.
+pub(crate) fn naive_multiply_into<T: Coefficient>(a: &[T], b: &[T], result: &mut [T]) {
+    naive_multiply_into_abortable(a, b, result, &|| false);
+}
+
+/// Rows of a naive tile, or coefficients of a compensated product, between two polls of an abort check
+const ABORT_CHECK_ROWS: usize = 1024;
+
+/// [`naive_multiply_into`] polling `should_abort` every [`ABORT_CHECK_ROWS`] rows of a tile,
+/// `false` if it fired and `result` is incomplete
+///
+/// Products short enough for a single tile are never interrupted.
+fn naive_multiply_into_abortable<T: Coefficient>(
+    a: &[T],
+    b: &[T],
+    result: &mut [T],
+    should_abort: &dyn Fn() -> bool,
+) -> bool {
+    let n = a.len();
+    let m = b.len();
+    let tile = (NAIVE_TILE_BYTES / core::mem::size_of::<T>()).max(1);
+
+    if result.len() <= tile {
+        result.fill(T::ZERO);
+        for i in 0..n {
+            for j in 0..m {
+                result[i + j] = mul_add(a[i], b[j], result[i + j]);
+            }
+        }
+    } else {
+        let mut buffer = vec![T::ZERO; tile];
+        for (t, out) in result.chunks_mut(tile).enumerate() {
+            // terms a[i]·b[j] with start <= i + j < end
+            let (start, end) = (t * tile, t * tile + out.len());
+            let acc = &mut buffer[..out.len()];
+            acc.fill(T::ZERO);
+            let (mut i, i_end) = (start.saturating_sub(m - 1), end.min(n));
+            let i_start = i;
+            // four rows of a at once, adding their terms to each coefficient in order
+            while i + 4 <= i_end {
+                if (i - i_start) % ABORT_CHECK_ROWS == 0 && should_abort() {
+                    return false;
+                }
+                let a_i = [a[i], a[i + 1], a[i + 2], a[i + 3]];
+                // row i + d contributes to the coefficients i + d .. i + d + m
+                let add_terms = |acc: &mut [T], k: usize| {
+                    let mut r = acc[k - start];
+                    for (d, &a_d) in a_i.iter().enumerate() {
+                        if k >= i + d && k - i - d < m {
+                            r = mul_add(a_d, b[k - i - d], r);
+                        }
+                    }
+                    acc[k - start] = r;
+                };
+                // all four rows contribute to the coefficients lo .. hi
+                let (first, last) = (start.max(i), end.min(i + 3 + m));
+                let (lo, hi) = (start.max(i + 3), end.min(i + m));
+                if lo >= hi {
+                    for k in first..last {
+                        add_terms(acc, k);
+                    }
+                    i += 4;
+                    continue;
+                }
+                for k in (first..lo).chain(hi..last) {
+                    add_terms(acc, k);
+                }
+                let rows = (b[lo - i - 3..].iter())
+                    .zip(&b[lo - i - 2..])
+                    .zip(&b[lo - i - 1..])
+                    .zip(&b[lo - i..]);
+                for (r, (((&b3, &b2), &b1), &b0)) in
+                    acc[lo - start..hi - start].iter_mut().zip(rows)
+                {
+                    let sum = mul_add(a_i[1], b1, mul_add(a_i[0], b0, *r));
+                    *r = mul_add(a_i[3], b3, mul_add(a_i[2], b2, sum));
+                }
+                i += 4;
+            }
+            for i in i..i_end {
+                let j_start = start.saturating_sub(i);
+                let j_end = (end - i).min(m);
+                let a_i = a[i];
+                for (r, &b_j) in acc[i + j_start - start..]
+                    .iter_mut()
+                    .zip(&b[j_start..j_end])
+                {
+                    *r = mul_add(a_i, b_j, *r);
+                }
+            }
+            out.copy_from_slice(acc);
+        }
+    }
+    record(n * m, n * m);
+    true
+}
+
+/// Naive squaring computing each cross term `a_i·a_j` (i < j) only once
+fn naive_square_impl<T: Coefficient>(a: &[T]) -> Vec<T> {
+    let n = a.len();
+
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let mut result = vec![T::ZERO; 2 * n - 1];
+    naive_square_into(a, &mut result);
+    result
+}
+
+/// Naive squaring overwriting `result`, which holds `2 * a.len() - 1` coefficients
+fn naive_square_into<T: Coefficient>(a: &[T], result: &mut [T]) {
+    let n = a.len();
+
+    result.fill(T::ZERO);
+    for i in 0..n {
+        for j in i + 1..n {
+            result[i + j] = mul_add(a[i], a[j], result[i + j]);
+        }
+    }
+    for (i, r) in result.iter_mut().enumerate() {
+        *r *= T::from_f64(2.0);
+        if i % 2 == 0 {
+            *r = mul_add(a[i / 2], a[i / 2], *r);
+        }
+    }
+    let cross_terms = n * (n - 1) / 2;
+    record(cross_terms + (2 * n - 1) + n, cross_terms + n);
+}
+
+/// Error-free transformation of a sum: `a + b = s + e` exactly (Knuth's TwoSum)
+fn two_sum<T: Coefficient>(a: T, b: T) -> (T, T) {
+    let s = a + b;
+    let bb = s - a;
+    (s, (a - (s - bb)) + (b - bb))
+}
+
+/// Error-free transformation of a product: `a * b = p + e` exactly (Dekker's TwoProduct)
+///
+/// Uses Veltkamp splitting instead of FMA, so it overflows for magnitudes near the largest finite value
+/// (above ~1e300 for `f64`).
+fn two_product<T: Coefficient>(a: T, b: T) -> (T, T) {
+    let split = |x: T| {
+        let c = T::SPLITTER * x;
+        let hi = c - (c - x);
+        (hi, x - hi)
+    };
+    let p = a * b;
+    let (a_hi, a_lo) = split(a);
+    let (b_hi, b_lo) = split(b);
+    let e = a_lo * b_lo - (((p - a_hi * b_hi) - a_lo * b_hi) - a_hi * b_lo);
+    (p, e)
+}
+
+/// Naive polynomial multiplication with compensated accumulation
+///
+/// Every coefficient is computed as a compensated dot product (Ogita-Rump-Oishi `Dot2`),
+/// i.e. as if accumulated in twice the working precision.
+fn naive_compensated_multiply_impl<T: Coefficient>(a: &[T], b: &[T]) -> Vec<T> {
+    let n = a.len();
+    let m = b.len();
+
+    if n == 0 || m == 0 {
+        return Vec::new();
+    }
+
+    // TwoProduct: 7 multiplications, 10 additions; TwoSum and the compensation: 8 additions
+    record(7 * n * m, 18 * n * m + n + m - 1);
+    (0..n + m - 1)
+        .map(|k| compensated_coefficient(a, b, k))
+        .collect()
+}
+
+/// Coefficient `k` of the product of nonempty `a` and `b` as a compensated dot product
+fn compensated_coefficient<T: Coefficient>(a: &[T], b: &[T], k: usize) -> T {
+    let (n, m) = (a.len(), b.len());
+    let mut sum = T::ZERO;
+    let mut compensation = T::ZERO;
+    for i in k.saturating_sub(m - 1)..=k.min(n - 1) {
+        let (product, product_error) = two_product(a[i], b[k - i]);
+        let (t, sum_error) = two_sum(sum, product);
+        sum = t;
+        compensation += product_error + sum_error;
+    }
+    sum + compensation
+}
+
+/// [`naive_compensated_multiply_impl`] polling `should_abort` every [`ABORT_CHECK_ROWS`] coefficients
+#[cfg(feature = "std")]
+fn naive_compensated_multiply_abortable<T: Coefficient>(
+    a: &[T],
+    b: &[T],
+    should_abort: &dyn Fn() -> bool,
+) -> Option<Vec<T>> {
+    let (n, m) = (a.len(), b.len());
+    if n == 0 || m == 0 {
+        return Some(Vec::new());
+    }
+    record(7 * n * m, 18 * n * m + n + m - 1);
+    let mut result = Vec::with_capacity(n + m - 1);
+    for k in 0..n + m - 1 {
+        if k % ABORT_CHECK_ROWS == 0 && should_abort() {
+            return None;
+        }
+        result.push(compensated_coefficient(a, b, k));
+    }
+    Some(result)
+}
+
+/// Implementation of Cook-Tooms algorithm with k=3 (Toom-3)
+fn cook_tooms_k3_impl<T: Coefficient>(a: &[T], b: &[T]) -> Vec<T> {
+    thresholded_multiply_impl(a, b, 5) // only use naive for length < 5
+}
+
+/// Thresholded version that chooses between algorithms based on input size
+///
+/// The recursion nests about log₃(n) Toom-3 levels (the threshold is at least 5);
+/// [`thresholded_multiply_iterative`] computes the same product without recursion.
+pub fn thresholded_multiply_impl<T: Coefficient>(a: &[T], b: &[T], threshold: usize) -> Vec<T> {
+    toom3_impl(a, b, threshold, &mut RecursionHooks::none())
+}
+
+/// Hybrid multiplication: Toom-3 for long operands, then Karatsuba, then the naive algorithm
+pub fn hybrid_multiply_impl<T: Coefficient>(a: &[T], b: &[T], config: HybridConfig) -> Vec<T> {
+    let mut hooks = RecursionHooks::none();
+    hooks.karatsuba_threshold = Some(config.karatsuba_threshold);
+    toom3_impl(a, b, config.toom3_threshold, &mut hooks)
+}
+
+/// Recursive Karatsuba multiplication, switching to the naive algorithm
+/// once an operand is shorter than `threshold`
+fn karatsuba_impl<T: Coefficient>(a: &[T], b: &[T], threshold: usize) -> Vec<T> {
+    let n = a.len();
+    let m = b.len();
+    let thr = core::cmp::max(threshold, 2); // at least 2 to work
+    if n < thr || m < thr {
+        span!(TRACE, "base_case", n = n, m = m);
+        return naive_multiply_impl(a, b);
+    }
+    span!(TRACE, "karatsuba", n = n, m = m);
+
+    // Split both operands at half of the longer length: a = a0 + x^half·a1
+    let half = n.max(m).div_ceil(2);
+    let (a0, a1) = a.split_at(half.min(n));
+    let (b0, b1) = b.split_at(half.min(m));
+    let mut result = vec![T::ZERO; n + m - 1];
+    let mut add_at = |offset: usize, product: &[T]| {
+        for (r, p) in result[offset..].iter_mut().zip(product) {
+            *r += *p;
+        }
+        record(0, product.len());
+    };
+
+    // Unbalanced operands: one of them has no high part
+    if a1.is_empty() || b1.is_empty() {
+        let (short, long0, long1) = if a1.is_empty() {
+            (a, b0, b1)
+        } else {
+            (b, a0, a1)
+        };
+        add_at(0, &karatsuba_impl(short, long0, thr));
+        add_at(half, &karatsuba_impl(short, long1, thr));
+        return result;
+    }
+
+    // (a0 + a1)(b0 + b1) = z0 + z1 + z2
+    let sum = |low: &[T], high: &[T]| -> Vec<T> {
+        let mut sum = low.to_vec();
+        for (s, h) in sum.iter_mut().zip(high) {
+            *s += *h;
+        }
+        record(0, high.len());
+        sum
+    };
+    let z0 = karatsuba_impl(a0, b0, thr);
+    let z2 = karatsuba_impl(a1, b1, thr);
+    let mut z1 = karatsuba_impl(&sum(a0, a1), &sum(b0, b1), thr);
+    for (k, z) in z1.iter_mut().enumerate() {
+        *z -= z0.get(k).copied().unwrap_or(T::ZERO) + z2.get(k).copied().unwrap_or(T::ZERO);
+    }
+    record(0, 2 * z1.len());
+    add_at(0, &z0);
+    add_at(half, &z1);
+    add_at(2 * half, &z2);
+    result
+}
+
+/// Thresholded squaring using Toom-3 with a single evaluation per level
+pub fn thresholded_square_impl<T: Coefficient>(a: &[T], threshold: usize) -> Vec<T> {
+    let n = a.len();
+    let thr = core::cmp::max(threshold, 5); // at least 5 to work
+
+    if n < thr {
+        return naive_square_impl(a);
+    }
+
+    let mut result = vec![T::ZERO; 2 * n - 1];
+    let mut workspace = vec![T::ZERO; square_workspace_len(n, thr)];
+    toom3_square_into(a, &mut result, thr, &mut workspace);
+    result
+}
+
+/// Coefficients of the workspace needed by [`toom3_square_into`]
+fn square_workspace_len(n: usize, thr: usize) -> usize {
+    if n < thr {
+        return 0;
+    }
+    let n_chunk = n.div_ceil(3);
+    3 * n_chunk + 5 * (2 * n_chunk - 1) + square_workspace_len(n_chunk, thr)
+}
+
+/// Toom-3 squaring into `result`, carving the buffers of every level from `workspace`
+fn toom3_square_into<T: Coefficient>(a: &[T], result: &mut [T], thr: usize, workspace: &mut [T]) {
+    let n = a.len();
+    if n == 0 {
+        return;
+    }
+    if n < thr {
+        span!(TRACE, "base_case", n = n, m = n);
+        naive_square_into(a, result);
+        return;
+    }
+    span!(TRACE, "toom3_square", n = n);
+
+    let n_chunk = n.div_ceil(3);
+    let (points, workspace) = workspace.split_at_mut(3 * n_chunk);
+    let (products, workspace) = workspace.split_at_mut(5 * (2 * n_chunk - 1));
+    let a_at = toom3_evaluate_into(a, n_chunk, points);
+    for (point, product) in a_at
+        .into_iter()
+        .zip(products.chunks_exact_mut(2 * n_chunk - 1))
+    {
+        let (product, padding) = product.split_at_mut(product_len(point.len(), point.len()));
+        toom3_square_into(point, product, thr, workspace);
+        padding.fill(T::ZERO);
+    }
+    toom3_interpolate_into(products, n_chunk, result);
+}
+
+/// Thresholded autocorrelation, i.e. the product `a·rev(a)` with `2 * a.len() - 1` coefficients
+///
+/// The product is symmetric, and so are the Toom-3 products that make it up:
+/// the values at 1 and -1 are autocorrelations again, and the value at inf is the value at 0
+/// reversed, so each level takes 2 general products and 2 recursive autocorrelations instead of
+/// 5 products, and the naive base case only computes the non-negative lags.
+pub(crate) fn thresholded_autocorrelation_impl<T: Coefficient>(
+    a: &[T],
+    threshold: usize,
+) -> Vec<T> {
+    let n = a.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    let thr = core::cmp::max(threshold, 5); // at least 5 to work
+    let mut result = vec![T::ZERO; 2 * n - 1];
+    if n < thr {
+        for lag in 0..n {
+            let mut sum = T::ZERO;
+            for (x, y) in a[lag..].iter().zip(a) {
+                sum = mul_add(*x, *y, sum);
+            }
+            (result[n - 1 + lag], result[n - 1 - lag]) = (sum, sum);
         }
-        Polynomial { coeffs: result }
+        record(n * (n + 1) / 2, n * (n + 1) / 2);
+        return result;
     }
 
-    /// Generate a random polynomial with given size
-    ///
-    /// - `range_min`: Minimum value for coefficients
-    /// - `range_max`: Maximum value for coefficients
-    /// - `size`: Number of coefficients
-    /// ## Example
-    /// ```
-    /// use mult_polynomial::Polynomial;
-    /// let p = Polynomial::random(-10.0, 10.0, 5);
-    /// ```
-    pub fn random(range_min: f64, range_max: f64, size: usize) -> Polynomial {
-        assert!(size > 0);
-        assert!(range_min < range_max);
-        let mut rng = rand::thread_rng();
-        Polynomial::new(
-            (0..size)
-                .map(|_| rng.gen_range(range_min..range_max))
-                .collect(),
-        )
-    }
+    // pad to 3 full chunks, so that rev(a) = rev(a2) + x^n_chunk·rev(a1) + x^(2·n_chunk)·rev(a0)
+    // and the product gains as many leading zeros as there is padding
+    let n_chunk = n.div_ceil(3);
+    let padding = 3 * n_chunk - n;
+    let mut padded = a.to_vec();
+    padded.resize(3 * n_chunk, T::ZERO);
+    let mut points = vec![T::ZERO; 3 * n_chunk];
+    let [a0, a_at_1, a_at_neg1, a_at_2, a2] = toom3_evaluate_into(&padded, n_chunk, &mut points);
+    let (a1, reversed) = (&padded[n_chunk..2 * n_chunk], |x: &[T]| -> Vec<T> {
+        x.iter().rev().copied().collect()
+    });
+    // rev(a) at 2 is rev(4·a0 + 2·a1 + a2)
+    let (two, four) = (T::from_f64(2.0), T::from_f64(4.0));
+    let reversed_at_2: Vec<T> = (0..n_chunk)
+        .rev()
+        .map(|i| four * a0[i] + two * a1[i] + a2[i])
+        .collect();
+    record(2 * n_chunk, 2 * n_chunk);
 
-    /// Get the degree of the polynomial
-    pub fn degree(&self) -> usize {
-        if self.coeffs.is_empty() {
-            0
-        } else {
-            self.coeffs.len() - 1
+    let v_0 = thresholded_multiply_impl(a0, &reversed(a2), thr);
+    let products: Vec<T> = [
+        v_0.clone(),
+        thresholded_autocorrelation_impl(a_at_1, thr),
+        thresholded_autocorrelation_impl(a_at_neg1, thr),
+        thresholded_multiply_impl(a_at_2, &reversed_at_2, thr),
+        reversed(&v_0),
+    ]
+    .concat();
+    let mut full = vec![T::ZERO; 6 * n_chunk - 1];
+    toom3_interpolate_into(&products, n_chunk, &mut full);
+    result.copy_from_slice(&full[padding..padding + 2 * n - 1]);
+    result
+}
+
+/// Thresholded multiplication reporting the fraction of the recursion tree completed
+///
+/// Each of the 5 sub-products of a Toom-3 level accounts for an equal share of its parent,
+/// and the callback is invoked as base cases finish (throttled to steps of at least 0.1%).
+pub fn thresholded_multiply_with_progress<T: Coefficient>(
+    a: &[T],
+    b: &[T],
+    threshold: usize,
+    progress: &mut dyn FnMut(f64),
+) -> Vec<T> {
+    let mut hooks = RecursionHooks::none();
+    hooks.progress = Some(progress);
+    let result = toom3_impl(a, b, threshold, &mut hooks);
+    hooks.finish();
+    result
+}
+
+/// Thresholded multiplication with the threshold `schedule(depth)` after `depth` Toom-3 levels
+pub fn thresholded_multiply_with_schedule<T: Coefficient>(
+    a: &[T],
+    b: &[T],
+    schedule: &dyn Fn(usize) -> usize,
+) -> Vec<T> {
+    let mut hooks = RecursionHooks::none();
+    hooks.schedule = Some(schedule);
+    toom3_impl(a, b, schedule(0), &mut hooks)
+}
+
+/// Shape of the recursion tree of a thresholded multiplication
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MultiplyStats {
+    /// Number of nested Toom-3 levels on the deepest path (0 if the naive algorithm was used directly)
+    pub max_depth: usize,
+    /// Number of invocations of the recursive multiplication, including the top-level one
+    pub calls: usize,
+    /// Number of products computed by the naive algorithm at the bottom of the recursion
+    pub base_cases: usize,
+    /// Number of block decompositions of very unbalanced operands
+    pub block_splits: usize,
+}
+
+/// Thresholded multiplication recording the shape of the recursion tree
+pub fn thresholded_multiply_with_stats<T: Coefficient>(
+    a: &[T],
+    b: &[T],
+    threshold: usize,
+) -> (Vec<T>, MultiplyStats) {
+    let mut stats = MultiplyStats::default();
+    let mut hooks = RecursionHooks::none();
+    hooks.stats = Some(&mut stats);
+    let result = toom3_impl(a, b, threshold, &mut hooks);
+    (result, stats)
+}
+
+/// Thresholded multiplication recording the recursion tree, e.g. to draw it with Graphviz
+///
+/// See [`RecursionTree::to_dot`] and [`RecursionTree::to_json`].
+pub fn thresholded_multiply_with_tree<T: Coefficient>(
+    a: &[T],
+    b: &[T],
+    threshold: usize,
+) -> (Vec<T>, RecursionTree) {
+    let mut tree = RecursionTree::default();
+    let mut hooks = RecursionHooks::none();
+    hooks.tree = Some(&mut tree);
+    let result = toom3_impl(a, b, threshold, &mut hooks);
+    (result, tree)
+}
+
+/// Thresholded multiplication driven by an explicit work list instead of the call stack
+///
+/// Performs the same splits and floating-point operations as [`thresholded_multiply_impl`],
+/// so the results are bit-identical, but keeps the pending sub-products of every Toom-3 level
+/// and block decomposition in a heap-allocated stack of frames. The call stack therefore stays
+/// flat whatever the operand lengths and threshold, and the sub-products waiting in the frames
+/// are independent tasks that a scheduler could hand to other threads. Each sub-product owns
+/// copies of its operands, so it needs about twice the workspace of the recursive version.
+/// ## Example
+/// ```
+/// use mult_polynomial::{thresholded_multiply_impl, thresholded_multiply_iterative};
+/// let a: Vec<f64> = (0..500).map(|i| (i % 7) as f64 - 3.0).collect();
+/// let b: Vec<f64> = (0..300).map(|i| (i % 5) as f64 * 0.5).collect();
+/// assert_eq!(
+///     thresholded_multiply_iterative(&a, &b, 8),
+///     thresholded_multiply_impl(&a, &b, 8)
+/// );
+/// ```
+pub fn thresholded_multiply_iterative<T: Coefficient>(
+    a: &[T],
+    b: &[T],
+    threshold: usize,
+) -> Vec<T> {
+    let thr = core::cmp::max(threshold, 5); // at least 5 to work
+    let mut stack: Vec<Frame<T>> = Vec::new();
+    let mut next = Some((a.to_vec(), b.to_vec()));
+    loop {
+        let mut product = match next.take().map(|(a, b)| Frame::split(a, b, thr)) {
+            Some(Ok(frame)) => {
+                stack.push(frame);
+                None
+            }
+            Some(Err(product)) => Some(product),
+            None => None,
+        };
+        // hand finished products to their frames, combining the completed ones
+        while let Some(finished) = product.take() {
+            let Some(frame) = stack.last_mut() else {
+                return finished;
+            };
+            frame.products.push(finished);
+            if frame.pending.is_empty() {
+                product = stack.pop().map(Frame::combine);
+            }
         }
+        next = stack.last_mut().and_then(|frame| frame.pending.pop());
     }
+}
 
-    /// Evaluate the polynomial at a given point x
-    pub fn evaluate(&self, x: f64) -> f64 {
-        let mut result = 0.0;
-        let mut power = 1.0;
+/// Split of one product of [`thresholded_multiply_iterative`] into sub-products
+struct Frame<T> {
+    /// Number of coefficients of the product
+    len: usize,
+    /// How the sub-products are recombined
+    split: FrameSplit,
+    /// Operands of the sub-products still to compute, the next one last
+    pending: Vec<(Vec<T>, Vec<T>)>,
+    /// Finished sub-products, in order
+    products: Vec<Vec<T>>,
+}
 
-        for coeff in &self.coeffs {
-            result += coeff * power;
-            power *= x;
+/// Recombination of the sub-products of a [`Frame`]
+enum FrameSplit {
+    /// The 5 products at the Toom-3 evaluation points of chunks of `n_chunk` coefficients
+    Toom3 { n_chunk: usize },
+    /// The products of the blocks of `block` coefficients of the longer operand
+    Blocked { block: usize },
+}
+
+impl<T: Coefficient> Frame<T> {
+    /// Split the product of `a` and `b` like [`toom3_into`], or compute it if it is a base case
+    fn split(a: Vec<T>, b: Vec<T>, thr: usize) -> Result<Frame<T>, Vec<T>> {
+        let (n, m) = (a.len(), b.len());
+        if n < thr || m < thr {
+            let mut product = vec![T::ZERO; product_len(n, m)];
+            naive_multiply_into(&a, &b, &mut product);
+            return Err(product);
         }
+        let (split, mut pending): (FrameSplit, Vec<_>) = if n >= 2 * m || m >= 2 * n {
+            let (short, long) = if n <= m { (a, b) } else { (b, a) };
+            let block = short.len();
+            let pending = (long.chunks(block))
+                .map(|chunk| (short.clone(), chunk.to_vec()))
+                .collect();
+            (FrameSplit::Blocked { block }, pending)
+        } else {
+            let n_chunk = n.max(m).div_ceil(3);
+            let (mut a_points, mut b_points) =
+                (vec![T::ZERO; 3 * n_chunk], vec![T::ZERO; 3 * n_chunk]);
+            let a_at = toom3_evaluate_into(&a, n_chunk, &mut a_points);
+            let b_at = toom3_evaluate_into(&b, n_chunk, &mut b_points);
+            let pending = (a_at.iter().zip(&b_at))
+                .map(|(a_k, b_k)| (a_k.to_vec(), b_k.to_vec()))
+                .collect();
+            (FrameSplit::Toom3 { n_chunk }, pending)
+        };
+        pending.reverse();
+        Ok(Frame {
+            len: product_len(n, m),
+            products: Vec::with_capacity(pending.len()),
+            split,
+            pending,
+        })
+    }
 
+    /// Recombine the finished sub-products into the product
+    fn combine(self) -> Vec<T> {
+        let mut result = vec![T::ZERO; self.len];
+        match self.split {
+            FrameSplit::Toom3 { n_chunk } => {
+                let mut products = vec![T::ZERO; 5 * (2 * n_chunk - 1)];
+                for (padded, product) in
+                    (products.chunks_exact_mut(2 * n_chunk - 1)).zip(&self.products)
+                {
+                    padded[..product.len()].copy_from_slice(product);
+                }
+                toom3_interpolate_into(&products, n_chunk, &mut result);
+            }
+            FrameSplit::Blocked { block } => {
+                for (k, product) in self.products.iter().enumerate() {
+                    for (r, p) in result[k * block..].iter_mut().zip(product) {
+                        *r += *p;
+                    }
+                    record(0, product.len());
+                }
+            }
+        }
         result
     }
+}
 
-    /// Multiply with another polynomial using the naive O(n²) algorithm
-    pub fn multiply_naive(&self, other: &Polynomial) -> Polynomial {
-        Polynomial::new(naive_multiply_impl(&self.coeffs, &other.coeffs))
+/// Multiplication with `algorithm` that gives up once `should_abort` returns `true`
+///
+/// `should_abort` is polled after every Toom-3 base case, and by the naive kernels every
+/// [`ABORT_CHECK_ROWS`] rows of a tile or coefficients; `None` is returned if it fired.
+/// Completed products are bit-identical to [`multiply_impl`].
+#[cfg(feature = "std")]
+pub(crate) fn multiply_abortable<T: Coefficient>(
+    a: &[T],
+    b: &[T],
+    algorithm: PolynomialMultAlg,
+    should_abort: &dyn Fn() -> bool,
+) -> Option<Vec<T>> {
+    let toom3 = |threshold: usize, karatsuba_threshold: Option<usize>| {
+        let mut hooks = RecursionHooks::none();
+        hooks.should_abort = Some(should_abort);
+        hooks.karatsuba_threshold = karatsuba_threshold;
+        let result = toom3_impl(a, b, threshold, &mut hooks);
+        (!hooks.aborted).then_some(result)
+    };
+    match algorithm {
+        PolynomialMultAlg::Naive => {
+            if a.is_empty() || b.is_empty() {
+                return Some(Vec::new());
+            }
+            let mut result = vec![T::ZERO; a.len() + b.len() - 1];
+            naive_multiply_into_abortable(a, b, &mut result, should_abort).then_some(result)
+        }
+        PolynomialMultAlg::NaiveCompensated => {
+            naive_compensated_multiply_abortable(a, b, should_abort)
+        }
+        PolynomialMultAlg::CookTooms => toom3(5, None),
+        PolynomialMultAlg::Thresholded(threshold) => toom3(threshold, None),
+        PolynomialMultAlg::Hybrid(config) => {
+            toom3(config.toom3_threshold, Some(config.karatsuba_threshold))
+        }
     }
+}
 
-    /// Multiply with another polynomial using the Cook-Tooms algorithm with k=3
-    pub fn multiply_cook_tooms_k3(&self, other: &Polynomial) -> Polynomial {
-        Polynomial::new(cook_tooms_k3_impl(&self.coeffs, &other.coeffs))
-    }
+/// Thresholded multiplication reusing the workspace held in `scratch`,
+/// with the threshold `schedule(depth)` after `depth` Toom-3 levels if given
+///
+/// The workspace is kept in `scratch` afterwards, so repeated calls stop allocating it.
+#[cfg(feature = "std")]
+pub(crate) fn thresholded_multiply_with_scratch<T: Coefficient>(
+    a: &[T],
+    b: &[T],
+    threshold: usize,
+    schedule: Option<&dyn Fn(usize) -> usize>,
+    scratch: &mut Scratch<T>,
+) -> Vec<T> {
+    let mut hooks = RecursionHooks::none();
+    hooks.scratch = Some(scratch);
+    hooks.schedule = schedule;
+    toom3_impl(a, b, threshold, &mut hooks)
+}
 
-    /// Multiply with another polynomial using a thresholded approach
-    pub fn multiply_thresholded(&self, other: &Polynomial, threshold: usize) -> Polynomial {
-        Polynomial::new(thresholded_multiply_impl(
-            &self.coeffs,
-            &other.coeffs,
-            threshold,
-        ))
+/// Number of Toom-3 levels performed before reaching the naive base case,
+/// and the operand length at the base case
+pub(crate) fn recursion_shape(n: usize, m: usize, threshold: usize) -> (usize, usize) {
+    let thr = core::cmp::max(threshold, 5);
+    let (short, long) = (n.min(m), n.max(m));
+    if short < thr {
+        return (0, short);
+    }
+    // unbalanced operands are decomposed into square blocks first
+    let mut len = if long >= 2 * short { short } else { long };
+    let mut depth = 0;
+    while len >= thr {
+        // both operands are split into chunks of a third of the longer length
+        len = len.div_ceil(3);
+        depth += 1;
     }
+    (depth, len)
 }
 
-/// Basic naive implementation of polynomial multiplication
-fn naive_multiply_impl(a: &[f64], b: &[f64]) -> Vec<f64> {
-    let n = a.len();
-    let m = b.len();
-
-    if n == 0 || m == 0 {
-        return Vec::new();
+/// Number of Karatsuba levels performed on operands of length `len` before reaching
+/// the naive base case, and the operand length at the base case
+pub(crate) fn karatsuba_shape(len: usize, threshold: usize) -> (usize, usize) {
+    let thr = core::cmp::max(threshold, 2);
+    let (mut len, mut depth) = (len, 0);
+    while len >= thr {
+        len = len.div_ceil(2);
+        depth += 1;
     }
+    (depth, len)
+}
 
-    let mut result = vec![0.0; n + m - 1];
+/// Workspace of the Toom-3 recursion kept across calls
+#[derive(Debug, Default)]
+pub(crate) struct Scratch<T = f64> {
+    workspace: Vec<T>,
+}
 
-    for i in 0..n {
-        for j in 0..m {
-            result[i + j] += a[i] * b[j];
+#[cfg(feature = "std")]
+impl<T> Scratch<T> {
+    /// Scratch with room for `capacity` coefficients allocated up front
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        Scratch {
+            workspace: Vec::with_capacity(capacity),
         }
     }
 
-    result
+    /// Number of coefficients the workspace holds without allocating
+    pub(crate) fn capacity(&self) -> usize {
+        self.workspace.capacity()
+    }
 }
 
-/// Implementation of Cook-Tooms algorithm with k=3 (Toom-3)
-fn cook_tooms_k3_impl(a: &[f64], b: &[f64]) -> Vec<f64> {
-    thresholded_multiply_impl(a, b, 5) // only use naive for length < 5
+/// Optional hooks threaded through the Toom-3 recursion
+struct RecursionHooks<'a, T> {
+    /// Progress callback receiving the fraction of the recursion tree completed
+    progress: Option<&'a mut dyn FnMut(f64)>,
+    /// Last fraction passed to the progress callback
+    reported: f64,
+    /// Cancellation check polled after every base case
+    should_abort: Option<&'a dyn Fn() -> bool>,
+    /// Whether the recursion was cancelled
+    aborted: bool,
+    /// Workspace reused across calls instead of allocating a new one
+    scratch: Option<&'a mut Scratch<T>>,
+    /// Recursion statistics to update
+    stats: Option<&'a mut MultiplyStats>,
+    /// Recursion tree to add the calls to
+    tree: Option<&'a mut RecursionTree>,
+    /// Node of the tree the next call is a child of
+    tree_node: Option<usize>,
+    /// Number of Toom-3 levels above the current call
+    depth: usize,
+    /// Threshold of the Karatsuba algorithm used for the base cases, if any
+    karatsuba_threshold: Option<usize>,
+    /// Threshold per recursion depth, overriding the fixed threshold
+    schedule: Option<&'a dyn Fn(usize) -> usize>,
 }
 
-/// Thresholded version that chooses between algorithms based on input size
-pub fn thresholded_multiply_impl(a: &[f64], b: &[f64], threshold: usize) -> Vec<f64> {
-    let n = a.len();
-    let m = b.len();
-    let thr = std::cmp::max(threshold, 5); // at least 5 to work
+impl<T> RecursionHooks<'_, T> {
+    /// Minimum increase of the fraction between two progress callback invocations
+    const STEP: f64 = 1e-3;
 
-    // Threshold check
-    if n < thr || m < thr {
-        return naive_multiply_impl(a, b);
+    fn none() -> Self {
+        RecursionHooks {
+            progress: None,
+            reported: 0.0,
+            should_abort: None,
+            aborted: false,
+            scratch: None,
+            stats: None,
+            tree: None,
+            tree_node: None,
+            depth: 0,
+            karatsuba_threshold: None,
+            schedule: None,
+        }
     }
 
-    // Determine chunk size for splitting polynomials into 3 parts
-    let n_chunk = (n + 2) / 3; // ceiling division
+    /// Borrow a workspace of `len` coefficients for the whole recursion
+    fn take_workspace(&mut self, len: usize) -> Vec<T>
+    where
+        T: Coefficient,
+    {
+        let mut workspace = (self.scratch.as_mut())
+            .map(|scratch| core::mem::take(&mut scratch.workspace))
+            .unwrap_or_default();
+        workspace.resize(len, T::ZERO);
+        workspace
+    }
+
+    /// Give back the workspace of a finished recursion
+    fn return_workspace(&mut self, workspace: Vec<T>) {
+        if let Some(scratch) = self.scratch.as_mut() {
+            scratch.workspace = workspace;
+        }
+    }
+
+    /// Mark the recursion tree as completed up to `fraction`
+    fn advance(&mut self, fraction: f64) {
+        if let Some(should_abort) = self.should_abort {
+            self.aborted = should_abort();
+        }
+        if let Some(callback) = self.progress.as_mut() {
+            if fraction - self.reported >= Self::STEP {
+                self.reported = fraction;
+                callback(fraction);
+            }
+        }
+    }
+
+    /// Report completion if it has not been reported yet
+    fn finish(&mut self) {
+        if let Some(callback) = self.progress.as_mut() {
+            if self.reported < 1.0 {
+                self.reported = 1.0;
+                callback(1.0);
+            }
+        }
+    }
+}
 
-    // Split a into 3 parts with proper padding to ensure consistent sizes
-    let mut a0 = vec![0.0; n_chunk];
-    let mut a1 = vec![0.0; n_chunk];
-    let mut a2 = vec![0.0; n_chunk];
+/// Block decomposition for operands of very different lengths
+///
+/// The longer operand is sliced into blocks of the shorter operand's length,
+/// each block is multiplied by Toom-3, and the block products are added at their offsets.
+/// This avoids padding the shorter operand to a third of the longer one's length.
+#[allow(clippy::too_many_arguments)]
+fn blocked_multiply_into<T: Coefficient>(
+    a: &[T],
+    b: &[T],
+    result: &mut [T],
+    threshold: usize,
+    hooks: &mut RecursionHooks<T>,
+    workspace: &mut [T],
+    lo: f64,
+    hi: f64,
+) {
+    let (short, long) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    let block = short.len();
+    let blocks = long.len().div_ceil(block);
+    let step = (hi - lo) / blocks as f64;
+    let node = hooks.tree_node;
 
-    for i in 0..n_chunk.min(n) {
-        a0[i] = a[i];
+    let (product, workspace) = workspace.split_at_mut(2 * block - 1);
+    result.fill(T::ZERO);
+    for (k, chunk) in long.chunks(block).enumerate() {
+        let l = lo + k as f64 * step;
+        let product = &mut product[..block + chunk.len() - 1];
+        hooks.tree_node = node;
+        toom3_into(
+            short,
+            chunk,
+            product,
+            threshold,
+            hooks,
+            workspace,
+            l,
+            l + step,
+        );
+        for (r, p) in result[k * block..].iter_mut().zip(&*product) {
+            *r += *p;
+        }
+        record(0, product.len());
     }
+}
 
-    for i in 0..n_chunk.min(n.saturating_sub(n_chunk)) {
-        a1[i] = a[i + n_chunk];
+/// Recursive Toom-3 multiplication with a single workspace allocated up front
+fn toom3_impl<T: Coefficient>(
+    a: &[T],
+    b: &[T],
+    threshold: usize,
+    hooks: &mut RecursionHooks<T>,
+) -> Vec<T> {
+    if a.is_empty() || b.is_empty() {
+        // still a (trivial) base case for the hooks
+        toom3_into(a, b, &mut [], threshold, hooks, &mut [], 0.0, 1.0);
+        return Vec::new();
+    }
+    let mut result = vec![T::ZERO; product_len(a.len(), b.len())];
+    let workspace_len = toom3_workspace_len(a.len(), b.len(), threshold, hooks.schedule, 0);
+    let mut workspace = hooks.take_workspace(workspace_len);
+    toom3_into(
+        a,
+        b,
+        &mut result,
+        threshold,
+        hooks,
+        &mut workspace,
+        0.0,
+        1.0,
+    );
+    hooks.return_workspace(workspace);
+    if hooks.aborted {
+        return Vec::new();
     }
+    result
+}
 
-    for i in 0..n_chunk.min(n.saturating_sub(2 * n_chunk)) {
-        a2[i] = a[i + 2 * n_chunk];
+/// Coefficients of the workspace needed by [`toom3_into`] at recursion depth `depth`,
+/// following the same decisions as the recursion
+fn toom3_workspace_len(
+    n: usize,
+    m: usize,
+    threshold: usize,
+    schedule: Option<&dyn Fn(usize) -> usize>,
+    depth: usize,
+) -> usize {
+    let threshold = schedule.map_or(threshold, |schedule| schedule(depth));
+    let thr = core::cmp::max(threshold, 5);
+    if n < thr || m < thr {
+        return 0;
     }
+    if n >= 2 * m || m >= 2 * n {
+        // one block product at a time, the last block possibly shorter
+        let (short, long) = (n.min(m), n.max(m));
+        let last = long - (long.div_ceil(short) - 1) * short;
+        let blocks = toom3_workspace_len(short, short, thr, schedule, depth)
+            .max(toom3_workspace_len(short, last, thr, schedule, depth));
+        return 2 * short - 1 + blocks;
+    }
+    // 3 evaluations of both operands and the 5 products, then the workspace of the sub-products,
+    // the one at infinity multiplying the (possibly shorter) high parts
+    let n_chunk = n.max(m).div_ceil(3);
+    let high = |len: usize| len.saturating_sub(2 * n_chunk);
+    6 * n_chunk
+        + 5 * (2 * n_chunk - 1)
+        + toom3_workspace_len(n_chunk, n_chunk, thr, schedule, depth + 1).max(toom3_workspace_len(
+            high(n),
+            high(m),
+            thr,
+            schedule,
+            depth + 1,
+        ))
+}
 
-    // Split b into 3 parts with proper padding
-    let mut b0 = vec![0.0; n_chunk];
-    let mut b1 = vec![0.0; n_chunk];
-    let mut b2 = vec![0.0; n_chunk];
+/// Toom-3 multiplication into `result` covering the progress span `[lo, hi)`,
+/// carving the buffers of every level from `workspace`
+#[allow(clippy::too_many_arguments)]
+fn toom3_into<T: Coefficient>(
+    a: &[T],
+    b: &[T],
+    result: &mut [T],
+    threshold: usize,
+    hooks: &mut RecursionHooks<T>,
+    workspace: &mut [T],
+    lo: f64,
+    hi: f64,
+) {
+    if hooks.aborted {
+        return;
+    }
 
-    for i in 0..n_chunk.min(m) {
-        b0[i] = b[i];
+    let n = a.len();
+    let m = b.len();
+    let depth = hooks.depth;
+    let threshold = hooks.schedule.map_or(threshold, |schedule| schedule(depth));
+    let thr = core::cmp::max(threshold, 5); // at least 5 to work
+    let is_base_case = n < thr || m < thr;
+    let is_blocked = !is_base_case && (n >= 2 * m || m >= 2 * n);
+    if let Some(stats) = hooks.stats.as_mut() {
+        stats.calls += 1;
+        stats.max_depth = stats.max_depth.max(depth);
+        stats.base_cases += is_base_case as usize;
+        stats.block_splits += is_blocked as usize;
     }
+    if let Some(tree) = hooks.tree.as_mut() {
+        let algorithm = match (is_base_case, is_blocked) {
+            (true, _) if hooks.karatsuba_threshold.is_some() => NodeAlgorithm::Karatsuba,
+            (true, _) => NodeAlgorithm::Naive,
+            (false, true) => NodeAlgorithm::Blocked,
+            (false, false) => NodeAlgorithm::Toom3,
+        };
+        hooks.tree_node = Some(tree.push(hooks.tree_node, n, m, algorithm));
+    }
+    let node = hooks.tree_node;
 
-    for i in 0..n_chunk.min(m.saturating_sub(n_chunk)) {
-        b1[i] = b[i + n_chunk];
+    // Threshold check
+    if is_base_case {
+        span!(TRACE, "base_case", n = n, m = m);
+        match hooks.karatsuba_threshold {
+            Some(karatsuba_threshold) => {
+                result.copy_from_slice(&karatsuba_impl(a, b, karatsuba_threshold))
+            }
+            None => naive_multiply_into(a, b, result),
+        }
+        hooks.advance(hi);
+        return;
     }
 
-    for i in 0..n_chunk.min(m.saturating_sub(2 * n_chunk)) {
-        b2[i] = b[i + 2 * n_chunk];
+    span!(TRACE, "toom3", n = n, m = m, depth = depth);
+    // Very unbalanced operands: slice the longer one into blocks of the shorter one's length
+    if is_blocked {
+        blocked_multiply_into(a, b, result, thr, hooks, workspace, lo, hi);
+        return;
     }
 
-    // Evaluate at 5 points: 0, 1, -1, 2, inf
-    // let a_at_0 = a0.clone();
-    let mut a_at_1 = vec![0.0; n_chunk];
-    let mut a_at_neg1 = vec![0.0; n_chunk];
-    let mut a_at_2 = vec![0.0; n_chunk];
-    let mut a_at_inf = vec![0.0; n_chunk];
+    // Determine chunk size for splitting polynomials into 3 parts
+    let n_chunk = n.max(m).div_ceil(3); // ceiling division
+    let (a_points, workspace) = workspace.split_at_mut(3 * n_chunk);
+    let (b_points, workspace) = workspace.split_at_mut(3 * n_chunk);
+    let (products, workspace) = workspace.split_at_mut(5 * (2 * n_chunk - 1));
+
+    // Split both operands into 3 parts and evaluate at 5 points: 0, 1, -1, 2, inf
+    let a_at = toom3_evaluate_into(a, n_chunk, a_points);
+    let b_at = toom3_evaluate_into(b, n_chunk, b_points);
+
+    // Pointwise multiplication at each evaluation point, zero-padding the shorter products
+    let step = (hi - lo) / 5.0;
+    hooks.depth = depth + 1;
+    for (k, product) in products.chunks_exact_mut(2 * n_chunk - 1).enumerate() {
+        let l = lo + k as f64 * step;
+        let (a_k, b_k) = (a_at[k], b_at[k]);
+        let (product, padding) = product.split_at_mut(product_len(a_k.len(), b_k.len()));
+        hooks.tree_node = node;
+        toom3_into(a_k, b_k, product, thr, hooks, workspace, l, l + step);
+        padding.fill(T::ZERO);
+    }
+    hooks.depth = depth;
 
-    // let b_at_0 = b0.clone();
-    let mut b_at_1 = vec![0.0; n_chunk];
-    let mut b_at_neg1 = vec![0.0; n_chunk];
-    let mut b_at_2 = vec![0.0; n_chunk];
-    let mut b_at_inf = vec![0.0; n_chunk];
+    toom3_interpolate_into(products, n_chunk, result);
+}
 
-    for i in 0..n_chunk {
-        a_at_1[i] = a0[i] + a1[i] + a2[i];
-        a_at_neg1[i] = a0[i] - a1[i] + a2[i];
-        a_at_2[i] = a0[i] + 2.0 * a1[i] + 4.0 * a2[i];
-        a_at_inf[i] = a2[i];
+/// Split `a` into 3 parts of at most `n_chunk` coefficients and evaluate them
+/// at the 5 points 0, 1, -1, 2, inf
+///
+/// The values at 0 and inf are the low and high parts of `a` itself, without copies or padding;
+/// the other 3 are written to `points`, which holds `3 * n_chunk` coefficients.
+/// The low part must be complete, i.e. `a` must have at least `n_chunk` coefficients.
+pub(crate) fn toom3_evaluate_into<'a, T: Coefficient>(
+    a: &'a [T],
+    n_chunk: usize,
+    points: &'a mut [T],
+) -> [&'a [T]; 5] {
+    let (a0, rest) = a.split_at(n_chunk);
+    let (a1, a2) = rest.split_at(n_chunk.min(rest.len()));
+    let (a_at_1, points) = points.split_at_mut(n_chunk);
+    let (a_at_neg1, a_at_2) = points.split_at_mut(n_chunk);
 
-        b_at_1[i] = b0[i] + b1[i] + b2[i];
-        b_at_neg1[i] = b0[i] - b1[i] + b2[i];
-        b_at_2[i] = b0[i] + 2.0 * b1[i] + 4.0 * b2[i];
-        b_at_inf[i] = b2[i];
+    // Evaluate at 1, -1 and 2 with Bodrato's sequence, sharing a0 + a2 and reusing the value at 1
+    // for the one at 2 = 2(a(1) + a2) - a0; past the end of a1 and a2 (a2 is never longer than a1)
+    // their coefficients are zeros, so each range gets its own loop instead of a branch
+    let two = T::from_f64(2.0);
+    let mut evaluate = |i: usize, x1: T, x2: T| {
+        let even = a0[i] + x2;
+        a_at_1[i] = even + x1;
+        a_at_neg1[i] = even - x1;
+        a_at_2[i] = two * (a_at_1[i] + x2) - a0[i];
+    };
+    for i in 0..a2.len() {
+        evaluate(i, a1[i], a2[i]);
+    }
+    for (i, &x1) in a1.iter().enumerate().skip(a2.len()) {
+        evaluate(i, x1, T::ZERO);
     }
+    for i in a1.len()..n_chunk {
+        evaluate(i, T::ZERO, T::ZERO);
+    }
+    record(n_chunk, 5 * n_chunk);
+
+    [a0, a_at_1, a_at_neg1, a_at_2, a2]
+}
 
-    // Pointwise multiplication at each evaluation point
-    let p0 = thresholded_multiply_impl(&a0, &b0, thr);
-    let p1 = thresholded_multiply_impl(&a_at_1, &b_at_1, thr);
-    let p2 = thresholded_multiply_impl(&a_at_neg1, &b_at_neg1, thr);
-    let p3 = thresholded_multiply_impl(&a_at_2, &b_at_2, thr);
-    let p4 = thresholded_multiply_impl(&a_at_inf, &b_at_inf, thr);
+/// Number of coefficients of the product of operands with `n` and `m` coefficients
+fn product_len(n: usize, m: usize) -> usize {
+    if n == 0 || m == 0 {
+        0
+    } else {
+        n + m - 1
+    }
+}
 
-    let result_len = n + m - 1;
-    let mut result = vec![0.0; result_len];
+/// Recombine the products at the 5 evaluation points, stored one after another
+/// in `products`, overwriting `result`
+pub(crate) fn toom3_interpolate_into<T: Coefficient>(
+    products: &[T],
+    n_chunk: usize,
+    result: &mut [T],
+) {
+    let product_len = products.len() / 5;
+    let [p0, p1, p2, p3, p4] =
+        core::array::from_fn(|k| &products[k * product_len..(k + 1) * product_len]);
+    let result_len = result.len();
+    result.fill(T::ZERO);
+    let mut additions = 0;
 
-    let mut add_to_result = |pos: usize, val: f64| {
+    let mut add_to_result = |pos: usize, val: T| {
         if pos < result_len {
             result[pos] += val;
+            additions += 1;
         }
     };
 
@@ -215,21 +1818,24 @@ pub fn thresholded_multiply_impl(a: &[f64], b: &[f64], threshold: usize) -> Vec<
         .max(p3.len())
         .max(p4.len());
 
+    let [two, three] = [2.0, 3.0].map(T::from_f64);
     for i in 0..max_coeff {
-        let v_0 = if i < p0.len() { p0[i] } else { 0.0 }; // 0
-        let v_1 = if i < p1.len() { p1[i] } else { 0.0 }; // 1
-        let v_neg1 = if i < p2.len() { p2[i] } else { 0.0 }; // -1
-        let v_2 = if i < p3.len() { p3[i] } else { 0.0 }; // 2
-        let v_inf = if i < p4.len() { p4[i] } else { 0.0 }; // inf
+        let v_0 = if i < p0.len() { p0[i] } else { T::ZERO }; // 0
+        let v_1 = if i < p1.len() { p1[i] } else { T::ZERO }; // 1
+        let v_neg1 = if i < p2.len() { p2[i] } else { T::ZERO }; // -1
+        let v_2 = if i < p3.len() { p3[i] } else { T::ZERO }; // 2
+        let v_inf = if i < p4.len() { p4[i] } else { T::ZERO }; // inf
 
-        // Interpolation formulas for Toom-3
+        // Bodrato's operation-minimal interpolation sequence: 8 additions, 1 division by 3
+        // and 2 exact halvings, with the coefficient each step leaves noted
         let r0 = v_0;
         let r4 = v_inf;
-        let r2 = (v_1 + v_neg1) / 2.0 - v_0 - v_inf;
-        let y = (v_1 - v_neg1) / 2.0;
-        let x = (v_2 - v_0 - 4.0 * r2 - 16.0 * v_inf) / 2.0;
-        let r3 = (x - y) / 3.0;
-        let r1 = y - r3;
+        let r3 = (v_2 - v_neg1) / three; // r1 + r2 + 3r3 + 5r4
+        let r1 = (v_1 - v_neg1) / two; // r1 + r3
+        let r2 = v_1 - v_0; // r1 + r2 + r3 + r4
+        let r3 = (r3 - r2) / two - two * v_inf; // r3
+        let r2 = r2 - r1 - v_inf; // r2
+        let r1 = r1 - r3; // r1
 
         add_to_result(i, r0);
         add_to_result(i + n_chunk, r1);
@@ -237,15 +1843,172 @@ pub fn thresholded_multiply_impl(a: &[f64], b: &[f64], threshold: usize) -> Vec<
         add_to_result(i + 3 * n_chunk, r3);
         add_to_result(i + 4 * n_chunk, r4);
     }
-
-    result
+    record(4 * max_coeff, 8 * max_coeff + additions);
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// Multiplication algorithm
+///
+/// Written as `naive`, `naive-compensated`, `cook-tooms`, `thresholded:<threshold>`
+/// and `hybrid:<toom3_threshold>:<karatsuba_threshold>` by [`Display`](fmt::Display),
+/// and parsed back by [`FromStr`], which also accepts `thresholded` and `hybrid`
+/// without (all) thresholds to use the defaults, and `toom3` as another name of `thresholded`.
+/// ## Example
+/// ```
+/// use mult_polynomial::PolynomialMultAlg;
+/// let alg: PolynomialMultAlg = "thresholded:32".parse().unwrap();
+/// assert_eq!(alg, PolynomialMultAlg::Thresholded(32));
+/// assert_eq!(alg.to_string(), "thresholded:32");
+/// assert_eq!(PolynomialMultAlg::all(&[16, 32]).count(), 6);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum PolynomialMultAlg {
     Naive,
+    NaiveCompensated,
     CookTooms,
     Thresholded(usize),
+    Hybrid(HybridConfig),
+}
+
+/// Thresholds of the hybrid algorithm
+///
+/// Operands at least `toom3_threshold` long are split by Toom-3,
+/// those at least `karatsuba_threshold` long by Karatsuba,
+/// and shorter ones are multiplied by the naive algorithm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HybridConfig {
+    /// Minimum operand length for a Toom-3 split
+    pub toom3_threshold: usize,
+    /// Minimum operand length for a Karatsuba split
+    pub karatsuba_threshold: usize,
+}
+
+impl Default for HybridConfig {
+    fn default() -> Self {
+        HybridConfig {
+            toom3_threshold: 256,
+            karatsuba_threshold: 64,
+        }
+    }
+}
+
+impl PolynomialMultAlg {
+    /// Threshold used by [`PolynomialMultAlg::auto`] for switching to the naive algorithm
+    pub const AUTO_THRESHOLD: usize = f64::AUTO_THRESHOLD;
+
+    /// Names of the algorithms, as returned by [`PolynomialMultAlg::name`]
+    pub const NAMES: [&'static str; 5] = [
+        "naive",
+        "naive-compensated",
+        "cook-tooms",
+        "thresholded",
+        "hybrid",
+    ];
+
+    /// Name of the algorithm without its thresholds
+    pub fn name(self) -> &'static str {
+        match self {
+            PolynomialMultAlg::Naive => "naive",
+            PolynomialMultAlg::NaiveCompensated => "naive-compensated",
+            PolynomialMultAlg::CookTooms => "cook-tooms",
+            PolynomialMultAlg::Thresholded(_) => "thresholded",
+            PolynomialMultAlg::Hybrid(_) => "hybrid",
+        }
+    }
+
+    /// Every algorithm, with one thresholded variant per threshold
+    /// and the hybrid one with [`HybridConfig::default`]
+    pub fn all(thresholds: &[usize]) -> impl Iterator<Item = PolynomialMultAlg> + '_ {
+        [
+            PolynomialMultAlg::Naive,
+            PolynomialMultAlg::NaiveCompensated,
+            PolynomialMultAlg::CookTooms,
+        ]
+        .into_iter()
+        .chain(
+            thresholds
+                .iter()
+                .map(|&t| PolynomialMultAlg::Thresholded(t)),
+        )
+        .chain([PolynomialMultAlg::Hybrid(HybridConfig::default())])
+    }
+
+    /// Replace the threshold of the thresholded algorithm, or the Toom-3 threshold
+    /// of the hybrid one; the other algorithms are returned unchanged
+    pub fn with_threshold(self, threshold: usize) -> PolynomialMultAlg {
+        match self {
+            PolynomialMultAlg::Thresholded(_) => PolynomialMultAlg::Thresholded(threshold),
+            PolynomialMultAlg::Hybrid(config) => PolynomialMultAlg::Hybrid(HybridConfig {
+                toom3_threshold: threshold,
+                ..config
+            }),
+            algorithm => algorithm,
+        }
+    }
+
+    /// Choose a reasonable algorithm for operands with `n` and `m` coefficients
+    pub fn auto(n: usize, m: usize) -> PolynomialMultAlg {
+        Self::auto_for::<f64>(n, m)
+    }
+
+    /// Choose a reasonable algorithm for operands with `n` and `m` coefficients of type `T`
+    pub fn auto_for<T: Coefficient>(n: usize, m: usize) -> PolynomialMultAlg {
+        if n.min(m) < T::AUTO_THRESHOLD {
+            PolynomialMultAlg::Naive
+        } else {
+            PolynomialMultAlg::Thresholded(T::AUTO_THRESHOLD)
+        }
+    }
+}
+
+impl fmt::Display for PolynomialMultAlg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolynomialMultAlg::Thresholded(threshold) => write!(f, "thresholded:{}", threshold),
+            PolynomialMultAlg::Hybrid(config) => write!(
+                f,
+                "hybrid:{}:{}",
+                config.toom3_threshold, config.karatsuba_threshold
+            ),
+            algorithm => f.write_str(algorithm.name()),
+        }
+    }
+}
+
+impl FromStr for PolynomialMultAlg {
+    type Err = PolyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let unknown = || PolyError::UnknownAlgorithm(s.to_string());
+        let mut parts = s.split(':');
+        let name = parts.next().unwrap_or_default();
+        let thresholds = parts
+            .map(|t| t.parse::<usize>().map_err(|_| unknown()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let default = HybridConfig::default();
+        match (name, thresholds.as_slice()) {
+            ("naive", &[]) => Ok(PolynomialMultAlg::Naive),
+            ("naive-compensated", &[]) => Ok(PolynomialMultAlg::NaiveCompensated),
+            ("cook-tooms", &[]) => Ok(PolynomialMultAlg::CookTooms),
+            ("thresholded" | "toom3", &[]) => {
+                Ok(PolynomialMultAlg::Thresholded(Self::AUTO_THRESHOLD))
+            }
+            ("thresholded" | "toom3", &[threshold]) => {
+                Ok(PolynomialMultAlg::Thresholded(threshold))
+            }
+            ("hybrid", &[]) => Ok(PolynomialMultAlg::Hybrid(default)),
+            ("hybrid", &[toom3_threshold]) => Ok(PolynomialMultAlg::Hybrid(HybridConfig {
+                toom3_threshold,
+                ..default
+            })),
+            ("hybrid", &[toom3_threshold, karatsuba_threshold]) => {
+                Ok(PolynomialMultAlg::Hybrid(HybridConfig {
+                    toom3_threshold,
+                    karatsuba_threshold,
+                }))
+            }
+            _ => Err(unknown()),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -277,10 +2040,11 @@ mod tests {
         let p2 = Polynomial::new(vec![0.0]);
 
         let result = p1.multiply_naive(&p2);
-        assert_eq!(result.coeffs, vec![]);
+        assert!(result.coeffs.is_empty());
     }
 
     /// Test multiplication of larger given polynomials
+    #[cfg(feature = "std")]
     #[test]
     fn test_given_polynomial_multiplication() {
         // Fixed test case with simple coefficients
@@ -307,6 +2071,7 @@ mod tests {
     }
 
     /// Test multiplication with random polynomials
+    #[cfg(feature = "thread_rng")]
     #[test]
     fn test_random_polynomials_multiplication() {
         let p1 = Polynomial::random(-10.0, 10.0, 10);
@@ -350,4 +2115,572 @@ mod tests {
         let result = p1.multiply_naive(&p_empty);
         assert_eq!(result, Polynomial::new(vec![]));
     }
+
+    /// Test progress reporting of the thresholded multiplication
+    #[cfg(feature = "thread_rng")]
+    #[test]
+    fn test_progress_callback() {
+        let p1 = Polynomial::random(-10.0, 10.0, 500);
+        let p2 = Polynomial::random(-10.0, 10.0, 500);
+
+        let mut fractions = Vec::new();
+        let result = p1.multiply_thresholded_with_progress(&p2, 8, |f| fractions.push(f));
+
+        // Same product as without progress reporting
+        assert_eq!(result, p1.multiply_thresholded(&p2, 8));
+
+        // Progress is monotone and ends at exactly 1
+        assert!(fractions.len() > 1);
+        assert!(fractions.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(*fractions.last().unwrap(), 1.0);
+
+        // Base case only: a single completion report
+        let mut calls = 0;
+        p1.multiply_thresholded_with_progress(&p2, 1000, |_| calls += 1);
+        assert_eq!(calls, 1);
+    }
+
+    /// Test the compensated naive multiplication against catastrophic cancellation
+    #[cfg(feature = "thread_rng")]
+    #[test]
+    fn test_naive_compensated() {
+        let p1 = Polynomial::random(-10.0, 10.0, 50);
+        let p2 = Polynomial::random(-10.0, 10.0, 50);
+        assert_eq!(p1.multiply_naive_compensated(&p2), p1.multiply_naive(&p2));
+
+        // The coefficient of x² is 1e16 + 1 - 1e16 = 1, but the 1 is absorbed
+        // when accumulated into 1e16 in plain floating point
+        let p1 = Polynomial::new(vec![1e16, 1.0, -1e16]);
+        let p2 = Polynomial::new(vec![1.0, 1.0, 1.0]);
+        assert_eq!(p1.multiply_naive(&p2).coeffs[2], 0.0);
+        assert_eq!(p1.multiply_naive_compensated(&p2).coeffs[2], 1.0);
+    }
+
+    /// Test very unbalanced operand sizes
+    #[cfg(feature = "thread_rng")]
+    #[test]
+    fn test_unbalanced_sizes() {
+        for (n, m) in [
+            (6, 30),
+            (30, 6),
+            (11, 1000),
+            (100, 199),
+            (100, 200),
+            (64, 65),
+        ] {
+            let p1 = Polynomial::random(-10.0, 10.0, n);
+            let p2 = Polynomial::random(-10.0, 10.0, m);
+            let expected = p1.multiply_naive(&p2);
+            let result = p1.multiply_thresholded(&p2, 5);
+            assert_eq!(result.coeffs.len(), expected.coeffs.len());
+            for (r, e) in result.coeffs.iter().zip(&expected.coeffs) {
+                assert!((r - e).abs() < 1e-9 * n.max(m) as f64, "({}, {})", n, m);
+            }
+        }
+    }
+
+    /// Test tolerance-aware comparison of Toom-3 and naive products
+    #[cfg(feature = "thread_rng")]
+    #[test]
+    fn test_approx_eq() {
+        let p1 = Polynomial::random(-1e3, 1e3, 500);
+        let p2 = Polynomial::random(-1e-3, 1e-3, 300);
+        let naive = p1.multiply_naive(&p2);
+        let toom3 = p1.multiply_cook_tooms_k3(&p2);
+        assert!(toom3.approx_eq(&naive, 1e-9, 1e-9));
+        assert!(naive.approx_eq(&toom3, 1e-9, 1e-9));
+
+        let mut shifted = naive.clone();
+        shifted.coeffs[10] += 1e-3;
+        assert!(!shifted.approx_eq(&naive, 1e-9, 1e-9));
+        // a missing trailing coefficient compares as zero
+        let short = Polynomial::new(vec![1.0, 2.0]);
+        let padded = Polynomial::with_normalization(vec![1.0, 2.0, 1e-20], Normalization::Keep);
+        assert!(short.approx_eq(&padded, 1e-15, 0.0));
+    }
+
+    /// Test that the normalization policy is kept through the multiplication results
+    #[test]
+    fn test_normalization() {
+        let tiny = vec![1.0, 1e-10];
+        assert_eq!(Polynomial::new(tiny.clone()).degree(), 1);
+        assert_eq!(Polynomial::new(tiny.clone()).square().degree(), 1);
+        let p = Polynomial::with_normalization(tiny.clone(), Normalization::Keep);
+        for alg in [
+            PolynomialMultAlg::Naive,
+            PolynomialMultAlg::NaiveCompensated,
+            PolynomialMultAlg::CookTooms,
+            PolynomialMultAlg::Thresholded(1),
+        ] {
+            let product = p.multiply(&p, alg);
+            assert_eq!(product.degree(), 2);
+            assert_eq!(product.normalization(), Normalization::Keep);
+        }
+        assert_eq!(p.pow(4).degree(), 4);
+        let zeros = Polynomial::with_normalization(vec![1.0, 0.0, 0.0], Normalization::Keep);
+        assert_eq!(zeros.coeffs.len(), 3);
+        assert_eq!(Polynomial::from(vec![1.0, 0.0]).coeffs, vec![1.0]);
+        let coarse =
+            Polynomial::with_normalization(vec![1.0, 0.5, 1e-3], Normalization::Trim(1e-2));
+        assert_eq!(coarse.degree(), 1);
+        assert_eq!(coarse.multiply_naive(&coarse).degree(), 2);
+        let p = Polynomial::with_normalization(tiny, Normalization::Trim(1e-30));
+        assert_eq!(p.multiply_cook_tooms_k3(&p).coeffs.len(), 3);
+    }
+
+    /// Test the derivatives at a point against the derivative polynomials
+    #[test]
+    fn test_evaluate_derivatives() {
+        let p = Polynomial::new(vec![3.0, -1.0, 0.5, 2.0, -0.25, 1.0]);
+        for x in [-1.5, 0.0, 0.3, 2.0] {
+            let (value, slope) = p.evaluate_with_derivative(x);
+            assert!((value - p.evaluate(x)).abs() < 1e-12);
+            assert!((slope - p.derivative().evaluate(x)).abs() < 1e-12);
+            let derivatives = p.evaluate_derivatives(x, 7);
+            let mut q = p.clone();
+            for d in &derivatives {
+                assert!((d - q.evaluate(x)).abs() < 1e-9 * (1.0 + d.abs()));
+                q = q.derivative();
+            }
+            assert_eq!(derivatives[6..], [0.0, 0.0]);
+        }
+        let zero = Polynomial::new(vec![0.0]);
+        assert_eq!(zero.evaluate_with_derivative(1.0), (0.0, 0.0));
+        assert_eq!(zero.evaluate_derivatives(1.0, 0), vec![0.0]);
+    }
+
+    /// Test the queries and norms, also on the zero polynomial and with kept trailing zeros
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_queries_and_norms() {
+        let p = Polynomial::new(vec![2.0, 0.0, -1.0, 4.0]);
+        assert!(!p.is_zero());
+        assert_eq!((p.len(), p.num_terms()), (4, 3));
+        assert_eq!(p.leading_coefficient(), Some(4.0));
+        assert_eq!(p.monic().unwrap().coeffs, vec![0.5, 0.0, -0.25, 1.0]);
+        assert_eq!((p.norm_l1(), p.norm_l2()), (7.0, 21.0f64.sqrt()));
+        assert_eq!((p.norm_inf(), p.height()), (4.0, 4.0));
+
+        let zero = Polynomial::new(vec![0.0, 1e-20]);
+        assert!(zero.is_zero() && zero.is_empty());
+        assert_eq!((zero.degree(), zero.leading_coefficient()), (0, None));
+        assert_eq!(zero.degree_checked(), None);
+        assert_eq!(p.degree_checked(), Some(3));
+        assert_eq!(zero.monic(), Err(PolyError::DivisionByZero));
+        assert_eq!(
+            (zero.norm_l1(), zero.norm_l2(), zero.height()),
+            (0.0, 0.0, 0.0)
+        );
+        assert!(!Polynomial::new(vec![3.0]).is_zero());
+
+        let kept = Polynomial::with_normalization(vec![0.0, 2.0, 0.0], Normalization::Keep);
+        assert_eq!((kept.len(), kept.num_terms()), (3, 1));
+        assert_eq!(kept.leading_coefficient(), Some(2.0));
+        assert_eq!((kept.degree(), kept.degree_checked()), (2, Some(1)));
+        let zeros = Polynomial::with_normalization(vec![0.0; 3], Normalization::Keep);
+        assert!(zeros.is_zero());
+        assert_eq!(zeros.degree_checked(), None);
+
+        // no overflow of the squares
+        let huge = Polynomial::new(vec![3e300, 4e300]);
+        assert!((huge.norm_l2() / 5e300 - 1.0).abs() < 1e-15);
+        assert_eq!(Polynomial::new(vec![1.0f32, -2.0]).height(), 2.0);
+    }
+
+    /// Test that the algorithm names round-trip through `Display` and `FromStr`
+    #[test]
+    fn test_algorithm_names() {
+        let algorithms: Vec<_> = PolynomialMultAlg::all(&[1, 64]).collect();
+        assert_eq!(algorithms.len(), 6);
+        for algorithm in algorithms {
+            assert_eq!(algorithm.to_string().parse(), Ok(algorithm));
+            assert!(PolynomialMultAlg::NAMES.contains(&algorithm.name()));
+        }
+        assert_eq!(
+            "hybrid:100".parse(),
+            Ok(PolynomialMultAlg::Hybrid(HybridConfig {
+                toom3_threshold: 100,
+                karatsuba_threshold: 64
+            }))
+        );
+        assert_eq!(
+            "thresholded".parse(),
+            Ok(PolynomialMultAlg::Thresholded(64))
+        );
+        assert_eq!("toom3:16".parse(), Ok(PolynomialMultAlg::Thresholded(16)));
+        for name in ["fft", "naive:3", "thresholded:x", "hybrid:1:2:3", ""] {
+            assert_eq!(
+                name.parse::<PolynomialMultAlg>(),
+                Err(PolyError::UnknownAlgorithm(name.to_string()))
+            );
+        }
+        assert_eq!(
+            PolynomialMultAlg::CookTooms.with_threshold(8),
+            PolynomialMultAlg::CookTooms
+        );
+    }
+
+    /// Test building polynomials from their roots
+    #[test]
+    fn test_from_roots() {
+        assert_eq!(Polynomial::from_roots(&[]), Polynomial::new(vec![1.0]));
+        assert_eq!(
+            Polynomial::from_roots(&[2.0]),
+            Polynomial::new(vec![-2.0, 1.0])
+        );
+        // (x - 1)ⁿ has binomial coefficients with alternating signs
+        let p = Polynomial::from_roots(&[1.0; 10]);
+        assert_eq!(p, Polynomial::new(vec![1.0, -1.0]).pow(10));
+        // the product tree agrees with adding one factor at a time
+        let roots: Vec<f64> = (0..100)
+            .map(|i| ((i * 37) % 100) as f64 / 50.0 - 1.0)
+            .collect();
+        let sequential = roots.iter().fold(Polynomial::new(vec![1.0]), |acc, r| {
+            acc.multiply_naive(&Polynomial::new(vec![-r, 1.0]))
+        });
+        let p = Polynomial::from_roots(&roots);
+        assert_eq!(p.degree(), 100);
+        let scale = sequential.coeffs.iter().fold(0.0f64, |m, c| m.max(c.abs()));
+        assert!(p.approx_eq(&sequential, 1e-12 * scale, 0.0));
+    }
+
+    /// Test that the threshold schedule is applied per recursion depth
+    #[cfg(feature = "thread_rng")]
+    #[test]
+    fn test_threshold_schedule() {
+        let p1 = Polynomial::random(-10.0, 10.0, 729);
+        let p2 = Polynomial::random(-10.0, 10.0, 500);
+        let expected = p1.multiply_naive(&p2);
+        // a constant schedule is the thresholded algorithm
+        let constant = p1.multiply_thresholded_with_schedule(&p2, |_| 16);
+        assert_eq!(constant.coeffs, p1.multiply_thresholded(&p2, 16).coeffs);
+        for (schedule, max_depth) in [
+            (&[1000][..], 0),
+            (&[16, 1000][..], 1),
+            (&[500, 100, 10][..], 4),
+            (&[10, 10, 100][..], 2),
+        ] {
+            let schedule = |depth: usize| schedule[depth.min(schedule.len() - 1)];
+            let product = p1.multiply_thresholded_with_schedule(&p2, schedule);
+            assert!(product.approx_eq(&expected, 1e-9, 1e-9));
+            let mut stats = MultiplyStats::default();
+            let mut hooks = RecursionHooks::none();
+            hooks.stats = Some(&mut stats);
+            hooks.schedule = Some(&schedule);
+            toom3_impl(&p1.coeffs, &p2.coeffs, 0, &mut hooks);
+            assert_eq!(stats.max_depth, max_depth);
+        }
+    }
+
+    /// The tiled naive kernel matches the plain row-by-row loop bit for bit
+    #[cfg(feature = "thread_rng")]
+    #[test]
+    fn test_naive_tiled() {
+        fn reference<T: Coefficient>(a: &[T], b: &[T]) -> Vec<T> {
+            let mut result = vec![T::ZERO; a.len() + b.len() - 1];
+            for i in 0..a.len() {
+                for j in 0..b.len() {
+                    result[i + j] = mul_add(a[i], b[j], result[i + j]);
+                }
+            }
+            result
+        }
+        // a tile holds 2048 f64 coefficients
+        for (n, m) in [
+            (3000, 2500),
+            (2049, 1),
+            (1, 4100),
+            (5000, 2),
+            (2047, 3),
+            (4097, 4),
+            (6, 9000),
+        ] {
+            let p1 = Polynomial::random(-10.0, 10.0, n);
+            let p2 = Polynomial::random(-10.0, 10.0, m);
+            assert_eq!(
+                naive_multiply_impl(&p1.coeffs, &p2.coeffs),
+                reference(&p1.coeffs, &p2.coeffs),
+                "n = {}, m = {}",
+                n,
+                m
+            );
+        }
+        let a: Vec<f32> = (0..5000)
+            .map(|i| ((i * 7919) % 1000) as f32 / 999.0)
+            .collect();
+        assert_eq!(
+            naive_multiply_impl(&a, &a[..3001]),
+            reference(&a, &a[..3001])
+        );
+    }
+
+    /// The work-list multiplication repeats the recursion exactly, including its operation counts
+    #[cfg(feature = "thread_rng")]
+    #[test]
+    fn test_thresholded_iterative() {
+        for (n, m) in [
+            (729, 729),
+            (500, 300),
+            (1000, 7),
+            (37, 1000),
+            (4, 4),
+            (0, 10),
+        ] {
+            let p1 = Polynomial::random(-10.0, 10.0, n.max(1));
+            let p2 = Polynomial::random(-10.0, 10.0, m.max(1));
+            let (a, b) = (&p1.coeffs[..n], &p2.coeffs[..m]);
+            for threshold in [1, 8, 32, 2000] {
+                assert_eq!(
+                    thresholded_multiply_iterative(a, b, threshold),
+                    thresholded_multiply_impl(a, b, threshold),
+                    "n = {}, m = {}, threshold = {}",
+                    n,
+                    m,
+                    threshold
+                );
+            }
+        }
+        let a: Vec<f32> = (0..400).map(|i| (i % 11) as f32 - 5.0).collect();
+        assert_eq!(
+            thresholded_multiply_iterative(&a, &a[..150], 5),
+            thresholded_multiply_impl(&a, &a[..150], 5)
+        );
+        #[cfg(feature = "op_count")]
+        {
+            let p = Polynomial::random(-10.0, 10.0, 300);
+            let (_, iterative) =
+                crate::count_ops(|| thresholded_multiply_iterative(&p.coeffs, &p.coeffs[..100], 8));
+            let (_, recursive) =
+                crate::count_ops(|| thresholded_multiply_impl(&p.coeffs, &p.coeffs[..100], 8));
+            assert_eq!(iterative, recursive);
+        }
+    }
+
+    /// Test Karatsuba and the hybrid cascade on balanced and unbalanced operands
+    #[cfg(feature = "thread_rng")]
+    #[test]
+    fn test_hybrid() {
+        for (n, m) in [(1, 1), (7, 7), (100, 100), (300, 41), (13, 500), (257, 256)] {
+            let p1 = Polynomial::random(-10.0, 10.0, n);
+            let p2 = Polynomial::random(-10.0, 10.0, m);
+            let expected = p1.multiply_naive(&p2);
+            for threshold in [1, 2, 5, 16] {
+                let product = Polynomial::new(karatsuba_impl(&p1.coeffs, &p2.coeffs, threshold));
+                assert!(product.approx_eq(&expected, 1e-9, 1e-9));
+            }
+            for (toom3_threshold, karatsuba_threshold) in [(10, 3), (64, 16), (1000, 8)] {
+                let config = HybridConfig {
+                    toom3_threshold,
+                    karatsuba_threshold,
+                };
+                let product = p1.multiply(&p2, PolynomialMultAlg::Hybrid(config));
+                assert!(product.approx_eq(&expected, 1e-9, 1e-9));
+            }
+        }
+        assert_eq!(karatsuba_shape(100, 16), (3, 13));
+        assert_eq!(karatsuba_shape(10, 16), (0, 10));
+    }
+
+    /// Test that a single workspace, linear in the operand length, serves the whole recursion
+    #[cfg(feature = "thread_rng")]
+    #[test]
+    fn test_workspace() {
+        let mut scratch = Scratch::default();
+        for (n, m, threshold) in [(729, 729, 5), (1000, 999, 16), (100, 1000, 8), (40, 61, 5)] {
+            let p1 = Polynomial::random(-10.0, 10.0, n);
+            let p2 = Polynomial::random(-10.0, 10.0, m);
+            let product = thresholded_multiply_impl(&p1.coeffs, &p2.coeffs, threshold);
+            assert!(p1
+                .with_coeffs(product.clone())
+                .approx_eq(&p1.multiply_naive(&p2), 1e-9, 1e-9));
+            let mut hooks = RecursionHooks::none();
+            hooks.scratch = Some(&mut scratch);
+            assert_eq!(
+                toom3_impl(&p1.coeffs, &p2.coeffs, threshold, &mut hooks),
+                product
+            );
+            let len = toom3_workspace_len(n, m, threshold, None, 0);
+            assert_eq!(scratch.workspace.len(), len);
+            assert!(len <= 10 * n.max(m));
+        }
+        let p = Polynomial::random(-10.0, 10.0, 500);
+        assert!(p
+            .square_thresholded(8)
+            .approx_eq(&p.multiply_naive(&p), 1e-9, 1e-9));
+    }
+
+    /// Test the recursion statistics against the predicted recursion shape
+    #[cfg(feature = "thread_rng")]
+    #[test]
+    fn test_multiply_stats() {
+        let p = Polynomial::random(-10.0, 10.0, 200);
+        for threshold in [5, 16, 64, 300] {
+            let (product, stats) = p.multiply_thresholded_with_stats(&p, threshold);
+            assert_eq!(product, p.multiply_thresholded(&p, threshold));
+            let (depth, _) = recursion_shape(200, 200, threshold);
+            assert_eq!(stats.max_depth, depth);
+            assert_eq!(stats.base_cases, 5usize.pow(depth as u32));
+            assert_eq!(stats.calls, (5usize.pow(depth as u32 + 1) - 1) / 4);
+            assert_eq!(stats.block_splits, 0);
+        }
+
+        // 1000 coefficients against 10 are split into 100 blocks of 10
+        let short = Polynomial::random(-10.0, 10.0, 10);
+        let long = Polynomial::random(-10.0, 10.0, 1000);
+        let (_, stats) = short.multiply_thresholded_with_stats(&long, 5);
+        assert_eq!(stats.block_splits, 1);
+        assert_eq!(stats.calls, 1 + 100 * 6); // one Toom-3 level with chunks of 4 per block
+    }
+
+    /// Test squaring and powers against general multiplication
+    #[cfg(feature = "thread_rng")]
+    #[test]
+    fn test_square_and_pow() {
+        for size in [1, 2, 7, 50, 301] {
+            let p = Polynomial::random(-10.0, 10.0, size);
+            let expected = p.multiply_naive(&p);
+            let tolerance = 1e-9 * size as f64;
+            for result in [p.square(), p.square_thresholded(16)] {
+                assert_eq!(result.coeffs.len(), expected.coeffs.len());
+                for (r, e) in result.coeffs.iter().zip(&expected.coeffs) {
+                    assert!((r - e).abs() < tolerance);
+                }
+            }
+        }
+        assert_eq!(
+            Polynomial::<f64>::new(vec![]).square(),
+            Polynomial::new(vec![])
+        );
+
+        let p = Polynomial::new(vec![1.0, -1.0]);
+        assert_eq!(p.pow(0), Polynomial::new(vec![1.0]));
+        assert_eq!(p.pow(4), Polynomial::new(vec![1.0, -4.0, 6.0, -4.0, 1.0]));
+        assert_eq!(p.pow(5), p.pow(4).multiply_naive(&p));
+    }
+
+    /// Test every algorithm on `f32` coefficients against the `f64` product
+    #[cfg(feature = "thread_rng")]
+    #[test]
+    fn test_f32() {
+        let p1 = Polynomial::random(-1.0, 1.0, 300).cast::<f32>();
+        let p2 = Polynomial::random(-1.0, 1.0, 200).cast::<f32>();
+        let expected = p1.cast::<f64>().multiply_naive(&p2.cast::<f64>());
+        for alg in [
+            PolynomialMultAlg::Naive,
+            PolynomialMultAlg::NaiveCompensated,
+            PolynomialMultAlg::CookTooms,
+            PolynomialMultAlg::Thresholded(32),
+            PolynomialMultAlg::Hybrid(HybridConfig::default()),
+            PolynomialMultAlg::auto_for::<f32>(300, 200),
+        ] {
+            let product = p1.multiply(&p2, alg);
+            assert!(
+                product.cast::<f64>().approx_eq(&expected, 1e-3, 0.0),
+                "{:?}",
+                alg
+            );
+        }
+        // compensated accumulation is exact to the rounding of the result
+        let compensated = p1.multiply_naive_compensated(&p2).cast::<f64>();
+        assert!(compensated.approx_eq(&expected, 0.0, f32::EPSILON as f64));
+        assert!(p1
+            .square()
+            .cast::<f64>()
+            .approx_eq(&p1.cast::<f64>().square(), 1e-3, 0.0));
+        // trimming and comparison use the f32 tolerance
+        assert_eq!(Polynomial::<f32>::new(vec![1.0, 1e-4]).degree(), 0);
+        assert_eq!(
+            Polynomial::<f32>::new(vec![1.0, 2.0]),
+            Polynomial::new(vec![1.0 + 1e-4, 2.0])
+        );
+        assert_eq!(
+            PolynomialMultAlg::auto_for::<f32>(100, 100),
+            PolynomialMultAlg::Naive
+        );
+    }
+
+    /// Test Estrin's scheme against the sequential evaluation, around every block boundary
+    #[cfg(feature = "thread_rng")]
+    #[test]
+    fn test_evaluate_fast() {
+        for n in (1..=40).chain([1000, 100_001]) {
+            let p = Polynomial::random(-1.0, 1.0, n);
+            for x in [-1.0, -0.7, 0.0, 0.3, 0.999, 1.0] {
+                let (fast, slow) = (p.evaluate_fast(x), p.evaluate(x));
+                // rounding errors scale with the sum of the absolute terms
+                let scale: f64 = (p.coeffs.iter().enumerate())
+                    .map(|(k, c)| (c * x.powi(k as i32)).abs())
+                    .sum();
+                assert!(
+                    (fast - slow).abs() <= 1e-13 * scale.max(1.0),
+                    "n = {}, x = {}",
+                    n,
+                    x
+                );
+            }
+        }
+        assert_eq!(Polynomial::new(vec![]).evaluate_fast(2.0), 0.0);
+        let p = Polynomial::<f32>::new(vec![1.0; 9]);
+        assert_eq!(p.evaluate_fast(2.0), 511.0);
+    }
+
+    /// Test that the rounding error of Toom-3 shrinks with 256-bit coefficients
+    #[test]
+    #[cfg(feature = "f256")]
+    fn test_f256() {
+        use f256::f256;
+        use rand::{rngs::StdRng, SeedableRng};
+        let mut rng = StdRng::seed_from_u64(1);
+        let p1 = Polynomial::random_with_rng(-10.0, 10.0, 200, &mut rng);
+        let p2 = Polynomial::random_with_rng(-10.0, 10.0, 150, &mut rng);
+        let (q1, q2) = (p1.cast::<f256>(), p2.cast::<f256>());
+        // products of f64 inputs are exact in 256 bits with the naive algorithm
+        let exact = q1.multiply_naive(&q2);
+        let error = |product: &Polynomial<f256>| {
+            (product.coeffs.iter().zip(&exact.coeffs))
+                .map(|(&c, &e)| (c - e).abs().to_f64())
+                .fold(0.0, f64::max)
+        };
+        let toom3_f64 = error(&p1.multiply_cook_tooms_k3(&p2).cast());
+        let toom3_f256 = error(&q1.multiply_cook_tooms_k3(&q2));
+        assert!(toom3_f64 > 1e-13);
+        assert!(toom3_f256 < 1e-60);
+        assert!(q1
+            .multiply_thresholded(&q2, 16)
+            .approx_eq(&exact, 1e-60, 0.0));
+        assert!(exact
+            .cast::<f64>()
+            .approx_eq(&p1.multiply_naive(&p2), 1e-9, 1e-12));
+    }
+
+    /// Test that products are bit-identical to reference values computed on another machine
+    #[test]
+    #[cfg(any(
+        not(feature = "fast_fma"),
+        feature = "strict_fp",
+        not(any(target_feature = "fma", target_arch = "aarch64"))
+    ))]
+    fn test_strict_fp_golden() {
+        // deterministic inputs from a linear congruential generator
+        let mut state = 1u64;
+        let mut next = || {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 11) as f64 / (1u64 << 53) as f64 * 20.0 - 10.0
+        };
+        let p1 = Polynomial::new((0..500).map(|_| next()).collect());
+        let p2 = Polynomial::new((0..400).map(|_| next()).collect());
+
+        assert_eq!(p1.multiply_naive(&p2).fingerprint(), 0xffc5e5b8c861e6a9);
+        assert_eq!(
+            p1.multiply_cook_tooms_k3(&p2).fingerprint(),
+            0x7cd07d5d61c1b9e2
+        );
+        assert_eq!(
+            p1.multiply_thresholded(&p2, 32).fingerprint(),
+            0x464d4ded6750de22
+        );
+        assert_eq!(p1.square().fingerprint(), 0xf08740858516c6f2);
+    }
 }
@@ -0,0 +1,149 @@
+//! Python bindings via PyO3
+//!
+//! Enabled with the `python` feature. Build and install into the current virtual environment
+//! with `maturin develop --release --features python`, then:
+//! ```python
+//! import mult_polynomial as mp
+//! p = mp.Polynomial.random(-10.0, 10.0, 1000)
+//! product = p.multiply_thresholded(p, 64).coeffs
+//! seconds = mp.benchmark_single("cook-tooms", p, p)
+//! ```
+
+use crate::benchmark::{
+    benchmark_single as benchmark_single_impl, run_comprehensive_benchmark, BenchmarkConfig,
+    RunsPerSize,
+};
+use crate::{PolyError, Polynomial, PolynomialMultAlg};
+use pyo3::exceptions::{PyIOError, PyValueError};
+use pyo3::prelude::*;
+
+/// Polynomial exposed to Python as `mult_polynomial.Polynomial`
+#[pyclass(name = "Polynomial", module = "mult_polynomial")]
+pub struct PyPolynomial {
+    inner: Polynomial,
+}
+
+#[pymethods]
+impl PyPolynomial {
+    /// Create a polynomial from coefficients, from lowest to highest degree
+    #[new]
+    fn new(coeffs: Vec<f64>) -> Self {
+        PyPolynomial {
+            inner: Polynomial::new(coeffs),
+        }
+    }
+
+    /// Generate a random polynomial with given size
+    #[staticmethod]
+    fn random(range_min: f64, range_max: f64, size: usize) -> PyResult<Self> {
+        let inner = Polynomial::try_random(range_min, range_max, size)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(PyPolynomial { inner })
+    }
+
+    /// Coefficients, from lowest to highest degree
+    #[getter]
+    fn coeffs(&self) -> Vec<f64> {
+        self.inner.coeffs.clone()
+    }
+
+    /// Get the degree of the polynomial
+    fn degree(&self) -> usize {
+        self.inner.degree()
+    }
+
+    /// Evaluate the polynomial at a given point x
+    fn evaluate(&self, x: f64) -> f64 {
+        self.inner.evaluate(x)
+    }
+
+    /// Multiply using the naive O(n²) algorithm
+    fn multiply_naive(&self, py: Python<'_>, other: &PyPolynomial) -> PyPolynomial {
+        let inner = py.detach(|| self.inner.multiply_naive(&other.inner));
+        PyPolynomial { inner }
+    }
+
+    /// Multiply using the Cook-Tooms algorithm with k=3
+    fn multiply_cook_tooms_k3(&self, py: Python<'_>, other: &PyPolynomial) -> PyPolynomial {
+        let inner = py.detach(|| self.inner.multiply_cook_tooms_k3(&other.inner));
+        PyPolynomial { inner }
+    }
+
+    /// Multiply using a thresholded approach
+    fn multiply_thresholded(
+        &self,
+        py: Python<'_>,
+        other: &PyPolynomial,
+        threshold: usize,
+    ) -> PyPolynomial {
+        let inner = py.detach(|| self.inner.multiply_thresholded(&other.inner, threshold));
+        PyPolynomial { inner }
+    }
+
+    fn __len__(&self) -> usize {
+        self.inner.coeffs.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Polynomial({:?})", self.inner.coeffs)
+    }
+}
+
+/// Time a single multiplication in seconds
+///
+/// `algorithm` is one of `naive`, `naive-compensated`, `cook-tooms`, `thresholded` or `hybrid`
+/// (with `threshold` as its Toom-3 threshold), see [`PolynomialMultAlg::NAMES`].
+#[pyfunction]
+#[pyo3(signature = (algorithm, p1, p2, threshold = PolynomialMultAlg::AUTO_THRESHOLD))]
+fn benchmark_single(
+    py: Python<'_>,
+    algorithm: &str,
+    p1: &PyPolynomial,
+    p2: &PyPolynomial,
+    threshold: usize,
+) -> PyResult<f64> {
+    let algorithm = (algorithm.parse::<PolynomialMultAlg>())
+        .map_err(|e| PyValueError::new_err(e.to_string()))?
+        .with_threshold(threshold);
+    Ok(py.detach(|| benchmark_single_impl(algorithm, &p1.inner, &p2.inner).as_secs_f64()))
+}
+
+/// Run the comprehensive benchmark, writing `all_results.csv` to `output_dir`
+///
+/// Each size is run `max(min_runs, total_size // size)` times.
+#[pyfunction]
+#[pyo3(signature = (min_runs = 10, total_size = 100000, output_dir = "output_data", seed = None))]
+fn run_benchmark(
+    py: Python<'_>,
+    min_runs: usize,
+    total_size: usize,
+    output_dir: &str,
+    seed: Option<u64>,
+) -> PyResult<()> {
+    let config = BenchmarkConfig {
+        output_dir: output_dir.into(),
+        seed,
+        ..BenchmarkConfig::default()
+    };
+    py.detach(|| {
+        let runs = RunsPerSize {
+            min_runs,
+            total_size,
+        };
+        run_comprehensive_benchmark(&config, &|size| runs.runs(size).max(1))
+    })
+    .map(|_| ())
+    .map_err(|e| match e {
+        PolyError::InvalidArgument(_) => PyValueError::new_err(e.to_string()),
+        _ => PyIOError::new_err(e.to_string()),
+    })
+}
+
+/// Polynomial multiplication algorithms
+#[pymodule]
+fn mult_polynomial(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyPolynomial>()?;
+    m.add_function(wrap_pyfunction!(benchmark_single, m)?)?;
+    m.add_function(wrap_pyfunction!(run_benchmark, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,283 @@
+//! Random polynomials with configurable coefficient distributions
+//!
+//! Requires the `std` feature. [`Polynomial::random`] draws uniform coefficients;
+//! [`RandomPolynomial`] also covers integer, sparse and monic-like inputs.
+
+use crate::polynomial::{check_range, check_size};
+use crate::{PolyError, Polynomial};
+use rand::Rng;
+use std::f64::consts::TAU;
+
+/// Distribution of the coefficients of a random polynomial
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CoefficientDistribution {
+    /// Uniform on `[min, max)`
+    Uniform { min: f64, max: f64 },
+    /// Normal with the given mean and standard deviation
+    Normal { mean: f64, std_dev: f64 },
+    /// Magnitude log-uniform on `[min, max)` with a random sign, spanning many orders of magnitude
+    LogUniform { min: f64, max: f64 },
+}
+
+impl CoefficientDistribution {
+    /// Draw one coefficient
+    fn sample<R: Rng + ?Sized>(self, rng: &mut R) -> f64 {
+        match self {
+            CoefficientDistribution::Uniform { min, max } => rng.gen_range(min..max),
+            CoefficientDistribution::Normal { mean, std_dev } => {
+                // Box-Muller transform, with `1 - u` in (0, 1] to avoid ln(0)
+                let (u1, u2): (f64, f64) = (rng.gen(), rng.gen());
+                mean + std_dev * (-2.0 * (1.0 - u1).ln()).sqrt() * (TAU * u2).cos()
+            }
+            CoefficientDistribution::LogUniform { min, max } => {
+                let magnitude = rng.gen_range(min.ln()..max.ln()).exp();
+                if rng.gen() {
+                    magnitude
+                } else {
+                    -magnitude
+                }
+            }
+        }
+    }
+
+    /// Check the parameters, failing on an empty range or a negative spread
+    fn validate(self) -> Result<(), PolyError> {
+        match self {
+            CoefficientDistribution::Uniform { min, max } => check_range(min, max),
+            CoefficientDistribution::Normal { std_dev, .. } if std_dev >= 0.0 => Ok(()),
+            CoefficientDistribution::Normal { std_dev, .. } => Err(PolyError::InvalidArgument(
+                format!("standard deviation {} is negative", std_dev),
+            )),
+            CoefficientDistribution::LogUniform { min, max } if 0.0 < min => check_range(min, max),
+            CoefficientDistribution::LogUniform { min, .. } => Err(PolyError::InvalidArgument(
+                format!("log-uniform minimum {} is not positive", min),
+            )),
+        }
+    }
+}
+
+/// Builder of random polynomials
+/// ## Example
+/// ```
+/// use mult_polynomial::{CoefficientDistribution, RandomPolynomial};
+/// // integer coefficients in [-5, 5], a fifth of them nonzero, with leading coefficient 1
+/// let p = RandomPolynomial::new(100)
+///     .distribution(CoefficientDistribution::Uniform { min: -5.0, max: 5.0 })
+///     .integer(true)
+///     .density(0.2)
+///     .leading(1.0)
+///     .generate();
+/// assert_eq!(p.degree(), 99);
+/// assert!(p.coeffs.iter().all(|c| c.fract() == 0.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RandomPolynomial {
+    size: usize,
+    distribution: CoefficientDistribution,
+    integer: bool,
+    density: f64,
+    leading: Option<f64>,
+}
+
+impl RandomPolynomial {
+    /// Polynomials with `size` coefficients, uniform on `[-10, 10)` like [`Polynomial::random`]
+    pub fn new(size: usize) -> Self {
+        RandomPolynomial {
+            size,
+            distribution: CoefficientDistribution::Uniform {
+                min: -10.0,
+                max: 10.0,
+            },
+            integer: false,
+            density: 1.0,
+            leading: None,
+        }
+    }
+
+    /// Draw the coefficients from `distribution`
+    pub fn distribution(mut self, distribution: CoefficientDistribution) -> Self {
+        self.distribution = distribution;
+        self
+    }
+
+    /// Round every coefficient to the nearest integer
+    pub fn integer(mut self, integer: bool) -> Self {
+        self.integer = integer;
+        self
+    }
+
+    /// Keep each coefficient (except a fixed leading one) with probability `density`
+    /// and set it to zero otherwise
+    pub fn density(mut self, density: f64) -> Self {
+        self.density = density;
+        self
+    }
+
+    /// Fix the coefficient of the highest degree, e.g. `1.0` for monic polynomials
+    pub fn leading(mut self, leading: f64) -> Self {
+        self.leading = Some(leading);
+        self
+    }
+
+    /// Generate a polynomial from the thread-local random number generator
+    ///
+    /// Requires the `thread_rng` feature (on by default).
+    /// Panics on invalid parameters, see [`RandomPolynomial::try_generate`].
+    #[cfg(feature = "thread_rng")]
+    pub fn generate(&self) -> Polynomial {
+        self.generate_with_rng(&mut rand::thread_rng())
+    }
+
+    /// Generate a polynomial from the thread-local random number generator, failing with
+    /// [`PolyError::InvalidArgument`] on a zero size, a density outside of `[0, 1]`
+    /// or invalid distribution parameters
+    #[cfg(feature = "thread_rng")]
+    pub fn try_generate(&self) -> Result<Polynomial, PolyError> {
+        self.try_generate_with_rng(&mut rand::thread_rng())
+    }
+
+    /// Generate a polynomial from the given random number generator
+    ///
+    /// Zero trailing coefficients are trimmed as in [`Polynomial::new`],
+    /// so without a fixed leading coefficient a sparse polynomial may have a lower degree.
+    /// Panics on invalid parameters, see [`RandomPolynomial::try_generate_with_rng`].
+    pub fn generate_with_rng<R: Rng + ?Sized>(&self, rng: &mut R) -> Polynomial {
+        self.try_generate_with_rng(rng)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Generate a polynomial from the given random number generator, failing on invalid parameters
+    pub fn try_generate_with_rng<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> Result<Polynomial, PolyError> {
+        check_size(self.size)?;
+        if !(0.0..=1.0).contains(&self.density) {
+            return Err(PolyError::InvalidArgument(format!(
+                "density {} is not in [0, 1]",
+                self.density
+            )));
+        }
+        self.distribution.validate()?;
+        let mut coeffs: Vec<f64> = (0..self.size)
+            .map(|_| {
+                if self.density < 1.0 && !rng.gen_bool(self.density) {
+                    return 0.0;
+                }
+                let c = self.distribution.sample(rng);
+                if self.integer {
+                    c.round()
+                } else {
+                    c
+                }
+            })
+            .collect();
+        if let Some(leading) = self.leading {
+            coeffs[self.size - 1] = leading;
+        }
+        Ok(Polynomial::new(coeffs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    /// Test the sample statistics of the distributions
+    #[test]
+    fn test_distributions() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let n = 20000;
+        let sample = |distribution, rng: &mut StdRng| {
+            (RandomPolynomial::new(n).distribution(distribution))
+                .leading(1.0)
+                .generate_with_rng(rng)
+                .coeffs
+        };
+        let normal = sample(
+            CoefficientDistribution::Normal {
+                mean: 3.0,
+                std_dev: 2.0,
+            },
+            &mut rng,
+        );
+        let mean = normal.iter().sum::<f64>() / n as f64;
+        let variance = normal.iter().map(|c| (c - mean).powi(2)).sum::<f64>() / n as f64;
+        assert!((mean - 3.0).abs() < 0.1);
+        assert!((variance.sqrt() - 2.0).abs() < 0.1);
+
+        let log_uniform = sample(
+            CoefficientDistribution::LogUniform {
+                min: 1e-6,
+                max: 1e6,
+            },
+            &mut rng,
+        );
+        assert!(log_uniform.iter().all(|c| (1e-6..1e6).contains(&c.abs())));
+        // a quarter of the magnitudes falls in each quarter of the exponent range
+        let small = log_uniform.iter().filter(|c| c.abs() < 1e-3).count();
+        assert!((small as f64 / n as f64 - 0.25).abs() < 0.02);
+        let negative = log_uniform.iter().filter(|&&c| c < 0.0).count();
+        assert!((negative as f64 / n as f64 - 0.5).abs() < 0.02);
+    }
+
+    /// Test integer, sparse and fixed leading coefficients
+    #[cfg(feature = "thread_rng")]
+    #[test]
+    fn test_shape_options() {
+        let mut rng = StdRng::seed_from_u64(9);
+        let p = RandomPolynomial::new(10000)
+            .integer(true)
+            .density(0.1)
+            .leading(-2.0)
+            .generate_with_rng(&mut rng);
+        assert_eq!(p.coeffs.len(), 10000);
+        assert_eq!(p.coeffs[9999], -2.0);
+        assert!(p.coeffs.iter().all(|c| c.fract() == 0.0));
+        let nonzero = p.terms().count();
+        // rounding to zero also removes about a twentieth of the kept coefficients
+        assert!((nonzero as f64 / 10000.0 - 0.095).abs() < 0.015);
+        // seeded generation is reproducible and the defaults match `Polynomial::random_with_rng`
+        let builder = RandomPolynomial::new(50);
+        assert_eq!(
+            builder.generate_with_rng(&mut StdRng::seed_from_u64(1)),
+            Polynomial::random_with_rng(-10.0, 10.0, 50, &mut StdRng::seed_from_u64(1))
+        );
+        let empty = RandomPolynomial::new(20).density(0.0).generate();
+        assert!(empty.coeffs.is_empty());
+    }
+
+    /// Invalid parameters are reported instead of panicking
+    #[cfg(feature = "thread_rng")]
+    #[test]
+    fn test_invalid_parameters() {
+        let invalid = |builder: RandomPolynomial| {
+            matches!(builder.try_generate(), Err(PolyError::InvalidArgument(_)))
+        };
+        assert!(invalid(RandomPolynomial::new(0)));
+        assert!(invalid(RandomPolynomial::new(10).density(1.5)));
+        for distribution in [
+            CoefficientDistribution::Uniform { min: 1.0, max: 1.0 },
+            CoefficientDistribution::Uniform {
+                min: 0.0,
+                max: f64::INFINITY,
+            },
+            CoefficientDistribution::Normal {
+                mean: 0.0,
+                std_dev: -1.0,
+            },
+            CoefficientDistribution::LogUniform { min: 0.0, max: 1.0 },
+        ] {
+            assert!(invalid(
+                RandomPolynomial::new(10).distribution(distribution)
+            ));
+        }
+        assert!(Polynomial::try_random(0.0, f64::NAN, 10).is_err());
+        assert!(Polynomial::try_random(-1.0, 1.0, 0).is_err());
+        assert_eq!(
+            Polynomial::try_random(-1.0, 1.0, 10).unwrap().coeffs.len(),
+            10
+        );
+    }
+}
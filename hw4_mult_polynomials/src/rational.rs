@@ -0,0 +1,219 @@
+//! Rational functions, quotients of two polynomials (e.g. transfer functions `H(s)`)
+
+use crate::gcd::{add, multiply};
+use crate::polynomial::multiply_impl;
+use crate::{PolyError, Polynomial, PolynomialMultAlg};
+use alloc::vec;
+
+/// Represents a rational function `numerator / denominator`
+///
+/// Sums and products are not reduced, so that a chain of them costs only multiplications;
+/// [`RationalFunction::simplify`] cancels the common factor once at the end.
+/// ## Example
+/// ```
+/// use mult_polynomial::{Polynomial, RationalFunction};
+/// // 1/(s + 1) in series with (s + 1)/(s + 2) is 1/(s + 2)
+/// let g = RationalFunction::new(Polynomial::new(vec![1.0]), Polynomial::new(vec![1.0, 1.0])).unwrap();
+/// let h = RationalFunction::new(Polynomial::new(vec![1.0, 1.0]), Polynomial::new(vec![2.0, 1.0])).unwrap();
+/// let series = g.multiply_auto(&h).simplify();
+/// assert_eq!(series.numerator, Polynomial::new(vec![1.0]));
+/// assert_eq!(series.denominator, Polynomial::new(vec![2.0, 1.0]));
+/// assert_eq!(series.evaluate(2.0), 0.25);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RationalFunction {
+    /// Polynomial above the fraction bar
+    pub numerator: Polynomial,
+    /// Polynomial below the fraction bar, never zero
+    pub denominator: Polynomial,
+}
+
+impl RationalFunction {
+    /// Create `numerator / denominator`, failing with [`PolyError::DivisionByZero`] on a zero denominator
+    pub fn new(numerator: Polynomial, denominator: Polynomial) -> Result<Self, PolyError> {
+        if denominator.is_zero() {
+            return Err(PolyError::DivisionByZero);
+        }
+        Ok(RationalFunction {
+            numerator,
+            denominator,
+        })
+    }
+
+    /// Whether this is the zero function
+    pub fn is_zero(&self) -> bool {
+        self.numerator.is_zero()
+    }
+
+    /// Evaluate at a given point x, infinite or NaN at a pole
+    pub fn evaluate(&self, x: f64) -> f64 {
+        self.numerator.evaluate(x) / self.denominator.evaluate(x)
+    }
+
+    /// Multiply with another rational function using the given algorithm for both products
+    pub fn multiply(
+        &self,
+        other: &RationalFunction,
+        algorithm: PolynomialMultAlg,
+    ) -> RationalFunction {
+        let product = |a: &Polynomial, b: &Polynomial| {
+            if a.coeffs.is_empty() || b.coeffs.is_empty() {
+                return a.with_coeffs(vec![]);
+            }
+            a.with_coeffs(multiply_impl(&a.coeffs, &b.coeffs, algorithm))
+        };
+        RationalFunction {
+            numerator: product(&self.numerator, &other.numerator),
+            denominator: product(&self.denominator, &other.denominator),
+        }
+    }
+
+    /// Multiply with another rational function using the algorithm chosen by [`PolynomialMultAlg::auto`]
+    pub fn multiply_auto(&self, other: &RationalFunction) -> RationalFunction {
+        let product =
+            |a: &Polynomial, b: &Polynomial| a.with_coeffs(multiply(&a.coeffs, &b.coeffs));
+        RationalFunction {
+            numerator: product(&self.numerator, &other.numerator),
+            denominator: product(&self.denominator, &other.denominator),
+        }
+    }
+
+    /// Sum with another rational function, `(a·d + c·b) / (b·d)` for `a/b + c/d`
+    ///
+    /// Equal denominators are kept as they are and only the numerators added.
+    pub fn add(&self, other: &RationalFunction) -> RationalFunction {
+        let (a, b) = (&self.numerator.coeffs, &self.denominator.coeffs);
+        let (c, d) = (&other.numerator.coeffs, &other.denominator.coeffs);
+        let (numerator, denominator) = if b == d {
+            (add(a, c), b.clone())
+        } else {
+            (add(&multiply(a, d), &multiply(c, b)), multiply(b, d))
+        };
+        RationalFunction {
+            numerator: self.numerator.with_coeffs(numerator),
+            denominator: self.denominator.with_coeffs(denominator),
+        }
+    }
+
+    /// Cancel the greatest common divisor of the numerator and the denominator and make the denominator monic
+    ///
+    /// The common factor is found by [`Polynomial::gcd`], so factors are cancelled
+    /// up to its tolerance; the zero function becomes `0 / 1`.
+    pub fn simplify(&self) -> RationalFunction {
+        if self.is_zero() {
+            return RationalFunction {
+                numerator: self.numerator.with_coeffs(vec![]),
+                denominator: self.denominator.with_coeffs(vec![1.0]),
+            };
+        }
+        let gcd = self.numerator.gcd(&self.denominator);
+        let cancel = |p: &Polynomial| match gcd.degree() {
+            0 => p.clone(),
+            _ => {
+                p.div_rem(&gcd)
+                    .expect("the gcd of nonzero polynomials is nonzero")
+                    .0
+            }
+        };
+        let (numerator, denominator) = (cancel(&self.numerator), cancel(&self.denominator));
+        let lead = (denominator.leading_coefficient()).expect("the denominator is nonzero");
+        RationalFunction {
+            numerator: numerator.with_coeffs(numerator.coeffs.iter().map(|c| c / lead).collect()),
+            denominator: denominator
+                .with_coeffs(denominator.coeffs.iter().map(|c| c / lead).collect()),
+        }
+    }
+}
+
+impl From<Polynomial> for RationalFunction {
+    fn from(p: Polynomial) -> Self {
+        let denominator = p.with_coeffs(vec![1.0]);
+        RationalFunction {
+            numerator: p,
+            denominator,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test the zero denominator, evaluation and the cancellation of common roots
+    #[test]
+    fn test_new_and_simplify() {
+        let one = Polynomial::new(vec![1.0]);
+        assert_eq!(
+            RationalFunction::new(one.clone(), Polynomial::new(vec![0.0])),
+            Err(PolyError::DivisionByZero)
+        );
+        // 2(x - 1)(x - 2)(x + 3) / (4(x - 2)(x + 3)(x - 5))
+        let numerator = Polynomial::from_roots(&[1.0, 2.0, -3.0]);
+        let numerator = numerator.with_coeffs(numerator.coeffs.iter().map(|c| 2.0 * c).collect());
+        let denominator = Polynomial::from_roots(&[2.0, -3.0, 5.0]);
+        let denominator =
+            denominator.with_coeffs(denominator.coeffs.iter().map(|c| 4.0 * c).collect());
+        let f = RationalFunction::new(numerator, denominator).unwrap();
+        let simplified = f.simplify();
+        let expected = Polynomial::from_roots(&[1.0]);
+        let expected = expected.with_coeffs(expected.coeffs.iter().map(|c| 0.5 * c).collect());
+        assert!(simplified.numerator.approx_eq(&expected, 1e-12, 1e-12));
+        assert!(simplified
+            .denominator
+            .approx_eq(&Polynomial::from_roots(&[5.0]), 1e-12, 1e-12));
+        for x in [-1.5, 0.0, 0.5, 4.0] {
+            assert!((simplified.evaluate(x) - f.evaluate(x)).abs() < 1e-12);
+        }
+        assert!(f.evaluate(5.0).is_infinite());
+
+        let coprime = RationalFunction::new(one.clone(), Polynomial::from_roots(&[1.0, 2.0]));
+        assert_eq!(coprime.clone().unwrap().simplify(), coprime.unwrap());
+        let zero = RationalFunction::new(Polynomial::new(vec![]), Polynomial::from_roots(&[3.0]));
+        let zero = zero.unwrap().simplify();
+        assert!(zero.is_zero());
+        assert_eq!(zero.denominator, one);
+    }
+
+    /// Test sums and products against the values of the operands, including closing a feedback loop
+    #[test]
+    fn test_arithmetic() {
+        let g = RationalFunction::new(
+            Polynomial::new(vec![3.0, 1.0]),
+            Polynomial::from_roots(&[-1.0, -2.0]),
+        )
+        .unwrap();
+        let h = RationalFunction::new(Polynomial::new(vec![2.0]), Polynomial::from_roots(&[-2.0]))
+            .unwrap();
+        let sum = g.add(&h);
+        let product = g.multiply_auto(&h);
+        assert_eq!(product, g.multiply(&h, PolynomialMultAlg::Naive));
+        for x in [-3.0, -0.5, 0.0, 1.0, 10.0] {
+            let (gx, hx) = (g.evaluate(x), h.evaluate(x));
+            assert!((sum.evaluate(x) - (gx + hx)).abs() < 1e-12);
+            assert!((product.evaluate(x) - gx * hx).abs() < 1e-12);
+        }
+        // the common factor (x + 2) of the denominators cancels
+        assert_eq!(
+            sum.simplify().denominator,
+            Polynomial::from_roots(&[-1.0, -2.0])
+        );
+        let same = g.add(&g);
+        assert_eq!(same.denominator, g.denominator);
+        assert_eq!(same.numerator, Polynomial::new(vec![6.0, 2.0]));
+
+        // unity feedback around g: g / (1 + g) = (x + 3) / ((x + 1)(x + 2) + x + 3)
+        let one = RationalFunction::from(Polynomial::new(vec![1.0]));
+        let loop_gain = one.add(&g);
+        let closed = g
+            .multiply_auto(
+                &RationalFunction::new(loop_gain.denominator, loop_gain.numerator).unwrap(),
+            )
+            .simplify();
+        assert!(closed
+            .numerator
+            .approx_eq(&Polynomial::new(vec![3.0, 1.0]), 1e-12, 1e-12));
+        assert!(closed
+            .denominator
+            .approx_eq(&Polynomial::new(vec![5.0, 4.0, 1.0]), 1e-12, 1e-12));
+    }
+}
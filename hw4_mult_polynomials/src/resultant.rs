@@ -0,0 +1,128 @@
+//! Resultants and discriminants through the Euclidean remainder sequence
+
+use crate::Polynomial;
+
+/// `x` raised to a non-negative integer power, by repeated squaring (`powi` needs `std`)
+pub(crate) fn powu(mut x: f64, mut e: usize) -> f64 {
+    let mut result = 1.0;
+    while e > 0 {
+        if e & 1 == 1 {
+            result *= x;
+        }
+        x *= x;
+        e >>= 1;
+    }
+    result
+}
+
+impl Polynomial {
+    /// Resultant of the two polynomials, zero exactly when they have a common root
+    ///
+    /// Follows the remainder sequence `res(a, b) = (-1)^(deg a·deg b)·lc(b)^(deg a - deg r)·res(b, r)`
+    /// with `r = a mod b`, so it takes O(n·m) operations. The resultant with the zero polynomial is 0.
+    /// Remainders that vanish up to the trimming of [`Polynomial::new`] count as zero,
+    /// so nearly common roots also give a zero resultant.
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// // roots 1, 2 and 3, 4: res = ∏(rᵢ - sⱼ) = (1-3)(1-4)(2-3)(2-4) = 12
+    /// let a = Polynomial::from_roots(&[1.0, 2.0]);
+    /// let b = Polynomial::from_roots(&[3.0, 4.0]);
+    /// assert!((a.resultant(&b) - 12.0).abs() < 1e-9);
+    /// assert_eq!(a.resultant(&Polynomial::from_roots(&[2.0, 5.0])), 0.0);
+    /// ```
+    pub fn resultant(&self, other: &Polynomial) -> f64 {
+        let (mut a, mut b) = (self.clone(), other.clone());
+        let mut factor = 1.0;
+        loop {
+            if a.coeffs.is_empty() || b.coeffs.is_empty() {
+                return 0.0;
+            }
+            let (n, m) = (a.degree(), b.degree());
+            let lead = b.coeffs[m];
+            if m == 0 {
+                return factor * powu(lead, n);
+            }
+            let r = match a.div_rem(&b) {
+                Ok((_, r)) => r,
+                Err(_) => unreachable!("the divisor is not zero"),
+            };
+            if r.coeffs.is_empty() {
+                return 0.0;
+            }
+            if n * m % 2 == 1 {
+                factor = -factor;
+            }
+            factor *= powu(lead, n - r.degree());
+            (a, b) = (b, r);
+        }
+    }
+
+    /// Discriminant `(-1)^(n(n-1)/2)·res(p, p') / lc(p)`, zero exactly when there is a repeated root
+    ///
+    /// Constant polynomials have discriminant 0.
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// // b² - 4ac for x² - 3x + 2
+    /// let p = Polynomial::new(vec![2.0, -3.0, 1.0]);
+    /// assert!((p.discriminant() - 1.0).abs() < 1e-12);
+    /// ```
+    pub fn discriminant(&self) -> f64 {
+        let n = self.degree();
+        let Some(&lead) = self.coeffs.last() else {
+            return 0.0;
+        };
+        let sign = if (n * n.saturating_sub(1) / 2) % 2 == 1 {
+            -1.0
+        } else {
+            1.0
+        };
+        sign * self.resultant(&self.derivative()) / lead
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    /// Test the resultant against the product of root differences
+    #[test]
+    fn test_resultant() {
+        let roots_a = [0.5, -1.0, 2.0, 0.25, -0.75];
+        let roots_b = [1.5, -0.5, 3.0];
+        let (a, b) = (
+            Polynomial::from_roots(&roots_a),
+            Polynomial::from_roots(&roots_b),
+        );
+        let expected: f64 = (roots_a.iter())
+            .flat_map(|r| roots_b.iter().map(move |s| r - s))
+            .product();
+        assert!((a.resultant(&b) - expected).abs() < 1e-9 * expected.abs());
+        // res(b, a) = (-1)^(nm)·res(a, b), and scaling a by c scales the resultant by c^m
+        assert!((b.resultant(&a) + expected).abs() < 1e-9 * expected.abs());
+        let scaled = Polynomial::new(a.coeffs.iter().map(|c| 2.0 * c).collect());
+        assert!((scaled.resultant(&b) - 8.0 * expected).abs() < 1e-9 * expected.abs());
+        // constants and the zero polynomial
+        assert_eq!(Polynomial::new(vec![3.0]).resultant(&b), 27.0);
+        assert_eq!(a.resultant(&Polynomial::new(vec![2.0])), 32.0);
+        assert_eq!(a.resultant(&Polynomial::new(vec![])), 0.0);
+        assert_eq!(a.resultant(&Polynomial::from_roots(&[-1.0, 7.0])), 0.0);
+    }
+
+    /// Test the discriminant against the product of squared root differences
+    #[test]
+    fn test_discriminant() {
+        let roots = [0.5, -1.0, 2.0, 1.25];
+        let expected: f64 = (0..roots.len())
+            .flat_map(|i| (i + 1..roots.len()).map(move |j| (i, j)))
+            .map(|(i, j)| (roots[i] - roots[j]) * (roots[i] - roots[j]))
+            .product();
+        let p = Polynomial::from_roots(&roots);
+        assert!((p.discriminant() - expected).abs() < 1e-9 * expected);
+        assert_eq!(Polynomial::from_roots(&[1.0, 3.0, 1.0]).discriminant(), 0.0);
+        assert_eq!(Polynomial::new(vec![1.0, 2.0]).discriminant(), 1.0);
+        assert_eq!(Polynomial::new(vec![5.0]).discriminant(), 0.0);
+    }
+}
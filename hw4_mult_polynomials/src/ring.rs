@@ -0,0 +1,215 @@
+//! Arithmetic in the quotient ring `R[x]/(f)` of polynomials modulo a fixed polynomial
+
+use crate::polynomial::multiply_impl;
+use crate::series::inverse_series;
+use crate::{PolyError, Polynomial, PolynomialMultAlg};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Polynomials modulo a fixed polynomial `f`, reduced after every operation
+///
+/// The power series inverse of the reversed modulus is computed once by Newton iteration,
+/// so reducing a product of two elements takes two multiplications by the fast multipliers
+/// instead of a long division. Elements are [`Polynomial`]s of degree below `deg(f)`;
+/// operands of higher degree are reduced first.
+/// ## Example
+/// ```
+/// use mult_polynomial::{Polynomial, PolynomialRing};
+/// // the complex numbers as R[x]/(x² + 1)
+/// let ring = PolynomialRing::new(&Polynomial::new(vec![1.0, 0.0, 1.0])).unwrap();
+/// let i = Polynomial::new(vec![0.0, 1.0]);
+/// assert_eq!(ring.mul(&i, &i), Polynomial::new(vec![-1.0]));
+/// let z = Polynomial::new(vec![1.0, 1.0]); // 1 + i
+/// assert_eq!(ring.pow(&z, 4), Polynomial::new(vec![-4.0]));
+/// assert_eq!(ring.add(&z, &i), Polynomial::new(vec![1.0, 2.0]));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PolynomialRing {
+    modulus: Vec<f64>,
+    /// Inverse of the reversed modulus as a power series, up to the longest quotient
+    inverse: Vec<f64>,
+}
+
+impl PolynomialRing {
+    /// Fix the modulus, failing with [`PolyError::DivisionByZero`] for the zero polynomial
+    ///
+    /// Trailing zeros kept by [`Normalization::Keep`](crate::Normalization::Keep) are dropped first.
+    pub fn new(modulus: &Polynomial) -> Result<Self, PolyError> {
+        let modulus = modulus.significant_coeffs();
+        if modulus.is_empty() {
+            return Err(PolyError::DivisionByZero);
+        }
+        let reversed: Vec<f64> = modulus.iter().rev().copied().collect();
+        let len = modulus.len() - 1;
+        Ok(PolynomialRing {
+            modulus: modulus.to_vec(),
+            inverse: inverse_series(&reversed, len),
+        })
+    }
+
+    /// The modulus `f`
+    pub fn modulus(&self) -> Polynomial {
+        Polynomial::new(self.modulus.clone())
+    }
+
+    /// Remainder of `p` modulo `f`, the representative of its class in the ring
+    ///
+    /// Polynomials of degree below `2·deg(f) - 1` take two multiplications;
+    /// longer ones fall back to long division, see [`Polynomial::div_rem`].
+    pub fn reduce(&self, p: &Polynomial) -> Polynomial {
+        p.with_coeffs(self.reduce_coeffs(&p.coeffs))
+    }
+
+    /// Sum of two elements
+    pub fn add(&self, a: &Polynomial, b: &Polynomial) -> Polynomial {
+        let (a, b) = (self.reduce(a), self.reduce(b));
+        let (long, short) = if a.coeffs.len() >= b.coeffs.len() {
+            (&a, &b)
+        } else {
+            (&b, &a)
+        };
+        let mut sum = long.coeffs.clone();
+        for (s, c) in sum.iter_mut().zip(&short.coeffs) {
+            *s += c;
+        }
+        a.with_coeffs(sum)
+    }
+
+    /// Difference of two elements
+    pub fn sub(&self, a: &Polynomial, b: &Polynomial) -> Polynomial {
+        let negated = b.with_coeffs(b.coeffs.iter().map(|c| -c).collect());
+        self.add(a, &negated)
+    }
+
+    /// Product of two elements
+    pub fn mul(&self, a: &Polynomial, b: &Polynomial) -> Polynomial {
+        let (a, b) = (self.reduce(a), self.reduce(b));
+        a.with_coeffs(self.reduce_coeffs(&self.multiply(&a.coeffs, &b.coeffs)))
+    }
+
+    /// Compute `aᵉ` by repeated squaring, reducing after every product
+    pub fn pow(&self, a: &Polynomial, e: u64) -> Polynomial {
+        let base = self.reduce(a);
+        a.with_coeffs(self.pow_coeffs(base.coeffs, e))
+    }
+
+    /// `baseᵉ` for a reduced `base`
+    fn pow_coeffs(&self, mut base: Vec<f64>, mut e: u64) -> Vec<f64> {
+        let mut result = self.reduce_coeffs(&[1.0]);
+        while e > 0 {
+            if e & 1 == 1 {
+                result = self.reduce_coeffs(&self.multiply(&result, &base));
+            }
+            e >>= 1;
+            if e > 0 {
+                base = self.reduce_coeffs(&self.multiply(&base, &base));
+            }
+        }
+        result
+    }
+
+    /// Multiply with the algorithm chosen by [`PolynomialMultAlg::auto`]
+    fn multiply(&self, a: &[f64], b: &[f64]) -> Vec<f64> {
+        if a.is_empty() || b.is_empty() {
+            return vec![];
+        }
+        multiply_impl(a, b, PolynomialMultAlg::auto(a.len(), b.len()))
+    }
+
+    /// Remainder of `a` of any length
+    fn reduce_coeffs(&self, a: &[f64]) -> Vec<f64> {
+        let m = self.modulus.len();
+        if a.len() < m {
+            return a.to_vec();
+        }
+        // reversing turns the quotient into the first coefficients of rev(a) / rev(modulus)
+        let k = a.len() - m + 1;
+        if k > self.inverse.len() {
+            let (_, remainder) = Polynomial::new(a.to_vec())
+                .div_rem(&self.modulus())
+                .expect("the modulus is nonzero");
+            return remainder.coeffs;
+        }
+        let a_reversed: Vec<f64> = a.iter().rev().take(k).copied().collect();
+        let mut quotient = self.multiply(&a_reversed, &self.inverse[..k]);
+        quotient.resize(k, 0.0);
+        quotient.reverse();
+
+        let product = self.multiply(&quotient, &self.modulus);
+        let mut remainder: Vec<f64> = a[..m - 1].to_vec();
+        for (r, p) in remainder.iter_mut().zip(&product) {
+            *r -= p;
+        }
+        remainder
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Normalization;
+
+    /// Test the ring operations against long division of the unreduced results
+    #[cfg(feature = "thread_rng")]
+    #[test]
+    fn test_ring_operations() {
+        // a Ring-LWE style modulus x⁶⁴ + 1
+        let mut f = vec![0.0; 65];
+        (f[0], f[64]) = (1.0, 1.0);
+        let f = Polynomial::new(f);
+        let ring = PolynomialRing::new(&f).unwrap();
+        assert_eq!(ring.modulus(), f);
+        let a = Polynomial::random(-1.0, 1.0, 64);
+        let b = Polynomial::random(-1.0, 1.0, 64);
+        let remainder = |p: &Polynomial| p.div_rem(&f).unwrap().1;
+        // the coefficients of products cancel, so compare relative to the largest one
+        let close =
+            |p: &Polynomial, q: &Polynomial| p.approx_eq(q, 1e-10 * q.height().max(1.0), 0.0);
+        assert!(close(&ring.mul(&a, &b), &remainder(&a.multiply_naive(&b))));
+        // xⁿ + 1 turns the product into the negacyclic convolution
+        assert!(close(&ring.mul(&a, &b), &a.convolve_negacyclic(&b, 64)));
+        let diff = ring.sub(&a, &b);
+        assert!(close(&ring.add(&diff, &b), &a));
+        // unreduced operands, including one past the fast reduction
+        let long = Polynomial::random(-1.0, 1.0, 300);
+        assert!(close(&ring.reduce(&long), &remainder(&long)));
+        assert!(close(
+            &ring.mul(&long, &b),
+            &ring.mul(&remainder(&long), &b)
+        ));
+        // x has order 128 modulo x⁶⁴ + 1
+        let x = Polynomial::new(vec![0.0, 1.0]);
+        assert_eq!(ring.pow(&x, 128), Polynomial::new(vec![1.0]));
+        assert_eq!(ring.pow(&x, 64), Polynomial::new(vec![-1.0]));
+        let mut expected = Polynomial::new(vec![1.0]);
+        for e in 0..20 {
+            assert!(close(&ring.pow(&a, e), &expected), "e = {}", e);
+            expected = ring.mul(&expected, &a);
+        }
+        assert_eq!(
+            PolynomialRing::new(&Polynomial::new(vec![])),
+            Err(PolyError::DivisionByZero)
+        );
+        // everything vanishes modulo a constant
+        let constant = PolynomialRing::new(&Polynomial::new(vec![2.0])).unwrap();
+        assert!(constant.pow(&a, 3).coeffs.is_empty());
+    }
+
+    /// Kept trailing zeros of the modulus do not change the ring
+    #[test]
+    fn test_ring_kept_zeros() {
+        let padded =
+            Polynomial::with_normalization(vec![1.0, 0.0, 1.0, 0.0, 0.0], Normalization::Keep);
+        let ring = PolynomialRing::new(&padded).unwrap();
+        assert_eq!(ring.modulus(), Polynomial::new(vec![1.0, 0.0, 1.0]));
+        // x² ≡ -1 modulo x² + 1
+        let x = Polynomial::new(vec![0.0, 1.0]);
+        assert_eq!(ring.mul(&x, &x), Polynomial::new(vec![-1.0]));
+        for zeros in [vec![0.0], vec![0.0; 3]] {
+            assert_eq!(
+                PolynomialRing::new(&Polynomial::with_normalization(zeros, Normalization::Keep)),
+                Err(PolyError::DivisionByZero)
+            );
+        }
+    }
+}
@@ -0,0 +1,367 @@
+//! Versioned file formats for polynomials and benchmark results
+//!
+//! Every file starts with a header carrying the format version and the coefficient type,
+//! so files written by newer versions of the crate are rejected instead of misread.
+//! - Binary polynomial files: magic `MPLY`, format version (`u32`), coefficient type tag
+//!   (length-prefixed string), number of coefficients (`u64`), then the coefficients,
+//!   all little-endian.
+//! - Text files (e.g. benchmark CSV): a first line
+//!   `# mult_polynomial format=<version> coeff=<type> kind=<kind>`.
+//!
+//! For exchange with other tools (NumPy, MATLAB), polynomials can also be read and written
+//! without a header, as CSV or in a compact binary format (length-prefixed little-endian `f64`),
+//! and as NumPy `.npy` arrays with the `npy` feature.
+
+use crate::{PolyError, Polynomial};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+/// Current version of the file formats
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Tag of the coefficient type stored by this build
+pub const COEFF_TYPE: &str = "f64";
+
+/// Magic bytes at the start of binary polynomial files
+const MAGIC: &[u8; 4] = b"MPLY";
+
+/// Prefix of the header line of text files
+const TEXT_HEADER_PREFIX: &str = "# mult_polynomial";
+
+/// Check the format version and coefficient type read from a header
+fn validate(version: u32, coeff_type: &str) -> Result<(), PolyError> {
+    if version == 0 || version > FORMAT_VERSION {
+        return Err(PolyError::UnsupportedVersion {
+            found: version,
+            supported: FORMAT_VERSION,
+        });
+    }
+    if coeff_type != COEFF_TYPE {
+        return Err(PolyError::CoefficientTypeMismatch {
+            found: coeff_type.to_string(),
+            expected: COEFF_TYPE.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Header line identifying a text file of the given `kind` (e.g. `benchmark`)
+pub fn text_header(kind: &str) -> String {
+    format!(
+        "{} format={} coeff={} kind={}",
+        TEXT_HEADER_PREFIX, FORMAT_VERSION, COEFF_TYPE, kind
+    )
+}
+
+/// Validate the header line of a text file of the given `kind`
+pub fn check_text_header(line: &str, kind: &str) -> Result<(), PolyError> {
+    let fields = line
+        .trim()
+        .strip_prefix(TEXT_HEADER_PREFIX)
+        .ok_or_else(|| {
+            PolyError::InvalidFormat(format!("missing `{}` header line", TEXT_HEADER_PREFIX))
+        })?;
+
+    let mut version = None;
+    let mut coeff_type = None;
+    let mut file_kind = None;
+    for field in fields.split_whitespace() {
+        match field.split_once('=') {
+            Some(("format", value)) => version = value.parse::<u32>().ok(),
+            Some(("coeff", value)) => coeff_type = Some(value),
+            Some(("kind", value)) => file_kind = Some(value),
+            _ => {} // fields added by future versions
+        }
+    }
+
+    let version =
+        version.ok_or_else(|| PolyError::InvalidFormat("missing format version".to_string()))?;
+    let coeff_type = coeff_type
+        .ok_or_else(|| PolyError::InvalidFormat("missing coefficient type".to_string()))?;
+    validate(version, coeff_type)?;
+    match file_kind {
+        Some(found) if found == kind => Ok(()),
+        found => Err(PolyError::InvalidFormat(format!(
+            "expected a `{}` file, found `{}`",
+            kind,
+            found.unwrap_or("unknown")
+        ))),
+    }
+}
+
+/// Write the header of a binary polynomial file with `len` coefficients
+pub(crate) fn write_binary_header<W: Write>(writer: &mut W, len: u64) -> Result<(), PolyError> {
+    writer.write_all(MAGIC)?;
+    writer.write_all(&FORMAT_VERSION.to_le_bytes())?;
+    writer.write_all(&[COEFF_TYPE.len() as u8])?;
+    writer.write_all(COEFF_TYPE.as_bytes())?;
+    writer.write_all(&len.to_le_bytes())?;
+    Ok(())
+}
+
+/// Read and validate the header of a binary polynomial file, returning the number of coefficients
+pub(crate) fn read_binary_header<R: Read>(reader: &mut R) -> Result<u64, PolyError> {
+    let mut magic = [0; 4];
+    reader.read_exact(&mut magic)?;
+    if &magic != MAGIC {
+        return Err(PolyError::InvalidFormat(
+            "not a binary polynomial file (bad magic bytes)".to_string(),
+        ));
+    }
+
+    let mut word = [0; 4];
+    reader.read_exact(&mut word)?;
+    let version = u32::from_le_bytes(word);
+    // reject newer versions before interpreting anything else
+    if version == 0 || version > FORMAT_VERSION {
+        return Err(PolyError::UnsupportedVersion {
+            found: version,
+            supported: FORMAT_VERSION,
+        });
+    }
+
+    let mut tag_len = [0; 1];
+    reader.read_exact(&mut tag_len)?;
+    let mut tag = vec![0; tag_len[0] as usize];
+    reader.read_exact(&mut tag)?;
+    validate(version, &String::from_utf8_lossy(&tag))?;
+
+    let mut word = [0; 8];
+    reader.read_exact(&mut word)?;
+    Ok(u64::from_le_bytes(word))
+}
+
+impl Polynomial {
+    /// Write the polynomial in the versioned binary format
+    pub fn write_binary<W: Write>(&self, mut writer: W) -> Result<(), PolyError> {
+        write_binary_header(&mut writer, self.coeffs.len() as u64)?;
+        for c in &self.coeffs {
+            writer.write_all(&c.to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Read a polynomial in the versioned binary format
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// let p = Polynomial::new(vec![1.0, 2.0, 3.0]);
+    /// let mut bytes = Vec::new();
+    /// p.write_binary(&mut bytes).unwrap();
+    /// assert_eq!(Polynomial::read_binary(bytes.as_slice()).unwrap(), p);
+    /// ```
+    pub fn read_binary<R: Read>(mut reader: R) -> Result<Polynomial, PolyError> {
+        let len = read_binary_header(&mut reader)? as usize;
+        let mut word = [0; 8];
+        let mut coeffs = Vec::with_capacity(len.min(1 << 20));
+        for _ in 0..len {
+            reader.read_exact(&mut word)?;
+            coeffs.push(f64::from_le_bytes(word));
+        }
+        Ok(Polynomial::new(coeffs))
+    }
+}
+
+/// Open a file for reading, naming it in the error
+pub(crate) fn open(path: &Path) -> Result<BufReader<File>, PolyError> {
+    let file = File::open(path).map_err(|e| PolyError::Io(format!("{}: {}", path.display(), e)))?;
+    Ok(BufReader::new(file))
+}
+
+/// Create a file for writing, naming it in the error
+pub(crate) fn create(path: &Path) -> Result<BufWriter<File>, PolyError> {
+    let file =
+        File::create(path).map_err(|e| PolyError::Io(format!("{}: {}", path.display(), e)))?;
+    Ok(BufWriter::new(file))
+}
+
+impl Polynomial {
+    /// Read the coefficients, from lowest to highest degree, of a CSV or plain text file
+    ///
+    /// Values may be separated by commas, whitespace or line breaks, as written by
+    /// `numpy.savetxt` or MATLAB's `writematrix`; lines starting with `#` are skipped.
+    pub fn from_csv_file(path: impl AsRef<Path>) -> Result<Polynomial, PolyError> {
+        let mut text = String::new();
+        open(path.as_ref())?.read_to_string(&mut text)?;
+        let coeffs = (text.lines())
+            .filter(|line| !line.trim_start().starts_with('#'))
+            .flat_map(|line| line.split(|c: char| c == ',' || c.is_whitespace()))
+            .filter(|value| !value.is_empty())
+            .map(|value| {
+                value.parse::<f64>().map_err(|_| {
+                    PolyError::InvalidFormat(format!("`{}` is not a coefficient", value))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Polynomial::new(coeffs))
+    }
+
+    /// Write the coefficients to a text file, one per line from lowest to highest degree,
+    /// with as many digits as needed to read back the same values
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// let path = std::env::temp_dir().join("mult_polynomial_csv_example.csv");
+    /// let p = Polynomial::new(vec![0.1, -2.0, 3e-300]);
+    /// p.to_csv_file(&path).unwrap();
+    /// assert_eq!(Polynomial::from_csv_file(&path).unwrap(), p);
+    /// ```
+    pub fn to_csv_file(&self, path: impl AsRef<Path>) -> Result<(), PolyError> {
+        let mut writer = create(path.as_ref())?;
+        for c in &self.coeffs {
+            writeln!(writer, "{}", c)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Write the polynomial in the compact binary format: the number of coefficients (`u64`),
+    /// then the coefficients, all little-endian
+    ///
+    /// Unlike [`Polynomial::write_binary`] there is no header, so other tools read it directly,
+    /// e.g. `numpy.fromfile(path, "<f8", offset=8)`.
+    pub fn write_compact<W: Write>(&self, mut writer: W) -> Result<(), PolyError> {
+        writer.write_all(&(self.coeffs.len() as u64).to_le_bytes())?;
+        for c in &self.coeffs {
+            writer.write_all(&c.to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Read a polynomial in the compact binary format
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// let p = Polynomial::new(vec![1.0, 2.0, 3.0]);
+    /// let mut bytes = Vec::new();
+    /// p.write_compact(&mut bytes).unwrap();
+    /// assert_eq!(bytes.len(), 8 + 3 * 8);
+    /// assert_eq!(Polynomial::read_compact(bytes.as_slice()).unwrap(), p);
+    /// ```
+    pub fn read_compact<R: Read>(mut reader: R) -> Result<Polynomial, PolyError> {
+        let mut word = [0; 8];
+        reader.read_exact(&mut word)?;
+        let len = u64::from_le_bytes(word) as usize;
+        let mut coeffs = Vec::with_capacity(len.min(1 << 20));
+        for _ in 0..len {
+            reader.read_exact(&mut word)?;
+            coeffs.push(f64::from_le_bytes(word));
+        }
+        Ok(Polynomial::new(coeffs))
+    }
+
+    /// Read a file in the compact binary format
+    pub fn from_compact_file(path: impl AsRef<Path>) -> Result<Polynomial, PolyError> {
+        Polynomial::read_compact(open(path.as_ref())?)
+    }
+
+    /// Write a file in the compact binary format
+    pub fn to_compact_file(&self, path: impl AsRef<Path>) -> Result<(), PolyError> {
+        let mut writer = create(path.as_ref())?;
+        self.write_compact(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test the binary format round trip and its validation
+    #[cfg(feature = "thread_rng")]
+    #[test]
+    fn test_binary_format() {
+        let p = Polynomial::random(-10.0, 10.0, 100);
+        let mut bytes = Vec::new();
+        p.write_binary(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), 4 + 4 + 1 + 3 + 8 + 8 * 100);
+        assert_eq!(Polynomial::read_binary(bytes.as_slice()).unwrap(), p);
+
+        // newer format version
+        let mut newer = bytes.clone();
+        newer[4..8].copy_from_slice(&(FORMAT_VERSION + 1).to_le_bytes());
+        assert_eq!(
+            Polynomial::read_binary(newer.as_slice()),
+            Err(PolyError::UnsupportedVersion {
+                found: FORMAT_VERSION + 1,
+                supported: FORMAT_VERSION
+            })
+        );
+
+        // other coefficient type
+        let mut other = bytes.clone();
+        other[9..12].copy_from_slice(b"f32");
+        assert!(matches!(
+            Polynomial::read_binary(other.as_slice()),
+            Err(PolyError::CoefficientTypeMismatch { .. })
+        ));
+
+        // not a polynomial file, truncated file
+        assert!(matches!(
+            Polynomial::read_binary(&b"size,naive_time"[..]),
+            Err(PolyError::InvalidFormat(_))
+        ));
+        assert!(matches!(
+            Polynomial::read_binary(&bytes[..bytes.len() - 1]),
+            Err(PolyError::Io(_))
+        ));
+    }
+
+    /// Test the CSV and compact binary files, including files written by other tools
+    #[cfg(feature = "thread_rng")]
+    #[test]
+    fn test_files() {
+        let dir = std::env::temp_dir();
+        let p = Polynomial::random(-10.0, 10.0, 100);
+        let csv = dir.join("mult_polynomial_files_test.csv");
+        p.to_csv_file(&csv).unwrap();
+        assert_eq!(Polynomial::from_csv_file(&csv).unwrap(), p);
+        let compact = dir.join("mult_polynomial_files_test.f64");
+        p.to_compact_file(&compact).unwrap();
+        assert_eq!(std::fs::metadata(&compact).unwrap().len(), 8 + 8 * 100);
+        assert_eq!(Polynomial::from_compact_file(&compact).unwrap(), p);
+
+        // one row with a comment, as from MATLAB or `numpy.savetxt(..., delimiter=",")`
+        std::fs::write(&csv, "# coefficients\n1,-2.5, 3e2\n\n4\n").unwrap();
+        assert_eq!(
+            Polynomial::from_csv_file(&csv).unwrap(),
+            Polynomial::new(vec![1.0, -2.5, 300.0, 4.0])
+        );
+        std::fs::write(&csv, "1,x\n").unwrap();
+        assert!(matches!(
+            Polynomial::from_csv_file(&csv),
+            Err(PolyError::InvalidFormat(_))
+        ));
+        let missing = dir.join("mult_polynomial_files_test_missing.csv");
+        assert!(matches!(
+            Polynomial::from_csv_file(&missing),
+            Err(PolyError::Io(message)) if message.contains("missing")
+        ));
+        std::fs::write(&compact, [2, 0, 0, 0, 0, 0, 0, 0, 0]).unwrap();
+        assert!(matches!(
+            Polynomial::from_compact_file(&compact),
+            Err(PolyError::Io(_))
+        ));
+    }
+
+    /// Test the header line of text files
+    #[test]
+    fn test_text_header() {
+        let header = text_header("benchmark");
+        assert_eq!(check_text_header(&header, "benchmark"), Ok(()));
+        assert!(check_text_header(&header, "polynomial").is_err());
+        assert!(check_text_header("size,naive_time", "benchmark").is_err());
+        assert_eq!(
+            check_text_header(
+                "# mult_polynomial format=7 coeff=f64 kind=benchmark",
+                "benchmark"
+            ),
+            Err(PolyError::UnsupportedVersion {
+                found: 7,
+                supported: FORMAT_VERSION
+            })
+        );
+    }
+}
@@ -0,0 +1,311 @@
+//! Power series arithmetic modulo `xⁿ`
+//!
+//! A polynomial stands for the power series of its coefficients, and results keep the
+//! first `n` coefficients. Newton's iteration doubles the number of correct coefficients
+//! per step, so the cost stays within a constant factor of one multiplication,
+//! e.g. for generating functions: the logarithm is the integral of `f'/f`,
+//! the exponential inverts the logarithm by Newton's iteration, and so does the square root
+//! with the inverse.
+
+use crate::middle::middle_product_impl;
+use crate::polynomial::multiply_impl;
+use crate::{PolyError, Polynomial, PolynomialMultAlg};
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// At most `n` first coefficients of `a·b`, multiplying only the first `n` coefficients of each
+pub(crate) fn multiply_low(a: &[f64], b: &[f64], n: usize) -> Vec<f64> {
+    let (a, b) = (&a[..a.len().min(n)], &b[..b.len().min(n)]);
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    let mut product = multiply_impl(a, b, PolynomialMultAlg::auto(a.len(), b.len()));
+    product.truncate(n);
+    product
+}
+
+/// First `len` coefficients of the power series `1 / f`, by Newton's iteration `g ← g·(2 - f·g)`
+///
+/// With `g` correct to `p` coefficients, `f·g = 1 + xᵖ·e mod x²ᵖ`, so only the middle product `e`
+/// and the correction `g·e mod xᵖ` are computed, each about one `p × p` multiplication.
+/// `f[0]` must be nonzero.
+pub(crate) fn inverse_series(f: &[f64], len: usize) -> Vec<f64> {
+    let mut g = vec![1.0 / f[0]];
+    let mut precision = 1;
+    while precision < len {
+        let known = precision;
+        precision = (2 * precision).min(len);
+        let error = middle_product_impl(&f[..f.len().min(precision)], &g, known, precision);
+        let correction = multiply_low(&g, &error, precision - known);
+        g.resize(precision, 0.0);
+        for (c, d) in g[known..].iter_mut().zip(correction) {
+            *c -= d;
+        }
+    }
+    g.truncate(len);
+    g
+}
+
+/// First `len` coefficients of `log f` as `∫ f'/f`, for `f[0] = 1`
+fn log_series(f: &[f64], len: usize) -> Vec<f64> {
+    if len <= 1 {
+        return vec![0.0; len];
+    }
+    let derivative: Vec<f64> = (f.iter().enumerate().skip(1).take(len - 1))
+        .map(|(k, &c)| k as f64 * c)
+        .collect();
+    let mut quotient = multiply_low(&derivative, &inverse_series(f, len - 1), len - 1);
+    quotient.resize(len - 1, 0.0);
+    let mut log = vec![0.0];
+    log.extend((quotient.iter().enumerate()).map(|(k, &c)| c / (k + 1) as f64));
+    log
+}
+
+/// First `len` coefficients of `exp f`, for `f[0] = 0`, by Newton's iteration `g ← g·(1 + f - log g)`
+fn exp_series(f: &[f64], len: usize) -> Vec<f64> {
+    let mut g = vec![1.0];
+    let mut precision = 1;
+    while precision < len {
+        precision = (2 * precision).min(len);
+        let mut h = f[..f.len().min(precision)].to_vec();
+        h.resize(precision, 0.0);
+        for (c, l) in h.iter_mut().zip(log_series(&g, precision)) {
+            *c -= l;
+        }
+        h[0] += 1.0;
+        g = multiply_low(&g, &h, precision);
+        g.resize(precision, 0.0);
+    }
+    g.truncate(len);
+    g
+}
+
+/// First `len` coefficients of `√f`, for `f[0] > 0`, by Newton's iteration `g ← (g + f/g) / 2`
+#[cfg(feature = "std")]
+fn sqrt_series(f: &[f64], len: usize) -> Vec<f64> {
+    let mut g = vec![f[0].sqrt()];
+    let mut precision = 1;
+    while precision < len {
+        precision = (2 * precision).min(len);
+        let mut quotient = multiply_low(f, &inverse_series(&g, precision), precision);
+        quotient.resize(precision, 0.0);
+        g.resize(precision, 0.0);
+        for (c, q) in g.iter_mut().zip(quotient) {
+            *c = 0.5 * (*c + q);
+        }
+    }
+    g.truncate(len);
+    g
+}
+
+impl Polynomial {
+    /// Product modulo `xⁿ`, i.e. the first `n` coefficients of `self·other`
+    ///
+    /// Only the first `n` coefficients of each operand are multiplied.
+    pub fn multiply_mod_xn(&self, other: &Polynomial, n: usize) -> Polynomial {
+        self.with_coeffs(multiply_low(&self.coeffs, &other.coeffs, n))
+    }
+
+    /// Inverse power series modulo `xⁿ`: the polynomial `g` of degree below `n` with
+    /// `self·g = 1 mod xⁿ`, by Newton's iteration in O(M(n)) for a multiplication cost M(n)
+    ///
+    /// Fails with [`PolyError::DivisionByZero`] if the constant coefficient is zero,
+    /// as the series is then not invertible.
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// // 1 / (1 - x) = 1 + x + x² + …
+    /// let p = Polynomial::new(vec![1.0, -1.0]);
+    /// assert_eq!(p.inverse_mod_xn(4).unwrap(), Polynomial::new(vec![1.0; 4]));
+    /// assert!(Polynomial::new(vec![0.0, 1.0]).inverse_mod_xn(4).is_err());
+    /// ```
+    pub fn inverse_mod_xn(&self, n: usize) -> Result<Polynomial, PolyError> {
+        match self.coeffs.first() {
+            Some(&c) if c != 0.0 => Ok(self.with_coeffs(inverse_series(&self.coeffs, n))),
+            _ => Err(PolyError::DivisionByZero),
+        }
+    }
+
+    /// Logarithm of the power series modulo `xⁿ`, as the integral of `self' / self`
+    ///
+    /// Fails with [`PolyError::InvalidArgument`] unless the constant coefficient is 1.
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// // log(1 / (1 - x)) = x + x²/2 + x³/3 + …
+    /// let p = Polynomial::new(vec![1.0, -1.0]).inverse_mod_xn(5).unwrap();
+    /// let log = p.log_mod_xn(5).unwrap();
+    /// assert_eq!(log, Polynomial::new(vec![0.0, 1.0, 1.0 / 2.0, 1.0 / 3.0, 1.0 / 4.0]));
+    /// ```
+    pub fn log_mod_xn(&self, n: usize) -> Result<Polynomial, PolyError> {
+        if self.coeffs.first() != Some(&1.0) {
+            return Err(PolyError::InvalidArgument(
+                "the logarithm needs a constant coefficient of 1".to_string(),
+            ));
+        }
+        Ok(self.with_coeffs(log_series(&self.coeffs, n)))
+    }
+
+    /// Exponential of the power series modulo `xⁿ`, by Newton's iteration on the logarithm
+    ///
+    /// Fails with [`PolyError::InvalidArgument`] unless the constant coefficient is 0.
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// // exp(x + x²/2) is the exponential generating function of the involutions
+    /// let p = Polynomial::new(vec![0.0, 1.0, 0.5]);
+    /// let exp = p.exp_mod_xn(7).unwrap();
+    /// let factorials = [1.0, 1.0, 2.0, 6.0, 24.0, 120.0, 720.0];
+    /// let counts: Vec<f64> = exp.coeffs.iter().zip(factorials).map(|(c, f)| (c * f).round()).collect();
+    /// assert_eq!(counts, vec![1.0, 1.0, 2.0, 4.0, 10.0, 26.0, 76.0]);
+    /// ```
+    pub fn exp_mod_xn(&self, n: usize) -> Result<Polynomial, PolyError> {
+        if self.coeffs.first().is_some_and(|&c| c != 0.0) {
+            return Err(PolyError::InvalidArgument(
+                "the exponential needs a constant coefficient of 0".to_string(),
+            ));
+        }
+        Ok(self.with_coeffs(exp_series(&self.coeffs, n)))
+    }
+
+    /// Square root of the power series modulo `xⁿ` with a positive constant coefficient,
+    /// by Newton's iteration
+    ///
+    /// Requires the `std` feature. Fails with [`PolyError::InvalidArgument`] unless the constant
+    /// coefficient is positive.
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// // √(1 + 4x) = 1 + 2x - 2x² + 4x³ - …
+    /// let p = Polynomial::new(vec![1.0, 4.0]);
+    /// assert_eq!(p.sqrt_mod_xn(4).unwrap(), Polynomial::new(vec![1.0, 2.0, -2.0, 4.0]));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn sqrt_mod_xn(&self, n: usize) -> Result<Polynomial, PolyError> {
+        match self.coeffs.first() {
+            Some(&c) if c > 0.0 => Ok(self.with_coeffs(sqrt_series(&self.coeffs, n))),
+            _ => Err(PolyError::InvalidArgument(
+                "the square root needs a positive constant coefficient".to_string(),
+            )),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "thread_rng"))]
+mod tests {
+    use super::*;
+
+    /// Test the power series inverse
+    #[test]
+    fn test_inverse_series() {
+        // 1 / (1 - x) = 1 + x + x² + ...
+        assert_eq!(inverse_series(&[1.0, -1.0], 100), vec![1.0; 100]);
+        // small higher coefficients keep the series decaying
+        let mut f = Polynomial::random(-0.01, 0.01, 70).coeffs;
+        f[0] = 1.0;
+        let g = inverse_series(&f, 150);
+        let mut fg = multiply_impl(&f, &g, PolynomialMultAlg::Naive);
+        fg.truncate(150);
+        assert!((fg[0] - 1.0).abs() < 1e-12);
+        assert!(fg[1..].iter().all(|c| c.abs() < 1e-12));
+    }
+
+    /// Test the truncated product and the inverse modulo xⁿ on odd lengths and edge cases
+    #[test]
+    fn test_inverse_mod_xn() {
+        let p = Polynomial::random(-10.0, 10.0, 100);
+        let q = Polynomial::random(-10.0, 10.0, 30);
+        let low = p.multiply_mod_xn(&q, 57);
+        assert!(low.approx_eq(&p.multiply_naive(&q).low(57), 1e-9, 1e-9));
+        assert_eq!(p.multiply_mod_xn(&q, 0).coeffs.len(), 0);
+
+        let mut f = p.clone();
+        f.coeffs[0] = 100.0;
+        for n in [1, 2, 37, 100, 333] {
+            let g = f.inverse_mod_xn(n).unwrap();
+            assert!(g.coeffs.len() <= n);
+            let one = f.multiply_mod_xn(&g, n);
+            assert!(
+                one.approx_eq(&Polynomial::new(vec![1.0]), 1e-9, 0.0),
+                "n = {}",
+                n
+            );
+        }
+        assert!(f.inverse_mod_xn(0).unwrap().coeffs.is_empty());
+        assert_eq!(
+            Polynomial::new(vec![]).inverse_mod_xn(3),
+            Err(PolyError::DivisionByZero)
+        );
+    }
+
+    /// Test exp and log against known series and against each other
+    #[test]
+    fn test_exp_log() {
+        // exp(x) = Σ xᵏ/k!
+        let x = Polynomial::new(vec![0.0, 1.0]);
+        let exp = x.exp_mod_xn(12).unwrap();
+        let mut factorial = 1.0;
+        for (k, c) in exp.coeffs.iter().enumerate() {
+            factorial *= k.max(1) as f64;
+            assert!((c - 1.0 / factorial).abs() < 1e-15, "k = {}", k);
+        }
+        assert_eq!(exp.coeffs.len(), 12);
+        assert!(exp.log_mod_xn(12).unwrap().approx_eq(&x, 1e-15, 0.0));
+
+        // random series with small coefficients, odd lengths
+        let mut f = Polynomial::random(-0.1, 0.1, 90);
+        f.coeffs[0] = 0.0;
+        for n in [1, 2, 45, 90, 131] {
+            let exp = f.exp_mod_xn(n).unwrap();
+            let back = exp.log_mod_xn(n).unwrap();
+            assert!(back.approx_eq(&f.low(n), 1e-10, 1e-10), "n = {}", n);
+        }
+        // log(p·q) = log p + log q
+        let mut p = Polynomial::random(-0.5, 0.5, 40);
+        p.coeffs[0] = 1.0;
+        let mut q = Polynomial::random(-0.5, 0.5, 25);
+        q.coeffs[0] = 1.0;
+        let log_pq = p.multiply_naive(&q).log_mod_xn(60).unwrap();
+        let sum: Vec<f64> = (p.log_mod_xn(60).unwrap().coeffs.iter())
+            .zip(&q.log_mod_xn(60).unwrap().coeffs)
+            .map(|(a, b)| a + b)
+            .collect();
+        assert!(log_pq.approx_eq(&Polynomial::new(sum), 1e-9, 1e-9));
+
+        assert!(matches!(
+            Polynomial::new(vec![2.0, 1.0]).log_mod_xn(5),
+            Err(PolyError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            Polynomial::new(vec![1.0, 1.0]).exp_mod_xn(5),
+            Err(PolyError::InvalidArgument(_))
+        ));
+        assert_eq!(
+            Polynomial::new(vec![]).exp_mod_xn(3).unwrap(),
+            Polynomial::new(vec![1.0])
+        );
+    }
+
+    /// Test the square root against squaring, on odd lengths and a perfect square
+    #[test]
+    fn test_sqrt_mod_xn() {
+        let mut f = Polynomial::random(-1.0, 1.0, 80);
+        f.coeffs[0] = 4.0;
+        for n in [1, 2, 41, 80, 129] {
+            let g = f.sqrt_mod_xn(n).unwrap();
+            assert!(g.coeffs.len() <= n);
+            let square = g.multiply_mod_xn(&g, n);
+            assert!(square.approx_eq(&f.low(n), 1e-9, 1e-9), "n = {}", n);
+        }
+        let p = Polynomial::new(vec![3.0, -1.0, 0.5, 2.0]);
+        let root = p.square().sqrt_mod_xn(10).unwrap();
+        assert!(root.approx_eq(&p, 1e-12, 1e-12));
+        for invalid in [vec![], vec![0.0, 1.0], vec![-1.0, 1.0]] {
+            assert!(matches!(
+                Polynomial::new(invalid).sqrt_mod_xn(5),
+                Err(PolyError::InvalidArgument(_))
+            ));
+        }
+    }
+}
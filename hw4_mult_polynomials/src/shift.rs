@@ -0,0 +1,226 @@
+//! Multiplication and division by powers of x, slicing of the coefficients, and Taylor shifts
+//!
+//! For any `n`, a polynomial splits as `p = p.low(n) + (p.high(n) << n)`,
+//! which is the building block of divide-and-conquer multiplication schemes.
+
+use crate::polynomial::multiply_impl;
+use crate::{Polynomial, PolynomialMultAlg};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::{Shl, Shr};
+
+impl Polynomial {
+    /// Multiply by `xᵏ`, shifting the coefficients up by `k` (same as `self << k`)
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// let p = Polynomial::new(vec![1.0, 2.0]);
+    /// assert_eq!(p.mul_xk(2), Polynomial::new(vec![0.0, 0.0, 1.0, 2.0]));
+    /// ```
+    pub fn mul_xk(&self, k: usize) -> Polynomial {
+        if self.coeffs.is_empty() {
+            return self.clone();
+        }
+        let mut coeffs = vec![0.0; k];
+        coeffs.extend_from_slice(&self.coeffs);
+        self.with_coeffs(coeffs)
+    }
+
+    /// Divide by `xᵏ`, dropping the `k` lowest coefficients (same as `self >> k`)
+    ///
+    /// This is the quotient of the division by `xᵏ`; the remainder is [`Polynomial::low`].
+    pub fn div_xk(&self, k: usize) -> Polynomial {
+        self.with_coeffs(self.coeffs[k.min(self.coeffs.len())..].to_vec())
+    }
+
+    /// The `n` lowest coefficients, i.e. the remainder modulo `xⁿ`
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// let p = Polynomial::new(vec![1.0, 2.0, 3.0, 4.0, 5.0]);
+    /// let (low, high) = (p.low(2), p.high(2));
+    /// assert_eq!(low, Polynomial::new(vec![1.0, 2.0]));
+    /// assert_eq!(high, Polynomial::new(vec![3.0, 4.0, 5.0]));
+    /// assert_eq!(low.coeffs.len() + high.coeffs.len(), p.coeffs.len());
+    /// ```
+    pub fn low(&self, n: usize) -> Polynomial {
+        self.with_coeffs(self.coeffs[..n.min(self.coeffs.len())].to_vec())
+    }
+
+    /// The coefficients from degree `n` on, i.e. the quotient of the division by `xⁿ`
+    ///
+    /// Same as [`Polynomial::div_xk`], named to pair with [`Polynomial::low`].
+    pub fn high(&self, n: usize) -> Polynomial {
+        self.div_xk(n)
+    }
+
+    /// Taylor shift: the coefficients of `p(x + a)`
+    ///
+    /// Splits `p = low + xʰ·high` and computes `low(x + a) + (x + a)ʰ·high(x + a)` recursively,
+    /// so the work is dominated by the fast multiplication of the large halves.
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// // (x + 1)² = 1 + 2x + x²
+    /// let p = Polynomial::new(vec![0.0, 0.0, 1.0]);
+    /// assert_eq!(p.shift(1.0), Polynomial::new(vec![1.0, 2.0, 1.0]));
+    /// ```
+    pub fn shift(&self, a: f64) -> Polynomial {
+        self.with_coeffs(taylor_shift_impl(&self.coeffs, a))
+    }
+}
+
+/// Length below which the Taylor shift uses the quadratic Horner scheme
+const TAYLOR_SHIFT_THRESHOLD: usize = 32;
+
+/// Coefficients of `p(x + a)` by Horner's scheme `result ← result·(x + a) + c`, in O(n²)
+fn taylor_shift_horner(p: &[f64], a: f64) -> Vec<f64> {
+    let n = p.len();
+    let mut result = vec![0.0; n];
+    for &c in p.iter().rev() {
+        for j in (1..n).rev() {
+            result[j] = a * result[j] + result[j - 1];
+        }
+        result[0] = a * result[0] + c;
+    }
+    result
+}
+
+/// Coefficients of `p(x + a)` for the coefficients `p`
+fn taylor_shift_impl(p: &[f64], a: f64) -> Vec<f64> {
+    let n = p.len();
+    if n < TAYLOR_SHIFT_THRESHOLD {
+        return taylor_shift_horner(p, a);
+    }
+
+    let h = n / 2;
+    let low = taylor_shift_impl(&p[..h], a);
+    let high = taylor_shift_impl(&p[h..], a);
+    let x_plus_a_pow_h = Polynomial::new(vec![a, 1.0]).pow(h as u32);
+    let mut result = multiply_impl(
+        &high,
+        &x_plus_a_pow_h.coeffs,
+        PolynomialMultAlg::auto(high.len(), h + 1),
+    );
+    result.resize(n, 0.0);
+    for (r, l) in result.iter_mut().zip(&low) {
+        *r += l;
+    }
+    result
+}
+
+impl Shl<usize> for &Polynomial {
+    type Output = Polynomial;
+
+    /// Multiply by `xᵏ`, see [`Polynomial::mul_xk`]
+    fn shl(self, k: usize) -> Polynomial {
+        self.mul_xk(k)
+    }
+}
+
+impl Shl<usize> for Polynomial {
+    type Output = Polynomial;
+
+    /// Multiply by `xᵏ`, see [`Polynomial::mul_xk`]
+    fn shl(self, k: usize) -> Polynomial {
+        self.mul_xk(k)
+    }
+}
+
+impl Shr<usize> for &Polynomial {
+    type Output = Polynomial;
+
+    /// Divide by `xᵏ`, see [`Polynomial::div_xk`]
+    fn shr(self, k: usize) -> Polynomial {
+        self.div_xk(k)
+    }
+}
+
+impl Shr<usize> for Polynomial {
+    type Output = Polynomial;
+
+    /// Divide by `xᵏ`, see [`Polynomial::div_xk`]
+    fn shr(self, k: usize) -> Polynomial {
+        self.div_xk(k)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test shifting and slicing, including the empty and out-of-range cases
+    #[test]
+    fn test_shift_and_slice() {
+        let p = Polynomial::new(vec![1.0, 2.0, 3.0]);
+        assert_eq!(&p << 0, p);
+        assert_eq!(p.clone() << 1, Polynomial::new(vec![0.0, 1.0, 2.0, 3.0]));
+        assert_eq!((&p << 3) >> 3, p);
+        assert_eq!(&p >> 2, Polynomial::new(vec![3.0]));
+        assert!((&p >> 5).coeffs.is_empty());
+        assert!((Polynomial::new(vec![]) << 4).coeffs.is_empty());
+        assert_eq!(p.low(10), p);
+        assert!(p.low(0).coeffs.is_empty());
+        // trailing zeros of the low part are trimmed
+        assert_eq!(
+            Polynomial::new(vec![1.0, 0.0, 3.0]).low(2).coeffs,
+            vec![1.0]
+        );
+    }
+
+    /// Test the Taylor shift against evaluation and the quadratic base case
+    #[cfg(feature = "thread_rng")]
+    #[test]
+    fn test_taylor_shift() {
+        // (x - 2)³ = -8 + 12x - 6x² + x³
+        let cube = Polynomial::new(vec![0.0, 0.0, 0.0, 1.0]);
+        assert_eq!(
+            cube.shift(-2.0),
+            Polynomial::new(vec![-8.0, 12.0, -6.0, 1.0])
+        );
+        assert!(Polynomial::new(vec![]).shift(3.0).coeffs.is_empty());
+        // shifting back recovers the polynomial
+        let p = Polynomial::random(-1.0, 1.0, 20);
+        assert!(p.shift(0.5).shift(-0.5).approx_eq(&p, 1e-9, 1e-9));
+        for n in [5, 20, 31, 32, 100, 257] {
+            let p = Polynomial::random(-1.0, 1.0, n);
+            assert_eq!(p.shift(0.0), p);
+            for a in [0.5, -0.25] {
+                let shifted = p.shift(a);
+                assert_eq!(shifted.coeffs.len(), n);
+                let horner = Polynomial::new(taylor_shift_horner(&p.coeffs, a));
+                let scale = horner.coeffs.iter().fold(0.0f64, |m, c| m.max(c.abs()));
+                assert!(shifted.approx_eq(&horner, 1e-12 * scale, 0.0));
+            }
+        }
+        // the quadratic scheme against evaluation, for a degree low enough to evaluate stably
+        let p = Polynomial::random(-1.0, 1.0, 20);
+        let shifted = Polynomial::new(taylor_shift_horner(&p.coeffs, 0.5));
+        for x in [-0.5, 0.0, 0.3] {
+            assert!((shifted.evaluate(x) - p.evaluate(x + 0.5)).abs() < 1e-9);
+        }
+    }
+
+    /// Test the splitting identity and one level of divide and conquer built on it
+    #[cfg(feature = "thread_rng")]
+    #[test]
+    fn test_split_multiply() {
+        let add = |x: &Polynomial, y: &Polynomial| {
+            let mut sum = x.coeffs.clone();
+            sum.resize(x.coeffs.len().max(y.coeffs.len()), 0.0);
+            for (s, c) in sum.iter_mut().zip(&y.coeffs) {
+                *s += c;
+            }
+            Polynomial::new(sum)
+        };
+        let p = Polynomial::random(-10.0, 10.0, 40);
+        let q = Polynomial::random(-10.0, 10.0, 35);
+        for n in [0, 1, 17, 40, 100] {
+            assert_eq!(add(&p.low(n), &(p.high(n) << n)), p);
+        }
+        // p·q = p0·q + x²⁰·p1·q
+        let (p0, p1) = (p.low(20), p.high(20));
+        let product = add(&p0.multiply_naive(&q), &(p1.multiply_naive(&q) << 20));
+        assert!(product.approx_eq(&p.multiply_naive(&q), 1e-9, 1e-9));
+    }
+}
@@ -0,0 +1,150 @@
+//! Polynomials storing few coefficients inline, without heap allocation
+
+use crate::polynomial::{multiply_impl, naive_multiply_into};
+use crate::{Coefficient, Polynomial, PolynomialMultAlg, PolynomialView};
+use smallvec::SmallVec;
+
+/// Number of coefficients a [`SmallPolynomial`] stores without allocating,
+/// enough for the product of two polynomials of degree 7
+pub const SMALL_INLINE: usize = 16;
+
+/// Coefficient storage of a [`SmallPolynomial`]
+pub type SmallCoeffs<T> = SmallVec<[T; SMALL_INLINE]>;
+
+/// Polynomial whose coefficients are stored inline up to [`SMALL_INLINE`] of them,
+/// and spill to the heap transparently beyond
+///
+/// Requires the `smallvec` feature. Naive products of short operands are written
+/// straight into the inline storage, so millions of low-degree products run without
+/// a single allocation; longer operands go through the same multipliers as [`Polynomial`].
+/// ## Example
+/// ```
+/// use mult_polynomial::{Polynomial, SmallPolynomial};
+/// let p = SmallPolynomial::from_slice(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+/// let product = p.multiply_auto(&p);
+/// assert!(!product.coeffs.spilled());
+/// assert_eq!(product.to_polynomial(), Polynomial::new(p.coeffs.to_vec()).square());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SmallPolynomial<T: Coefficient = f64> {
+    /// Coefficients from lowest to highest degree
+    pub coeffs: SmallCoeffs<T>,
+}
+
+impl<T: Coefficient> SmallPolynomial<T> {
+    /// Create a new polynomial, trimming trailing coefficients as in [`Polynomial::new`]
+    pub fn new(mut coeffs: SmallCoeffs<T>) -> Self {
+        while coeffs
+            .last()
+            .is_some_and(|c| c.abs().to_f64() < T::TOLERANCE)
+        {
+            coeffs.pop();
+        }
+        SmallPolynomial { coeffs }
+    }
+
+    /// Copy the coefficients from a slice
+    pub fn from_slice(coeffs: &[T]) -> Self {
+        SmallPolynomial::new(SmallVec::from_slice(coeffs))
+    }
+
+    /// Get the degree of the polynomial (0 for the zero polynomial)
+    pub fn degree(&self) -> usize {
+        self.coeffs.len().saturating_sub(1)
+    }
+
+    /// Evaluate the polynomial at a given point x
+    pub fn evaluate(&self, x: T) -> T {
+        self.view().evaluate(x)
+    }
+
+    /// Borrow the coefficients as a [`PolynomialView`]
+    pub fn view(&self) -> PolynomialView<'_, T> {
+        PolynomialView::new(&self.coeffs)
+    }
+
+    /// Copy the coefficients into a polynomial stored on the heap
+    pub fn to_polynomial(&self) -> Polynomial<T> {
+        Polynomial::new(self.coeffs.to_vec())
+    }
+
+    /// Multiply with another polynomial using the given algorithm
+    ///
+    /// The naive algorithm writes into the inline storage when the product fits;
+    /// the others allocate, and their result becomes the heap storage without a copy.
+    pub fn multiply(
+        &self,
+        other: &SmallPolynomial<T>,
+        algorithm: PolynomialMultAlg,
+    ) -> SmallPolynomial<T> {
+        let (a, b) = (&self.coeffs[..], &other.coeffs[..]);
+        if a.is_empty() || b.is_empty() {
+            return SmallPolynomial::new(SmallVec::new());
+        }
+        if algorithm == PolynomialMultAlg::Naive {
+            let mut result = SmallVec::from_elem(T::ZERO, a.len() + b.len() - 1);
+            naive_multiply_into(a, b, &mut result);
+            return SmallPolynomial::new(result);
+        }
+        SmallPolynomial::new(SmallVec::from_vec(multiply_impl(a, b, algorithm)))
+    }
+
+    /// Multiply with another polynomial using the algorithm chosen by [`PolynomialMultAlg::auto`],
+    /// i.e. the naive one for short operands
+    pub fn multiply_auto(&self, other: &SmallPolynomial<T>) -> SmallPolynomial<T> {
+        let algorithm = PolynomialMultAlg::auto_for::<T>(self.coeffs.len(), other.coeffs.len());
+        self.multiply(other, algorithm)
+    }
+
+    /// Multiply with another polynomial using the naive O(n²) algorithm
+    pub fn multiply_naive(&self, other: &SmallPolynomial<T>) -> SmallPolynomial<T> {
+        self.multiply(other, PolynomialMultAlg::Naive)
+    }
+}
+
+impl<T: Coefficient> From<&Polynomial<T>> for SmallPolynomial<T> {
+    fn from(polynomial: &Polynomial<T>) -> Self {
+        SmallPolynomial::from_slice(&polynomial.coeffs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Products stay inline while they fit and match the heap-allocated ones either way
+    #[test]
+    fn test_small_products() {
+        let p = Polynomial::random(-10.0, 10.0, 5);
+        let q = Polynomial::random(-10.0, 10.0, 5);
+        let (a, b) = (SmallPolynomial::from(&p), SmallPolynomial::from(&q));
+        assert!(!a.coeffs.spilled());
+        let product = a.multiply_auto(&b);
+        assert!(!product.coeffs.spilled());
+        assert_eq!(product.coeffs.to_vec(), p.multiply_auto(&q).coeffs);
+        assert_eq!(product.degree(), 8);
+        assert_eq!(a.evaluate(0.5), p.evaluate(0.5));
+
+        // past the inline capacity, every algorithm still agrees
+        let p = Polynomial::random(-10.0, 10.0, 300);
+        let q = Polynomial::random(-10.0, 10.0, 200);
+        let (a, b) = (SmallPolynomial::from(&p), SmallPolynomial::from(&q));
+        assert!(a.coeffs.spilled());
+        for algorithm in [
+            PolynomialMultAlg::Naive,
+            PolynomialMultAlg::CookTooms,
+            PolynomialMultAlg::Thresholded(16),
+        ] {
+            let product = a.multiply(&b, algorithm);
+            assert_eq!(product.to_polynomial(), p.multiply(&q, algorithm));
+        }
+        assert_eq!(a.multiply_naive(&b).to_polynomial(), p.multiply_naive(&q));
+
+        let zero = SmallPolynomial::<f32>::from_slice(&[0.0, 0.0]);
+        assert!(zero.coeffs.is_empty());
+        assert!(zero
+            .multiply_auto(&SmallPolynomial::from_slice(&[1.0]))
+            .coeffs
+            .is_empty());
+    }
+}
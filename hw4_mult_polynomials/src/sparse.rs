@@ -0,0 +1,130 @@
+//! Products of sparse polynomials, term by term over the nonzero coefficients
+
+use crate::fp::mul_add;
+use crate::iter::nonzero_terms;
+use crate::op_count::record;
+use crate::{Coefficient, Polynomial};
+use alloc::collections::BinaryHeap;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Reverse;
+
+/// Whether the operands are sparse enough for [`sparse_multiply_impl`] to beat a dense product
+///
+/// Short operands stay with the naive algorithm, longer ones switch once the products of
+/// their nonzero terms, each costing a heap operation, are at most `(n + m) / 8`,
+/// well below the work of Toom-3 on the full lengths.
+pub(crate) fn prefers_sparse<T: Coefficient>(a: &[T], b: &[T]) -> bool {
+    if a.len().min(b.len()) < T::AUTO_THRESHOLD {
+        return false;
+    }
+    let budget = (a.len() + b.len()) / 8;
+    let nonzeros = |coeffs: &[T]| nonzero_terms(coeffs).count();
+    let n = nonzeros(a);
+    n == 0 || nonzeros(b).saturating_mul(n) <= budget
+}
+
+/// Product of two coefficient slices from their nonzero terms only
+///
+/// The term products of the operand with fewer nonzero coefficients times the other one are
+/// merged in increasing exponent with a binary heap holding one cursor per term, so the work
+/// is `O(s·t·log s)` for `s ≤ t` nonzero terms, independent of the degrees. Each coefficient
+/// sums its terms in a fixed order, so the result is deterministic.
+pub(crate) fn sparse_multiply_impl<T: Coefficient>(a: &[T], b: &[T]) -> Vec<T> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let (mut short, mut long) = (
+        nonzero_terms(a).collect::<Vec<_>>(),
+        nonzero_terms(b).collect::<Vec<_>>(),
+    );
+    if short.len() > long.len() {
+        core::mem::swap(&mut short, &mut long);
+    }
+    let mut result = vec![T::ZERO; a.len() + b.len() - 1];
+    if short.is_empty() {
+        return result;
+    }
+    // one cursor per term of the shorter operand, at its next term of the longer one
+    let mut heap: BinaryHeap<Reverse<(usize, usize, usize)>> = (short.iter().enumerate())
+        .map(|(i, &(k, _))| Reverse((k + long[0].0, i, 0)))
+        .collect();
+    while let Some(Reverse((exponent, i, j))) = heap.pop() {
+        result[exponent] = mul_add(short[i].1, long[j].1, result[exponent]);
+        if let Some(&(k, _)) = long.get(j + 1) {
+            heap.push(Reverse((short[i].0 + k, i, j + 1)));
+        }
+    }
+    let products = short.len() * long.len();
+    record(products, products);
+    result
+}
+
+impl<T: Coefficient> Polynomial<T> {
+    /// Multiply with another polynomial term by term, skipping the zero coefficients
+    ///
+    /// The cost grows with the numbers of nonzero coefficients instead of the degrees,
+    /// e.g. squaring `x⁵⁰⁰⁰⁰ + 1` takes four term products. [`Polynomial::multiply_auto`]
+    /// switches to this path by itself when the operands are sparse enough.
+    /// ## Example
+    /// ```
+    /// use mult_polynomial::Polynomial;
+    /// let mut coeffs = vec![0.0; 50001];
+    /// (coeffs[0], coeffs[50000]) = (1.0, 1.0);
+    /// let p = Polynomial::new(coeffs);
+    /// let square = p.multiply_sparse(&p);
+    /// assert_eq!(square.degree(), 100000);
+    /// assert_eq!(square.coeffs[50000], 2.0);
+    /// assert_eq!(square.terms().count(), 3);
+    /// ```
+    pub fn multiply_sparse(&self, other: &Polynomial<T>) -> Polynomial<T> {
+        self.with_coeffs(sparse_multiply_impl(&self.coeffs, &other.coeffs))
+    }
+}
+
+#[cfg(all(test, feature = "thread_rng"))]
+mod tests {
+    use super::*;
+    use crate::RandomPolynomial;
+
+    /// Test sparse products against the naive algorithm, and the detection of sparse operands
+    #[test]
+    fn test_sparse_multiply() {
+        for density in [0.01, 0.2, 1.0] {
+            let p = RandomPolynomial::new(700).density(density).generate();
+            let q = RandomPolynomial::new(400).density(density).generate();
+            let tolerance = 1e-12 * p.height() * q.height() * 400.0;
+            assert!(p
+                .multiply_sparse(&q)
+                .approx_eq(&p.multiply_naive(&q), tolerance, 0.0));
+            assert!(q
+                .multiply_sparse(&p)
+                .approx_eq(&p.multiply_naive(&q), tolerance, 0.0));
+        }
+
+        let mut coeffs = vec![0.0; 50001];
+        (coeffs[0], coeffs[50000]) = (1.0, 1.0);
+        let p = Polynomial::new(coeffs);
+        assert!(prefers_sparse(&p.coeffs, &p.coeffs));
+        let square = p.multiply_auto(&p);
+        assert_eq!(square.coeffs.len(), 100001);
+        for (k, c) in square.coeffs.iter().enumerate() {
+            let expected = match k {
+                0 | 100000 => 1.0,
+                50000 => 2.0,
+                _ => 0.0,
+            };
+            assert_eq!(*c, expected);
+        }
+
+        let dense = Polynomial::random(-1.0, 1.0, 1000);
+        assert!(!prefers_sparse(&dense.coeffs, &dense.coeffs));
+        // 2000 term products are fewer than an eighth of the lengths
+        assert!(prefers_sparse(&dense.coeffs, &p.coeffs));
+        assert!(!prefers_sparse(&[1.0, 0.0, 1.0], &[1.0, 0.0, 1.0]));
+        assert!(Polynomial::<f64>::new(vec![])
+            .multiply_sparse(&p)
+            .coeffs
+            .is_empty());
+    }
+}
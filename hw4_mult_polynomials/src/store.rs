@@ -0,0 +1,257 @@
+//! SQLite store accumulating benchmark runs across machines and dates
+//!
+//! Enabled with the `sqlite` feature. Set [`BenchmarkConfig::database`] to append the runs of
+//! every benchmark to one database, then query it with any SQLite client, e.g.
+//! ```sql
+//! SELECT machine, algorithm, size, min(seconds) FROM all_runs GROUP BY 1, 2, 3;
+//! ```
+
+use crate::benchmark::{BenchmarkConfig, SizeResult};
+use crate::PolyError;
+use rusqlite::{params, Connection};
+use std::path::Path;
+
+/// Tables of the store: one row per benchmark (`sessions`) and per timed run (`runs`),
+/// joined in the view `all_runs`
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS sessions (
+    id INTEGER PRIMARY KEY,
+    started_at TEXT NOT NULL DEFAULT (datetime('now')),
+    machine TEXT NOT NULL,
+    os TEXT NOT NULL,
+    arch TEXT NOT NULL,
+    seed TEXT NOT NULL,
+    threads INTEGER NOT NULL,
+    crate_version TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS runs (
+    session INTEGER NOT NULL REFERENCES sessions(id),
+    size INTEGER NOT NULL,
+    other_size INTEGER NOT NULL,
+    aspect_ratio INTEGER NOT NULL,
+    distribution TEXT NOT NULL DEFAULT 'uniform',
+    algorithm TEXT NOT NULL,
+    run INTEGER NOT NULL,
+    seconds REAL NOT NULL
+);
+CREATE VIEW IF NOT EXISTS all_runs AS
+    SELECT sessions.*, size, other_size, aspect_ratio, distribution, algorithm, run, seconds
+    FROM runs JOIN sessions ON runs.session = sessions.id;
+";
+
+/// SQLite database of benchmark runs, created on first use
+///
+/// Every benchmark is a session recording the machine, the seed of the inputs and the start time;
+/// its runs are rows of `runs` with the size, the input distribution, the algorithm
+/// (named as in the CSV columns) and the time in seconds. The view `all_runs` has one row per run with all of these columns.
+/// ## Example
+/// ```
+/// use mult_polynomial::benchmark::{run_comprehensive_benchmark, BenchmarkConfig};
+/// use mult_polynomial::{PolynomialMultAlg, ResultStore};
+/// let dir = std::env::temp_dir().join("mult_polynomial_store_doc");
+/// let _ = std::fs::remove_dir_all(&dir);
+/// let config = BenchmarkConfig {
+///     sizes: vec![8, 32],
+///     algorithms: vec![PolynomialMultAlg::Naive],
+///     database: Some(dir.join("results.db")),
+///     machine: Some("laptop".to_string()),
+///     seed: Some(1),
+///     output_dir: dir.clone(),
+///     ..BenchmarkConfig::default()
+/// };
+/// run_comprehensive_benchmark(&config, &|_| 3).unwrap();
+/// let store = ResultStore::open(dir.join("results.db")).unwrap();
+/// let runs: i64 = (store.connection())
+///     .query_row("SELECT count(*) FROM all_runs WHERE machine = 'laptop'", [], |row| row.get(0))
+///     .unwrap();
+/// assert_eq!(runs, 2 * 3);
+/// ```
+pub struct ResultStore {
+    connection: Connection,
+}
+
+impl ResultStore {
+    /// Open the database at `path`, creating the file and its tables if needed
+    pub fn open(path: impl AsRef<Path>) -> Result<ResultStore, PolyError> {
+        let connection = Connection::open(path)?;
+        connection.execute_batch(SCHEMA)?;
+        // databases from before the distribution sweep: all their runs had uniform inputs
+        if connection
+            .prepare("SELECT distribution FROM runs LIMIT 0")
+            .is_err()
+        {
+            connection.execute_batch(
+                "ALTER TABLE runs ADD COLUMN distribution TEXT NOT NULL DEFAULT 'uniform';
+                 DROP VIEW all_runs;",
+            )?;
+            connection.execute_batch(SCHEMA)?;
+        }
+        Ok(ResultStore { connection })
+    }
+
+    /// Connection to the database, for queries
+    pub fn connection(&self) -> &Connection {
+        &self.connection
+    }
+
+    /// Record the start of a benchmark with the inputs drawn from `seed`, returning its session id
+    pub fn begin_session(&self, config: &BenchmarkConfig, seed: u64) -> Result<i64, PolyError> {
+        let machine = config.machine.clone().unwrap_or_else(host_name);
+        self.connection.execute(
+            "INSERT INTO sessions (machine, os, arch, seed, threads, crate_version)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                machine,
+                std::env::consts::OS,
+                std::env::consts::ARCH,
+                // SQLite integers are signed, so the seed is kept exactly as text
+                seed.to_string(),
+                config.threads as i64,
+                env!("CARGO_PKG_VERSION"),
+            ],
+        )?;
+        Ok(self.connection.last_insert_rowid())
+    }
+
+    /// Append every run of a measured cell to `session`, in one transaction
+    pub(crate) fn insert_cell(
+        &mut self,
+        session: i64,
+        names: &[String],
+        result: &SizeResult,
+    ) -> Result<(), PolyError> {
+        let transaction = self.connection.transaction()?;
+        {
+            let mut insert = transaction.prepare_cached(
+                "INSERT INTO runs
+                 (session, size, other_size, aspect_ratio, distribution, algorithm, run, seconds)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            )?;
+            for (name, times) in names.iter().zip(&result.times) {
+                for (run, time) in times.iter().enumerate() {
+                    insert.execute(params![
+                        session,
+                        result.size as i64,
+                        result.other_size as i64,
+                        result.aspect_ratio as i64,
+                        result.distribution.to_string(),
+                        name,
+                        run as i64,
+                        time,
+                    ])?;
+                }
+            }
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+}
+
+/// Name of this machine from the environment or `/etc/hostname`, `unknown` if there is none
+fn host_name() -> String {
+    (["HOSTNAME", "COMPUTERNAME"].iter())
+        .find_map(|key| std::env::var(key).ok())
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmark::{run_comprehensive_benchmark, InputDistribution};
+    use crate::PolynomialMultAlg;
+
+    /// Runs of several benchmarks accumulate in one database, one row per run
+    #[test]
+    fn test_result_store() {
+        let dir = std::env::temp_dir().join("mult_polynomial_store_test");
+        let _ = std::fs::remove_dir_all(&dir);
+        let database = dir.join("results.db");
+        let config = BenchmarkConfig {
+            sizes: vec![4, 16],
+            aspect_ratios: vec![1, 2],
+            algorithms: vec![PolynomialMultAlg::Naive, PolynomialMultAlg::Thresholded(8)],
+            output_dir: dir.clone(),
+            database: Some(database.clone()),
+            seed: Some(u64::MAX),
+            ..BenchmarkConfig::default()
+        };
+        let first = run_comprehensive_benchmark(&config, &|_| 3).unwrap();
+        let second = BenchmarkConfig {
+            machine: Some("other".to_string()),
+            seed: Some(2),
+            ..config.clone()
+        };
+        run_comprehensive_benchmark(&second, &|_| 2).unwrap();
+
+        let store = ResultStore::open(&database).unwrap();
+        let count = |sql: &str| -> i64 {
+            (store.connection())
+                .query_row(sql, [], |row| row.get(0))
+                .unwrap()
+        };
+        assert_eq!(count("SELECT count(*) FROM sessions"), 2);
+        assert_eq!(count("SELECT count(*) FROM all_runs"), 4 * 2 * (3 + 2));
+        assert_eq!(
+            count("SELECT count(*) FROM all_runs WHERE machine = 'other' AND seed = '2'"),
+            4 * 2 * 2
+        );
+        let seconds: f64 = (store.connection())
+            .query_row(
+                "SELECT seconds FROM all_runs WHERE seed = ?1 AND size = 16
+                 AND aspect_ratio = 2 AND algorithm = 'threshold_8' AND run = 2",
+                [u64::MAX.to_string()],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(seconds, first[3].times[1][2]);
+    }
+
+    /// Databases without the distribution column are migrated, their runs taken as uniform
+    #[test]
+    fn test_result_store_migration() {
+        let dir = std::env::temp_dir().join("mult_polynomial_store_migration_test");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let database = dir.join("results.db");
+        let old_schema = SCHEMA
+            .replace("    distribution TEXT NOT NULL DEFAULT 'uniform',\n", "")
+            .replace("distribution, ", "");
+        let connection = Connection::open(&database).unwrap();
+        connection.execute_batch(&old_schema).unwrap();
+        connection
+            .execute_batch(
+                "INSERT INTO sessions (machine, os, arch, seed, threads, crate_version)
+                 VALUES ('old', 'linux', 'x86_64', '1', 1, '0.1.0');
+                 INSERT INTO runs (session, size, other_size, aspect_ratio, algorithm, run, seconds)
+                 VALUES (1, 8, 8, 1, 'naive_time', 0, 0.5);",
+            )
+            .unwrap();
+        drop(connection);
+
+        let config = BenchmarkConfig {
+            sizes: vec![8],
+            distributions: vec![InputDistribution::Gaussian],
+            algorithms: vec![PolynomialMultAlg::Naive],
+            output_dir: dir.clone(),
+            database: Some(database.clone()),
+            seed: Some(1),
+            ..BenchmarkConfig::default()
+        };
+        run_comprehensive_benchmark(&config, &|_| 2).unwrap();
+        let store = ResultStore::open(&database).unwrap();
+        let distributions: Vec<(String, i64)> = (store.connection())
+            .prepare("SELECT distribution, count(*) FROM all_runs GROUP BY 1 ORDER BY 1")
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            distributions,
+            [("gaussian".to_string(), 2), ("uniform".to_string(), 1)]
+        );
+    }
+}
@@ -0,0 +1,180 @@
+//! Out-of-core multiplication of polynomials stored in binary files
+
+use crate::polynomial::multiply_impl;
+use crate::serialization::{read_binary_header, write_binary_header};
+use crate::{PolyError, PolynomialMultAlg};
+use std::io::{BufWriter, Read, Seek, SeekFrom, Write};
+
+/// Binary polynomial file read block by block
+struct BlockReader<R> {
+    reader: R,
+    /// Byte offset of the first coefficient
+    data_start: u64,
+    /// Number of coefficients
+    len: u64,
+    /// Raw bytes of the last block read
+    bytes: Vec<u8>,
+}
+
+impl<R: Read + Seek> BlockReader<R> {
+    fn new(mut reader: R) -> Result<Self, PolyError> {
+        let len = read_binary_header(&mut reader)?;
+        let data_start = reader.stream_position()?;
+        Ok(BlockReader {
+            reader,
+            data_start,
+            len,
+            bytes: Vec::new(),
+        })
+    }
+
+    /// Read the coefficients of block `index` into `buffer`
+    fn read_block(
+        &mut self,
+        index: u64,
+        block_size: u64,
+        buffer: &mut Vec<f64>,
+    ) -> Result<(), PolyError> {
+        let start = index * block_size;
+        let end = (start + block_size).min(self.len);
+        self.reader
+            .seek(SeekFrom::Start(self.data_start + 8 * start))?;
+        self.bytes.resize(8 * (end - start) as usize, 0);
+        self.reader.read_exact(&mut self.bytes)?;
+        buffer.clear();
+        buffer.extend(
+            (self.bytes.chunks_exact(8))
+                .map(|word| f64::from_le_bytes(word.try_into().expect("chunks have eight bytes"))),
+        );
+        Ok(())
+    }
+}
+
+/// Multiply two polynomials stored in the binary format without loading them into memory
+///
+/// Both operands are read in blocks of `block_size` coefficients; every pair of blocks is
+/// multiplied with `algorithm`, and the product is written to `out` in the binary format
+/// one block at a time through a buffer, so only O(`block_size`) coefficients are held in memory.
+/// The product is written with all `n + m - 1` coefficients, including trailing zeros.
+/// Returns the number of coefficients written.
+/// ## Example
+/// ```
+/// use mult_polynomial::{multiply_streaming, Polynomial, PolynomialMultAlg};
+/// use std::io::Cursor;
+/// let p = Polynomial::random(-10.0, 10.0, 1000);
+/// let mut file = Vec::new();
+/// p.write_binary(&mut file).unwrap();
+///
+/// let mut out = Vec::new();
+/// let alg = PolynomialMultAlg::Thresholded(32);
+/// multiply_streaming(Cursor::new(&file), Cursor::new(&file), &mut out, 256, alg).unwrap();
+/// let product = Polynomial::read_binary(out.as_slice()).unwrap();
+/// assert_eq!(product.coeffs.len(), 1999);
+/// ```
+pub fn multiply_streaming<A, B, W>(
+    a: A,
+    b: B,
+    out: W,
+    block_size: usize,
+    algorithm: PolynomialMultAlg,
+) -> Result<u64, PolyError>
+where
+    A: Read + Seek,
+    B: Read + Seek,
+    W: Write,
+{
+    assert!(block_size > 0, "block size must be positive");
+    let mut out = BufWriter::new(out);
+    let mut a = BlockReader::new(a)?;
+    let mut b = BlockReader::new(b)?;
+    if a.len == 0 || b.len == 0 {
+        write_binary_header(&mut out, 0)?;
+        return Ok(0);
+    }
+
+    let len = a.len + b.len - 1;
+    write_binary_header(&mut out, len)?;
+
+    let block = block_size as u64;
+    let a_blocks = a.len.div_ceil(block);
+    let b_blocks = b.len.div_ceil(block);
+    let (mut a_block, mut b_block) = (Vec::new(), Vec::new());
+    // upper half of the previous diagonal, overlapping the current output block
+    let mut carry = vec![0.0; block_size];
+    let mut written = 0;
+
+    // Output block d only receives products of blocks a_i · b_j with i + j ∈ {d - 1, d}
+    for d in 0..a_blocks + b_blocks - 1 {
+        let mut diagonal = vec![0.0; 2 * block_size];
+        for i in d.saturating_sub(b_blocks - 1)..=d.min(a_blocks - 1) {
+            a.read_block(i, block, &mut a_block)?;
+            b.read_block(d - i, block, &mut b_block)?;
+            let product = multiply_impl(&a_block, &b_block, algorithm);
+            for (s, p) in diagonal.iter_mut().zip(&product) {
+                *s += p;
+            }
+        }
+
+        let (low, high) = diagonal.split_at(block_size);
+        for (c, l) in carry.iter().zip(low) {
+            if written == len {
+                break;
+            }
+            out.write_all(&(c + l).to_le_bytes())?;
+            written += 1;
+        }
+        carry.copy_from_slice(high);
+    }
+    for c in &carry {
+        if written == len {
+            break;
+        }
+        out.write_all(&c.to_le_bytes())?;
+        written += 1;
+    }
+    out.flush()?;
+
+    Ok(written)
+}
+
+#[cfg(all(test, feature = "thread_rng"))]
+mod tests {
+    use super::*;
+    use crate::Polynomial;
+    use std::io::Cursor;
+
+    /// Test streaming products against in-memory products
+    #[test]
+    fn test_multiply_streaming() {
+        for (n, m, block_size) in [
+            (1000, 700, 128),
+            (50, 1000, 64),
+            (300, 300, 1000),
+            (1, 1, 1),
+        ] {
+            let p1 = Polynomial::random(-10.0, 10.0, n);
+            let p2 = Polynomial::random(-10.0, 10.0, m);
+            let (mut f1, mut f2) = (Vec::new(), Vec::new());
+            p1.write_binary(&mut f1).unwrap();
+            p2.write_binary(&mut f2).unwrap();
+
+            let mut out = Vec::new();
+            let written = multiply_streaming(
+                Cursor::new(&f1),
+                Cursor::new(&f2),
+                &mut out,
+                block_size,
+                PolynomialMultAlg::Thresholded(16),
+            )
+            .unwrap();
+            assert_eq!(written, (n + m - 1) as u64);
+
+            let product = Polynomial::read_binary(out.as_slice()).unwrap();
+            let expected = p1.multiply_naive(&p2);
+            assert_eq!(product.coeffs.len(), expected.coeffs.len());
+            for (r, e) in product.coeffs.iter().zip(&expected.coeffs) {
+                assert!((r - e).abs() < 1e-8);
+            }
+        }
+    }
+}
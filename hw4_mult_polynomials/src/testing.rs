@@ -0,0 +1,368 @@
+//! Helpers for testing multiplication algorithms against each other
+//!
+//! [`assert_products_agree`] compares every algorithm of the crate on one pair of operands.
+//! With the `proptest` feature, [`arb_polynomial`] and [`arb_polynomial_pair`] generate
+//! operands for property-based tests, including empty, zero and very unbalanced ones:
+//! ```ignore
+//! use mult_polynomial::testing::{arb_polynomial_pair, assert_products_agree};
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     #[test]
+//!     fn toom3_matches_naive((a, b) in arb_polynomial_pair(500)) {
+//!         assert_products_agree(&a, &b, 1e-10);
+//!     }
+//! }
+//! ```
+//!
+//! [`cross_check`] sweeps every operand length up to a bound over pathological [`Shape`]s
+//! and records the worst discrepancy between every pair of algorithms.
+
+#[cfg(feature = "std")]
+use crate::Normalization;
+use crate::{HybridConfig, Polynomial, PolynomialMultAlg};
+#[cfg(feature = "std")]
+use alloc::{format, string::String, vec, vec::Vec};
+
+/// Algorithms compared by [`assert_products_agree`]: the naive ones, Cook-Tooms,
+/// and thresholds small enough to recurse on small inputs
+pub const ALGORITHMS: [PolynomialMultAlg; 7] = [
+    PolynomialMultAlg::Naive,
+    PolynomialMultAlg::NaiveCompensated,
+    PolynomialMultAlg::CookTooms,
+    PolynomialMultAlg::Thresholded(8),
+    PolynomialMultAlg::Thresholded(17),
+    PolynomialMultAlg::Thresholded(PolynomialMultAlg::AUTO_THRESHOLD),
+    PolynomialMultAlg::Hybrid(HybridConfig {
+        toom3_threshold: 24,
+        karatsuba_threshold: 4,
+    }),
+];
+
+/// Assert that every algorithm in [`ALGORITHMS`] computes the product of `a` and `b`
+///
+/// The products are compared with the compensated naive product, and may deviate by
+/// `tolerance` times the largest coefficient of `|a|·|b|` (the product of the coefficient-wise
+/// absolute values). The comparison is norm-wise because Toom-3 spreads rounding errors
+/// across coefficients, so a coefficient that cancels to zero may still carry an error
+/// on the order of its neighbours.
+/// Panics with the algorithm and coefficient index on the first mismatch.
+pub fn assert_products_agree(a: &Polynomial, b: &Polynomial, tolerance: f64) {
+    let reference = a.multiply_naive_compensated(b);
+    let abs = |p: &Polynomial| Polynomial::new(p.coeffs.iter().map(|c| c.abs()).collect());
+    let scale = (abs(a).multiply_naive(&abs(b)).coeffs.iter()).fold(0.0, |m: f64, &c| m.max(c));
+
+    for algorithm in ALGORITHMS {
+        let product = a.multiply(b, algorithm);
+        let len = product.coeffs.len().max(reference.coeffs.len());
+        for k in 0..len {
+            let got = product.coeffs.get(k).copied().unwrap_or(0.0);
+            let expected = reference.coeffs.get(k).copied().unwrap_or(0.0);
+            assert!(
+                (got - expected).abs() <= tolerance * scale,
+                "{:?} differs at coefficient {} of {} x {} coefficients: {} vs {}",
+                algorithm,
+                k,
+                a.coeffs.len(),
+                b.coeffs.len(),
+                got,
+                expected
+            );
+        }
+    }
+}
+
+/// Coefficient pattern of the operands swept by [`cross_check`]
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shape {
+    /// Uniform in `[-1, 1]`
+    Random,
+    /// All coefficients zero, kept at full length
+    Zeros,
+    /// Uniform in `[-1e100, 1e100]`, whose products are close to overflowing in the sums of Toom-3
+    Huge,
+    /// Uniform in `[-1e-100, 1e-100]`, whose products are close to underflowing
+    Tiny,
+    /// Alternately about `1e8` and `1e-8`, so small coefficients sit next to large ones
+    Mixed,
+}
+
+#[cfg(feature = "std")]
+impl Shape {
+    /// Every shape, in sweep order
+    pub const ALL: [Shape; 5] = [
+        Shape::Random,
+        Shape::Zeros,
+        Shape::Huge,
+        Shape::Tiny,
+        Shape::Mixed,
+    ];
+
+    /// Lower-case name used in reports
+    pub fn name(self) -> &'static str {
+        match self {
+            Shape::Random => "random",
+            Shape::Zeros => "zeros",
+            Shape::Huge => "huge",
+            Shape::Tiny => "tiny",
+            Shape::Mixed => "mixed",
+        }
+    }
+
+    /// Polynomial with exactly `len` coefficients of this shape
+    fn generate<R: rand::Rng>(self, len: usize, rng: &mut R) -> Polynomial {
+        let coeffs = (0..len)
+            .map(|i| {
+                let x: f64 = rng.gen_range(-1.0..=1.0);
+                match self {
+                    Shape::Random => x,
+                    Shape::Zeros => 0.0,
+                    Shape::Huge => x * 1e100,
+                    Shape::Tiny => x * 1e-100,
+                    Shape::Mixed if i % 2 == 0 => x * 1e8,
+                    Shape::Mixed => x * 1e-8,
+                }
+            })
+            .collect();
+        Polynomial::with_normalization(coeffs, Normalization::Keep)
+    }
+}
+
+/// Operand lengths and shape of one case of [`cross_check`]
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Case {
+    /// Length of the first operand
+    pub n: usize,
+    /// Length of the second operand
+    pub m: usize,
+    /// Coefficient pattern of both operands
+    pub shape: Shape,
+}
+
+/// Worst discrepancies between every pair of [`ALGORITHMS`], from [`cross_check`]
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct CrossCheck {
+    /// Largest discrepancy between algorithms `i` and `j` at `worst[i][j]`,
+    /// relative to the largest coefficient of `|a|·|b|` (absolute if that is zero)
+    pub worst: Vec<Vec<f64>>,
+    /// Case of each entry of `worst`, `None` on the diagonal or if nothing was swept
+    pub worst_case: Vec<Vec<Option<Case>>>,
+    /// Number of operand pairs multiplied by every algorithm
+    pub cases: usize,
+}
+
+#[cfg(feature = "std")]
+impl CrossCheck {
+    /// Whether every pair of algorithms agrees within `tolerance` (NaN never does)
+    pub fn passed(&self, tolerance: f64) -> bool {
+        self.worst.iter().flatten().all(|&d| d <= tolerance)
+    }
+
+    /// Largest discrepancy with its algorithm pair `(i, j)` and case
+    pub fn worst_pair(&self) -> Option<(usize, usize, f64, Case)> {
+        let mut worst: Option<(usize, usize, f64, Case)> = None;
+        for (i, row) in self.worst.iter().enumerate() {
+            for (j, &d) in row.iter().enumerate().skip(i + 1) {
+                let Some(case) = self.worst_case[i][j] else {
+                    continue;
+                };
+                if worst.is_none_or(|(_, _, w, _)| d.is_nan() || d > w) {
+                    worst = Some((i, j, d, case));
+                }
+            }
+        }
+        worst
+    }
+
+    /// Pass/fail matrix of the algorithm pairs at `tolerance`, followed by the worst case
+    pub fn report(&self, tolerance: f64) -> String {
+        let mut out = String::new();
+        for (i, algorithm) in ALGORITHMS.iter().enumerate() {
+            out += &format!("[{}] {}\n", i, algorithm);
+        }
+        out += "   ";
+        for j in 0..ALGORITHMS.len() {
+            out += &format!(" {:>4}", format!("[{}]", j));
+        }
+        out += "\n";
+        for (i, row) in self.worst.iter().enumerate() {
+            out += &format!("{:>3}", format!("[{}]", i));
+            for (j, &d) in row.iter().enumerate() {
+                let cell = match () {
+                    _ if i == j => "-",
+                    _ if d <= tolerance => "ok",
+                    _ => "FAIL",
+                };
+                out += &format!(" {:>4}", cell);
+            }
+            out += "\n";
+        }
+        match self.worst_pair() {
+            Some((i, j, d, case)) => {
+                out += &format!(
+                    "worst discrepancy {:e} between {} and {} at {} x {} {} coefficients\n",
+                    d,
+                    ALGORITHMS[i],
+                    ALGORITHMS[j],
+                    case.n,
+                    case.m,
+                    case.shape.name()
+                );
+            }
+            None => out += "no algorithm pairs compared\n",
+        }
+        out
+    }
+}
+
+/// Cross-check every pair of [`ALGORITHMS`] on all operand lengths up to `max_len`
+///
+/// For every length `n` in `0..=max_len` the first operand is multiplied by operands of
+/// lengths `n`, `1` and `⌈n/4⌉`, for every [`Shape`], with coefficients drawn from `seed`.
+/// Operands keep their trailing zeros, so the all-zero shapes run at their full length.
+/// Discrepancies are norm-wise as in [`assert_products_agree`].
+/// ## Example
+/// ```
+/// use mult_polynomial::testing::cross_check;
+/// let check = cross_check(20, 42);
+/// assert!(check.passed(1e-10));
+/// ```
+#[cfg(feature = "std")]
+pub fn cross_check(max_len: usize, seed: u64) -> CrossCheck {
+    use rand::{rngs::StdRng, SeedableRng};
+    let count = ALGORITHMS.len();
+    let mut check = CrossCheck {
+        worst: vec![vec![0.0; count]; count],
+        worst_case: vec![vec![None; count]; count],
+        cases: 0,
+    };
+    let mut rng = StdRng::seed_from_u64(seed);
+    for n in 0..=max_len {
+        let mut lengths = vec![n, 1, n.div_ceil(4)];
+        lengths.sort_unstable();
+        lengths.dedup();
+        for m in lengths {
+            for shape in Shape::ALL {
+                let a = shape.generate(n, &mut rng);
+                let b = shape.generate(m, &mut rng);
+                let case = Case { n, m, shape };
+                check.record(&a, &b, case);
+            }
+        }
+    }
+    check
+}
+
+#[cfg(feature = "std")]
+impl CrossCheck {
+    /// Multiply `a` and `b` with every algorithm and update the worst discrepancies
+    fn record(&mut self, a: &Polynomial, b: &Polynomial, case: Case) {
+        let abs = |p: &Polynomial| {
+            let coeffs = p.coeffs.iter().map(|c| c.abs()).collect();
+            Polynomial::with_normalization(coeffs, Normalization::Keep)
+        };
+        let scale = (abs(a).multiply_naive(&abs(b)).coeffs.iter()).fold(0.0, |m: f64, &c| m.max(c));
+        let products: Vec<Polynomial> = ALGORITHMS.iter().map(|&alg| a.multiply(b, alg)).collect();
+        for i in 0..products.len() {
+            for j in i + 1..products.len() {
+                let (x, y) = (&products[i].coeffs, &products[j].coeffs);
+                let deviation = (0..x.len().max(y.len())).fold(0.0, |m: f64, k| {
+                    let d =
+                        (x.get(k).copied().unwrap_or(0.0) - y.get(k).copied().unwrap_or(0.0)).abs();
+                    if d.is_nan() || m.is_nan() {
+                        f64::NAN
+                    } else {
+                        m.max(d)
+                    }
+                });
+                let d = if scale > 0.0 {
+                    deviation / scale
+                } else {
+                    deviation
+                };
+                // a NaN stays the worst case once found
+                let worst = self.worst[i][j];
+                let first = self.worst_case[i][j].is_none();
+                if !worst.is_nan() && (first || d.is_nan() || d > worst) {
+                    self.worst[i][j] = d;
+                    self.worst[j][i] = d;
+                    self.worst_case[i][j] = Some(case);
+                    self.worst_case[j][i] = Some(case);
+                }
+            }
+        }
+        self.cases += 1;
+    }
+}
+
+/// Strategy for polynomials with up to `max_len` coefficients in `[-1e3, 1e3]`
+///
+/// A fifth of the coefficients are exactly zero, and trailing zeros are trimmed
+/// as by [`Polynomial::new`], so degenerate and empty polynomials are common.
+#[cfg(any(test, feature = "proptest"))]
+pub fn arb_polynomial(max_len: usize) -> impl proptest::strategy::Strategy<Value = Polynomial> {
+    use proptest::prelude::*;
+    let coeff = prop_oneof![1 => Just(0.0), 4 => -1e3..1e3f64];
+    proptest::collection::vec(coeff, 0..=max_len).prop_map(Polynomial::new)
+}
+
+/// Strategy for pairs of polynomials with up to `max_len` coefficients each,
+/// where two thirds of the pairs have very different lengths
+#[cfg(any(test, feature = "proptest"))]
+pub fn arb_polynomial_pair(
+    max_len: usize,
+) -> impl proptest::strategy::Strategy<Value = (Polynomial, Polynomial)> {
+    use proptest::prelude::*;
+    let short_len = max_len / 20 + 1;
+    prop_oneof![
+        (arb_polynomial(max_len), arb_polynomial(max_len)),
+        (arb_polynomial(short_len), arb_polynomial(max_len)),
+        (arb_polynomial(max_len), arb_polynomial(short_len)),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use proptest::prelude::*;
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(1000))]
+
+        /// All algorithms agree on random shapes, including empty and unbalanced operands
+        #[test]
+        fn test_products_agree((a, b) in arb_polynomial_pair(300)) {
+            assert_products_agree(&a, &b, 1e-10);
+        }
+    }
+
+    /// Small integer coefficients are multiplied exactly by every algorithm
+    #[test]
+    fn test_exact_integer_products() {
+        let a = Polynomial::new((0..100).map(|i| (i % 7) as f64 - 3.0).collect());
+        let b = Polynomial::new((0..40).map(|i| (i % 5) as f64).collect());
+        assert_products_agree(&a, &b, 0.0);
+        assert_products_agree(&a, &Polynomial::new(vec![]), 0.0);
+    }
+
+    /// The sweep covers empty and pathological operands and every algorithm pair agrees
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_cross_check() {
+        let check = cross_check(60, 1);
+        // one length pair for n = 1, two for n = 0 and n ≤ 4, three above
+        assert_eq!(check.cases, 5 * (2 + 1 + 2 * 3 + 3 * 56));
+        assert!(check.passed(1e-10), "{}", check.report(1e-10));
+        for (i, row) in check.worst.iter().enumerate() {
+            assert_eq!(row[i], 0.0);
+            assert!(row.iter().zip(&check.worst).all(|(&d, col)| col[i] == d));
+        }
+        let report = check.report(1e-10);
+        assert!(report.contains("worst discrepancy") && !report.contains("FAIL"));
+        assert!(!check.passed(-1.0));
+    }
+}
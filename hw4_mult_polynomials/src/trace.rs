@@ -0,0 +1,61 @@
+//! Spans for `tracing` subscribers, compiled out without the `tracing` feature
+
+/// Enter a span at the given level until the end of the enclosing block,
+/// e.g. `span!(TRACE, "toom3", n = a.len())`; expands to nothing without the `tracing` feature
+macro_rules! span {
+    ($level:ident, $name:literal $(, $field:ident = $value:expr)* $(,)?) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::span!(tracing::Level::$level, $name $(, $field = $value)*).entered();
+        // the fields still count as used
+        #[cfg(not(feature = "tracing"))]
+        let _ = ($(&$value,)*);
+    };
+}
+pub(crate) use span;
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use crate::{thresholded_multiply_with_stats, Polynomial};
+    use std::sync::{Arc, Mutex};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    /// Subscriber recording the names of the spans created
+    #[derive(Default)]
+    struct Recorder {
+        names: Arc<Mutex<Vec<&'static str>>>,
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut names = self.names.lock().unwrap();
+            names.push(span.metadata().name());
+            Id::from_u64(names.len() as u64)
+        }
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+        fn event(&self, _: &Event<'_>) {}
+        fn enter(&self, _: &Id) {}
+        fn exit(&self, _: &Id) {}
+    }
+
+    /// Every Toom-3 level and every base case of a product opens one span
+    #[test]
+    fn test_spans() {
+        let recorder = Recorder::default();
+        let names = Arc::clone(&recorder.names);
+        let p1 = Polynomial::random(-1.0, 1.0, 500);
+        let p2 = Polynomial::random(-1.0, 1.0, 120);
+        let (_, stats) = tracing::subscriber::with_default(recorder, || {
+            thresholded_multiply_with_stats(&p1.coeffs, &p2.coeffs, 16)
+        });
+        let names = names.lock().unwrap();
+        let count = |name: &str| names.iter().filter(|&&n| n == name).count();
+        assert!(stats.block_splits > 0 && stats.max_depth > 1);
+        assert_eq!(count("base_case"), stats.base_cases);
+        assert_eq!(count("toom3"), stats.calls - stats.base_cases);
+    }
+}
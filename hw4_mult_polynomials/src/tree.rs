@@ -0,0 +1,228 @@
+//! Recursion trees of the thresholded multiplication, exported as Graphviz DOT or JSON
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+/// Algorithm applied at a node of the recursion tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeAlgorithm {
+    /// Toom-3 split into the 5 products at `0, 1, -1, 2, ∞`
+    Toom3,
+    /// Very unbalanced operands sliced into blocks of the shorter one's length
+    Blocked,
+    /// Naive product below the threshold (a leaf)
+    Naive,
+    /// Karatsuba product below the Toom-3 threshold of the hybrid algorithm (a leaf)
+    Karatsuba,
+}
+
+impl NodeAlgorithm {
+    /// Lowercase name used in the exports
+    pub fn name(self) -> &'static str {
+        match self {
+            NodeAlgorithm::Toom3 => "toom3",
+            NodeAlgorithm::Blocked => "blocked",
+            NodeAlgorithm::Naive => "naive",
+            NodeAlgorithm::Karatsuba => "karatsuba",
+        }
+    }
+}
+
+/// Sub-product of the recursion with the lengths of its operands
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecursionNode {
+    /// Number of coefficients of the first operand
+    pub n: usize,
+    /// Number of coefficients of the second operand
+    pub m: usize,
+    /// Algorithm applied to this product
+    pub algorithm: NodeAlgorithm,
+    /// Number of levels above this node
+    pub depth: usize,
+    /// Indices of the sub-products in [`RecursionTree::nodes`], in the order they are computed
+    pub children: Vec<usize>,
+}
+
+/// Recursion tree recorded by [`crate::thresholded_multiply_with_tree`]
+///
+/// The nodes are stored in the order of the calls, the root first.
+/// ## Example
+/// ```
+/// use mult_polynomial::{thresholded_multiply_with_tree, NodeAlgorithm, Polynomial};
+/// let p = Polynomial::random(-1.0, 1.0, 30);
+/// let (_, tree) = thresholded_multiply_with_tree(&p.coeffs, &p.coeffs, 16);
+/// // one Toom-3 split into 5 products of 10 coefficients, computed naively
+/// assert_eq!(tree.nodes.len(), 6);
+/// assert_eq!(tree.nodes[0].algorithm, NodeAlgorithm::Toom3);
+/// assert!(tree.to_dot().contains("n0 -> n1 [label=\"x = 0\"]"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RecursionTree {
+    /// Every node of the tree, the root first
+    pub nodes: Vec<RecursionNode>,
+}
+
+impl RecursionTree {
+    /// Add a node below `parent` (none for the root), returning its index
+    pub(crate) fn push(
+        &mut self,
+        parent: Option<usize>,
+        n: usize,
+        m: usize,
+        algorithm: NodeAlgorithm,
+    ) -> usize {
+        let index = self.nodes.len();
+        let depth = parent.map_or(0, |parent| self.nodes[parent].depth + 1);
+        if let Some(parent) = parent {
+            self.nodes[parent].children.push(index);
+        }
+        self.nodes.push(RecursionNode {
+            n,
+            m,
+            algorithm,
+            depth,
+            children: Vec::new(),
+        });
+        index
+    }
+
+    /// Label of the edge to the `k`-th child of `parent`:
+    /// the evaluation point of a Toom-3 split or the index of a block
+    fn edge_label(&self, parent: usize, k: usize) -> String {
+        match self.nodes[parent].algorithm {
+            NodeAlgorithm::Toom3 => {
+                let point = ["0", "1", "-1", "2", "∞"][k];
+                format!("x = {}", point)
+            }
+            _ => format!("block {}", k),
+        }
+    }
+
+    /// Graphviz DOT description of the tree, e.g. for `dot -Tsvg`
+    ///
+    /// Every node shows its algorithm and operand lengths, every edge the evaluation point
+    /// or block it stands for.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph recursion {\n    node [shape=box];\n");
+        for (i, node) in self.nodes.iter().enumerate() {
+            let _ = writeln!(
+                dot,
+                "    n{} [label=\"{}\\n{} × {}\"];",
+                i,
+                node.algorithm.name(),
+                node.n,
+                node.m
+            );
+        }
+        for (i, node) in self.nodes.iter().enumerate() {
+            for (k, &child) in node.children.iter().enumerate() {
+                let label = self.edge_label(i, k);
+                let _ = writeln!(dot, "    n{} -> n{} [label=\"{}\"];", i, child, label);
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Nested JSON description of the tree,
+    /// each node as `{"algorithm", "n", "m", "depth", "edge", "children"}`
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        if !self.nodes.is_empty() {
+            self.write_json(&mut json, 0, None);
+        }
+        json
+    }
+
+    fn write_json(&self, json: &mut String, index: usize, edge: Option<String>) {
+        let node = &self.nodes[index];
+        let _ = write!(
+            json,
+            "{{\"algorithm\":\"{}\",\"n\":{},\"m\":{},\"depth\":{},",
+            node.algorithm.name(),
+            node.n,
+            node.m,
+            node.depth
+        );
+        if let Some(edge) = edge {
+            let _ = write!(json, "\"edge\":\"{}\",", edge);
+        }
+        json.push_str("\"children\":[");
+        for (k, &child) in node.children.iter().enumerate() {
+            if k > 0 {
+                json.push(',');
+            }
+            self.write_json(json, child, Some(self.edge_label(index, k)));
+        }
+        json.push_str("]}");
+    }
+}
+
+#[cfg(all(test, feature = "thread_rng"))]
+mod tests {
+    use super::*;
+    use crate::{
+        thresholded_multiply_impl, thresholded_multiply_with_stats, thresholded_multiply_with_tree,
+        Polynomial,
+    };
+
+    /// The tree has a node per call of the recursion and leaves exactly at the base cases
+    #[test]
+    fn test_recursion_tree() {
+        let p1 = Polynomial::random(-1.0, 1.0, 500);
+        let p2 = Polynomial::random(-1.0, 1.0, 120);
+        let (product, tree) = thresholded_multiply_with_tree(&p1.coeffs, &p2.coeffs, 16);
+        assert_eq!(
+            product,
+            thresholded_multiply_impl(&p1.coeffs, &p2.coeffs, 16)
+        );
+        let (_, stats) = thresholded_multiply_with_stats(&p1.coeffs, &p2.coeffs, 16);
+        let count = |algorithm| {
+            (tree.nodes.iter())
+                .filter(|node| node.algorithm == algorithm)
+                .count()
+        };
+        assert_eq!(tree.nodes.len(), stats.calls);
+        assert_eq!(count(NodeAlgorithm::Naive), stats.base_cases);
+        assert_eq!(count(NodeAlgorithm::Blocked), stats.block_splits);
+        let root = &tree.nodes[0];
+        assert_eq!(
+            (root.n, root.m, root.algorithm),
+            (500, 120, NodeAlgorithm::Blocked)
+        );
+        assert_eq!(root.children.len(), 5);
+        for node in &tree.nodes {
+            let expected = match node.algorithm {
+                NodeAlgorithm::Toom3 => 5,
+                NodeAlgorithm::Blocked => node.n.max(node.m).div_ceil(node.n.min(node.m)),
+                _ => 0,
+            };
+            assert_eq!(node.children.len(), expected);
+            for &child in &node.children {
+                assert_eq!(tree.nodes[child].depth, node.depth + 1);
+            }
+        }
+
+        // every node is exported once, every child with the edge it hangs on
+        let dot = tree.to_dot();
+        assert_eq!(dot.matches(" -> ").count(), tree.nodes.len() - 1);
+        assert!(dot.contains("n0 [label=\"blocked\\n500 × 120\"]"));
+        assert!(dot.contains("[label=\"x = ∞\"]") && dot.contains("[label=\"block 4\"]"));
+        let json = tree.to_json();
+        assert_eq!(json.matches("\"algorithm\"").count(), tree.nodes.len());
+        assert_eq!(json.matches("\"edge\"").count(), tree.nodes.len() - 1);
+        assert!(json.starts_with(
+            "{\"algorithm\":\"blocked\",\"n\":500,\"m\":120,\"depth\":0,\"children\":[{"
+        ));
+
+        // below the threshold the root is the only node
+        let (_, tree) = thresholded_multiply_with_tree(&p2.coeffs[..10], &p2.coeffs, 16);
+        assert_eq!(tree.nodes.len(), 1);
+        assert_eq!(
+            tree.to_json(),
+            "{\"algorithm\":\"naive\",\"n\":10,\"m\":120,\"depth\":0,\"children\":[]}"
+        );
+    }
+}
@@ -0,0 +1,358 @@
+//! Search for the fastest threshold of the thresholded algorithm
+//!
+//! Requires the `std` feature. Instead of timing every power of two, [`tune_threshold`] runs a
+//! golden-section search on the logarithm of the threshold (or times a geometric grid),
+//! with every candidate timed on the same random inputs of each size.
+
+use crate::benchmark::{benchmark_single_warm, Aggregation};
+use crate::serialization::text_header;
+use crate::{PolyError, Polynomial, PolynomialMultAlg};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::Path;
+
+/// Strategy choosing the thresholds to time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Search {
+    /// Golden-section search on `ln(threshold)`, assuming a single minimum of the score
+    #[default]
+    GoldenSection,
+    /// `points` geometrically spaced thresholds, including both bounds
+    Grid {
+        /// Number of thresholds
+        points: usize,
+    },
+}
+
+/// Settings of the threshold search
+#[derive(Debug, Clone)]
+pub struct TuneConfig {
+    /// Input sizes (number of coefficients of both operands) the threshold is tuned for
+    pub sizes: Vec<usize>,
+    /// Smallest threshold considered
+    pub min_threshold: usize,
+    /// Largest threshold considered
+    pub max_threshold: usize,
+    /// How the candidate thresholds are chosen
+    pub search: Search,
+    /// Timed runs of every threshold at every size, each on its own input pair
+    pub runs: usize,
+    /// Untimed multiplications before the first run of every threshold and size
+    pub warmup_runs: usize,
+    /// How the runs are combined into the time of a threshold at a size
+    pub aggregation: Aggregation,
+    /// Seed for the random inputs, chosen by the caller (e.g. from the operating system)
+    /// so that the search does not draw entropy itself
+    pub seed: u64,
+}
+
+impl Default for TuneConfig {
+    /// Golden-section search over thresholds 2 to 2^15 at sizes 1024, 4096 and 16384,
+    /// taking the fastest of 5 runs on the inputs of seed 0
+    fn default() -> Self {
+        TuneConfig {
+            sizes: vec![1024, 4096, 16384],
+            min_threshold: 2,
+            max_threshold: 1 << 15,
+            search: Search::GoldenSection,
+            runs: 5,
+            warmup_runs: 1,
+            aggregation: Aggregation::Min,
+            seed: 0,
+        }
+    }
+}
+
+impl TuneConfig {
+    /// Check the settings, failing with [`PolyError::InvalidArgument`] on no or zero sizes,
+    /// an empty threshold range, zero runs or grid points, or a trimmed fraction outside of `[0, 0.5)`
+    pub fn validate(&self) -> Result<(), PolyError> {
+        let invalid = |message: &str| Err(PolyError::InvalidArgument(message.to_string()));
+        if self.sizes.is_empty() || self.sizes.contains(&0) {
+            return invalid("sizes must be given and positive");
+        }
+        if self.min_threshold == 0 || self.min_threshold > self.max_threshold {
+            return invalid("thresholds must satisfy 1 <= min_threshold <= max_threshold");
+        }
+        if self.runs == 0 {
+            return invalid("number of runs must be positive");
+        }
+        if self.search == (Search::Grid { points: 0 }) {
+            return invalid("number of grid points must be positive");
+        }
+        if let Aggregation::TrimmedMean(fraction) = self.aggregation {
+            if !(0.0..0.5).contains(&fraction) {
+                return invalid("trimmed fraction must be in [0, 0.5)");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Timings of one threshold at every size
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThresholdTiming {
+    /// Threshold of the thresholded algorithm
+    pub threshold: usize,
+    /// Aggregated time in seconds at each size, in the order of [`TuneConfig::sizes`]
+    pub times: Vec<f64>,
+    /// Geometric mean of `times`, so that every size weighs the same
+    pub score: f64,
+}
+
+/// Outcome of the threshold search
+#[derive(Debug, Clone, PartialEq)]
+pub struct TuneResult {
+    /// Sizes the threshold was tuned for
+    pub sizes: Vec<usize>,
+    /// Every timed threshold, in increasing order
+    pub timings: Vec<ThresholdTiming>,
+    /// Threshold with the lowest score
+    pub best: usize,
+    /// Seed of the random inputs, [`TuneConfig::seed`]
+    pub seed: u64,
+}
+
+impl TuneResult {
+    /// Timings of the best threshold
+    pub fn best_timing(&self) -> &ThresholdTiming {
+        (self.timings.iter())
+            .find(|timing| timing.threshold == self.best)
+            .expect("the best threshold was timed")
+    }
+
+    /// Write the comparison table to a CSV file, one row per threshold with the time at every size,
+    /// the score and the score relative to the best one
+    pub fn write_csv(&self, path: impl AsRef<Path>) -> Result<(), PolyError> {
+        let file = std::fs::File::create(path)?;
+        let mut file = std::io::BufWriter::new(file);
+        writeln!(file, "{}", text_header("tune"))?;
+        write!(file, "threshold")?;
+        for size in &self.sizes {
+            write!(file, ",time_{}", size)?;
+        }
+        writeln!(file, ",score,relative")?;
+        let best = self.best_timing().score;
+        for timing in &self.timings {
+            write!(file, "{}", timing.threshold)?;
+            for time in &timing.times {
+                write!(file, ",{}", time)?;
+            }
+            writeln!(file, ",{},{}", timing.score, timing.score / best)?;
+        }
+        file.flush()?;
+        Ok(())
+    }
+}
+
+/// Random input pairs of every size, shared by all thresholds
+struct Inputs {
+    pairs: Vec<Vec<(Polynomial, Polynomial)>>,
+    seed: u64,
+}
+
+impl Inputs {
+    fn new(config: &TuneConfig) -> Inputs {
+        let seed = config.seed;
+        let mut rng = StdRng::seed_from_u64(seed);
+        let pairs = (config.sizes.iter())
+            .map(|&size| {
+                (0..config.runs)
+                    .map(|_| {
+                        let p1 = Polynomial::random_with_rng(-10.0, 10.0, size, &mut rng);
+                        let p2 = Polynomial::random_with_rng(-10.0, 10.0, size, &mut rng);
+                        (p1, p2)
+                    })
+                    .collect()
+            })
+            .collect();
+        Inputs { pairs, seed }
+    }
+
+    /// Time the thresholded algorithm with `threshold` at every size
+    fn time(&self, config: &TuneConfig, threshold: usize) -> ThresholdTiming {
+        let algorithm = PolynomialMultAlg::Thresholded(threshold);
+        let times: Vec<f64> = (self.pairs.iter())
+            .map(|pairs| {
+                let runs: Vec<f64> = (pairs.iter().enumerate())
+                    .map(|(run, (p1, p2))| {
+                        let warmup_runs = if run == 0 { config.warmup_runs } else { 0 };
+                        benchmark_single_warm(&algorithm, p1, p2, warmup_runs).as_secs_f64()
+                    })
+                    .collect();
+                config.aggregation.apply(&runs)
+            })
+            .collect();
+        let log_mean = times.iter().map(|t| t.ln()).sum::<f64>() / times.len() as f64;
+        ThresholdTiming {
+            threshold,
+            times,
+            score: log_mean.exp(),
+        }
+    }
+}
+
+/// Find the threshold of the thresholded algorithm with the lowest geometric mean time
+/// over the configured sizes
+///
+/// Every threshold is timed once (the golden-section search reuses earlier timings),
+/// and all of them are returned for comparison. Timing noise can mislead the golden-section
+/// search when the times are flat around the optimum, which the table makes visible.
+/// ## Example
+/// ```
+/// use mult_polynomial::tune::{tune_threshold, Search, TuneConfig};
+/// let config = TuneConfig {
+///     sizes: vec![64, 128],
+///     min_threshold: 4,
+///     max_threshold: 64,
+///     search: Search::Grid { points: 5 },
+///     runs: 1,
+///     seed: 1,
+///     ..TuneConfig::default()
+/// };
+/// let result = tune_threshold(&config).unwrap();
+/// let thresholds: Vec<usize> = result.timings.iter().map(|t| t.threshold).collect();
+/// assert_eq!(thresholds, vec![4, 8, 16, 32, 64]);
+/// assert!(thresholds.contains(&result.best));
+/// ```
+pub fn tune_threshold(config: &TuneConfig) -> Result<TuneResult, PolyError> {
+    config.validate()?;
+    let inputs = Inputs::new(config);
+    let mut timings: BTreeMap<usize, ThresholdTiming> = BTreeMap::new();
+    let (min, max) = (config.min_threshold, config.max_threshold);
+    let mut score = |threshold: usize| {
+        let timing = (timings.entry(threshold)).or_insert_with(|| inputs.time(config, threshold));
+        timing.score
+    };
+    let (low, high) = ((min as f64).ln(), (max as f64).ln());
+    let threshold = |x: f64| (x.exp().round() as usize).clamp(min, max);
+    match config.search {
+        Search::Grid { points } => {
+            for i in 0..points {
+                let fraction = if points > 1 {
+                    i as f64 / (points - 1) as f64
+                } else {
+                    0.0
+                };
+                score(threshold(low + fraction * (high - low)));
+            }
+        }
+        Search::GoldenSection => {
+            let ratio = (5f64.sqrt() - 1.0) / 2.0;
+            let (mut low, mut high) = (low, high);
+            let mut x1 = high - ratio * (high - low);
+            let mut x2 = low + ratio * (high - low);
+            let (mut f1, mut f2) = (score(threshold(x1)), score(threshold(x2)));
+            // narrow the bracket until it holds at most three thresholds, then time them all
+            while threshold(high) - threshold(low) > 2 {
+                if f1 <= f2 {
+                    (high, x2, f2) = (x2, x1, f1);
+                    x1 = high - ratio * (high - low);
+                    f1 = score(threshold(x1));
+                } else {
+                    (low, x1, f1) = (x1, x2, f2);
+                    x2 = low + ratio * (high - low);
+                    f2 = score(threshold(x2));
+                }
+            }
+            for t in threshold(low)..=threshold(high) {
+                score(t);
+            }
+        }
+    }
+    let timings: Vec<ThresholdTiming> = timings.into_values().collect();
+    let best = (timings.iter())
+        .min_by(|a, b| a.score.total_cmp(&b.score))
+        .map(|timing| timing.threshold)
+        .expect("at least one threshold is timed");
+    Ok(TuneResult {
+        sizes: config.sizes.clone(),
+        timings,
+        best,
+        seed: inputs.seed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Small seeded search settings
+    fn config(search: Search) -> TuneConfig {
+        TuneConfig {
+            sizes: vec![32, 100],
+            min_threshold: 2,
+            max_threshold: 100,
+            search,
+            runs: 2,
+            seed: 7,
+            ..TuneConfig::default()
+        }
+    }
+
+    /// The golden-section search narrows down to neighbouring thresholds
+    /// and the grid times every point once
+    #[test]
+    fn test_tune_threshold() {
+        let result = tune_threshold(&config(Search::GoldenSection)).unwrap();
+        let thresholds: Vec<usize> = result.timings.iter().map(|t| t.threshold).collect();
+        assert!(thresholds.windows(2).all(|w| w[0] < w[1]));
+        assert!(thresholds.iter().all(|t| (2..=100).contains(t)));
+        // far fewer thresholds than a scan of the whole range
+        assert!(thresholds.len() < 20, "{:?}", thresholds);
+        let best = result.best_timing();
+        assert!(result.timings.iter().all(|t| t.score >= best.score));
+        for timing in &result.timings {
+            assert_eq!(timing.times.len(), 2);
+            let geometric_mean = (timing.times[0] * timing.times[1]).sqrt();
+            assert!((timing.score - geometric_mean).abs() <= 1e-9 * geometric_mean);
+        }
+
+        let result = tune_threshold(&config(Search::Grid { points: 3 })).unwrap();
+        let thresholds: Vec<usize> = result.timings.iter().map(|t| t.threshold).collect();
+        assert_eq!(thresholds, vec![2, 14, 100]);
+
+        let path = std::env::temp_dir().join("mult_polynomial_tune_test.csv");
+        result.write_csv(&path).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], text_header("tune"));
+        assert_eq!(lines[1], "threshold,time_32,time_100,score,relative");
+        assert_eq!(lines.len(), 5);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    /// Invalid settings are rejected before any timing
+    #[test]
+    fn test_tune_invalid_config() {
+        let invalid = [
+            TuneConfig {
+                sizes: vec![],
+                ..config(Search::GoldenSection)
+            },
+            TuneConfig {
+                min_threshold: 200,
+                ..config(Search::GoldenSection)
+            },
+            TuneConfig {
+                runs: 0,
+                ..config(Search::GoldenSection)
+            },
+            config(Search::Grid { points: 0 }),
+        ];
+        for config in &invalid {
+            assert!(matches!(
+                tune_threshold(config),
+                Err(PolyError::InvalidArgument(_))
+            ));
+        }
+        let single = TuneConfig {
+            min_threshold: 16,
+            max_threshold: 16,
+            ..config(Search::GoldenSection)
+        };
+        assert_eq!(tune_threshold(&single).unwrap().best, 16);
+    }
+}
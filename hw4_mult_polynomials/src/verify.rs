@@ -0,0 +1,171 @@
+//! Probabilistic verification of products by evaluation at random points
+
+use crate::fingerprint::{field_evaluate, field_mul, field_point};
+use crate::Polynomial;
+use rand::Rng;
+
+/// Relative tolerance of a product check, about the square root of the machine epsilon
+///
+/// Loose enough for the rounding errors of every algorithm in the crate,
+/// tight enough to catch wrong coefficients of non-negligible size.
+const TOLERANCE: f64 = 1.5e-8;
+
+/// Check that `c` is the product of `a` and `b` at `trials` random points
+///
+/// Requires the `thread_rng` feature (on by default); [`verify_product_with_rng`] takes the
+/// generator instead.
+/// ## Example
+/// ```
+/// use mult_polynomial::{verify_product, Polynomial};
+/// let a = Polynomial::random(-10.0, 10.0, 1000);
+/// let b = Polynomial::random(-10.0, 10.0, 1000);
+/// let mut c = a.multiply_thresholded(&b, 64);
+/// assert!(verify_product(&a, &b, &c, 10));
+/// c.coeffs[500] += 1.0;
+/// assert!(!verify_product(&a, &b, &c, 10));
+/// ```
+#[cfg(feature = "thread_rng")]
+pub fn verify_product(a: &Polynomial, b: &Polynomial, c: &Polynomial, trials: usize) -> bool {
+    verify_product_with_rng(a, b, c, trials, &mut rand::thread_rng())
+}
+
+/// Check that `c` is the product of `a` and `b` at `trials` random points drawn from `rng`
+///
+/// Schwartz–Zippel style: a nonzero polynomial `a·b - c` of degree `d` vanishes at a random
+/// point with negligible probability, so `a(r)·b(r) ≈ c(r)` at a few random `r` gives strong
+/// evidence that `c = a·b`, in O(n) time per trial instead of recomputing the product.
+///
+/// The points are drawn from the complex unit circle, where every coefficient has the same
+/// weight and no evaluation overflows. The check allows a relative error of about `1.5e-8`
+/// against `Σ|aᵢ| · Σ|bⱼ|`, which bounds every coefficient of the product;
+/// errors of a coefficient below that scale are not detected.
+pub fn verify_product_with_rng<R: Rng + ?Sized>(
+    a: &Polynomial,
+    b: &Polynomial,
+    c: &Polynomial,
+    trials: usize,
+    rng: &mut R,
+) -> bool {
+    // the product of nonzero polynomials has exactly n + m - 1 coefficients
+    let expected_len = match (a.coeffs.len(), b.coeffs.len()) {
+        (0, _) | (_, 0) => 0,
+        (n, m) => n + m - 1,
+    };
+    if c.coeffs.len() != expected_len {
+        return false;
+    }
+
+    let norm = |p: &Polynomial| p.coeffs.iter().map(|x| x.abs()).sum::<f64>();
+    let scale = norm(a) * norm(b);
+    (0..trials).all(|_| {
+        let r = random_unit_point(rng);
+        let (a_re, a_im) = evaluate_complex(&a.coeffs, r);
+        let (b_re, b_im) = evaluate_complex(&b.coeffs, r);
+        let (c_re, c_im) = evaluate_complex(&c.coeffs, r);
+        let re = a_re * b_re - a_im * b_im - c_re;
+        let im = a_re * b_im + a_im * b_re - c_im;
+        re.abs().max(im.abs()) <= TOLERANCE * scale
+    })
+}
+
+/// Check that `c` is exactly the product of `a` and `b`, by their field fingerprints at `seed`
+///
+/// Products whose coefficients are all representable without rounding (e.g. integers below 2⁵³,
+/// or few binary digits) map to the products of the fingerprints, see
+/// [`Polynomial::field_fingerprint`]. A wrong `c` passes for at most `degree / (2⁶¹ - 1)` of all
+/// seeds, and so does a product with any rounding error, which this check does not tolerate.
+/// ## Example
+/// ```
+/// use mult_polynomial::{verify_exact_product, Polynomial};
+/// let a = Polynomial::new(vec![1.0, 2.0, 3.0]);
+/// let b = Polynomial::new(vec![-1.0, 0.5]);
+/// let mut c = a.multiply_naive(&b);
+/// assert!(verify_exact_product(&a, &b, &c, 42));
+/// c.coeffs[1] += 1e-9;
+/// assert!(!verify_exact_product(&a, &b, &c, 42));
+/// ```
+pub fn verify_exact_product(a: &Polynomial, b: &Polynomial, c: &Polynomial, seed: u64) -> bool {
+    let point = field_point(seed);
+    let (x, y) = (
+        field_evaluate(&a.coeffs, point),
+        field_evaluate(&b.coeffs, point),
+    );
+    field_mul(x, y) == field_evaluate(&c.coeffs, point)
+}
+
+/// Random point `(re, im)` on the complex unit circle
+///
+/// Uses the rational parametrization `((1 - t²) + 2ti) / (1 + t²)` with a random sign,
+/// which needs no trigonometric functions (unavailable without `std`).
+fn random_unit_point<R: Rng + ?Sized>(rng: &mut R) -> (f64, f64) {
+    let t: f64 = rng.gen_range(-1.0..=1.0);
+    let sign = if rng.gen::<bool>() { 1.0 } else { -1.0 };
+    let d = 1.0 + t * t;
+    (sign * (1.0 - t * t) / d, sign * 2.0 * t / d)
+}
+
+/// Evaluate real coefficients at the complex point `(re, im)` with Horner's scheme
+fn evaluate_complex(coeffs: &[f64], (re, im): (f64, f64)) -> (f64, f64) {
+    coeffs
+        .iter()
+        .rev()
+        .fold((0.0, 0.0), |(acc_re, acc_im), &c| {
+            (acc_re * re - acc_im * im + c, acc_re * im + acc_im * re)
+        })
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::PolynomialMultAlg;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    /// Products of every algorithm pass and corrupted products fail
+    #[test]
+    fn test_verify_product() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let a = Polynomial::random_with_rng(-10.0, 10.0, 700, &mut rng);
+        let b = Polynomial::random_with_rng(-10.0, 10.0, 300, &mut rng);
+        for algorithm in [
+            PolynomialMultAlg::Naive,
+            PolynomialMultAlg::CookTooms,
+            PolynomialMultAlg::Thresholded(16),
+        ] {
+            let c = a.multiply(&b, algorithm);
+            assert!(verify_product_with_rng(&a, &b, &c, 20, &mut rng));
+
+            let mut wrong = c.clone();
+            wrong.coeffs[0] += 1.0;
+            assert!(!verify_product_with_rng(&a, &b, &wrong, 20, &mut rng));
+            let mut wrong = c.clone();
+            wrong.coeffs.swap(100, 101);
+            assert!(!verify_product_with_rng(&a, &b, &wrong, 20, &mut rng));
+            wrong.coeffs.pop();
+            assert!(!verify_product_with_rng(&a, &b, &wrong, 20, &mut rng));
+        }
+
+        let empty = Polynomial::new(vec![]);
+        assert!(verify_product_with_rng(&a, &empty, &empty, 5, &mut rng));
+    }
+
+    /// Exact products of integer polynomials pass for every seed, off by one coefficient fail
+    #[test]
+    fn test_verify_exact_product() {
+        let integers = |n| {
+            crate::RandomPolynomial::new(n)
+                .integer(true)
+                .generate_with_rng(&mut StdRng::seed_from_u64(n as u64))
+        };
+        let (a, b) = (integers(3000), integers(2000));
+        let c = a.multiply_naive(&b);
+        let mut wrong = c.clone();
+        wrong.coeffs[1234] += 1.0;
+        for seed in 0..5 {
+            assert!(verify_exact_product(&a, &b, &c, seed));
+            assert!(!verify_exact_product(&a, &b, &wrong, seed));
+        }
+        let empty = Polynomial::new(vec![]);
+        assert!(verify_exact_product(&a, &empty, &empty, 0));
+    }
+}
@@ -0,0 +1,218 @@
+//! Borrowed polynomials over coefficients stored elsewhere
+
+use crate::polynomial::{multiply_impl, thresholded_square_impl};
+use crate::sparse::{prefers_sparse, sparse_multiply_impl};
+use crate::{Coefficient, HybridConfig, MultiplyAlgorithm, Polynomial, PolynomialMultAlg};
+
+/// Polynomial borrowing its coefficients, from lowest to highest degree
+///
+/// Like [`Polynomial::new`], trailing coefficients with absolute value below
+/// [`Coefficient::TOLERANCE`] are left out, here by shortening the slice, so coefficients
+/// kept in other containers (array rows, memory-mapped files, ...) are multiplied without
+/// copying them into a `Vec` first. Products are owned [`Polynomial`]s, identical to those
+/// of the polynomials created from the same coefficients.
+/// ## Example
+/// ```
+/// use mult_polynomial::{Polynomial, PolynomialView};
+/// let samples = [1.0, 2.0, 3.0, 4.0, 0.0];
+/// let p = PolynomialView::new(&samples[..3]);
+/// let q = PolynomialView::new(&samples[3..]);
+/// assert_eq!(q.degree(), 0);
+/// assert_eq!(p.evaluate(2.0), 17.0);
+/// assert_eq!(p.multiply_naive(&q), Polynomial::new(vec![4.0, 8.0, 12.0]));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PolynomialView<'a, T = f64> {
+    coeffs: &'a [T],
+}
+
+impl<'a, T: Coefficient> PolynomialView<'a, T> {
+    /// View the coefficients, without the trailing ones below [`Coefficient::TOLERANCE`]
+    pub fn new(coeffs: &'a [T]) -> Self {
+        let len = (coeffs.iter())
+            .rposition(|c| c.abs().to_f64() >= T::TOLERANCE)
+            .map_or(0, |last| last + 1);
+        PolynomialView {
+            coeffs: &coeffs[..len],
+        }
+    }
+
+    /// Coefficients of the view, from lowest to highest degree
+    pub fn coeffs(&self) -> &'a [T] {
+        self.coeffs
+    }
+
+    /// Copy the coefficients into an owned polynomial
+    pub fn to_polynomial(&self) -> Polynomial<T> {
+        Polynomial::new(self.coeffs.to_vec())
+    }
+
+    /// Get the degree of the polynomial (0 for the zero polynomial)
+    pub fn degree(&self) -> usize {
+        self.coeffs.len().saturating_sub(1)
+    }
+
+    /// Whether this is the zero polynomial
+    pub fn is_zero(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    /// Evaluate the polynomial at a given point x
+    pub fn evaluate(&self, x: T) -> T {
+        let mut result = T::ZERO;
+        let mut power = T::ONE;
+        for &coeff in self.coeffs {
+            result += coeff * power;
+            power *= x;
+        }
+        result
+    }
+
+    /// Multiply with another polynomial using the given algorithm
+    pub fn multiply(
+        &self,
+        other: &PolynomialView<'_, T>,
+        algorithm: PolynomialMultAlg,
+    ) -> Polynomial<T> {
+        Polynomial::new(multiply_impl(self.coeffs, other.coeffs, algorithm))
+    }
+
+    /// Multiply with another polynomial using the algorithm chosen by [`PolynomialMultAlg::auto`],
+    /// or term by term if few coefficients are nonzero, like [`Polynomial::multiply_auto`]
+    pub fn multiply_auto(&self, other: &PolynomialView<'_, T>) -> Polynomial<T> {
+        if prefers_sparse(self.coeffs, other.coeffs) {
+            return Polynomial::new(sparse_multiply_impl(self.coeffs, other.coeffs));
+        }
+        let algorithm = PolynomialMultAlg::auto_for::<T>(self.coeffs.len(), other.coeffs.len());
+        self.multiply(other, algorithm)
+    }
+
+    /// Multiply with another polynomial using the naive O(n²) algorithm
+    pub fn multiply_naive(&self, other: &PolynomialView<'_, T>) -> Polynomial<T> {
+        self.multiply(other, PolynomialMultAlg::Naive)
+    }
+
+    /// Multiply with another polynomial using the naive O(n²) algorithm
+    /// with compensated (Kahan-style) accumulation of each coefficient
+    pub fn multiply_naive_compensated(&self, other: &PolynomialView<'_, T>) -> Polynomial<T> {
+        self.multiply(other, PolynomialMultAlg::NaiveCompensated)
+    }
+
+    /// Multiply with another polynomial using the Cook-Tooms algorithm with k=3
+    pub fn multiply_cook_tooms_k3(&self, other: &PolynomialView<'_, T>) -> Polynomial<T> {
+        self.multiply(other, PolynomialMultAlg::CookTooms)
+    }
+
+    /// Multiply with another polynomial using a thresholded approach
+    pub fn multiply_thresholded(
+        &self,
+        other: &PolynomialView<'_, T>,
+        threshold: usize,
+    ) -> Polynomial<T> {
+        self.multiply(other, PolynomialMultAlg::Thresholded(threshold))
+    }
+
+    /// Multiply with another polynomial using Toom-3, Karatsuba and the naive algorithm
+    /// depending on the operand length, see [`HybridConfig`]
+    pub fn multiply_hybrid(
+        &self,
+        other: &PolynomialView<'_, T>,
+        config: HybridConfig,
+    ) -> Polynomial<T> {
+        self.multiply(other, PolynomialMultAlg::Hybrid(config))
+    }
+
+    /// Square the polynomial using the Cook-Tooms algorithm with k=3
+    pub fn square(&self) -> Polynomial<T> {
+        self.square_thresholded(5)
+    }
+
+    /// Square the polynomial using a thresholded approach
+    pub fn square_thresholded(&self, threshold: usize) -> Polynomial<T> {
+        Polynomial::new(thresholded_square_impl(self.coeffs, threshold))
+    }
+}
+
+impl PolynomialView<'_> {
+    /// Multiply with another polynomial using the given algorithm, built-in or user-defined
+    pub fn multiply_with(
+        &self,
+        other: &PolynomialView<'_>,
+        algorithm: &dyn MultiplyAlgorithm,
+    ) -> Polynomial {
+        Polynomial::new(algorithm.multiply(self.coeffs, other.coeffs))
+    }
+}
+
+impl<'a, T: Coefficient> From<&'a [T]> for PolynomialView<'a, T> {
+    fn from(coeffs: &'a [T]) -> Self {
+        PolynomialView::new(coeffs)
+    }
+}
+
+impl<'a, T: Coefficient> From<&'a Polynomial<T>> for PolynomialView<'a, T> {
+    /// View the coefficients of a polynomial, trimmed according to the default policy
+    fn from(polynomial: &'a Polynomial<T>) -> Self {
+        PolynomialView::new(&polynomial.coeffs)
+    }
+}
+
+impl<T: Coefficient> Polynomial<T> {
+    /// Borrow the coefficients as a [`PolynomialView`]
+    pub fn view(&self) -> PolynomialView<'_, T> {
+        PolynomialView::from(self)
+    }
+}
+
+#[cfg(all(test, feature = "thread_rng"))]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    /// Every product of views matches the product of the owned polynomials exactly
+    #[test]
+    fn test_view_products() {
+        let p = Polynomial::random(-10.0, 10.0, 300);
+        let q = Polynomial::random(-10.0, 10.0, 200);
+        // trailing zeros are trimmed by both the view and the polynomial
+        let mut padded = q.coeffs.clone();
+        padded.extend([0.0, 1e-15]);
+        let (u, v) = (p.view(), PolynomialView::new(&padded[..]));
+        assert_eq!(v.coeffs(), &q.coeffs[..]);
+        assert_eq!(v.degree(), q.degree());
+        assert_eq!(u.evaluate(0.5), p.evaluate(0.5));
+        let hybrid = HybridConfig::default();
+        let pairs = [
+            (u.multiply_auto(&v), p.multiply_auto(&q)),
+            (u.multiply_naive(&v), p.multiply_naive(&q)),
+            (
+                u.multiply_naive_compensated(&v),
+                p.multiply_naive_compensated(&q),
+            ),
+            (u.multiply_cook_tooms_k3(&v), p.multiply_cook_tooms_k3(&q)),
+            (
+                u.multiply_thresholded(&v, 16),
+                p.multiply_thresholded(&q, 16),
+            ),
+            (u.multiply_hybrid(&v, hybrid), p.multiply_hybrid(&q, hybrid)),
+            (
+                u.multiply_with(&v, &PolynomialMultAlg::Naive),
+                p.multiply_naive(&q),
+            ),
+            (u.square(), p.square()),
+            (u.square_thresholded(32), p.square_thresholded(32)),
+        ];
+        for (view_product, product) in pairs {
+            assert_eq!(view_product.coeffs, product.coeffs);
+        }
+        assert_eq!(v.to_polynomial().coeffs, q.coeffs);
+
+        let zero = PolynomialView::new(&[0.0, 0.0][..]);
+        assert!(zero.is_zero() && zero.degree() == 0);
+        assert!(zero.multiply_naive(&u).coeffs.is_empty());
+        let single: Vec<f32> = vec![1.0, 2.0];
+        let single = PolynomialView::from(&single[..]);
+        assert_eq!(single.square().coeffs, vec![1.0, 4.0, 4.0]);
+    }
+}
@@ -0,0 +1,102 @@
+//! WebAssembly bindings via `wasm-bindgen`
+//!
+//! Enabled with the `wasm` feature. Build with
+//! `wasm-pack build --target web -- --features wasm` and use from JavaScript:
+//! ```js
+//! import init, { Polynomial, benchmark } from "./pkg/mult_polynomial.js";
+//! await init();
+//! const p = new Polynomial(new Float64Array([1, 2, 3]));
+//! const product = p.multiplyCookTooms(p).coeffs(); // Float64Array
+//! const [naiveMs, cookToomsMs, thresholdedMs] = benchmark(4096, 64, 5);
+//! ```
+
+use crate::{Polynomial, PolynomialMultAlg};
+use wasm_bindgen::prelude::*;
+
+/// Polynomial exposed to JavaScript as `Polynomial`
+#[wasm_bindgen(js_name = Polynomial)]
+pub struct JsPolynomial {
+    inner: Polynomial,
+}
+
+#[wasm_bindgen(js_class = Polynomial)]
+impl JsPolynomial {
+    /// Create a polynomial from a `Float64Array` of coefficients, from lowest to highest degree
+    #[wasm_bindgen(constructor)]
+    pub fn new(coeffs: &[f64]) -> JsPolynomial {
+        JsPolynomial {
+            inner: Polynomial::new(coeffs.to_vec()),
+        }
+    }
+
+    /// Generate a random polynomial with given size, throwing on a zero size or an empty range
+    pub fn random(range_min: f64, range_max: f64, size: usize) -> Result<JsPolynomial, JsError> {
+        let inner = Polynomial::try_random(range_min, range_max, size)
+            .map_err(|e| JsError::new(&e.to_string()))?;
+        Ok(JsPolynomial { inner })
+    }
+
+    /// Coefficients as a `Float64Array`, from lowest to highest degree
+    pub fn coeffs(&self) -> Vec<f64> {
+        self.inner.coeffs.clone()
+    }
+
+    /// Get the degree of the polynomial
+    pub fn degree(&self) -> usize {
+        self.inner.degree()
+    }
+
+    /// Evaluate the polynomial at a given point x
+    pub fn evaluate(&self, x: f64) -> f64 {
+        self.inner.evaluate(x)
+    }
+
+    /// Multiply using the naive O(n²) algorithm
+    #[wasm_bindgen(js_name = multiplyNaive)]
+    pub fn multiply_naive(&self, other: &JsPolynomial) -> JsPolynomial {
+        JsPolynomial {
+            inner: self.inner.multiply_naive(&other.inner),
+        }
+    }
+
+    /// Multiply using the Cook-Tooms algorithm with k=3
+    #[wasm_bindgen(js_name = multiplyCookTooms)]
+    pub fn multiply_cook_tooms_k3(&self, other: &JsPolynomial) -> JsPolynomial {
+        JsPolynomial {
+            inner: self.inner.multiply_cook_tooms_k3(&other.inner),
+        }
+    }
+
+    /// Multiply using a thresholded approach
+    #[wasm_bindgen(js_name = multiplyThresholded)]
+    pub fn multiply_thresholded(&self, other: &JsPolynomial, threshold: usize) -> JsPolynomial {
+        JsPolynomial {
+            inner: self.inner.multiply_thresholded(&other.inner, threshold),
+        }
+    }
+}
+
+/// Benchmark the three algorithms on random polynomials with `size` coefficients
+///
+/// Returns a `Float64Array` with the mean time in milliseconds over `runs` runs
+/// of the naive, Cook-Tooms and thresholded algorithm.
+#[wasm_bindgen]
+pub fn benchmark(size: usize, threshold: usize, runs: usize) -> Vec<f64> {
+    let algorithms = [
+        PolynomialMultAlg::Naive,
+        PolynomialMultAlg::CookTooms,
+        PolynomialMultAlg::Thresholded(threshold),
+    ];
+    let mut times = vec![0.0; algorithms.len()];
+    for _ in 0..runs {
+        let p1 = Polynomial::random(-10.0, 10.0, size);
+        let p2 = Polynomial::random(-10.0, 10.0, size);
+        for (time, &algorithm) in times.iter_mut().zip(&algorithms) {
+            // `std::time::Instant` is not available in the browser
+            let start = js_sys::Date::now();
+            p1.multiply(&p2, algorithm);
+            *time += (js_sys::Date::now() - start) / runs as f64;
+        }
+    }
+    times
+}